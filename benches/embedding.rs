@@ -0,0 +1,76 @@
+//! Embedding and extraction benchmarks
+//!
+//! Compares the LSB patterns and seed sources over several image sizes, on raw
+//! sample buffers so PNG decoding and encoding don't dominate the results.
+//!
+//! Run with `cargo bench`, or `cargo bench --features instrument` to also get
+//! per-phase timings from `EmbedResult::timing`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+use std::hint::black_box;
+
+// Square RGB image sizes, in pixels per side
+const IMAGE_SIDES: [usize; 3] = [64, 256, 1024];
+
+fn configs() -> [(&'static str, LSBConfig); 3] {
+    [
+        ("linear", LSBConfig::linear()),
+        ("random", LSBConfig::random()),
+        (
+            "password",
+            LSBConfig::random().with_password("benchmark password".to_string()),
+        ),
+    ]
+}
+
+// Cover samples and a payload filling about half of the capacity
+fn cover_and_payload(side: usize) -> (Vec<u8>, Vec<u8>) {
+    let image: Vec<u8> = (0..side * side * 3).map(|i| (i % 251) as u8).collect();
+    let payload: Vec<u8> = (0..image.len() / 16).map(|i| (i % 256) as u8).collect();
+    (image, payload)
+}
+
+fn bench_embed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("embed");
+    group.sample_size(10);
+
+    for side in IMAGE_SIDES {
+        let (image, payload) = cover_and_payload(side);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        for (name, config) in configs() {
+            group.bench_with_input(BenchmarkId::new(name, side), &config, |b, config| {
+                b.iter_batched_ref(
+                    || image.clone(),
+                    |image| LSBEmbedder::embed(image, black_box(&payload), config).unwrap(),
+                    criterion::BatchSize::LargeInput,
+                );
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_extract(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract");
+    group.sample_size(10);
+
+    for side in IMAGE_SIDES {
+        let (mut image, payload) = cover_and_payload(side);
+        group.throughput(Throughput::Bytes(payload.len() as u64));
+
+        for (name, config) in configs() {
+            LSBEmbedder::embed(&mut image, &payload, &config).unwrap();
+            group.bench_with_input(BenchmarkId::new(name, side), &config, |b, config| {
+                b.iter(|| LSBEmbedder::extract(black_box(&mut image), config).unwrap());
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_embed, bench_extract);
+criterion_main!(benches);
@@ -0,0 +1,287 @@
+//! Embedding into a single frame of an animated PNG (APNG).
+//!
+//! [`embed_payload_into_frame`] decodes every frame of the animation, embeds
+//! into the samples of the chosen frame only, and re-encodes all frames with
+//! their original frame controls (size, offset, delay, dispose and blend
+//! operations). The frame index is recorded in the payload header, so
+//! [`extract_payload_from_frame`] finds the payload without being told which
+//! frame holds it.
+//!
+//! Frames are numbered in file order: frame 0 is the default image, followed
+//! by the other frames of the animation. When the default image is not part
+//! of the animation, it still counts as frame 0.
+
+use std::io::BufWriter;
+
+use crate::{
+    EmbeddingOptions, PngerError, Strategy, decode_png_info, embed_into_buffer, obfuscation,
+    strategy::lsb::LSBEmbedder,
+    utils::{setup_png_encoder, with_color_samples},
+    whole_image_only,
+};
+
+/// One decoded frame of an animation.
+struct Frame {
+    // Frame control of the frame, `None` for a default image outside the
+    // animation
+    control: Option<png::FrameControl>,
+    data: Vec<u8>,
+}
+
+/// Embeds a payload into one frame of an APNG, leaving the others intact.
+///
+/// Only the samples of frame `frame_index` (in file order, the default image
+/// being frame 0) are modified; every other frame is re-encoded with
+/// identical samples and frame controls. The capacity is that of the chosen
+/// frame, which may be smaller than the image when the frame only covers
+/// part of it. The frame index is stored in the header, see
+/// [`LSBConfig::with_frame_index`](crate::strategy::lsb::LSBConfig::with_frame_index).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_payload_into_frame, extract_payload_from_frame};
+///
+/// let animation = std::fs::read("animation.png")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+///
+/// let stego = embed_payload_into_frame(&animation, 2, b"hidden in frame 2", options.clone())?;
+/// let (frame_index, payload) = extract_payload_from_frame(&stego, options)?;
+/// assert_eq!((frame_index, payload.as_slice()), (2, &b"hidden in frame 2"[..]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - The image is not animated (`PngerError::UnsupportedMode`)
+/// - `frame_index` is past the last frame (`PngerError::InvalidFormat`)
+/// - `options` use the DCT or PVD strategy or palette mode (`PngerError::UnsupportedMode`)
+/// - The payload does not fit in the frame (`PngerError::PayloadTooLarge`)
+pub fn embed_payload_into_frame<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    png_data: P,
+    frame_index: usize,
+    payload_data: D,
+    mut options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    check_frame_options(&options)?;
+    let (info, mut frames) = decode_frames(png_data.as_ref())?;
+    let frame_count = frames.len();
+    let frame = frames.get_mut(frame_index).ok_or_else(|| {
+        PngerError::InvalidFormat(format!(
+            "Frame {frame_index} is out of range, the animation has {frame_count} frames"
+        ))
+    })?;
+
+    let index = u32::try_from(frame_index).map_err(|_| {
+        PngerError::InvalidFormat(format!("Frame index {frame_index} is too large"))
+    })?;
+    if let Strategy::LSB(config) = &mut options.strategy {
+        *config = std::mem::take(config).with_frame_index(index);
+    }
+    let alpha = options.alpha_layout(&info);
+    let compression = options.output_compression;
+    with_color_samples(&mut frame.data, alpha, |carrier| {
+        embed_into_buffer(carrier, payload_data.as_ref(), options)
+    })?;
+    encode_frames(&info, &frames, compression)
+}
+
+/// Extracts a payload embedded with [`embed_payload_into_frame`].
+///
+/// Frames are tried in order; the first one holding a payload whose header
+/// records its own index is returned, along with that index. Payloads
+/// embedded into the whole image, which record no frame index, are ignored.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - The image is not animated, or `options` use the DCT or PVD strategy or
+///   palette mode (`PngerError::UnsupportedMode`)
+/// - No frame holds a payload recording its index; the error of the last
+///   failed extraction is returned, or `PngerError::InvalidFormat` when
+///   every frame held a payload for another frame
+pub fn extract_payload_from_frame<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<(usize, Vec<u8>), PngerError> {
+    check_frame_options(&options)?;
+    let Strategy::LSB(config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+    let (info, mut frames) = decode_frames(png_data.as_ref())?;
+    let alpha = options.alpha_layout(&info);
+
+    let mut last_error = None;
+    for (frame_index, frame) in frames.iter_mut().enumerate() {
+        let extracted = with_color_samples(&mut frame.data, alpha, |carrier| {
+            LSBEmbedder::extract(carrier, config)
+        });
+        match extracted {
+            Ok(result) if result.frame_index == u32::try_from(frame_index).ok() => {
+                let payload = match options.obfuscation {
+                    Some(obfuscation) => {
+                        obfuscation::deobfuscate_payload(&result.payload, obfuscation)?
+                    }
+                    None => result.payload,
+                };
+                return Ok((frame_index, payload));
+            }
+            Ok(_) => {}
+            Err(error) => last_error = Some(error),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        PngerError::InvalidFormat("No frame holds a payload embedded into it".to_string())
+    }))
+}
+
+fn check_frame_options(options: &EmbeddingOptions) -> Result<(), PngerError> {
+    match &options.strategy {
+        Strategy::LSB(_) if options.palette_mode() => Err(PngerError::UnsupportedMode(
+            "Frame embedding does not support palette mode".to_string(),
+        )),
+        Strategy::LSB(_) => Ok(()),
+        Strategy::DCT(_) | Strategy::PVD(_) => Err(whole_image_only()),
+    }
+}
+
+/// Decodes every frame of an animated PNG, in file order.
+fn decode_frames(png_data: &[u8]) -> Result<(png::Info<'static>, Vec<Frame>), PngerError> {
+    let (mut reader, info) = decode_png_info(png_data)?;
+    let Some(animation) = info.animation_control else {
+        return Err(PngerError::UnsupportedMode(
+            "Frame embedding needs an animated PNG".to_string(),
+        ));
+    };
+    // A default image outside the animation comes before the counted frames
+    let separate_default = info.frame_control.is_none();
+    let frame_count = animation.num_frames as usize + usize::from(separate_default);
+
+    let mut frames = Vec::with_capacity(frame_count);
+    for _ in 0..frame_count {
+        let mut data = vec![0; reader.output_buffer_size()];
+        let output = reader.next_frame(&mut data)?;
+        data.truncate(output.buffer_size());
+        frames.push(Frame {
+            control: if frames.is_empty() && separate_default {
+                None
+            } else {
+                reader.info().frame_control
+            },
+            data,
+        });
+    }
+    Ok((info, frames))
+}
+
+/// Encodes decoded frames back into an animated PNG.
+fn encode_frames(
+    info: &png::Info,
+    frames: &[Frame],
+    compression: Option<png::Compression>,
+) -> Result<Vec<u8>, PngerError> {
+    let mut writer_buffer = BufWriter::new(Vec::new());
+    let mut encoder = setup_png_encoder(info, &mut writer_buffer)?;
+    if let Some(compression) = compression {
+        encoder.set_compression(compression);
+    }
+    if frames.first().is_some_and(|frame| frame.control.is_none()) {
+        encoder.set_sep_def_img(true)?;
+    }
+
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        if let Some(control) = frame.control {
+            // Reset the position first, as sizes and positions are checked
+            // against each other
+            writer.set_frame_position(0, 0)?;
+            writer.set_frame_dimension(control.width, control.height)?;
+            writer.set_frame_position(control.x_offset, control.y_offset)?;
+            writer.set_frame_delay(control.delay_num, control.delay_den)?;
+            writer.set_dispose_op(control.dispose_op)?;
+            writer.set_blend_op(control.blend_op)?;
+        }
+        writer.write_image_data(&frame.data)?;
+    }
+    writer.finish()?;
+
+    writer_buffer.into_inner().map_err(|e| PngerError::IoError {
+        message: format!("Failed to extract buffer: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::encoder;
+
+    // 4-frame 32x32 RGB animation whose frames 1 to 3 cover a 16x16 square
+    // at increasing offsets
+    fn create_apng() -> Vec<u8> {
+        let mut png_data = Vec::new();
+        let mut encoder = encoder(&mut png_data, 32, 32, png::ColorType::Rgb);
+        encoder.set_animated(4, 0).unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[10; 32 * 32 * 3]).unwrap();
+        for frame in 1..4u8 {
+            writer.set_frame_dimension(16, 16).unwrap();
+            writer
+                .set_frame_position(u32::from(frame), u32::from(frame))
+                .unwrap();
+            writer.set_frame_delay(u16::from(frame), 10).unwrap();
+            writer.write_image_data(&[frame * 40; 16 * 16 * 3]).unwrap();
+        }
+        writer.finish().unwrap();
+        png_data
+    }
+
+    #[test]
+    fn test_embed_into_frame_leaves_other_frames_intact() {
+        let cover = create_apng();
+        let options = EmbeddingOptions::linear();
+        let stego = embed_payload_into_frame(&cover, 2, b"f2", options.clone()).unwrap();
+
+        let (_, original) = decode_frames(&cover).unwrap();
+        let (info, embedded) = decode_frames(&stego).unwrap();
+        assert_eq!(info.animation_control.unwrap().num_frames, 4);
+        assert_eq!(embedded.len(), 4);
+        for (index, (before, after)) in original.iter().zip(&embedded).enumerate() {
+            let (before_control, after_control) = (before.control.unwrap(), after.control.unwrap());
+            assert_eq!(
+                (
+                    before_control.width,
+                    before_control.x_offset,
+                    before_control.delay_num
+                ),
+                (
+                    after_control.width,
+                    after_control.x_offset,
+                    after_control.delay_num
+                )
+            );
+            if index == 2 {
+                assert_ne!(before.data, after.data);
+            } else {
+                assert_eq!(before.data, after.data, "frame {index} changed");
+            }
+        }
+
+        assert_eq!(
+            extract_payload_from_frame(&stego, options.clone()).unwrap(),
+            (2, b"f2".to_vec())
+        );
+        assert!(matches!(
+            embed_payload_into_frame(&cover, 4, b"f4", options.clone()),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        // A 16x16 frame cannot hold what fits the 32x32 default image
+        assert!(embed_payload_into_frame(&cover, 0, [7; 200], options.clone()).is_ok());
+        assert!(matches!(
+            embed_payload_into_frame(&cover, 1, [7; 200], options),
+            Err(PngerError::PayloadTooLarge { .. } | PngerError::InsufficientCapacity)
+        ));
+    }
+}
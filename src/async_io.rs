@@ -0,0 +1,129 @@
+//! Asynchronous file API for [`tokio`] applications.
+//!
+//! Files are read with [`tokio::fs`], and the CPU-bound embedding and
+//! extraction run on the blocking thread pool through
+//! [`tokio::task::spawn_blocking`], so they never stall the async runtime.
+//! The algorithms themselves are the synchronous ones: a payload embedded
+//! with these functions extracts with the synchronous API and vice versa.
+
+use std::path::Path;
+
+use crate::{
+    EmbeddingOptions, PngerError, embed_payload_from_bytes_with_options,
+    extract_payload_from_bytes_with_options,
+};
+
+/// Embeds a payload into a PNG file without blocking the async runtime.
+///
+/// Asynchronous version of
+/// [`embed_payload_from_file_with_options`](crate::embed_payload_from_file_with_options).
+/// Like it, returns the modified PNG and leaves writing it to the caller.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_payload_from_file_async};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let result = embed_payload_from_file_async("image.png", b"payload", options).await?;
+/// tokio::fs::write("output.png", result).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The PNG file cannot be read (`PngerError::FileIo`)
+/// - The embedding fails (see [`embed_payload_from_bytes_with_options`])
+/// - The blocking task panics or is cancelled (`PngerError::IoError`)
+pub async fn embed_payload_from_file_async<P: AsRef<Path>, D: AsRef<[u8]>>(
+    png_path: P,
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let png_data = tokio::fs::read(png_path).await?;
+    let payload_data = payload_data.as_ref().to_vec();
+    run_blocking(move || embed_payload_from_bytes_with_options(png_data, payload_data, options))
+        .await
+}
+
+/// Extracts a payload from a PNG file without blocking the async runtime.
+///
+/// Asynchronous version of
+/// [`extract_payload_from_file_with_options`](crate::extract_payload_from_file_with_options).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, extract_payload_from_file_async};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let payload = extract_payload_from_file_async("output.png", options).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The PNG file cannot be read (`PngerError::FileIo`)
+/// - The extraction fails (see [`extract_payload_from_bytes_with_options`])
+/// - The blocking task panics or is cancelled (`PngerError::IoError`)
+pub async fn extract_payload_from_file_async<P: AsRef<Path>>(
+    png_path: P,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let png_data = tokio::fs::read(png_path).await?;
+    run_blocking(move || extract_payload_from_bytes_with_options(png_data, options)).await
+}
+
+/// Runs a CPU-bound operation on the blocking thread pool.
+async fn run_blocking<F>(operation: F) -> Result<Vec<u8>, PngerError>
+where
+    F: FnOnce() -> Result<Vec<u8>, PngerError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .map_err(|e| PngerError::IoError {
+            message: format!("Blocking task failed: {e}"),
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::gradient_png;
+
+    #[test]
+    fn test_async_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("pnger-async-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cover = dir.join("cover.png");
+        let stego = dir.join("stego.png");
+        std::fs::write(&cover, gradient_png(32, 32, png::ColorType::Rgb)).unwrap();
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let options = EmbeddingOptions::random_with_password("async");
+        let extracted = runtime.block_on(async {
+            let embedded = embed_payload_from_file_async(&cover, b"async payload", options.clone())
+                .await
+                .unwrap();
+            tokio::fs::write(&stego, embedded).await.unwrap();
+            extract_payload_from_file_async(&stego, options).await
+        });
+        assert_eq!(extracted.unwrap(), b"async payload");
+
+        let missing = runtime.block_on(extract_payload_from_file_async(
+            dir.join("missing.png"),
+            EmbeddingOptions::linear(),
+        ));
+        assert!(matches!(missing, Err(PngerError::FileIo(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
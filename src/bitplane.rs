@@ -0,0 +1,105 @@
+//! Visualization of a single bit plane.
+//!
+//! Rendering one bit of every sample as a black and white image shows where a
+//! payload sits: a linear payload appears as a noisy band at the top of an
+//! otherwise smooth plane, a random one as noise spread over the whole image.
+
+use std::io::BufWriter;
+
+use crate::{PngerError, read_whole_image, strategy::lsb::BitIndex};
+
+/// Renders the `bit_index` plane of a PNG image as a 1-bit grayscale PNG.
+///
+/// The output has the dimensions of the image. A pixel is white when an odd
+/// number of its samples, alpha included, have the selected bit set, and
+/// black otherwise: any change to a single sample of the plane flips the
+/// pixel, so embedded bits stand out even in multi-channel images. The input
+/// is only read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::export_bit_plane;
+/// use pnger::strategy::lsb::BitIndex;
+///
+/// let stego = std::fs::read("output.png")?;
+/// std::fs::write("plane0.png", export_bit_plane(&stego, BitIndex::Bit0)?)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the data is not a valid 8-bit PNG
+/// image supported for embedding.
+pub fn export_bit_plane<P: AsRef<[u8]>>(
+    png_data: P,
+    bit_index: BitIndex,
+) -> Result<Vec<u8>, PngerError> {
+    let (image_data, layout) = read_whole_image(png_data.as_ref())?;
+    let mask = 1u8 << bit_index.position();
+
+    // Rows are packed 8 pixels per byte, most significant bit first
+    let row_len = layout.width.div_ceil(8);
+    let mut plane = vec![0u8; row_len * layout.height];
+    let pixels = image_data.chunks_exact(layout.channels).enumerate();
+    for (pixel, samples) in pixels {
+        let parity = samples.iter().fold(0, |parity, sample| parity ^ sample) & mask;
+        if parity != 0 {
+            let (y, x) = (pixel / layout.width, pixel % layout.width);
+            plane[y * row_len + x / 8] |= 0x80 >> (x % 8);
+        }
+    }
+
+    let mut writer_buffer = BufWriter::new(Vec::new());
+    let mut encoder = png::Encoder::new(
+        &mut writer_buffer,
+        layout.width as u32,
+        layout.height as u32,
+    );
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::One);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&plane)?;
+    writer.finish()?;
+
+    writer_buffer.into_inner().map_err(|e| PngerError::IoError {
+        message: format!("Failed to extract buffer: {e}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_utils::encode_png;
+
+    #[test]
+    fn test_export_bit_plane() {
+        // 10x3 RGB image whose only set bit 1 is in the green sample of (9, 1)
+        let mut image_data = vec![0u8; 10 * 3 * 3];
+        image_data[(10 + 9) * 3 + 1] = 0b10;
+        let png_data = encode_png(10, 3, png::ColorType::Rgb, &image_data);
+
+        let plane = export_bit_plane(&png_data, BitIndex::Bit1).unwrap();
+        let decoder = png::Decoder::new(Cursor::new(plane.as_slice()));
+        let mut reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (10, 3));
+        assert_eq!(
+            (info.color_type, info.bit_depth),
+            (png::ColorType::Grayscale, png::BitDepth::One)
+        );
+        let mut rows = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut rows).unwrap();
+        assert_eq!(rows, [0, 0, 0, 0b0100_0000, 0, 0]);
+
+        let plane = export_bit_plane(&png_data, BitIndex::Bit0).unwrap();
+        let mut reader = png::Decoder::new(Cursor::new(plane.as_slice()))
+            .read_info()
+            .unwrap();
+        let mut rows = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut rows).unwrap();
+        assert!(rows.iter().all(|&byte| byte == 0));
+    }
+}
@@ -0,0 +1,99 @@
+//! Runtime introspection of what the current build supports.
+//!
+//! Some capabilities depend on Cargo features. Applications exposing PNGer
+//! options, such as plugin hosts, can query [`supported_capabilities`] to
+//! hide the options this build cannot honor.
+
+/// Capabilities of the current build of the library.
+///
+/// Names are lowercase identifiers, matching the CLI values where they
+/// exist (`lsb`, `xor`). New capabilities may be added in any release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Embedding strategies, see [`Strategy`](crate::Strategy).
+    pub strategies: Vec<&'static str>,
+    /// Payload obfuscation methods, see [`Obfuscation`](crate::Obfuscation).
+    /// `custom` stands for user-supplied [`PayloadTransform`](crate::obfuscation::PayloadTransform)s.
+    pub obfuscations: Vec<&'static str>,
+    /// Key derivation functions used for password-derived seeds.
+    pub kdfs: Vec<&'static str>,
+    /// Embedding into `image::DynamicImage` buffers (`image` feature).
+    pub image_interop: bool,
+    /// Asynchronous file API (`tokio` feature).
+    pub async_io: bool,
+    /// Per-phase embedding timings (`instrument` feature).
+    pub timing: bool,
+    /// Debug events through the `log` crate (`logging` feature).
+    pub logging: bool,
+    /// Memory-mapped reading of input files (`mmap` feature).
+    pub mmap: bool,
+    /// Gzip-wrapped output files (`gzip` feature).
+    pub gzip: bool,
+    /// JSON split manifests, see [`Manifest`](crate::Manifest) (`json` feature).
+    pub json: bool,
+    /// Cover file formats, see [`codec`](crate::codec).
+    pub cover_formats: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// Checks whether `name` is a supported strategy, obfuscation or KDF.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let capabilities = pnger::supported_capabilities();
+    /// assert!(capabilities.supports("xor"));
+    /// assert!(!capabilities.supports("rot13"));
+    /// ```
+    pub fn supports(&self, name: &str) -> bool {
+        self.strategies
+            .iter()
+            .chain(&self.obfuscations)
+            .chain(&self.kdfs)
+            .any(|&supported| supported == name)
+    }
+}
+
+/// Reports the strategies, obfuscations and optional features of this build.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::supported_capabilities;
+///
+/// let capabilities = supported_capabilities();
+/// assert!(capabilities.strategies.contains(&"lsb"));
+/// if !capabilities.image_interop {
+///     println!("image crate support disabled");
+/// }
+/// ```
+pub fn supported_capabilities() -> Capabilities {
+    Capabilities {
+        strategies: vec!["lsb", "dct", "pvd"],
+        obfuscations: vec!["xor", "custom"],
+        kdfs: vec!["argon2id"],
+        image_interop: cfg!(feature = "image"),
+        async_io: cfg!(feature = "tokio"),
+        timing: cfg!(feature = "instrument"),
+        logging: cfg!(feature = "logging"),
+        mmap: cfg!(feature = "mmap"),
+        gzip: cfg!(feature = "gzip"),
+        json: cfg!(feature = "json"),
+        cover_formats: vec!["png", "bmp"],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_build_supports_lsb_and_xor() {
+        let capabilities = supported_capabilities();
+        assert!(capabilities.supports("lsb"));
+        assert!(capabilities.supports("xor"));
+        assert_eq!(capabilities.image_interop, cfg!(feature = "image"));
+        assert!(!capabilities.supports("aes"));
+    }
+}
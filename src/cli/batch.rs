@@ -0,0 +1,117 @@
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use pnger::embed_payload_from_bytes_with_options;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::EmbeddingArgs;
+
+#[derive(Args)]
+pub struct EmbedBatchArgs {
+    /// Directory of cover PNG files
+    #[arg(long, value_name = "DIR")]
+    pub input_dir: PathBuf,
+
+    /// Directory of payload files, matched to covers by file name without extension
+    #[arg(long, value_name = "DIR")]
+    pub payload_dir: PathBuf,
+
+    /// Directory receiving the output images, created if missing
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: PathBuf,
+
+    #[command(flatten)]
+    pub embedding: EmbeddingArgs,
+}
+
+impl EmbedBatchArgs {
+    /// Embed the matching payload into every cover of the input directory.
+    ///
+    /// Each cover is reported on its own line; a failing cover does not stop
+    /// the batch. Fails after the summary if any cover failed.
+    pub fn run(&self) -> Result<()> {
+        let options = self.embedding.get_options()?;
+        let covers = list_files(&self.input_dir)?
+            .into_iter()
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("png"))
+            })
+            .collect::<Vec<_>>();
+        let mut payloads: BTreeMap<OsString, Vec<PathBuf>> = BTreeMap::new();
+        for path in list_files(&self.payload_dir)? {
+            if let Some(stem) = path.file_stem() {
+                payloads.entry(stem.to_owned()).or_default().push(path);
+            }
+        }
+        fs::create_dir_all(&self.output_dir).with_context(|| {
+            format!(
+                "Failed to create output directory '{}'",
+                self.output_dir.display()
+            )
+        })?;
+
+        let mut failed = 0;
+        for cover in &covers {
+            let name = cover.file_name().unwrap_or_default().to_string_lossy();
+            let candidates = cover
+                .file_stem()
+                .and_then(|stem| payloads.get(stem))
+                .map_or(&[][..], Vec::as_slice);
+            let result = match candidates {
+                [payload] => self.embed_one(cover, payload, options.clone()),
+                [] => Err(anyhow::anyhow!("no matching payload")),
+                _ => Err(anyhow::anyhow!(
+                    "{} payloads match, expected one",
+                    candidates.len()
+                )),
+            };
+            match result {
+                Ok(()) => println!("ok      {name}"),
+                Err(e) => {
+                    failed += 1;
+                    println!("FAILED  {name}: {e:#}");
+                }
+            }
+        }
+
+        println!("{} embedded, {failed} failed", covers.len() - failed);
+        if failed > 0 {
+            bail!("{failed} of {} covers failed", covers.len());
+        }
+        Ok(())
+    }
+
+    fn embed_one(
+        &self,
+        cover: &Path,
+        payload: &Path,
+        options: pnger::EmbeddingOptions,
+    ) -> Result<()> {
+        let png_data = fs::read(cover).context("Failed to read cover")?;
+        let payload_data = fs::read(payload)
+            .with_context(|| format!("Failed to read payload '{}'", payload.display()))?;
+        let result = embed_payload_from_bytes_with_options(png_data, payload_data, options)
+            .context("Failed to embed payload into PNG")?;
+        let output_path = self.output_dir.join(cover.file_name().unwrap_or_default());
+        fs::write(&output_path, result)
+            .with_context(|| format!("Failed to write output file '{}'", output_path.display()))
+    }
+}
+
+/// Regular files of `dir`, sorted by name.
+fn list_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory '{}'", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -1,13 +1,15 @@
+pub mod batch;
 pub mod lsb;
 
 use anyhow::bail;
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use pnger::{
     EmbeddingOptions, Obfuscation,
     strategy::{Strategy, lsb::SEED_SIZE},
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use batch::EmbedBatchArgs;
 use lsb::LSBPatternArg;
 
 const PNGER_DEFAULT_XOR_KEY: &str = "PNGER_DEFAULT_XOR_KEY";
@@ -53,6 +55,9 @@ pub enum StrategyArg {
     # Combined: LSB password + XOR
     pnger -i image.png -p payload.bin -o output.png --lsb-password \"mypassword\" --xor --xor-key \"encrypt\"
 
+    # Check the payload can be extracted back before writing output.png
+    pnger -i image.png -p payload.bin -o output.png --lsb-password \"mypassword\" --verify
+
     # Extract payload from image.png and save to payload.json
     pnger -x -i output.png -o payload.json
 
@@ -60,11 +65,18 @@ pub enum StrategyArg {
     pnger -x -i output.png -o extracted.txt --lsb-password \"mypassword\" --xor --xor-key \"encrypt\"
 
     # Extract payload to stdout
-    pnger -x -i output.png --raw")]
+    pnger -x -i output.png --raw
+
+    # Embed secrets/NAME.* into each covers/NAME.png, writing out/NAME.png
+    pnger embed-batch --input-dir covers --payload-dir secrets --output-dir out --lsb-password \"mypassword\"")]
+#[command(subcommand_negates_reqs = true, args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input PNG file
-    #[arg(short, long, value_name = "FILE")]
-    pub input: PathBuf,
+    #[arg(short, long, value_name = "FILE", required = true)]
+    pub input: Option<PathBuf>,
 
     /// Payload file to embed
     #[arg(short, long, value_name = "FILE")]
@@ -78,14 +90,32 @@ pub struct Cli {
     #[arg(long, conflicts_with = "output")]
     pub raw: bool,
 
-    /// Embedding strategy to use
-    #[arg(short, long, value_enum, default_value_t = StrategyArg::Lsb)]
-    pub strategy: StrategyArg,
-
     /// Extract payload from input file
     #[arg(short = 'x', long)]
     pub extract: bool,
 
+    #[command(flatten)]
+    pub embedding: EmbeddingArgs,
+
+    /// Re-extract the payload after embedding and check it matches before writing the output
+    #[arg(long, conflicts_with = "extract")]
+    pub verify: bool,
+}
+
+/// Subcommands, as alternatives to the single-image flags.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Embed a payload into each cover image of a directory
+    EmbedBatch(EmbedBatchArgs),
+}
+
+/// Strategy and obfuscation flags, shared by single-image and batch modes.
+#[derive(Args)]
+pub struct EmbeddingArgs {
+    /// Embedding strategy to use
+    #[arg(short, long, value_enum, default_value_t = StrategyArg::Lsb)]
+    pub strategy: StrategyArg,
+
     /// Toggle payload obfuscation with XOR algorithm. By default a hard-coded value is used. The --xor-key flag can be used to customize the XOR key
     #[arg(long)]
     pub xor: bool,
@@ -118,6 +148,38 @@ impl Cli {
         cli.validate().map(|_| cli)
     }
 
+    /// Input PNG file, required unless a subcommand is used
+    pub fn input(&self) -> &Path {
+        self.input
+            .as_deref()
+            .expect("clap requires --input without a subcommand")
+    }
+
+    pub fn get_options(&self) -> Result<EmbeddingOptions, anyhow::Error> {
+        self.embedding.get_options()
+    }
+
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.command.is_some() {
+            return Ok(());
+        }
+
+        // either --output or --raw must be specified
+        if self.output.is_none() && !self.raw {
+            bail!(
+                "Error: must specify either --output <FILE> or --raw for output method.\nUse --help for more information."
+            );
+        }
+
+        if !self.extract && self.payload.is_none() {
+            bail!("Error: a payload file has to be specified with --payload")
+        }
+
+        Ok(())
+    }
+}
+
+impl EmbeddingArgs {
     /// Convert CLI arguments to Strategy
     fn get_strategy(&self) -> anyhow::Result<Strategy> {
         match self.strategy {
@@ -172,19 +234,4 @@ impl Cli {
         }
         Ok(options)
     }
-
-    fn validate(&self) -> anyhow::Result<()> {
-        // either --output or --raw must be specified
-        if self.output.is_none() && !self.raw {
-            bail!(
-                "Error: must specify either --output <FILE> or --raw for output method.\nUse --help for more information."
-            );
-        }
-
-        if !self.extract && self.payload.is_none() {
-            bail!("Error: a payload file has to be specified with --payload")
-        }
-
-        Ok(())
-    }
 }
@@ -0,0 +1,509 @@
+//! Cover image formats.
+//!
+//! The strategies work on raw samples and do not care which file format they
+//! came from. A [`CoverCodec`] decodes a cover file into samples and
+//! re-encodes the modified samples into the same format, so every strategy
+//! can be used with any supported format. [`PngCodec`] and [`BmpCodec`] are
+//! provided; [`embed_payload_into_cover`] and [`extract_payload_from_cover`]
+//! pick the codec from the file signature.
+
+use crate::{
+    EmbeddingOptions, PNG_SIGNATURE, PngerError, Strategy, decode_png_info, embed_into_buffer,
+    encode_png_with_data, extract_from_buffer, obfuscate, obfuscation, read_image_data,
+    strategy::{dct::DCTEmbedder, lsb::PixelLayout, pvd::PVDEmbedder},
+    utils::{AlphaLayout, with_color_samples},
+};
+
+/// Samples of a decoded cover image, with what is needed to re-encode them.
+#[derive(Debug, Clone)]
+pub struct DecodedCover<M> {
+    /// Pixel samples in row order, one or more bytes per sample.
+    pub samples: Vec<u8>,
+    /// Layout of `samples`, counting bytes rather than samples in
+    /// `channels`.
+    pub layout: PixelLayout,
+    /// Number of trailing bytes of each pixel holding alpha, 0 without an
+    /// alpha channel.
+    pub alpha_bytes: usize,
+    /// Format-specific data needed to re-encode the image.
+    pub metadata: M,
+}
+
+impl<M> DecodedCover<M> {
+    fn alpha_layout(&self, options: &EmbeddingOptions) -> Option<AlphaLayout> {
+        let channels = self.layout.channels / self.alpha_bytes.max(1);
+        (self.alpha_bytes > 0 && options.skips_alpha(channels))
+            .then(|| AlphaLayout::new(channels, self.alpha_bytes))
+    }
+}
+
+/// A cover image file format.
+///
+/// Implement it to embed into formats PNGer does not support natively, then
+/// use [`embed_payload_with_codec`] and [`extract_payload_with_codec`]. The
+/// format must be lossless, or the embedded bits will not survive encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::codec::{BmpCodec, CoverCodec};
+///
+/// assert!(BmpCodec.detect(b"BM\x00\x00"));
+/// assert!(!BmpCodec.detect(b"\x89PNG"));
+/// ```
+pub trait CoverCodec {
+    /// Format-specific data kept between decoding and encoding, such as
+    /// headers.
+    type Metadata;
+
+    /// Checks whether `data` starts with the signature of this format.
+    fn detect(&self, data: &[u8]) -> bool;
+
+    /// Decodes a file into its samples.
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: `data` is not a valid file of this format
+    /// - `PngerError::UnsupportedMode`: The file uses a variant of the
+    ///   format that cannot carry a payload
+    fn decode(&self, data: &[u8]) -> Result<DecodedCover<Self::Metadata>, PngerError>;
+
+    /// Encodes samples previously returned by [`decode`](Self::decode),
+    /// possibly modified, back into a file.
+    ///
+    /// # Errors
+    /// Returns an error if encoding fails or the samples no longer match the
+    /// layout.
+    fn encode(&self, cover: &DecodedCover<Self::Metadata>) -> Result<Vec<u8>, PngerError>;
+}
+
+/// PNG covers, as used by the rest of the crate.
+///
+/// The original compression level is kept on encoding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PngCodec;
+
+impl CoverCodec for PngCodec {
+    type Metadata = png::Info<'static>;
+
+    fn detect(&self, data: &[u8]) -> bool {
+        data.starts_with(&PNG_SIGNATURE)
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<DecodedCover<Self::Metadata>, PngerError> {
+        let (mut reader, info) = decode_png_info(data)?;
+        let samples = read_image_data(&mut reader)?;
+        let alpha_bytes = AlphaLayout::from_png_info(&info)
+            .map_or(0, |_| usize::from(info.bit_depth as u8).div_ceil(8));
+        Ok(DecodedCover {
+            samples,
+            layout: PixelLayout::packed(
+                info.width as usize,
+                info.height as usize,
+                info.bytes_per_pixel(),
+            ),
+            alpha_bytes,
+            metadata: info,
+        })
+    }
+
+    fn encode(&self, cover: &DecodedCover<Self::Metadata>) -> Result<Vec<u8>, PngerError> {
+        encode_png_with_data(&cover.metadata, &cover.samples, None)
+    }
+}
+
+/// Uncompressed 24-bit and 32-bit Windows bitmaps.
+///
+/// Samples are in file order (blue, green, red, then the fourth byte for
+/// 32-bit images), with rows from top to bottom whatever the row order of
+/// the file. The fourth byte of 32-bit images is not treated as alpha, since
+/// uncompressed bitmaps usually leave it unused. Everything but the pixel
+/// samples, including row padding, is written back unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BmpCodec;
+
+/// Original file and pixel array position of a decoded bitmap.
+#[derive(Debug, Clone)]
+pub struct BmpMetadata {
+    file: Vec<u8>,
+    pixel_offset: usize,
+    row_stride: usize,
+    bottom_up: bool,
+}
+
+// BITMAPFILEHEADER, then at least a BITMAPINFOHEADER
+const BMP_FILE_HEADER_SIZE: usize = 14;
+const BMP_INFO_HEADER_SIZE: usize = 40;
+const BI_RGB: u32 = 0;
+
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(
+        data[offset..offset + 4]
+            .try_into()
+            .expect("slice of 4 bytes"),
+    )
+}
+
+impl BmpMetadata {
+    // Offset in the file of row `y`, counted from the top of the image
+    const fn row_offset(&self, y: usize, height: usize) -> usize {
+        let file_row = if self.bottom_up { height - 1 - y } else { y };
+        self.pixel_offset + file_row * self.row_stride
+    }
+}
+
+impl CoverCodec for BmpCodec {
+    type Metadata = BmpMetadata;
+
+    fn detect(&self, data: &[u8]) -> bool {
+        data.starts_with(b"BM")
+    }
+
+    fn decode(&self, data: &[u8]) -> Result<DecodedCover<Self::Metadata>, PngerError> {
+        if !self.detect(data) || data.len() < BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE {
+            return Err(PngerError::InvalidFormat("Not a BMP file".to_string()));
+        }
+        if (read_u32(data, BMP_FILE_HEADER_SIZE) as usize) < BMP_INFO_HEADER_SIZE {
+            return Err(PngerError::UnsupportedMode(
+                "BMP files with a core header are not supported".to_string(),
+            ));
+        }
+
+        let pixel_offset = read_u32(data, 10) as usize;
+        let width = read_u32(data, 18) as i32;
+        let height = read_u32(data, 22) as i32;
+        let bits_per_pixel = read_u16(data, 28);
+        let compression = read_u32(data, 30);
+        if !matches!(bits_per_pixel, 24 | 32) || compression != BI_RGB {
+            return Err(PngerError::UnsupportedMode(format!(
+                "Only uncompressed 24-bit and 32-bit BMP files are supported, \
+                 not {bits_per_pixel}-bit with compression {compression}"
+            )));
+        }
+        if width <= 0 || height == 0 {
+            return Err(PngerError::InvalidFormat(format!(
+                "Invalid BMP dimensions {width}x{height}"
+            )));
+        }
+
+        let bytes_per_pixel = usize::from(bits_per_pixel / 8);
+        let layout = PixelLayout::packed(
+            width as usize,
+            height.unsigned_abs() as usize,
+            bytes_per_pixel,
+        );
+        // Rows are padded to a multiple of 4 bytes
+        let row_stride = layout.row_len().div_ceil(4) * 4;
+        let metadata = BmpMetadata {
+            file: data.to_vec(),
+            pixel_offset,
+            row_stride,
+            bottom_up: height > 0,
+        };
+        let pixel_end = row_stride
+            .checked_mul(layout.height)
+            .and_then(|len| len.checked_add(pixel_offset));
+        if pixel_end.is_none_or(|end| end > data.len()) {
+            return Err(PngerError::InvalidFormat(
+                "BMP pixel array is truncated".to_string(),
+            ));
+        }
+
+        let mut samples = Vec::with_capacity(layout.sample_count());
+        for y in 0..layout.height {
+            let start = metadata.row_offset(y, layout.height);
+            samples.extend_from_slice(&data[start..start + layout.row_len()]);
+        }
+
+        Ok(DecodedCover {
+            samples,
+            layout,
+            alpha_bytes: 0,
+            metadata,
+        })
+    }
+
+    fn encode(&self, cover: &DecodedCover<Self::Metadata>) -> Result<Vec<u8>, PngerError> {
+        let layout = cover.layout;
+        if cover.samples.len() != layout.sample_count() {
+            return Err(PngerError::PayloadError {
+                message: format!(
+                    "Image buffer holds {} bytes but a {}x{} bitmap needs {}",
+                    cover.samples.len(),
+                    layout.width,
+                    layout.height,
+                    layout.sample_count()
+                ),
+            });
+        }
+
+        let mut file = cover.metadata.file.clone();
+        for (y, row) in cover.samples.chunks(layout.row_len()).enumerate() {
+            let start = cover.metadata.row_offset(y, layout.height);
+            file[start..start + row.len()].copy_from_slice(row);
+        }
+        Ok(file)
+    }
+}
+
+/// Embeds a payload into a cover file decoded with `codec`.
+///
+/// Works like [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options)
+/// for any format. Palette mode and the output compression override are
+/// PNG-specific and not available here.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::EmbeddingOptions;
+/// use pnger::codec::{BmpCodec, embed_payload_with_codec};
+///
+/// let bmp_data = std::fs::read("cover.bmp")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let result = embed_payload_with_codec(&BmpCodec, &bmp_data, b"payload", options)?;
+/// std::fs::write("output.bmp", result)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// - `PngerError::UnsupportedMode`: Palette mode is enabled
+/// - Any error of the codec or the embedding
+pub fn embed_payload_with_codec<C: CoverCodec, D: AsRef<[u8]>>(
+    codec: &C,
+    cover_data: &[u8],
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if options.palette_mode() {
+        return Err(palette_mode_png_only());
+    }
+    let mut cover = codec.decode(cover_data)?;
+    match &options.strategy {
+        Strategy::LSB(_) => {
+            let alpha = cover.alpha_layout(&options);
+            with_color_samples(&mut cover.samples, alpha, |carrier| {
+                embed_into_buffer(carrier, payload_data.as_ref(), options.clone())
+            })?;
+        }
+        Strategy::DCT(dct_config) => {
+            let payload_data = obfuscate(payload_data.as_ref(), &options)?;
+            DCTEmbedder::embed(&mut cover.samples, cover.layout, &payload_data, dct_config)?;
+        }
+        Strategy::PVD(pvd_config) => {
+            let payload_data = obfuscate(payload_data.as_ref(), &options)?;
+            PVDEmbedder::embed(&mut cover.samples, cover.layout, &payload_data, pvd_config)?;
+        }
+    }
+    codec.encode(&cover)
+}
+
+/// Extracts a payload from a cover file decoded with `codec`.
+///
+/// Counterpart of [`embed_payload_with_codec`].
+///
+/// # Errors
+/// - `PngerError::UnsupportedMode`: Palette mode is enabled
+/// - Any error of the codec or the extraction
+pub fn extract_payload_with_codec<C: CoverCodec>(
+    codec: &C,
+    cover_data: &[u8],
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if options.palette_mode() {
+        return Err(palette_mode_png_only());
+    }
+    let cover = codec.decode(cover_data)?;
+    let payload_data = match &options.strategy {
+        Strategy::LSB(_) => {
+            let mut carrier = match cover.alpha_layout(&options) {
+                Some(alpha) => alpha.color_samples(&cover.samples),
+                None => cover.samples,
+            };
+            return extract_from_buffer(&mut carrier, options);
+        }
+        Strategy::DCT(dct_config) => {
+            DCTEmbedder::extract(&cover.samples, cover.layout, dct_config)?
+        }
+        Strategy::PVD(_) => PVDEmbedder::extract(&cover.samples, cover.layout)?,
+    };
+    match options.obfuscation {
+        Some(obfuscation) => obfuscation::deobfuscate_payload(&payload_data, obfuscation),
+        None => Ok(payload_data),
+    }
+}
+
+/// Embeds a payload into a PNG or BMP cover, detected from its signature.
+///
+/// PNG covers go through [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options)
+/// and support every option; other formats use [`embed_payload_with_codec`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_payload_into_cover};
+///
+/// let cover_data = std::fs::read("cover.bmp")?;
+/// let result = embed_payload_into_cover(&cover_data, b"payload", EmbeddingOptions::default())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// - `PngerError::InvalidFormat`: The cover is neither a PNG nor a BMP file
+/// - Any error of the embedding
+pub fn embed_payload_into_cover<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    cover_data: P,
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let cover_data = cover_data.as_ref();
+    if PngCodec.detect(cover_data) {
+        crate::embed_payload_from_bytes_with_options(cover_data, payload_data, options)
+    } else if BmpCodec.detect(cover_data) {
+        embed_payload_with_codec(&BmpCodec, cover_data, payload_data, options)
+    } else {
+        Err(unknown_format())
+    }
+}
+
+/// Extracts a payload from a PNG or BMP cover, detected from its signature.
+///
+/// Counterpart of [`embed_payload_into_cover`].
+///
+/// # Errors
+/// - `PngerError::InvalidFormat`: The cover is neither a PNG nor a BMP file
+/// - Any error of the extraction
+pub fn extract_payload_from_cover<P: AsRef<[u8]>>(
+    cover_data: P,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let cover_data = cover_data.as_ref();
+    if PngCodec.detect(cover_data) {
+        crate::extract_payload_from_bytes_with_options(cover_data, options)
+    } else if BmpCodec.detect(cover_data) {
+        extract_payload_with_codec(&BmpCodec, cover_data, options)
+    } else {
+        Err(unknown_format())
+    }
+}
+
+fn palette_mode_png_only() -> PngerError {
+    PngerError::UnsupportedMode("Palette mode is only available for PNG covers".to_string())
+}
+
+fn unknown_format() -> PngerError {
+    PngerError::InvalidFormat("Unrecognized cover format, expected PNG or BMP".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{strategy::lsb::LSBConfig, test_utils::gradient_png};
+
+    /// Bottom-up bitmap with padded rows, filled with a gradient.
+    fn bmp(width: u32, height: i32, bits_per_pixel: u16) -> Vec<u8> {
+        let bytes_per_pixel = usize::from(bits_per_pixel / 8);
+        let row_stride = (width as usize * bytes_per_pixel).div_ceil(4) * 4;
+        let pixel_len = row_stride * height.unsigned_abs() as usize;
+        let pixel_offset = BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE;
+
+        let mut file = Vec::new();
+        file.extend_from_slice(b"BM");
+        file.extend_from_slice(&((pixel_offset + pixel_len) as u32).to_le_bytes());
+        file.extend_from_slice(&[0; 4]);
+        file.extend_from_slice(&(pixel_offset as u32).to_le_bytes());
+        file.extend_from_slice(&(BMP_INFO_HEADER_SIZE as u32).to_le_bytes());
+        file.extend_from_slice(&width.to_le_bytes());
+        file.extend_from_slice(&height.to_le_bytes());
+        file.extend_from_slice(&1u16.to_le_bytes());
+        file.extend_from_slice(&bits_per_pixel.to_le_bytes());
+        file.extend_from_slice(&BI_RGB.to_le_bytes());
+        file.extend_from_slice(&(pixel_len as u32).to_le_bytes());
+        file.extend_from_slice(&[0; 16]);
+        file.extend((0..pixel_len).map(|i| (i % 251) as u8));
+        file
+    }
+
+    #[test]
+    fn test_png_codec_matches_byte_api() {
+        // Grayscale-alpha images keep their alpha without any option, RGBA
+        // images only when alpha is preserved
+        for (color_type, options) in [
+            (png::ColorType::GrayscaleAlpha, EmbeddingOptions::linear()),
+            (png::ColorType::Rgba, EmbeddingOptions::linear()),
+            (
+                png::ColorType::Rgba,
+                EmbeddingOptions::linear().with_alpha_preserved(),
+            ),
+        ] {
+            let png_data = gradient_png(32, 32, color_type);
+            let payload = b"through the codec";
+            let embedded =
+                embed_payload_with_codec(&PngCodec, &png_data, payload, options.clone()).unwrap();
+
+            let expected =
+                crate::embed_payload_from_bytes_with_options(&png_data, payload, options.clone())
+                    .unwrap();
+            let (mut reader, _) = crate::decode_png_info(&embedded).unwrap();
+            let (mut expected_reader, _) = crate::decode_png_info(&expected).unwrap();
+            assert_eq!(
+                crate::read_image_data(&mut reader).unwrap(),
+                crate::read_image_data(&mut expected_reader).unwrap()
+            );
+            assert_eq!(
+                extract_payload_with_codec(&PngCodec, &embedded, options).unwrap(),
+                payload
+            );
+        }
+    }
+
+    #[test]
+    fn test_bmp_roundtrip() {
+        let payload = b"hidden in a bitmap";
+        for (height, bits_per_pixel) in [(30, 24), (-30, 24), (30, 32)] {
+            let cover = bmp(31, height, bits_per_pixel);
+            for options in [
+                EmbeddingOptions::linear(),
+                EmbeddingOptions::new(Strategy::LSB(LSBConfig::random().with_seed([4; 32]))),
+                EmbeddingOptions::new(Strategy::PVD(Default::default())),
+            ] {
+                let embedded = embed_payload_into_cover(&cover, payload, options.clone()).unwrap();
+
+                // Only pixel samples change: headers and row padding are kept
+                assert_eq!(embedded.len(), cover.len());
+                assert_eq!(embedded[..54], cover[..54]);
+                let row_len = 31 * usize::from(bits_per_pixel / 8);
+                let row_stride = row_len.div_ceil(4) * 4;
+                for row in 0..30 {
+                    let padding = 54 + row * row_stride + row_len..54 + (row + 1) * row_stride;
+                    assert_eq!(embedded[padding.clone()], cover[padding]);
+                }
+
+                let extracted = extract_payload_from_cover(&embedded, options).unwrap();
+                assert_eq!(extracted, payload);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bmp_rejects_unsupported_files() {
+        let mut cover = bmp(8, 8, 24);
+        cover[28] = 8;
+        assert!(matches!(
+            BmpCodec.decode(&cover),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+
+        let cover = bmp(8, 8, 24);
+        assert!(matches!(
+            BmpCodec.decode(&cover[..cover.len() - 1]),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            extract_payload_from_cover(b"GIF89a", EmbeddingOptions::linear()),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! Reusable decoded cover images.
+//!
+//! Embedding several payloads into the same PNG with the byte-based API decodes
+//! the image on every call. [`CoverImage`] decodes it once and hands out fresh
+//! copies of the pristine pixel buffer for each embedding.
+
+use std::path::Path;
+
+use crate::{
+    EmbeddingOptions, PngerError, check_carrier_format, decode_any_png, embed_decoded,
+    io::load_file, read_image_data,
+};
+
+/// A PNG cover image decoded once and reusable for many embeddings.
+///
+/// Each call to [`embed`](Self::embed) works on a copy of the original pixel
+/// data, so payloads never leak into one another and the cover stays pristine.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{CoverImage, EmbeddingOptions};
+///
+/// let cover = CoverImage::from_file("image.png")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+///
+/// for (i, payload) in [b"first", b"other"].iter().enumerate() {
+///     let png = cover.embed(payload, &options)?;
+///     std::fs::write(format!("output_{i}.png"), png)?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoverImage {
+    info: png::Info<'static>,
+    image_data: Vec<u8>,
+}
+
+impl CoverImage {
+    /// Decodes a cover image from PNG bytes.
+    ///
+    /// Any PNG image is accepted: whether it can carry a payload depends on
+    /// the options of each embedding, indexed images requiring palette mode
+    /// for example.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the data is not a valid PNG image.
+    pub fn from_bytes<P: AsRef<[u8]>>(png_data: P) -> Result<Self, PngerError> {
+        let (mut reader, info) = decode_any_png(png_data.as_ref())?;
+        let image_data = read_image_data(&mut reader)?;
+        Ok(Self { info, image_data })
+    }
+
+    /// Reads and decodes a cover image from a PNG file.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file cannot be read or is not
+    /// a valid PNG image.
+    pub fn from_file<P: AsRef<Path>>(png_path: P) -> Result<Self, PngerError> {
+        Self::from_bytes(load_file(png_path)?)
+    }
+
+    /// Embeds a payload into a copy of the cover and encodes it as PNG.
+    ///
+    /// Equivalent to [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options)
+    /// on the original PNG, without decoding it again: every strategy and
+    /// option is supported, including palette mode and strict minimal diff.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - The image type does not match the options, such as an indexed image
+    ///   outside palette mode (`PngerError::UnsupportedMode`)
+    /// - Embedding or PNG encoding fails, see
+    ///   [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options)
+    pub fn embed<D: AsRef<[u8]>>(
+        &self,
+        payload_data: D,
+        options: &EmbeddingOptions,
+    ) -> Result<Vec<u8>, PngerError> {
+        check_carrier_format(&self.info, options)?;
+        embed_decoded(
+            self.info.clone(),
+            self.image_data.clone(),
+            payload_data.as_ref(),
+            options,
+        )
+    }
+
+    /// Width of the cover image in pixels.
+    pub fn width(&self) -> u32 {
+        self.info.width
+    }
+
+    /// Height of the cover image in pixels.
+    pub fn height(&self) -> u32 {
+        self.info.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Strategy, embed_payload_from_bytes_with_options, extract_payload_from_bytes_with_options,
+        strategy::{dct::DCTConfig, lsb::LSBConfig, pvd::PVDConfig},
+        test_utils::{gradient_png, indexed_png},
+    };
+
+    #[test]
+    fn test_cover_image_multiple_payloads() {
+        let cover = CoverImage::from_bytes(gradient_png(64, 64, png::ColorType::Rgb)).unwrap();
+        assert_eq!((cover.width(), cover.height()), (64, 64));
+
+        let payloads: [&[u8]; 3] = [
+            b"first payload",
+            b"second",
+            b"the third and longest payload",
+        ];
+        let options = [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random(),
+            EmbeddingOptions::random_with_password("password").with_xor_string("key"),
+        ];
+
+        for (payload, options) in payloads.iter().zip(options) {
+            let png = cover.embed(payload, &options).unwrap();
+            let extracted = extract_payload_from_bytes_with_options(&png, options).unwrap();
+            assert_eq!(&extracted, payload);
+        }
+    }
+
+    #[test]
+    fn test_cover_image_matches_byte_api() {
+        // Every strategy and carrier goes through the shared embedding path
+        let rgb = gradient_png(64, 64, png::ColorType::Rgb);
+        let indexed = indexed_png(16, 16);
+        let cases = [
+            (&rgb, EmbeddingOptions::linear().strict_minimal_diff(true)),
+            (
+                &rgb,
+                EmbeddingOptions::random_with_password("password").with_xor_string("key"),
+            ),
+            (&rgb, EmbeddingOptions::new(Strategy::DCT(DCTConfig::new()))),
+            (&rgb, EmbeddingOptions::new(Strategy::PVD(PVDConfig::new()))),
+            (
+                &indexed,
+                EmbeddingOptions::new(Strategy::LSB(LSBConfig::linear().palette_mode())),
+            ),
+        ];
+        for (png_data, options) in cases {
+            let cover = CoverImage::from_bytes(png_data).unwrap();
+            let png = cover.embed(b"cached", &options).unwrap();
+            assert_eq!(
+                png,
+                embed_payload_from_bytes_with_options(png_data, b"cached", options.clone())
+                    .unwrap()
+            );
+            assert_eq!(
+                extract_payload_from_bytes_with_options(&png, options).unwrap(),
+                b"cached"
+            );
+        }
+
+        // The image type is checked against the options of each embedding
+        let cover = CoverImage::from_bytes(&indexed).unwrap();
+        assert!(matches!(
+            cover.embed(b"cached", &EmbeddingOptions::linear()),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+}
@@ -0,0 +1,185 @@
+//! Decoy payloads stored next to the real one.
+//!
+//! [`embed_dual`] writes a decoy payload into a visible `tEXt` chunk and the
+//! real payload into the pixels. Anyone inspecting the file finds the decoy
+//! with standard metadata tools, while the real payload needs the embedding
+//! options to be found at all, giving the owner of the image something
+//! plausible to disclose.
+
+use std::io::Cursor;
+
+use crate::{
+    EmbeddingOptions, PngerError, check_png_signature, embed_payload_from_bytes_with_options,
+    extract_payload_from_bytes_with_options,
+};
+
+/// Keyword of the `tEXt` chunk holding the decoy, one of the keywords
+/// registered by the PNG specification.
+pub const DECOY_KEYWORD: &str = "Comment";
+
+/// Embeds `real` into the pixels and `decoy` into a `Comment` text chunk.
+///
+/// The real payload is embedded with `options`, exactly like
+/// [`embed_payload_from_bytes_with_options`]. The decoy is then stored as is
+/// in a `tEXt` chunk with the [`DECOY_KEYWORD`] keyword, placed before the
+/// image data so that readers find it without decoding the pixels. `tEXt`
+/// chunks hold Latin-1 text: each decoy byte is one character, and NUL bytes
+/// are not allowed.
+///
+/// Other text chunks of the cover are dropped by the embedding, so the decoy
+/// is the only one in the output.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_dual, extract_dual};
+///
+/// let cover = std::fs::read("cover.png")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let stego = embed_dual(&cover, b"Holiday 2024", b"the real message", options.clone())?;
+///
+/// let (decoy, real) = extract_dual(&stego, options)?;
+/// assert_eq!(decoy, b"Holiday 2024");
+/// assert_eq!(real, b"the real message");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The decoy contains a NUL byte (`PngerError::InvalidFormat`)
+/// - Embedding the real payload fails, see
+///   [`embed_payload_from_bytes_with_options`]
+pub fn embed_dual<P: AsRef<[u8]>, D: AsRef<[u8]>, R: AsRef<[u8]>>(
+    png_data: P,
+    decoy: D,
+    real: R,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let decoy = decoy.as_ref();
+    if decoy.contains(&0) {
+        return Err(PngerError::InvalidFormat(
+            "The decoy is stored as Latin-1 text, which cannot contain NUL bytes".to_string(),
+        ));
+    }
+    let stego = embed_payload_from_bytes_with_options(png_data, real, options)?;
+
+    let mut text = Vec::with_capacity(DECOY_KEYWORD.len() + 1 + decoy.len());
+    text.extend_from_slice(DECOY_KEYWORD.as_bytes());
+    text.push(0);
+    text.extend_from_slice(decoy);
+    insert_before_image_data(&stego, b"tEXt", &text)
+}
+
+/// Extracts both payloads of an image produced by [`embed_dual`].
+///
+/// Returns the decoy, read from the first `tEXt` chunk with the
+/// [`DECOY_KEYWORD`] keyword, and the real payload, extracted with `options`
+/// like [`extract_payload_from_bytes_with_options`]. Each is read
+/// independently of the other.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image
+/// - No `Comment` text chunk is found (`PngerError::InvalidFormat`)
+/// - Extracting the real payload fails, see
+///   [`extract_payload_from_bytes_with_options`]
+pub fn extract_dual<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<(Vec<u8>, Vec<u8>), PngerError> {
+    let png_data = png_data.as_ref();
+    check_png_signature(png_data)?;
+    let reader = png::Decoder::new(Cursor::new(png_data)).read_info()?;
+    let decoy = reader
+        .info()
+        .uncompressed_latin1_text
+        .iter()
+        .find(|chunk| chunk.keyword == DECOY_KEYWORD)
+        .ok_or_else(|| PngerError::InvalidFormat(format!("No {DECOY_KEYWORD:?} text chunk found")))?
+        // Latin-1 characters are the bytes of the same value
+        .text
+        .chars()
+        .map(|character| character as u8)
+        .collect();
+
+    let real = extract_payload_from_bytes_with_options(png_data, options)?;
+    Ok((decoy, real))
+}
+
+/// Inserts a chunk before the first `IDAT` chunk of a PNG file.
+fn insert_before_image_data(
+    png_data: &[u8],
+    chunk_type: &[u8; 4],
+    data: &[u8],
+) -> Result<Vec<u8>, PngerError> {
+    let chunk_len = u32::try_from(data.len())
+        .map_err(|_| PngerError::InvalidFormat("Chunk data is too large".to_string()))?;
+
+    // Walk the chunks following the signature: length, type, data and CRC
+    let mut position = crate::PNG_SIGNATURE.len();
+    while let Some(&[a, b, c, d, ref kind @ ..]) = png_data.get(position..position + 8) {
+        if kind == b"IDAT" {
+            let mut chunk = chunk_type.to_vec();
+            chunk.extend_from_slice(data);
+            let crc = crc32fast::hash(&chunk);
+
+            let mut output = Vec::with_capacity(png_data.len() + chunk.len() + 8);
+            output.extend_from_slice(&png_data[..position]);
+            output.extend_from_slice(&chunk_len.to_be_bytes());
+            output.extend_from_slice(&chunk);
+            output.extend_from_slice(&crc.to_be_bytes());
+            output.extend_from_slice(&png_data[position..]);
+            return Ok(output);
+        }
+        position += 12 + u32::from_be_bytes([a, b, c, d]) as usize;
+    }
+    Err(PngerError::InvalidFormat(
+        "No image data chunk found".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::gradient_png;
+
+    #[test]
+    fn test_embed_dual_recovers_both_payloads() {
+        let cover = gradient_png(32, 32, png::ColorType::Rgb);
+
+        let options = EmbeddingOptions::random_with_password("dual");
+        let decoy = "Vacances à Nice"
+            .chars()
+            .map(|c| c as u8)
+            .collect::<Vec<_>>();
+        let stego = embed_dual(&cover, &decoy, b"real payload", options.clone()).unwrap();
+        assert_eq!(
+            extract_dual(&stego, options.clone()).unwrap(),
+            (decoy, b"real payload".to_vec())
+        );
+
+        // The real payload does not depend on the decoy, nor the decoy on
+        // the options
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&stego, options.clone()).unwrap(),
+            b"real payload"
+        );
+        let reader = png::Decoder::new(Cursor::new(stego.as_slice()))
+            .read_info()
+            .unwrap();
+        let text = &reader.info().uncompressed_latin1_text;
+        assert_eq!(text.len(), 1);
+        assert_eq!(text[0].text, "Vacances à Nice");
+
+        assert!(matches!(
+            extract_dual(&cover, options.clone()),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            embed_dual(&cover, b"nul\0byte", b"real", options),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+}
@@ -24,7 +24,7 @@
 //!
 //! match embed_payload_from_file("image.png", b"secret") {
 //!     Ok(result) => println!("Success!"),
-//!     Err(PngerError::PayloadTooLarge) => println!("Payload too large"),
+//!     Err(PngerError::PayloadTooLarge { .. }) => println!("Payload too large"),
 //!     Err(PngerError::FileIo(_)) => println!("File access error"),
 //!     Err(err) => println!("Other error: {}", err),
 //! }
@@ -39,9 +39,11 @@
 //! fn embed_with_retry(image_path: &str, payload: &[u8]) -> Result<Vec<u8>, String> {
 //!     match embed_payload_from_file(image_path, payload) {
 //!         Ok(result) => Ok(result),
-//!         Err(PngerError::PayloadTooLarge) => {
+//!         Err(PngerError::PayloadTooLarge { size, capacity }) => {
 //!             // Try with compression or smaller payload
-//!             Err("Payload too large, try compressing first".to_string())
+//!             Err(format!(
+//!                 "{size}-byte payload exceeds the {capacity}-byte capacity, try compressing first"
+//!             ))
 //!         }
 //!         Err(PngerError::FileIo(_)) => {
 //!             // Retry with different file path or check permissions
@@ -98,7 +100,7 @@ use thiserror::Error;
 ///         std::fs::write("output.png", result)?;
 ///         println!("Embedding successful!");
 ///     }
-///     Err(PngerError::PayloadTooLarge) => {
+///     Err(PngerError::PayloadTooLarge { .. }) => {
 ///         eprintln!("Error: The secret message is too large for this image.");
 ///         eprintln!("Try using a larger image or smaller payload.");
 ///     }
@@ -151,14 +153,22 @@ pub enum PngerError {
     /// - Payload size exceeds image pixel count (for LSB embedding)
     /// - Very small images with large payloads
     /// - Multiple payloads embedded in the same bit positions
+    /// - An [`Obfuscation::Custom`](crate::Obfuscation::Custom) transform growing
+    ///   the payload: capacity is checked after obfuscation, so `size` is the
+    ///   obfuscated size
     ///
     /// ## Solutions
     /// - Use a larger image with more pixels
     /// - Compress the payload before embedding
     /// - Split the payload across multiple images
     /// - Use different bit indices for multiple payloads
-    #[error("Payload is too large for the image")]
-    PayloadTooLarge,
+    #[error("Payload of {size} bytes is too large for the image, which holds {capacity} bytes")]
+    PayloadTooLarge {
+        /// Size of the payload as embedded, after obfuscation, in bytes.
+        size: usize,
+        /// Largest payload the image holds with the same options, in bytes.
+        capacity: usize,
+    },
 
     /// The image has insufficient capacity for the payload and metadata.
     ///
@@ -185,10 +195,10 @@ pub enum PngerError {
     ///
     /// ## Common Causes
     /// - Invalid embedding strategy parameters
-    /// - Unsupported PNG color modes
+    /// - Unsupported PNG color type and bit depth combinations (e.g. indexed 4-bit)
     /// - Invalid bit index values (> 7)
-    #[error("Unsupported embedding mode")]
-    UnsupportedMode,
+    #[error("Unsupported embedding mode: {0}")]
+    UnsupportedMode(String),
 
     /// A general I/O operation failed with additional context.
     ///
@@ -210,8 +220,10 @@ pub enum PngerError {
     /// This error occurs when the input data cannot be decoded as a valid PNG image.
     /// It wraps the underlying PNG library error for detailed diagnostics.
     ///
+    /// Data without the PNG signature, such as a JPEG, is rejected earlier
+    /// with [`InvalidFormat`](PngerError::InvalidFormat).
+    ///
     /// ## Common Causes
-    /// - File is not a PNG image
     /// - PNG data is corrupted or truncated
     /// - Unsupported PNG variants or extensions
     /// - Invalid PNG headers or chunk data
@@ -320,6 +332,7 @@ pub enum PngerError {
     /// that prevent successful processing.
     ///
     /// ## Common Causes
+    /// - Input is not a PNG image (`not a PNG: bad signature`)
     /// - Steganographic headers are corrupted
     /// - No embedded payload found in the image
     /// - Incompatible embedding format versions
@@ -327,3 +340,100 @@ pub enum PngerError {
     #[error("Invalid file format: {0}")]
     InvalidFormat(String),
 }
+
+impl PngerError {
+    /// Returns actionable guidance for the user, when there is any.
+    ///
+    /// The [`Display`](std::fmt::Display) message says what went wrong; the
+    /// hint says what to try next, so command-line and graphical tools can
+    /// show both without maintaining their own remediation text. Errors that
+    /// point to a bug or an environment failure rather than a user mistake
+    /// have no hint.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::{PngerError, embed_payload_from_bytes};
+    ///
+    /// # let png_data = Vec::new();
+    /// if let Err(err) = embed_payload_from_bytes(&png_data, b"secret") {
+    ///     eprintln!("error: {err}");
+    ///     if let Some(hint) = err.hint() {
+    ///         eprintln!("hint: {hint}");
+    ///     }
+    /// }
+    /// assert!(PngerError::PayloadTooLarge { size: 10, capacity: 4 }.hint().is_some());
+    /// ```
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::PayloadTooLarge { .. } => {
+                Some("Use a larger image, compress the payload, or split it across several images")
+            }
+            Self::InsufficientCapacity => Some(
+                "Use a larger image, or a password or manual seed so that no seed is stored in the image",
+            ),
+            Self::UnsupportedMode(_) => Some(
+                "Convert the image to 8-bit grayscale, RGB or RGBA, or use options supported by the chosen strategy",
+            ),
+            Self::PngDecodingError(_) => {
+                Some("Check that the file is a complete PNG image and was not truncated")
+            }
+            Self::FileIo(_) => {
+                Some("Check that the path exists and that it can be read or written")
+            }
+            Self::CryptoError(_) => {
+                Some("Check that the password or seed is the one used for embedding")
+            }
+            Self::RandomGenerationFailed => {
+                Some("Provide a password or manual seed instead of an auto-generated seed")
+            }
+            Self::InvalidSeedLength => Some("Seeds must be exactly 32 bytes long"),
+            Self::InvalidFormat(_) => Some(
+                "Check that the image holds a payload and that the extraction options (strategy, password, bit index, namespace) match the embedding ones",
+            ),
+            Self::IoError { .. }
+            | Self::PngEncodingError(_)
+            | Self::PayloadError { .. }
+            | Self::InvalidSaltLength => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints() {
+        let error = PngerError::PayloadTooLarge {
+            size: 10,
+            capacity: 4,
+        };
+        assert!(
+            error
+                .hint()
+                .is_some_and(|hint| hint.contains("larger image"))
+        );
+        assert_eq!(
+            error.to_string(),
+            "Payload of 10 bytes is too large for the image, which holds 4 bytes"
+        );
+        assert!(
+            PngerError::CryptoError("derivation failed".to_string())
+                .hint()
+                .is_some_and(|hint| hint.contains("password"))
+        );
+        assert!(
+            PngerError::InvalidFormat("Invalid magic number".to_string())
+                .hint()
+                .is_some_and(|hint| hint.contains("extraction options"))
+        );
+        assert_eq!(
+            PngerError::IoError {
+                message: "buffer".to_string()
+            }
+            .hint(),
+            None
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Histogram changes introduced by an embedding.
+//!
+//! LSB replacement only ever moves a sample between the two values of a
+//! pair `(2k, 2k + 1)`, so the counts of each pair keep their sum: the
+//! "pairs of values" signature chi-square steganalysis looks for. LSB
+//! matching, which adds or subtracts 1 at random instead of setting the bit,
+//! moves samples across pairs as well. [`histogram_delta`] quantifies how
+//! an embedding shifted each channel's histogram.
+
+use crate::{PngerError, read_whole_image};
+
+/// Returns the per-channel histogram differences between a cover and a
+/// stego image.
+///
+/// Entry `[channel][value]` is the number of samples of `channel` holding
+/// `value` in `stego_png`, minus that number in `original_png`. Channels
+/// follow the sample order of the color type, alpha included. With LSB
+/// replacement in bit 0, `delta[c][2k] + delta[c][2k + 1]` is zero for every
+/// pair; nonzero pair sums reveal changes crossing pairs, as LSB matching
+/// does.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::histogram_delta;
+///
+/// let cover = std::fs::read("cover.png")?;
+/// let stego = std::fs::read("output.png")?;
+/// for (channel, delta) in histogram_delta(&cover, &stego)?.iter().enumerate() {
+///     let moved: i64 = delta.iter().map(|count| count.abs()).sum::<i64>() / 2;
+///     println!("channel {channel}: {moved} samples moved");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - Either image is not a valid 8-bit PNG image supported for embedding
+/// - The images differ in dimensions or channel count
+///   (`PngerError::InvalidFormat`)
+pub fn histogram_delta<O: AsRef<[u8]>, S: AsRef<[u8]>>(
+    original_png: O,
+    stego_png: S,
+) -> Result<Vec<[i64; 256]>, PngerError> {
+    let (original, original_layout) = read_whole_image(original_png.as_ref())?;
+    let (stego, stego_layout) = read_whole_image(stego_png.as_ref())?;
+    if original_layout != stego_layout {
+        return Err(PngerError::InvalidFormat(format!(
+            "Images differ: {}x{} with {} channels, {}x{} with {} channels",
+            original_layout.width,
+            original_layout.height,
+            original_layout.channels,
+            stego_layout.width,
+            stego_layout.height,
+            stego_layout.channels
+        )));
+    }
+
+    let mut delta = vec![[0i64; 256]; original_layout.channels];
+    let pixels = original
+        .chunks_exact(original_layout.channels)
+        .zip(stego.chunks_exact(stego_layout.channels));
+    for (before, after) in pixels {
+        for (channel, (&before, &after)) in before.iter().zip(after).enumerate() {
+            delta[channel][usize::from(before)] -= 1;
+            delta[channel][usize::from(after)] += 1;
+        }
+    }
+    Ok(delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EmbeddingOptions, embed_payload_from_bytes_with_options, test_utils::encode_png};
+
+    fn encode_rgb(width: u32, height: u32, image_data: &[u8]) -> Vec<u8> {
+        encode_png(width, height, png::ColorType::Rgb, image_data)
+    }
+
+    // Sums of the counts of each value pair (2k, 2k + 1)
+    fn pair_sums(delta: &[i64; 256]) -> Vec<i64> {
+        delta
+            .chunks_exact(2)
+            .map(|pair| pair[0] + pair[1])
+            .collect()
+    }
+
+    #[test]
+    fn test_lsb_replacement_shifts_within_pairs() {
+        let cover_data: Vec<u8> = (0..48 * 48 * 3).map(|i| (i * 37 % 253) as u8).collect();
+        let cover = encode_rgb(48, 48, &cover_data);
+        let payload: Vec<u8> = (0..400).map(|i| (i * 91 % 256) as u8).collect();
+        let stego =
+            embed_payload_from_bytes_with_options(&cover, &payload, EmbeddingOptions::linear())
+                .unwrap();
+
+        let delta = histogram_delta(&cover, &stego).unwrap();
+        assert_eq!(delta.len(), 3);
+        for channel in &delta {
+            assert!(channel.iter().any(|&count| count != 0));
+            assert!(pair_sums(channel).iter().all(|&sum| sum == 0));
+        }
+
+        // LSB matching: the same samples change, by 1 in either direction
+        let (stego_data, _) = read_whole_image(&stego).unwrap();
+        let matched_data: Vec<u8> = cover_data
+            .iter()
+            .zip(&stego_data)
+            .enumerate()
+            .map(|(index, (&before, &after))| match before {
+                _ if before == after => before,
+                0 => 1,
+                255 => 254,
+                _ if index % 2 == 0 => before + 1,
+                _ => before - 1,
+            })
+            .collect();
+        let matched = encode_rgb(48, 48, &matched_data);
+        let delta = histogram_delta(&cover, &matched).unwrap();
+        for channel in &delta {
+            assert!(pair_sums(channel).iter().any(|&sum| sum != 0));
+        }
+
+        assert!(matches!(
+            histogram_delta(&cover, encode_rgb(24, 96, &cover_data)),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+}
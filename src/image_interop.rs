@@ -0,0 +1,213 @@
+//! Interoperability with the [`image`] crate.
+//!
+//! These functions embed into and extract from decoded [`DynamicImage`] buffers
+//! directly, without going through PNG bytes. Samples are processed in the same
+//! order as the PNG-based API: 8-bit images use their raw channel bytes, and
+//! 16-bit images use the big-endian byte representation of each sample, as
+//! found in a decoded PNG scanline. A payload embedded through one API can
+//! therefore be extracted through the other once the image is saved as PNG.
+//!
+//! Floating point images have no PNG equivalent and are rejected with
+//! [`PngerError::UnsupportedMode`].
+
+use image::DynamicImage;
+
+use crate::{
+    EmbeddingOptions, PngerError, embed_into_buffer, extract_from_buffer,
+    utils::{AlphaLayout, with_color_samples},
+};
+
+/// Embeds a payload into a decoded image in place.
+///
+/// Works like [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options)
+/// but operates on the pixel buffer of an `image` crate [`DynamicImage`],
+/// leaving encoding to the caller.
+///
+/// # Examples
+///
+/// ```rust
+/// use image::{DynamicImage, RgbaImage};
+/// use pnger::{EmbeddingOptions, embed_into_image, extract_from_image};
+///
+/// let mut img = DynamicImage::ImageRgba8(RgbaImage::new(64, 64));
+/// let options = EmbeddingOptions::random_with_password("secret");
+///
+/// embed_into_image(&mut img, b"hidden", options.clone())?;
+/// assert_eq!(extract_from_image(&img, options)?, b"hidden");
+/// # Ok::<(), pnger::PngerError>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The image uses floating point samples (`PngerError::UnsupportedMode`)
+/// - The embedding itself fails (see [`LSBEmbedder::embed`](crate::strategy::lsb::LSBEmbedder::embed))
+pub fn embed_into_image(
+    img: &mut DynamicImage,
+    payload_data: &[u8],
+    options: EmbeddingOptions,
+) -> Result<(), PngerError> {
+    let alpha = image_alpha_layout(img, &options);
+    let embed = |carrier: &mut [u8]| embed_into_buffer(carrier, payload_data, options);
+    match img {
+        DynamicImage::ImageLuma8(buffer) => with_color_samples(buffer, alpha, embed),
+        DynamicImage::ImageLumaA8(buffer) => with_color_samples(buffer, alpha, embed),
+        DynamicImage::ImageRgb8(buffer) => with_color_samples(buffer, alpha, embed),
+        DynamicImage::ImageRgba8(buffer) => with_color_samples(buffer, alpha, embed),
+        DynamicImage::ImageLuma16(buffer) => embed_into_samples(buffer, alpha, embed),
+        DynamicImage::ImageLumaA16(buffer) => embed_into_samples(buffer, alpha, embed),
+        DynamicImage::ImageRgb16(buffer) => embed_into_samples(buffer, alpha, embed),
+        DynamicImage::ImageRgba16(buffer) => embed_into_samples(buffer, alpha, embed),
+        _ => Err(unsupported_image()),
+    }
+}
+
+/// Extracts a payload from a decoded image.
+///
+/// Works like [`extract_payload_from_bytes_with_options`](crate::extract_payload_from_bytes_with_options)
+/// on the pixel buffer of an `image` crate [`DynamicImage`]. The image is not
+/// modified.
+///
+/// # Examples
+///
+/// ```rust
+/// use image::{DynamicImage, RgbImage};
+/// use pnger::{EmbeddingOptions, embed_into_image, extract_from_image};
+///
+/// let mut img = DynamicImage::ImageRgb8(RgbImage::new(32, 32));
+/// embed_into_image(&mut img, b"payload", EmbeddingOptions::linear())?;
+///
+/// let payload = extract_from_image(&img, EmbeddingOptions::linear())?;
+/// assert_eq!(payload, b"payload");
+/// # Ok::<(), pnger::PngerError>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The image uses floating point samples (`PngerError::UnsupportedMode`)
+/// - The extraction itself fails (see [`LSBEmbedder::extract`](crate::strategy::lsb::LSBEmbedder::extract))
+pub fn extract_from_image(
+    img: &DynamicImage,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let image_data = match img {
+        DynamicImage::ImageLuma8(_)
+        | DynamicImage::ImageLumaA8(_)
+        | DynamicImage::ImageRgb8(_)
+        | DynamicImage::ImageRgba8(_) => img.as_bytes().to_vec(),
+        DynamicImage::ImageLuma16(buffer) => samples_to_be_bytes(buffer),
+        DynamicImage::ImageLumaA16(buffer) => samples_to_be_bytes(buffer),
+        DynamicImage::ImageRgb16(buffer) => samples_to_be_bytes(buffer),
+        DynamicImage::ImageRgba16(buffer) => samples_to_be_bytes(buffer),
+        _ => return Err(unsupported_image()),
+    };
+    let mut image_data = match image_alpha_layout(img, &options) {
+        Some(alpha) => alpha.color_samples(&image_data),
+        None => image_data,
+    };
+    extract_from_buffer(&mut image_data, options)
+}
+
+/// Alpha layout to skip with `options`, if the image has any.
+fn image_alpha_layout(img: &DynamicImage, options: &EmbeddingOptions) -> Option<AlphaLayout> {
+    let color = img.color();
+    let channels = usize::from(color.channel_count());
+    (color.has_alpha() && options.skips_alpha(channels))
+        .then(|| AlphaLayout::new(channels, usize::from(color.bytes_per_pixel()) / channels))
+}
+
+/// Embeds into 16-bit samples through their big-endian byte representation.
+fn embed_into_samples(
+    samples: &mut [u16],
+    alpha: Option<AlphaLayout>,
+    embed: impl FnOnce(&mut [u8]) -> Result<(), PngerError>,
+) -> Result<(), PngerError> {
+    let mut image_data = samples_to_be_bytes(samples);
+    with_color_samples(&mut image_data, alpha, embed)?;
+    samples
+        .iter_mut()
+        .zip(image_data.chunks_exact(2))
+        .for_each(|(sample, bytes)| *sample = u16::from_be_bytes([bytes[0], bytes[1]]));
+    Ok(())
+}
+
+fn unsupported_image() -> PngerError {
+    PngerError::UnsupportedMode("floating point images are not supported".to_string())
+}
+
+fn samples_to_be_bytes(samples: &[u16]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| sample.to_be_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb, RgbaImage};
+
+    #[test]
+    fn test_rgba_image_roundtrip() {
+        let original = RgbaImage::from_fn(64, 64, |x, y| {
+            image::Rgba([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8, 255])
+        });
+        let mut img = DynamicImage::ImageRgba8(original.clone());
+        let payload = b"Hello from the image crate!";
+        let options = EmbeddingOptions::random_with_password("password").with_xor_string("key");
+
+        embed_into_image(&mut img, payload, options.clone()).unwrap();
+        assert_ne!(img.as_bytes(), original.as_raw().as_slice());
+
+        let extracted = extract_from_image(&img, options).unwrap();
+        assert_eq!(extracted, payload);
+    }
+
+    #[test]
+    fn test_sixteen_bit_matches_png_sample_order() {
+        let buffer: ImageBuffer<Rgb<u16>, Vec<u16>> =
+            ImageBuffer::from_fn(32, 32, |x, y| Rgb([x as u16 * 1000, y as u16 * 1000, 0]));
+        let mut img = DynamicImage::ImageRgb16(buffer);
+        let payload = b"16-bit payload";
+
+        embed_into_image(&mut img, payload, EmbeddingOptions::linear()).unwrap();
+
+        // The payload is readable from the big-endian bytes a PNG decoder yields
+        let DynamicImage::ImageRgb16(buffer) = &img else {
+            unreachable!()
+        };
+        let mut png_order = samples_to_be_bytes(buffer);
+        let extracted = extract_from_buffer(&mut png_order, EmbeddingOptions::linear()).unwrap();
+        assert_eq!(extracted, payload);
+    }
+
+    #[test]
+    fn test_preserve_alpha_leaves_alpha_untouched() {
+        let original = RgbaImage::from_fn(32, 32, |x, y| {
+            image::Rgba([x as u8, y as u8, (x ^ y) as u8, (x * 8) as u8])
+        });
+        let mut img = DynamicImage::ImageRgba8(original.clone());
+        let options = EmbeddingOptions::linear().with_alpha_preserved();
+
+        embed_into_image(&mut img, b"opaque to alpha", options.clone()).unwrap();
+        let embedded = img.as_rgba8().unwrap();
+        assert!(
+            embedded
+                .pixels()
+                .zip(original.pixels())
+                .all(|(embedded, original)| embedded[3] == original[3])
+        );
+        assert_eq!(
+            extract_from_image(&img, options).unwrap(),
+            b"opaque to alpha"
+        );
+    }
+
+    #[test]
+    fn test_float_image_unsupported() {
+        let mut img = DynamicImage::new_rgb32f(16, 16);
+        let result = embed_into_image(&mut img, b"test", EmbeddingOptions::linear());
+        assert!(matches!(result, Err(PngerError::UnsupportedMode(_))));
+    }
+}
@@ -1,11 +1,188 @@
 use std::fs::File;
-use std::io::{self, Read};
+use std::io;
 use std::path::Path;
 
+/// Contents of a file, memory-mapped with the `mmap` feature
+///
+/// Mapping avoids copying gigabyte-scale images into memory: pages are read
+/// from disk as the decoder reaches them.
+#[cfg(feature = "mmap")]
+pub fn load_file<P: AsRef<Path>>(path: P) -> io::Result<memmap2::Mmap> {
+    let file = File::open(path)?;
+    // SAFETY: the map is read-only and dropped before the calling API
+    // returns. Changes to the file by other processes while it is mapped
+    // show up in the decoded data, as documented on the file-based APIs;
+    // truncation may terminate the process with SIGBUS.
+    unsafe { memmap2::Mmap::map(&file) }
+}
+
 /// Read a file from disk
-pub fn read_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+#[cfg(not(feature = "mmap"))]
+pub fn load_file<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
+    use std::io::Read;
+
     let mut file = File::open(path)?;
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)?;
     Ok(buffer)
 }
+
+/// Magic bytes starting every gzip stream
+#[cfg(feature = "gzip")]
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Decompresses `data` if it is a gzip stream, and borrows it otherwise
+#[cfg(feature = "gzip")]
+fn gunzip_if_compressed(data: &[u8]) -> io::Result<std::borrow::Cow<'_, [u8]>> {
+    use std::io::Read;
+
+    if !data.starts_with(&GZIP_MAGIC) {
+        return Ok(std::borrow::Cow::Borrowed(data));
+    }
+    let mut decompressed = Vec::new();
+    flate2::read::GzDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(std::borrow::Cow::Owned(decompressed))
+}
+
+/// Embeds a payload into a PNG file and writes the result gzip-compressed.
+///
+/// Convenience for transport: the stego PNG is wrapped in a gzip stream and
+/// written to `output_path`, conventionally ending in `.png.gz`. The cover
+/// may itself be gzipped, which is detected from the gzip magic. Embedding
+/// works like [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options);
+/// gzip only wraps the finished image, so the pixels are unaffected.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_payload_to_gz_file, extract_payload_from_gz_file};
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// embed_payload_to_gz_file("cover.png", "stego.png.gz", b"payload", options.clone())?;
+/// let payload = extract_payload_from_gz_file("stego.png.gz", options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The cover cannot be read or decompressed, or the output cannot be
+///   written (`PngerError::FileIo`)
+/// - The embedding fails (see [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options))
+#[cfg(feature = "gzip")]
+pub fn embed_payload_to_gz_file<P: AsRef<Path>, Q: AsRef<Path>, D: AsRef<[u8]>>(
+    cover_path: P,
+    output_path: Q,
+    payload_data: D,
+    options: crate::EmbeddingOptions,
+) -> Result<(), crate::PngerError> {
+    use std::io::Write;
+
+    let cover = load_file(cover_path)?;
+    let png_data = crate::embed_payload_from_bytes_with_options(
+        gunzip_if_compressed(&cover)?,
+        payload_data,
+        options,
+    )?;
+
+    let file = io::BufWriter::new(File::create(output_path)?);
+    let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    encoder.write_all(&png_data)?;
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+/// Extracts a payload from a PNG file, gunzipping it first if needed.
+///
+/// Counterpart of [`embed_payload_to_gz_file`]. Files starting with the
+/// gzip magic are decompressed, others are read as plain PNG files, so both
+/// forms of a stego image are accepted.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The file cannot be read or decompressed (`PngerError::FileIo`)
+/// - The extraction fails (see [`extract_payload_from_bytes_with_options`](crate::extract_payload_from_bytes_with_options))
+#[cfg(feature = "gzip")]
+pub fn extract_payload_from_gz_file<P: AsRef<Path>>(
+    path: P,
+    options: crate::EmbeddingOptions,
+) -> Result<Vec<u8>, crate::PngerError> {
+    let data = load_file(path)?;
+    crate::extract_payload_from_bytes_with_options(gunzip_if_compressed(&data)?, options)
+}
+
+#[cfg(all(test, any(feature = "mmap", feature = "gzip")))]
+mod tests {
+    use super::*;
+    use crate::{EmbeddingOptions, test_utils::gradient_png};
+
+    fn cover_png() -> Vec<u8> {
+        gradient_png(32, 32, png::ColorType::Rgb)
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gz_file_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("pnger-gzip-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cover = dir.join("cover.png");
+        let stego = dir.join("stego.png.gz");
+        std::fs::write(&cover, cover_png()).unwrap();
+
+        let options = EmbeddingOptions::linear();
+        embed_payload_to_gz_file(&cover, &stego, b"zipped payload", options.clone()).unwrap();
+        let gz_data = std::fs::read(&stego).unwrap();
+        assert!(gz_data.starts_with(&GZIP_MAGIC));
+        assert_eq!(
+            extract_payload_from_gz_file(&stego, options.clone()).unwrap(),
+            b"zipped payload"
+        );
+
+        // Plain PNG files are read as they are
+        let png_data = gunzip_if_compressed(&gz_data).unwrap().into_owned();
+        let plain = dir.join("stego.png");
+        std::fs::write(&plain, png_data).unwrap();
+        assert_eq!(
+            extract_payload_from_gz_file(&plain, options).unwrap(),
+            b"zipped payload"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_mapped_file_roundtrip() {
+        use crate::{
+            PngerError, embed_payload_from_file_with_options,
+            extract_payload_from_file_with_options,
+        };
+
+        let dir = std::env::temp_dir().join(format!("pnger-mmap-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cover = dir.join("cover.png");
+        let stego = dir.join("stego.png");
+        let png_data = cover_png();
+        std::fs::write(&cover, &png_data).unwrap();
+        assert_eq!(&load_file(&cover).unwrap()[..], &png_data[..]);
+
+        let options = EmbeddingOptions::random_with_password("mapped");
+        let embedded =
+            embed_payload_from_file_with_options(&cover, b"mapped payload", options.clone())
+                .unwrap();
+        std::fs::write(&stego, embedded).unwrap();
+        assert_eq!(
+            extract_payload_from_file_with_options(&stego, options).unwrap(),
+            b"mapped payload"
+        );
+
+        let missing = extract_payload_from_file_with_options(
+            dir.join("missing.png"),
+            EmbeddingOptions::linear(),
+        );
+        assert!(matches!(missing, Err(PngerError::FileIo(_))));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
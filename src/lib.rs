@@ -122,26 +122,77 @@
 //! - **Format Errors**: Invalid PNG structure or corrupted data
 
 use std::{
+    borrow::Cow,
+    collections::HashSet,
+    hash::{Hash, Hasher},
     io::{BufWriter, Cursor},
     path::Path,
+    sync::Arc,
 };
 
+// Declared first so its macros are visible in every other module
+#[macro_use]
+mod logging;
+mod apng;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bitplane;
+mod capabilities;
+pub mod codec;
+mod cover;
+mod dual;
 pub mod error;
+mod histogram;
+#[cfg(feature = "image")]
+mod image_interop;
 mod io;
 pub mod obfuscation;
+pub mod prelude;
+mod region;
+mod sanitize;
+mod spec;
+mod split;
 pub mod strategy;
+#[cfg(test)]
+mod test_utils;
+mod text;
 mod utils;
 
 type PayloadSize = u32;
 
 // Re-exports for public API
+pub use crate::apng::{embed_payload_into_frame, extract_payload_from_frame};
+#[cfg(feature = "tokio")]
+pub use crate::async_io::{embed_payload_from_file_async, extract_payload_from_file_async};
+pub use crate::bitplane::export_bit_plane;
+pub use crate::capabilities::{Capabilities, supported_capabilities};
+pub use crate::codec::{embed_payload_into_cover, extract_payload_from_cover};
+pub use crate::cover::CoverImage;
+pub use crate::dual::{DECOY_KEYWORD, embed_dual, extract_dual};
+pub use crate::histogram::histogram_delta;
 pub use crate::obfuscation::Obfuscation;
+pub use crate::region::{
+    Region, embed_payload_in_region, embed_payload_in_rows, extract_payload_from_region,
+    extract_payload_from_rows,
+};
+pub use crate::sanitize::sanitize_metadata;
+pub use crate::split::{
+    MANIFEST_VERSION, Manifest, ManifestPart, embed_payload_auto_split,
+    extract_payload_with_manifest,
+};
 pub use crate::strategy::Strategy;
-use crate::strategy::lsb::LSBEmbedder;
+use crate::strategy::dct::{DCTConfig, DCTEmbedder};
+use crate::strategy::lsb::{LSBEmbedder, PixelLayout};
+use crate::strategy::pvd::PVDEmbedder;
+pub use crate::text::TextEncoding;
 pub use error::PngerError;
+#[cfg(feature = "image")]
+pub use image_interop::{embed_into_image, extract_from_image};
+#[cfg(feature = "gzip")]
+pub use io::{embed_payload_to_gz_file, extract_payload_from_gz_file};
 
-use io::read_file;
-use utils::setup_png_encoder;
+use io::load_file;
+use utils::{AlphaLayout, setup_png_encoder, validate_color_format, with_color_samples};
 
 /// Configuration options for payload embedding and extraction operations.
 ///
@@ -208,6 +259,9 @@ use utils::setup_png_encoder;
 pub struct EmbeddingOptions {
     strategy: Strategy,
     obfuscation: Option<Obfuscation>,
+    preserve_alpha: bool,
+    output_compression: Option<png::Compression>,
+    strict_minimal_diff: bool,
 }
 
 impl EmbeddingOptions {
@@ -230,6 +284,9 @@ impl EmbeddingOptions {
         Self {
             strategy,
             obfuscation: None,
+            preserve_alpha: false,
+            output_compression: None,
+            strict_minimal_diff: false,
         }
     }
 
@@ -255,6 +312,9 @@ impl EmbeddingOptions {
         Self {
             strategy,
             obfuscation: Some(obfuscation),
+            preserve_alpha: false,
+            output_compression: None,
+            strict_minimal_diff: false,
         }
     }
 
@@ -321,6 +381,26 @@ impl EmbeddingOptions {
         Self::new(Strategy::LSB(LSBConfig::random()))
     }
 
+    /// Create embedding options picking the LSB pattern from the cover.
+    ///
+    /// Noisy covers get the linear pattern, smooth ones a random pattern with
+    /// an auto-generated seed, see
+    /// [`LSBConfig::auto_pattern`](crate::strategy::lsb::LSBConfig::auto_pattern).
+    /// The choice is recorded in the header, so extraction needs no extra
+    /// setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::auto_pattern().with_xor_string("key");
+    /// ```
+    pub fn auto_pattern() -> Self {
+        use crate::strategy::lsb::LSBConfig;
+        Self::new(Strategy::LSB(LSBConfig::auto_pattern()))
+    }
+
     /// Create embedding options with LSB random strategy and password-derived seed.
     ///
     /// This is a convenience constructor that creates an `EmbeddingOptions` instance
@@ -401,6 +481,92 @@ impl EmbeddingOptions {
         self
     }
 
+    /// Enable XOR obfuscation with a freshly generated random key.
+    ///
+    /// Returns the options together with the generated key, which must be
+    /// stored and passed back (for example through [`with_xor_key`](Self::with_xor_key))
+    /// to extract the payload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let (options, key) = EmbeddingOptions::random().with_random_xor();
+    ///
+    /// // Later, for extraction
+    /// let extraction_options = EmbeddingOptions::random().with_xor_key(key);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system random number generator is unavailable.
+    pub fn with_random_xor(mut self) -> (Self, Vec<u8>) {
+        let key = obfuscation::random_xor_key();
+        self.obfuscation = Some(Obfuscation::Xor { key: key.clone() });
+        (self, key)
+    }
+
+    /// Obfuscate with a nonced transform, its nonce derived from the seed of
+    /// the options and `counter` instead of being stored.
+    ///
+    /// See [`Obfuscation::derived_nonce`], and its warning about nonce reuse:
+    /// only use a seed unique to the image, or a `counter` incremented for
+    /// every payload embedded with the same seed and key. The seed must be
+    /// known before embedding, so the options need the LSB strategy with a
+    /// random pattern and a password or manual seed. Set the password or seed
+    /// first; a password is derived once more here.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use pnger::{EmbeddingOptions, PngerError};
+    /// use pnger::obfuscation::{NONCE_SIZE, NoncedTransform};
+    ///
+    /// struct NonceXor;
+    ///
+    /// impl NoncedTransform for NonceXor {
+    ///     fn forward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+    ///         Ok(data.iter().zip(nonce.iter().cycle()).map(|(a, b)| a ^ b).collect())
+    ///     }
+    ///
+    ///     fn backward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+    ///         self.forward(nonce, data)
+    ///     }
+    /// }
+    ///
+    /// let options = EmbeddingOptions::random_with_password("image-42")
+    ///     .with_derived_nonce(Arc::new(NonceXor), 1)?;
+    ///
+    /// // No seed is known before embedding with an auto-generated one
+    /// assert!(EmbeddingOptions::random().with_derived_nonce(Arc::new(NonceXor), 1).is_err());
+    /// # Ok::<(), PngerError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - `PngerError::UnsupportedMode`: The options use another strategy than
+    ///   LSB, the linear pattern, or an auto-generated seed
+    /// - `PngerError::CryptoError`: The password derivation failed
+    pub fn with_derived_nonce(
+        mut self,
+        transform: Arc<dyn obfuscation::NoncedTransform>,
+        counter: u32,
+    ) -> Result<Self, PngerError> {
+        let seed = match &self.strategy {
+            Strategy::LSB(config) => config.known_seed()?,
+            Strategy::DCT(_) | Strategy::PVD(_) => None,
+        };
+        let seed = seed.ok_or_else(|| {
+            PngerError::UnsupportedMode(
+                "A derived nonce needs the LSB strategy with a password or manual seed".to_string(),
+            )
+        })?;
+        self.obfuscation = Some(Obfuscation::derived_nonce(transform, &seed, counter));
+        Ok(self)
+    }
+
     /// Remove any obfuscation, ensuring payload is embedded without encryption.
     ///
     /// This method explicitly removes any previously configured obfuscation,
@@ -457,267 +623,2136 @@ impl EmbeddingOptions {
             Strategy::LSB(config) => {
                 *config = std::mem::take(config).with_bit_index(bit_index);
             }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
         }
         self
     }
 
-    /// Conditionally set password if provided (fluent version).
+    /// Store the payload several times for robustness (fluent version).
     ///
-    /// This is a convenience method for scenarios where a password might be optional.
-    /// If `Some(password)` is provided, the underlying strategy will be configured
-    /// to use password-derived seeding. If `None`, the strategy remains unchanged.
+    /// The payload is embedded `copies` times over disjoint carrier bytes and
+    /// recovered by a per-bit majority vote, so it survives localized damage
+    /// to the image. The factor is stored in the header: extraction works with
+    /// the usual options. Available capacity is divided by `copies`.
     ///
-    /// Only works with random LSB strategies. Has no effect on linear strategies.
+    /// # Parameters
+    /// - `copies`: Number of payload copies (0 is treated as 1, odd values recommended)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret")
+    ///     .with_redundancy(3);
+    /// ```
+    pub fn with_redundancy(mut self, copies: u8) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_redundancy(copies);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Use a custom header magic for domain separation (fluent version).
+    ///
+    /// Payloads embedded under a namespace are only recognized by extraction
+    /// using the same namespace, so detection tools relying on the default
+    /// `PNGR` magic don't flag them, and vice versa.
     ///
     /// # Parameters
-    /// - `password`: Optional password string
+    /// - `magic`: 4-byte header magic replacing the default `PNGR`
     ///
     /// # Examples
     ///
     /// ```rust
     /// use pnger::EmbeddingOptions;
     ///
-    /// // With password
-    /// let password = Some("my_password".to_string());
-    /// let options = EmbeddingOptions::random()
-    ///     .with_password_if_some(password);
+    /// let options = EmbeddingOptions::random_with_password("secret")
+    ///     .with_namespace(*b"ACME");
+    /// ```
+    pub fn with_namespace(mut self, magic: [u8; 4]) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_namespace(magic);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Store the payload filename and MIME type alongside it (fluent version).
     ///
-    /// // Without password (no change to strategy)
-    /// let no_password: Option<String> = None;
-    /// let options = EmbeddingOptions::random()
-    ///     .with_password_if_some(no_password);
+    /// The metadata takes 2 bytes of capacity plus the length of both strings,
+    /// each limited to 255 bytes. It is recovered through
+    /// [`ExtractResult::metadata`](crate::strategy::lsb::ExtractResult::metadata).
+    ///
+    /// # Parameters
+    /// - `filename`: Original filename of the payload
+    /// - `mime_type`: MIME type of the payload
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret")
+    ///     .with_payload_metadata("report.pdf", "application/pdf");
     /// ```
-    pub fn with_password_if_some<S: Into<String>>(mut self, password: Option<S>) -> Self {
-        if let Some(pwd) = password {
-            match &mut self.strategy {
-                Strategy::LSB(config) => {
-                    *config = std::mem::take(config).with_password(pwd.into());
-                }
+    pub fn with_payload_metadata<N: Into<String>, M: Into<String>>(
+        mut self,
+        filename: N,
+        mime_type: M,
+    ) -> Self {
+        let metadata = crate::strategy::lsb::PayloadMetadata::new(filename, mime_type);
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_payload_metadata(metadata);
             }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
         }
         self
     }
-}
 
-/// Extracts a payload from a PNG file using the default embedding strategy.
-///
-/// This function reads a PNG file and extracts any payload that was previously embedded
-/// using PNGer's steganography techniques. It uses the default LSB strategy with random
-/// pattern detection and automatic seed recovery.
-///
-/// This is the primary extraction function for most use cases, providing a simple
-/// interface that handles file I/O and format detection automatically.
-///
-/// # Returns
-///
-/// Returns a `Vec<u8>` with the extracted payload data.
-///
-/// # Examples
-///
-/// ```no_run
-/// use pnger::extract_payload_from_file;
-///
-/// let payload = extract_payload_from_file("image_with_payload.png")?;
-/// println!("Extracted {} bytes", payload.len());
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
-///
-/// # Errors
-///
-/// This function will return an error if:
-/// - The PNG file cannot be read or doesn't exist
-/// - The file is not a valid PNG image
-/// - No embedded payload is found in the image
-/// - The embedded data is corrupted or incomplete
-/// - File I/O operations fail
-pub fn extract_payload_from_file<P: AsRef<Path>>(png_path: P) -> Result<Vec<u8>, PngerError> {
-    extract_payload_from_file_with_options(png_path, EmbeddingOptions::default())
-}
+    /// Store a 2-byte application tag in the header (fluent version).
+    ///
+    /// Lets a single extractor route payloads of several types without the
+    /// overhead of full metadata. The tag is recovered through
+    /// [`ExtractResult::tag`](crate::strategy::lsb::ExtractResult::tag).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_payload_tag(0x0102);
+    /// ```
+    pub fn with_payload_tag(mut self, tag: u16) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_payload_tag(tag);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
 
-/// Extracts a payload from a PNG file using custom embedding options.
-///
-/// This function provides advanced control over the extraction process by allowing
-/// you to specify the embedding strategy and obfuscation settings that were used
-/// during embedding. This is essential when non-default settings were used.
-///
-/// # Returns
-///
-/// Returns a `Vec<u8>` with the extracted payload data.
-///
-/// # Examples
-///
-/// ## Extract with Password Protection
-///
-/// ```no_run
-/// use pnger::{extract_payload_from_file_with_options, EmbeddingOptions, Strategy};
-/// use pnger::strategy::lsb::LSBConfig;
-///
-/// let strategy = Strategy::LSB(
-///     LSBConfig::random().with_password("secret123".to_string())
-/// );
-/// let options = EmbeddingOptions::new(strategy);
-///
-/// let payload = extract_payload_from_file_with_options("protected_image.png", options)?;
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
+    /// Store a second copy of the payload length in the header (fluent version).
+    ///
+    /// Lets extraction recover when one of the two length fields is
+    /// corrupted. See [`LSBConfig::with_redundant_length`](crate::strategy::lsb::LSBConfig::with_redundant_length).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_redundant_length(true);
+    /// ```
+    pub fn with_redundant_length(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_redundant_length(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Cap the payload size accepted during extraction (fluent version).
+    ///
+    /// Protects against untrusted images whose header claims an enormous
+    /// payload: extraction fails with `PngerError::InvalidFormat` instead of
+    /// attempting to read it. Has no effect on embedding.
+    ///
+    /// # Parameters
+    /// - `max_bytes`: Largest payload size, in bytes, extraction will accept
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret")
+    ///     .with_max_payload(1024 * 1024);
+    /// ```
+    pub fn with_max_payload(mut self, max_bytes: usize) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_max_payload(max_bytes);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Cap the memory of the password key derivation, in MiB (fluent version).
+    ///
+    /// Derivations needing more fail with `PngerError::CryptoError` before
+    /// allocating, see [`LSBConfig::with_max_kdf_memory`](crate::strategy::lsb::LSBConfig::with_max_kdf_memory).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_max_kdf_memory(64);
+    /// ```
+    pub fn with_max_kdf_memory(mut self, mib: u32) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_max_kdf_memory(mib);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Set the byte order of the header fields (fluent version).
+    ///
+    /// Use [`WireFormat::LittleEndian`](crate::strategy::lsb::WireFormat::LittleEndian)
+    /// when the header is read by tools expecting little-endian integers.
+    /// Extraction detects the byte order from the header.
+    ///
+    /// # Parameters
+    /// - `wire_format`: Byte order of the payload size, checksum and metadata length
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    /// use pnger::strategy::lsb::WireFormat;
+    ///
+    /// let options = EmbeddingOptions::linear().with_wire_format(WireFormat::LittleEndian);
+    /// ```
+    pub fn with_wire_format(mut self, wire_format: crate::strategy::lsb::WireFormat) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_wire_format(wire_format);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Randomize the unused target bits after embedding (fluent version).
+    ///
+    /// See [`LSBConfig::with_lsb_noise`](crate::strategy::lsb::LSBConfig::with_lsb_noise).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_lsb_noise(true);
+    /// ```
+    pub fn with_lsb_noise(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_lsb_noise(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Refuse to embed over an existing payload (fluent version).
+    ///
+    /// See [`LSBConfig::fail_if_occupied`](crate::strategy::lsb::LSBConfig::fail_if_occupied).
+    /// Embedding then fails with `PngerError::InvalidFormat` when the target
+    /// bit index already holds a valid header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").fail_if_occupied(true);
+    /// ```
+    pub fn fail_if_occupied(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).fail_if_occupied(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Record the character encoding of a text payload (fluent version).
+    ///
+    /// See [`LSBConfig::with_text_encoding`](crate::strategy::lsb::LSBConfig::with_text_encoding).
+    /// [`embed_text_from_bytes`] sets it for you.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::{EmbeddingOptions, TextEncoding};
+    ///
+    /// let options = EmbeddingOptions::linear().with_text_encoding(TextEncoding::Utf16);
+    /// ```
+    pub fn with_text_encoding(mut self, encoding: TextEncoding) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_text_encoding(encoding);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Mask the whole target bit plane with a seed-keyed keystream (fluent version).
+    ///
+    /// See [`LSBConfig::with_chaff`](crate::strategy::lsb::LSBConfig::with_chaff).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_chaff(true);
+    /// ```
+    pub fn with_chaff(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_chaff(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Keep payload bits out of near-saturated carrier bytes (fluent version).
+    ///
+    /// See [`LSBConfig::with_skip_saturated`](crate::strategy::lsb::LSBConfig::with_skip_saturated),
+    /// including its effect on capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_skip_saturated(true);
+    /// ```
+    pub fn with_skip_saturated(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_skip_saturated(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Bind the body permutation to the payload length (fluent version).
+    ///
+    /// See [`LSBConfig::with_length_bound_seed`](crate::strategy::lsb::LSBConfig::with_length_bound_seed).
+    /// Only applies to LSB strategies with a random pattern; ignored otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_length_bound_seed(true);
+    /// ```
+    pub fn with_length_bound_seed(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_length_bound_seed(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Set how payload bits are written into the carrier (fluent version).
+    ///
+    /// See [`LSBConfig::with_write_mode`](crate::strategy::lsb::LSBConfig::with_write_mode).
+    /// Only applies to LSB strategies; ignored otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    /// use pnger::strategy::lsb::WriteMode;
+    ///
+    /// let options = EmbeddingOptions::linear().with_write_mode(WriteMode::Or);
+    /// ```
+    pub fn with_write_mode(mut self, write_mode: crate::strategy::lsb::WriteMode) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_write_mode(write_mode);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Embed only into the even or odd carrier bytes (fluent version).
+    ///
+    /// See [`LSBConfig::with_parity`](crate::strategy::lsb::LSBConfig::with_parity).
+    /// Only applies to LSB strategies; ignored otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    /// use pnger::strategy::lsb::Parity;
+    ///
+    /// let options = EmbeddingOptions::linear().with_parity(Parity::Even);
+    /// ```
+    pub fn with_parity(mut self, parity: crate::strategy::lsb::Parity) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_parity(parity);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Spread the bits of each byte of a linear body (fluent version).
+    ///
+    /// See [`LSBConfig::with_diffusion_stride`](crate::strategy::lsb::LSBConfig::with_diffusion_stride).
+    /// Only applies to LSB strategies; ignored otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::linear().with_diffusion_stride(7);
+    /// ```
+    pub fn with_diffusion_stride(mut self, stride: u32) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_diffusion_stride(stride);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Store a hash of the payload in the header (fluent version).
+    ///
+    /// See [`LSBConfig::with_payload_hash`](crate::strategy::lsb::LSBConfig::with_payload_hash)
+    /// and [`embed_if_changed`]. Only applies to LSB strategies; ignored
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::linear().with_payload_hash(true);
+    /// ```
+    pub fn with_payload_hash(mut self, enabled: bool) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_payload_hash(enabled);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Target a different bit in each channel (fluent version).
+    ///
+    /// Carrier byte `i` uses bit `indices[i % N]`, so for 8-bit images each
+    /// index applies to one channel. The mapping is stored in the header.
+    ///
+    /// # Parameters
+    /// - `indices`: One bit index per channel, 1 to 4 entries
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    /// use pnger::strategy::lsb::BitIndex;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret")
+    ///     .with_channel_bit_indices([BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit1]);
+    /// ```
+    pub fn with_channel_bit_indices<const N: usize>(
+        mut self,
+        indices: [crate::strategy::lsb::BitIndex; N],
+    ) -> Self {
+        match &mut self.strategy {
+            Strategy::LSB(config) => {
+                *config = std::mem::take(config).with_channel_bit_indices(indices);
+            }
+            Strategy::DCT(_) | Strategy::PVD(_) => {}
+        }
+        self
+    }
+
+    /// Leave the alpha channel of RGBA images untouched (fluent version).
+    ///
+    /// Grayscale-alpha images always carry the payload in their gray samples
+    /// only, so their transparency is preserved exactly by default. With this
+    /// option RGBA images embed only into their color samples too, which
+    /// shrinks their capacity by a quarter. Images without alpha are not
+    /// affected.
+    ///
+    /// The color samples then form the carrier, so channel bit indices set
+    /// with [`with_channel_bit_indices`](Self::with_channel_bit_indices)
+    /// cycle over them: a single index targets the gray sample of
+    /// grayscale-alpha images, three indices the red, green and blue samples
+    /// of RGBA images.
+    ///
+    /// Extraction must use the same setting, since it changes which bytes
+    /// carry the payload.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").with_alpha_preserved();
+    /// ```
+    pub fn with_alpha_preserved(mut self) -> Self {
+        self.preserve_alpha = true;
+        self
+    }
+
+    /// Whether the alpha samples of pixels with `channels` samples, the last
+    /// being alpha, stay out of the carrier: always for grayscale-alpha, and
+    /// for RGBA when alpha is preserved.
+    fn skips_alpha(&self, channels: usize) -> bool {
+        channels == 2 || self.preserve_alpha
+    }
+
+    /// Compress the output PNG at `compression` (fluent version).
+    ///
+    /// By default the output keeps the compression level of the input image.
+    /// Use [`png::Compression::Best`] to minimize the file size for storage,
+    /// or [`png::Compression::Fast`] to speed up encoding.
+    ///
+    /// Compression is lossless: it changes how the pixels are stored in the
+    /// file, not the pixel values, so the embedded bits and the extraction
+    /// are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random().with_output_compression(png::Compression::Best);
+    /// ```
+    pub fn with_output_compression(mut self, compression: png::Compression) -> Self {
+        self.output_compression = Some(compression);
+        self
+    }
+
+    /// Verify that the output differs from the cover only in the planned
+    /// payload bits (fluent version).
+    ///
+    /// For forensic reproducibility. After embedding, the output PNG is
+    /// decoded again and compared with the cover: every modified carrier byte
+    /// must be one of the indices the header plans for the header and body
+    /// (see [`LSBEmbedder::planned_indices`](crate::strategy::lsb::LSBEmbedder::planned_indices)),
+    /// modified in its target bits only, no other sample or palette byte may
+    /// change, and the decoded output must match the embedded samples
+    /// exactly. Any difference, such as one introduced by the PNG encoder,
+    /// fails the embedding.
+    ///
+    /// Only the LSB strategy, without noise or chaff, has a plan to check
+    /// against; other configurations fail with `PngerError::UnsupportedMode`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// let options = EmbeddingOptions::random_with_password("secret").strict_minimal_diff(true);
+    /// ```
+    pub fn strict_minimal_diff(mut self, enabled: bool) -> Self {
+        self.strict_minimal_diff = enabled;
+        self
+    }
+
+    /// Check that extraction with these options can succeed at all.
+    ///
+    /// Lets callers fail fast on combinations no image can satisfy, before
+    /// decoding anything. LSB configurations are checked with
+    /// [`LSBConfig::validate_for_extraction`](crate::strategy::lsb::LSBConfig::validate_for_extraction),
+    /// such as chaff with an auto-generated seed, and DCT configurations for
+    /// a quantization step below the minimum. Passing does not guarantee
+    /// that an image holds a payload for these options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// assert!(EmbeddingOptions::random_with_password("secret").validate_for_extraction().is_ok());
+    /// assert!(EmbeddingOptions::random().with_chaff(true).validate_for_extraction().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Chaff that cannot be removed, invalid
+    ///   channel bit indices, or a DCT quantization step below the minimum
+    /// - `PngerError::CryptoError`: The key derivation memory limit is below
+    ///   the Argon2 memory cost
+    pub fn validate_for_extraction(&self) -> Result<(), PngerError> {
+        match &self.strategy {
+            Strategy::LSB(config) => config.validate_for_extraction(),
+            Strategy::DCT(config) => config.step().map(drop),
+            Strategy::PVD(_) => Ok(()),
+        }
+    }
+
+    /// Whether the palette of indexed images carries the payload.
+    fn palette_mode(&self) -> bool {
+        match &self.strategy {
+            Strategy::LSB(config) => config.is_palette_mode(),
+            Strategy::DCT(_) | Strategy::PVD(_) => false,
+        }
+    }
+
+    /// Bit index of the pixel indices carrying payload bits, in indexed
+    /// hybrid mode.
+    fn indexed_hybrid_bit(&self) -> Option<crate::strategy::lsb::BitIndex> {
+        match &self.strategy {
+            Strategy::LSB(config) if config.is_indexed_hybrid() => Some(config.bit_index()),
+            _ => None,
+        }
+    }
+
+    /// Alpha layout to skip for an image described by `info`, if any.
+    fn alpha_layout(&self, info: &png::Info) -> Option<AlphaLayout> {
+        AlphaLayout::from_png_info(info).filter(|_| self.skips_alpha(info.color_type.samples()))
+    }
+
+    /// Conditionally set password if provided (fluent version).
+    ///
+    /// This is a convenience method for scenarios where a password might be optional.
+    /// If `Some(password)` is provided, the underlying strategy will be configured
+    /// to use password-derived seeding. If `None`, the strategy remains unchanged.
+    ///
+    /// Only works with random LSB strategies. Has no effect on linear strategies.
+    ///
+    /// # Parameters
+    /// - `password`: Optional password string
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::EmbeddingOptions;
+    ///
+    /// // With password
+    /// let password = Some("my_password".to_string());
+    /// let options = EmbeddingOptions::random()
+    ///     .with_password_if_some(password);
+    ///
+    /// // Without password (no change to strategy)
+    /// let no_password: Option<String> = None;
+    /// let options = EmbeddingOptions::random()
+    ///     .with_password_if_some(no_password);
+    /// ```
+    pub fn with_password_if_some<S: Into<String>>(mut self, password: Option<S>) -> Self {
+        if let Some(pwd) = password {
+            match &mut self.strategy {
+                Strategy::LSB(config) => {
+                    *config = std::mem::take(config).with_password(pwd.into());
+                }
+                Strategy::DCT(_) | Strategy::PVD(_) => {}
+            }
+        }
+        self
+    }
+}
+
+/// Parses options from a compact spec such as `lsb:random:bit1:xor=mykey`.
+///
+/// The spec starts with the strategy (`lsb`, `dct` or `pvd`), followed by
+/// `:`-separated tokens in any order:
+///
+/// | Token | Effect |
+/// |-------|--------|
+/// | `linear`, `random` | LSB pattern, linear by default |
+/// | `bit0` to `bit7` | LSB bit index, `bit0` by default |
+/// | `password=<text>` | LSB password, implies `random` |
+/// | `xor=<text>` | XOR obfuscation key |
+/// | `xorhex=<hex>` | XOR obfuscation key given as hex digits |
+/// | `alpha` | Preserve the alpha channel |
+///
+/// Each token may appear once, and values cannot contain `:`: XOR keys
+/// holding one are written with `xorhex=`, `xorhex=613a62` being the key
+/// `a:b`. Meant for options taken from a command-line flag or an
+/// environment variable.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::EmbeddingOptions;
+///
+/// let options = EmbeddingOptions::try_from("lsb:random:bit1:xor=mykey").unwrap();
+///
+/// let error = EmbeddingOptions::try_from("lsb:bit9").unwrap_err();
+/// assert!(error.to_string().contains("bad bit index 'bit9'"));
+/// ```
+///
+/// # Errors
+/// - `PngerError::InvalidFormat`: Unknown strategy or token, missing value,
+///   repeated or conflicting tokens, or LSB tokens with another strategy
+/// - `PngerError::UnsupportedMode`: A password with the linear pattern
+impl TryFrom<&str> for EmbeddingOptions {
+    type Error = PngerError;
+
+    fn try_from(spec: &str) -> Result<Self, Self::Error> {
+        spec::parse(spec)
+    }
+}
+
+/// Options are equal when every setting is, including passwords, seeds and
+/// XOR keys. Custom obfuscations only match when they share the same
+/// transform instance, see [`Obfuscation`]. Equal options hash equally, so
+/// they can key a cache.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashSet;
+/// use pnger::EmbeddingOptions;
+///
+/// let options = EmbeddingOptions::random_with_password("secret").with_xor_string("key");
+/// let mut seen = HashSet::new();
+/// seen.insert(options.clone());
+/// assert!(seen.contains(&EmbeddingOptions::random_with_password("secret").with_xor_string("key")));
+/// assert!(!seen.contains(&EmbeddingOptions::random_with_password("other").with_xor_string("key")));
+/// ```
+impl PartialEq for EmbeddingOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.strategy == other.strategy
+            && self.obfuscation == other.obfuscation
+            && self.preserve_alpha == other.preserve_alpha
+            && compression_id(self.output_compression) == compression_id(other.output_compression)
+            && self.strict_minimal_diff == other.strict_minimal_diff
+    }
+}
+
+impl Eq for EmbeddingOptions {}
+
+impl Hash for EmbeddingOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.strategy.hash(state);
+        self.obfuscation.hash(state);
+        self.preserve_alpha.hash(state);
+        compression_id(self.output_compression).hash(state);
+        self.strict_minimal_diff.hash(state);
+    }
+}
+
+/// Stand-in for `png::Compression`, which implements neither `PartialEq`
+/// nor `Hash`.
+#[allow(deprecated)]
+fn compression_id(compression: Option<png::Compression>) -> Option<u8> {
+    compression.map(|compression| match compression {
+        png::Compression::Default => 0,
+        png::Compression::Fast => 1,
+        png::Compression::Best => 2,
+        png::Compression::Huffman => 3,
+        png::Compression::Rle => 4,
+    })
+}
+
+/// Returns the number of carrier bytes the header takes with `options`.
+///
+/// Carrier bytes are the raw sample bytes of the decoded image (for an 8-bit
+/// RGB image, 3 per pixel). Every payload byte needs 8 carrier bytes on top of
+/// this overhead. See [`LSBConfig::header_overhead`](crate::strategy::lsb::LSBConfig::header_overhead)
+/// for details.
+///
+/// With the DCT strategy, the carriers are coefficients (4 per 8x8 block)
+/// and the header takes [`HEADER_SIZE`](crate::strategy::dct::HEADER_SIZE)
+/// times 8 of them. With the PVD strategy, the header takes
+/// [`HEADER_SIZE`](crate::strategy::pvd::HEADER_SIZE) times 8 color samples,
+/// rounded up to whole rows, and the payload capacity depends on the image.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, header_overhead};
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let carrier_bytes = 64 * 64 * 3; // 64x64 RGB image
+///
+/// let capacity = (carrier_bytes - header_overhead(&options)) / 8;
+/// assert_eq!(capacity, 1513);
+/// ```
+pub fn header_overhead(options: &EmbeddingOptions) -> usize {
+    match &options.strategy {
+        Strategy::LSB(config) => config.header_overhead(),
+        Strategy::DCT(_) => strategy::dct::HEADER_SIZE * 8,
+        Strategy::PVD(_) => strategy::pvd::HEADER_SIZE * 8,
+    }
+}
+
+/// Suggests cover image dimensions holding a `payload_len`-byte payload.
+///
+/// Returns the smallest square-ish `(width, height)` whose capacity with
+/// `options`, header overhead included, fits the payload: the smallest square
+/// side first, then the smallest height for that width. `channels` is the
+/// number of 8-bit samples per pixel (3 for RGB, 4 for RGBA); alpha samples
+/// are not counted for grayscale-alpha, or for RGBA when `options` preserve
+/// alpha.
+///
+/// The estimate assumes obfuscation keeps the payload size, which holds for
+/// XOR. With the PVD strategy, it assumes a smooth cover, see
+/// [`PVDConfig::smooth_capacity`](crate::strategy::pvd::PVDConfig::smooth_capacity).
+/// In palette mode, at most 256 palette entries carry the payload whatever
+/// the dimensions.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, min_dimensions_for};
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let (width, height) = min_dimensions_for(10_000, &options, 3)?;
+/// assert_eq!((width, height), (164, 163));
+/// # Ok::<(), pnger::PngerError>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - `channels` is zero (`PngerError::InvalidFormat`)
+/// - No image up to the largest PNG dimensions holds the payload
+///   (`PngerError::PayloadTooLarge`)
+pub fn min_dimensions_for(
+    payload_len: usize,
+    options: &EmbeddingOptions,
+    channels: usize,
+) -> Result<(u32, u32), PngerError> {
+    if channels == 0 {
+        return Err(PngerError::InvalidFormat(
+            "Cover images need at least one channel".to_string(),
+        ));
+    }
+    // Payload capacity of a cover, `None` when the header does not fit
+    let capacity = |width: u32, height: u32| {
+        let (width, height) = (width as usize, height as usize);
+        let pixels = width.saturating_mul(height);
+        match &options.strategy {
+            Strategy::LSB(config) => {
+                let carrier_len = if config.is_palette_mode() {
+                    pixels.min(256) * 3
+                } else {
+                    let alpha = matches!(channels, 2 | 4) && options.skips_alpha(channels);
+                    pixels.saturating_mul(channels - usize::from(alpha))
+                };
+                (carrier_len >= config.header_overhead()).then(|| config.max_capacity(carrier_len))
+            }
+            Strategy::DCT(_) => strategy::dct::header_fits(width, height)
+                .then(|| DCTConfig::capacity(width, height)),
+            Strategy::PVD(config) => {
+                strategy::pvd::header_fits(PixelLayout::packed(width, height, channels))
+                    .then(|| config.smooth_capacity(width, height, channels))
+            }
+        }
+    };
+    let fits = |width, height| capacity(width, height).is_some_and(|bytes| bytes >= payload_len);
+
+    // Largest PNG dimension
+    const MAX_SIDE: u32 = i32::MAX as u32;
+    let mut side = 1;
+    while !fits(side, side) {
+        if side == MAX_SIDE {
+            return Err(PngerError::PayloadTooLarge {
+                size: payload_len,
+                capacity: capacity(MAX_SIDE, MAX_SIDE).unwrap_or(0),
+            });
+        }
+        side = side.saturating_mul(2).min(MAX_SIDE);
+    }
+    let side = smallest(side / 2 + 1, side, |side| fits(side, side));
+    Ok((side, smallest(1, side, |height| fits(side, height))))
+}
+
+/// Returns the ratio of the practical to the theoretical capacity of a cover.
+///
+/// The theoretical capacity is one bit per color sample, `width × height ×
+/// channels / 8` bytes. The practical capacity is the largest payload that
+/// fits with `options`: header, embedded seed, metadata and redundancy copies
+/// excluded, alpha samples excluded when preserved. Small images lose a
+/// larger share to the fixed header, so their efficiency is lower.
+///
+/// In palette mode, only the palette carries the payload, and the efficiency
+/// is tiny. With the PVD strategy, textured images hold several bits per
+/// sample pair, and the efficiency may exceed 1.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, capacity_efficiency};
+///
+/// let png_data = std::fs::read("cover.png")?;
+/// let efficiency = capacity_efficiency(&png_data, &EmbeddingOptions::random_with_password("secret"))?;
+/// println!("{:.1}% of the theoretical capacity is usable", efficiency * 100.0);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// - `PngerError::InvalidFormat`: `png_data` does not start with the PNG signature
+/// - `PngerError::PngDecodingError`: `png_data` is not a valid PNG
+/// - `PngerError::UnsupportedMode`: The options do not apply to the image
+///   (palette mode on a non-indexed image, invalid PVD range table)
+pub fn capacity_efficiency<P: AsRef<[u8]>>(
+    png_data: P,
+    options: &EmbeddingOptions,
+) -> Result<f64, PngerError> {
+    let png_data = png_data.as_ref();
+    let (_, info) = decode_png_info(png_data)?;
+    let theoretical = info.width as usize * info.height as usize * info.color_type.samples() / 8;
+    let practical = payload_capacity(png_data, options)?;
+    if theoretical == 0 {
+        return Ok(0.0);
+    }
+    Ok(practical as f64 / theoretical as f64)
+}
+
+/// Largest payload, in bytes, that fits in PNG data with `options`.
+///
+/// Obfuscation is assumed to keep the payload size, which holds for XOR.
+fn payload_capacity(png_data: &[u8], options: &EmbeddingOptions) -> Result<usize, PngerError> {
+    match &options.strategy {
+        Strategy::LSB(config) => {
+            let carrier_len = read_carrier(png_data, options)?.len();
+            Ok(config.max_capacity(carrier_len))
+        }
+        Strategy::DCT(_) => {
+            let (_, info) = decode_png_info(png_data)?;
+            Ok(DCTConfig::capacity(
+                info.width as usize,
+                info.height as usize,
+            ))
+        }
+        Strategy::PVD(config) => {
+            let (mut reader, info) = decode_png_info(png_data)?;
+            let image_data = read_image_data(&mut reader)?;
+            PVDEmbedder::capacity(&image_data, strategy::png_layout(&info)?, config)
+        }
+    }
+}
+
+// Smallest value of `low..=high` satisfying a monotonic `predicate`, or
+// `high` if none does
+fn smallest(mut low: u32, mut high: u32, predicate: impl Fn(u32) -> bool) -> u32 {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if predicate(mid) {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    high
+}
+
+/// Lists the differences between two options that break extraction.
+///
+/// A pure diagnostic for failed extractions: compares the options a payload
+/// was embedded with and the ones used to extract it, and describes each
+/// difference that makes the extraction fail, such as the strategy, pattern,
+/// bit index, password or seed, and obfuscation. An empty list means the
+/// options are compatible. Settings recorded in the header, and secrets of
+/// custom transforms, are not compared.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, explain_mismatch};
+///
+/// let embed = EmbeddingOptions::random_with_password("secret");
+/// let extract = EmbeddingOptions::linear();
+/// for mismatch in explain_mismatch(&embed, &extract) {
+///     eprintln!("{mismatch}");
+/// }
+/// # assert!(!explain_mismatch(&embed, &extract).is_empty());
+/// ```
+pub fn explain_mismatch(embed: &EmbeddingOptions, extract: &EmbeddingOptions) -> Vec<String> {
+    let mut mismatches = match (&embed.strategy, &extract.strategy) {
+        (Strategy::LSB(embed), Strategy::LSB(extract)) => embed.extraction_mismatches(extract),
+        (Strategy::DCT(embed), Strategy::DCT(extract)) if embed != extract => {
+            vec!["DCT settings: the quantization steps differ".to_string()]
+        }
+        (Strategy::PVD(embed), Strategy::PVD(extract)) if embed != extract => {
+            vec!["PVD settings: the range tables differ".to_string()]
+        }
+        (Strategy::DCT(_), Strategy::DCT(_)) | (Strategy::PVD(_), Strategy::PVD(_)) => Vec::new(),
+        (embed, extract) => vec![format!(
+            "strategy: embedded with {}, extracting with {}",
+            strategy_name(embed),
+            strategy_name(extract)
+        )],
+    };
+
+    if embed.preserve_alpha != extract.preserve_alpha {
+        mismatches.push(format!(
+            "alpha: {} when embedding, {} when extracting",
+            if embed.preserve_alpha {
+                "preserved"
+            } else {
+                "used"
+            },
+            if extract.preserve_alpha {
+                "preserved"
+            } else {
+                "used"
+            }
+        ));
+    }
+
+    match (&embed.obfuscation, &extract.obfuscation) {
+        (None, None) | (Some(Obfuscation::Custom(_)), Some(Obfuscation::Custom(_))) => {}
+        (Some(Obfuscation::Xor { key: embed }), Some(Obfuscation::Xor { key: extract })) => {
+            if embed.len() != extract.len() {
+                mismatches.push(format!(
+                    "obfuscation: the XOR key is {} bytes when embedding, {} bytes when extracting",
+                    embed.len(),
+                    extract.len()
+                ));
+            } else if embed != extract {
+                mismatches.push("obfuscation: the XOR keys differ".to_string());
+            }
+        }
+        (embed, extract) => mismatches.push(format!(
+            "obfuscation: {} when embedding, {} when extracting",
+            obfuscation_name(embed.as_ref()),
+            obfuscation_name(extract.as_ref())
+        )),
+    }
+    mismatches
+}
+
+const fn strategy_name(strategy: &Strategy) -> &'static str {
+    match strategy {
+        Strategy::LSB(_) => "LSB",
+        Strategy::DCT(_) => "DCT",
+        Strategy::PVD(_) => "PVD",
+    }
+}
+
+const fn obfuscation_name(obfuscation: Option<&Obfuscation>) -> &'static str {
+    match obfuscation {
+        None => "none",
+        Some(Obfuscation::Xor { .. }) => "XOR",
+        Some(Obfuscation::Custom(_)) => "a custom transform",
+    }
+}
+
+/// Extracts a payload from a PNG file using the default embedding strategy.
+///
+/// This function reads a PNG file and extracts any payload that was previously embedded
+/// using PNGer's steganography techniques. It uses the default LSB strategy with random
+/// pattern detection and automatic seed recovery.
+///
+/// This is the primary extraction function for most use cases, providing a simple
+/// interface that handles file I/O and format detection automatically.
+///
+/// # Returns
+///
+/// Returns a `Vec<u8>` with the extracted payload data.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::extract_payload_from_file;
+///
+/// let payload = extract_payload_from_file("image_with_payload.png")?;
+/// println!("Extracted {} bytes", payload.len());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The PNG file cannot be read or doesn't exist
+/// - The file is not a valid PNG image
+/// - No embedded payload is found in the image
+/// - The embedded data is corrupted or incomplete
+/// - File I/O operations fail
+pub fn extract_payload_from_file<P: AsRef<Path>>(png_path: P) -> Result<Vec<u8>, PngerError> {
+    extract_payload_from_file_with_options(png_path, EmbeddingOptions::default())
+}
+
+/// Extracts a payload from a PNG file using custom embedding options.
+///
+/// This function provides advanced control over the extraction process by allowing
+/// you to specify the embedding strategy and obfuscation settings that were used
+/// during embedding. This is essential when non-default settings were used.
+///
+/// # Returns
+///
+/// Returns a `Vec<u8>` with the extracted payload data.
+///
+/// # Memory Mapping
+///
+/// With the `mmap` feature, the file is memory-mapped instead of read into
+/// memory, which halves peak memory use on very large images. The file must
+/// not be modified while the call runs: changes show up in the decoded data,
+/// and truncating the file may terminate the process with `SIGBUS`.
+///
+/// # Examples
+///
+/// ## Extract with Password Protection
+///
+/// ```no_run
+/// use pnger::{extract_payload_from_file_with_options, EmbeddingOptions, Strategy};
+/// use pnger::strategy::lsb::LSBConfig;
+///
+/// let strategy = Strategy::LSB(
+///     LSBConfig::random().with_password("secret123".to_string())
+/// );
+/// let options = EmbeddingOptions::new(strategy);
+///
+/// let payload = extract_payload_from_file_with_options("protected_image.png", options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// ## Extract with Obfuscation
+///
+/// ```no_run
+/// use pnger::{extract_payload_from_file_with_options, EmbeddingOptions, Strategy, Obfuscation};
+/// use pnger::strategy::lsb::LSBConfig;
+///
+/// let strategy = Strategy::LSB(LSBConfig::linear());
+/// let obfuscation = Obfuscation::Xor { key: b"encryption_key".to_vec() };
+/// let options = EmbeddingOptions::new_with_obfuscation(strategy, obfuscation);
+///
+/// let payload = extract_payload_from_file_with_options("encrypted_image.png", options )?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The PNG file cannot be read or doesn't exist
+/// - The file is not a valid PNG image
+/// - The extraction strategy doesn't match the embedding strategy
+/// - Password or seed information is incorrect
+/// - Obfuscation settings don't match those used during embedding
+/// - No embedded payload is found
+/// - File I/O operations fail
+pub fn extract_payload_from_file_with_options<P: AsRef<Path>>(
+    png_path: P,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let png_data = load_file(png_path)?;
+    extract_payload_from_bytes_with_options(&png_data, options)
+}
+
+/// Extracts a payload from PNG data in memory using the default embedding strategy.
+///
+/// This function operates entirely in memory, making it ideal for scenarios where
+/// you already have PNG data loaded or want to avoid file I/O operations. It uses
+/// the default LSB strategy with automatic pattern detection.
+///
+/// This is the core extraction function used internally by the file-based API and
+/// provides the foundation for all extraction operations.
+///
+/// # Returns
+///
+/// Returns a `Vec<u8>` with the extracted payload data.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::extract_payload_from_bytes;
+///
+/// let png_data = std::fs::read("image_with_payload.png")?;
+/// let payload = extract_payload_from_bytes(&png_data)?;
+///
+/// println!("Extracted payload: {}", String::from_utf8_lossy(&payload));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not valid PNG format
+/// - No embedded payload is found in the image
+/// - The embedded data is corrupted or incomplete
+/// - PNG decoding operations fail
+///
+/// # Performance Notes
+///
+/// - Faster than file-based operations (no I/O overhead)
+/// - Memory usage scales with PNG size
+/// - Consider memory constraints with very large images
+pub fn extract_payload_from_bytes<P: AsRef<[u8]>>(png_data: P) -> Result<Vec<u8>, PngerError> {
+    extract_payload_from_bytes_with_options(png_data, EmbeddingOptions::default())
+}
+
+/// Extracts a payload from PNG data using custom embedding options.
+///
+/// This is the most flexible extraction function, providing full control over the
+/// extraction process while operating entirely in memory. It's the foundation for
+/// all other extraction functions and handles advanced scenarios like password
+/// protection and payload obfuscation.
+///
+/// # Returns
+///
+/// Returns a `Vec<u8>` with the extracted payload data.
+///
+/// # Examples
+///
+/// ## Advanced Extraction with All Features
+///
+/// ```no_run
+/// use pnger::{extract_payload_from_bytes_with_options, EmbeddingOptions, Strategy, Obfuscation};
+/// use pnger::strategy::lsb::{LSBConfig, BitIndex};
+///
+/// let png_data = std::fs::read("complex_image.png")?;
+///
+/// // Configure extraction to match embedding settings
+/// let strategy = Strategy::LSB(
+///     LSBConfig::random()
+///         .with_password("my_secret_password".to_string())
+///         .with_bit_index(BitIndex::Bit2)
+/// );
+/// let obfuscation = Obfuscation::Xor { key: b"encryption_key".to_vec() };
+/// let options = EmbeddingOptions::new_with_obfuscation(strategy, obfuscation);
+///
+/// let payload = extract_payload_from_bytes_with_options(&png_data, options)?;
+///
+/// // The payload is now decrypted and ready to use
+/// println!("Secret message: {}", String::from_utf8_lossy(&payload));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// ## Batch Processing
+///
+/// ```rust
+/// use pnger::{extract_payload_from_bytes_with_options, EmbeddingOptions, Strategy};
+/// use pnger::strategy::lsb::LSBConfig;
+///
+/// fn process_images(images: Vec<Vec<u8>>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+///     let mut results = Vec::new();
+///     for png_data in images {
+///         let strategy = Strategy::LSB(LSBConfig::linear());
+///         let options = EmbeddingOptions::new(strategy);
+///         let payload = extract_payload_from_bytes_with_options(&png_data, options)?;
+///         results.push(String::from_utf8(payload)?);
+///     }
+///     Ok(results)
+/// }
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not valid PNG format
+/// - The extraction strategy doesn't match the embedding strategy
+/// - Password or cryptographic settings are incorrect
+/// - Obfuscation key doesn't match the one used during embedding
+/// - No embedded payload is found or data is corrupted
+/// - PNG decoding operations fail
+///
+/// # Security Considerations
+///
+/// - Always use the same password that was used during embedding
+/// - Obfuscation keys must match exactly (case-sensitive)
+/// - Failed extraction may indicate wrong credentials or corrupted data
+/// - Consider implementing retry logic with different parameters if needed
+pub fn extract_payload_from_bytes_with_options<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let payload_data = match &options.strategy {
+        Strategy::LSB(_) => {
+            let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+            return extract_from_buffer(&mut image_data, options);
+        }
+        Strategy::DCT(dct_config) => {
+            let (image_data, layout) = read_whole_image(png_data.as_ref())?;
+            DCTEmbedder::extract(&image_data, layout, dct_config)?
+        }
+        Strategy::PVD(_) => {
+            let (image_data, layout) = read_whole_image(png_data.as_ref())?;
+            PVDEmbedder::extract(&image_data, layout)?
+        }
+    };
+    match options.obfuscation {
+        Some(obfuscation) => obfuscation::deobfuscate_payload(&payload_data, obfuscation),
+        None => Ok(payload_data),
+    }
+}
+
+/// Decodes the samples and layout of a whole image, for the strategies
+/// working on the image geometry.
+fn read_whole_image(png_data: &[u8]) -> Result<(Vec<u8>, PixelLayout), PngerError> {
+    let (mut reader, info) = decode_png_info(png_data)?;
+    let image_data = read_image_data(&mut reader)?;
+    let layout = strategy::png_layout(&info)?;
+    Ok((image_data, layout))
+}
+
+/// Re-embeds the payload of a PNG image under new options.
+///
+/// Extracts the payload with `old_options`, then embeds it again with
+/// `new_options` into the same image, so keys can be rotated without the
+/// plaintext ever being written out. Both steps work exactly like
+/// [`extract_payload_from_bytes_with_options`] and
+/// [`embed_payload_from_bytes_with_options`], with every option they
+/// support. The plaintext buffer is zeroed once the new embedding is done.
+///
+/// The new payload overwrites the header and the body positions of the new
+/// options. When the pattern or bit index changes, bits of the old embedding
+/// outside those positions are left in place; they cannot be read without
+/// the old options.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{
+///     EmbeddingOptions, embed_payload_from_bytes_with_options,
+///     extract_payload_from_bytes_with_options, rekey_payload,
+/// };
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3])?;
+/// # }
+///
+/// let old_options = EmbeddingOptions::random_with_password("old").with_xor_string("old key");
+/// let new_options = EmbeddingOptions::random_with_password("new").with_xor_string("new key");
+///
+/// let stego = embed_payload_from_bytes_with_options(&png_data, b"secret", old_options.clone())?;
+/// let rekeyed = rekey_payload(&stego, old_options, new_options.clone())?;
+/// assert_eq!(extract_payload_from_bytes_with_options(&rekeyed, new_options)?, b"secret");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The extraction with `old_options` fails (see [`extract_payload_from_bytes_with_options`])
+/// - The embedding with `new_options` fails (see [`embed_payload_from_bytes_with_options`])
+pub fn rekey_payload<P: AsRef<[u8]>>(
+    png_data: P,
+    old_options: EmbeddingOptions,
+    new_options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let png_data = png_data.as_ref();
+    let mut payload = extract_payload_from_bytes_with_options(png_data, old_options)?;
+    let embedded = embed_payload_from_bytes_with_options(png_data, &payload, new_options);
+    payload.fill(0);
+    embedded
+}
+
+/// Embeds a text payload in the given character encoding.
+///
+/// Encodes `text` with `encoding` and records the encoding in the header,
+/// then embeds it like [`embed_payload_from_bytes_with_options`].
+/// [`extract_payload_as_string`] reads the encoding back and decodes the
+/// text the same way on every platform.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, TextEncoding, embed_text_from_bytes, extract_payload_as_string};
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3])?;
+/// # }
+///
+/// let options = EmbeddingOptions::linear();
+/// let stego = embed_text_from_bytes(&png_data, "Grüße", TextEncoding::Utf16, options.clone())?;
+/// assert_eq!(extract_payload_as_string(&stego, options)?, "Grüße");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - `text` cannot be represented in `encoding` (`PngerError::PayloadError`)
+/// - `encoding` is not UTF-8 and the strategy is not LSB, whose header is
+///   where the encoding is recorded (`PngerError::UnsupportedMode`)
+/// - The embedding fails (see [`embed_payload_from_bytes_with_options`])
+pub fn embed_text_from_bytes<P: AsRef<[u8]>>(
+    png_data: P,
+    text: &str,
+    encoding: TextEncoding,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if encoding != TextEncoding::Utf8 && !matches!(options.strategy, Strategy::LSB(_)) {
+        return Err(PngerError::UnsupportedMode(format!(
+            "{} text requires the LSB strategy, which records the encoding",
+            encoding.name()
+        )));
+    }
+    let payload = encoding.encode(text)?;
+    embed_payload_from_bytes_with_options(png_data, payload, options.with_text_encoding(encoding))
+}
+
+/// Extracts a payload from PNG data and decodes it as text.
+///
+/// Convenience wrapper around [`extract_payload_from_bytes_with_options`] for
+/// the common case of text payloads. The payload is decoded with the
+/// encoding recorded by [`embed_text_from_bytes`], and as UTF-8 when none is.
+/// A payload that is not valid text usually means the extraction options do
+/// not match the embedding ones, so the error says so instead of surfacing a
+/// bare conversion failure.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, embed_payload_from_bytes_with_options, extract_payload_as_string};
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3])?;
+/// # }
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let stego = embed_payload_from_bytes_with_options(&png_data, "Hello!", options.clone())?;
+/// assert_eq!(extract_payload_as_string(&stego, options)?, "Hello!");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The extraction itself fails (see [`extract_payload_from_bytes_with_options`])
+/// - The payload is not valid text in its encoding (`PngerError::PayloadError`)
+pub fn extract_payload_as_string<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<String, PngerError> {
+    let Strategy::LSB(lsb_config) = &options.strategy else {
+        let payload = extract_payload_from_bytes_with_options(png_data, options)?;
+        return TextEncoding::Utf8.decode(&payload);
+    };
+
+    let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+    let result = LSBEmbedder::extract(&mut image_data, lsb_config)?;
+    let payload = match options.obfuscation {
+        Some(obfuscation) => obfuscation::deobfuscate_payload(&result.payload, obfuscation)?,
+        None => result.payload,
+    };
+    result.text_encoding.unwrap_or_default().decode(&payload)
+}
+
+/// Checks that PNG data yields back `payload_data` when extracted with `options`.
+///
+/// Meant to be called right after embedding, before distributing the image:
+/// it re-extracts the payload with the same options and compares it byte for
+/// byte with the original.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, embed_payload_from_bytes_with_options, verify_embedded_payload};
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3])?;
+/// # }
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let stego = embed_payload_from_bytes_with_options(&png_data, b"payload", options.clone())?;
+/// verify_embedded_payload(&stego, b"payload", options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The extraction itself fails (see [`extract_payload_from_bytes_with_options`])
+/// - The extracted payload differs from `payload_data` (`PngerError::PayloadError`)
+pub fn verify_embedded_payload<P: AsRef<[u8]>>(
+    png_data: P,
+    payload_data: &[u8],
+    options: EmbeddingOptions,
+) -> Result<(), PngerError> {
+    let extracted = extract_payload_from_bytes_with_options(png_data, options)?;
+    if extracted != payload_data {
+        return Err(PngerError::PayloadError {
+            message: format!(
+                "verification failed: extracted {} bytes that differ from the {}-byte payload",
+                extracted.len(),
+                payload_data.len()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Decodes PNG data into the carrier bytes payloads are extracted from.
+fn read_carrier(png_data: &[u8], options: &EmbeddingOptions) -> Result<Vec<u8>, PngerError> {
+    let (mut reader, info) = decode_carrier_png(png_data, options)?;
+    let image_data = read_image_data(&mut reader)?;
+    carrier_of(&info, image_data, options)
+}
+
+/// Carrier bytes of a decoded image, as [`with_carrier`] selects them.
+fn carrier_of(
+    info: &png::Info<'static>,
+    image_data: Vec<u8>,
+    options: &EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if options.palette_mode() {
+        let mut carrier = palette_entries(info)?.to_vec();
+        if let Some(bit_index) = options.indexed_hybrid_bit() {
+            let positions = hybrid_index_positions(info, &image_data, bit_index)?;
+            carrier.extend(positions.iter().map(|&position| image_data[position]));
+        }
+        return Ok(carrier);
+    }
+    Ok(match options.alpha_layout(info) {
+        Some(alpha) => alpha.color_samples(&image_data),
+        None => image_data,
+    })
+}
+
+/// Runs `f` on the carrier bytes of a decoded image and writes them back.
+///
+/// The carrier is the palette in palette mode, followed by the safe pixel
+/// indices in indexed hybrid mode, and the samples selected by `options`
+/// otherwise.
+fn with_carrier<R>(
+    info: &mut png::Info<'static>,
+    image_data: &mut [u8],
+    options: &EmbeddingOptions,
+    f: impl FnOnce(&mut [u8]) -> Result<R, PngerError>,
+) -> Result<R, PngerError> {
+    if !options.palette_mode() {
+        return with_color_samples(image_data, options.alpha_layout(info), f);
+    }
+    let mut carrier = palette_entries(info)?.to_vec();
+    let palette_len = carrier.len();
+    let positions = match options.indexed_hybrid_bit() {
+        Some(bit_index) => hybrid_index_positions(info, image_data, bit_index)?,
+        None => Vec::new(),
+    };
+    carrier.extend(positions.iter().map(|&position| image_data[position]));
+    let result = f(&mut carrier)?;
+    for (&position, &index) in positions.iter().zip(&carrier[palette_len..]) {
+        image_data[position] = index;
+    }
+    carrier.truncate(palette_len);
+    info.palette = Some(std::borrow::Cow::Owned(carrier));
+    Ok(result)
+}
+
+/// Largest per-channel distance, target bit masked, between the two palette
+/// entries an index may switch between in indexed hybrid mode.
+const HYBRID_MAX_DISTANCE: u8 = 8;
+
+/// Positions of the pixel indices carrying payload bits in indexed hybrid
+/// mode.
+///
+/// An index is safe when flipping its target bit selects a palette entry of
+/// near-identical color and the same transparency. The target bit of the
+/// palette is masked in the comparison, so embedding into the palette keeps
+/// the same indices safe.
+fn hybrid_index_positions(
+    info: &png::Info<'static>,
+    indices: &[u8],
+    bit_index: crate::strategy::lsb::BitIndex,
+) -> Result<Vec<usize>, PngerError> {
+    if info.bit_depth != png::BitDepth::Eight {
+        return Err(PngerError::UnsupportedMode(
+            "Indexed hybrid mode requires 8-bit indices".to_string(),
+        ));
+    }
+    let palette = palette_entries(info)?;
+    let transparency = info.trns.as_deref().unwrap_or_default();
+    let mask = 1u8 << u8::from(bit_index);
+    let entry = |index: usize| {
+        let alpha = transparency.get(index).copied().unwrap_or(u8::MAX);
+        palette
+            .get(3 * index..3 * index + 3)
+            .map(|rgb| (rgb, alpha))
+    };
+    let swappable: Vec<bool> = (0..256)
+        .map(
+            |index| match (entry(index), entry(index ^ usize::from(mask))) {
+                (Some((rgb, alpha)), Some((partner_rgb, partner_alpha))) => {
+                    alpha == partner_alpha
+                        && rgb.iter().zip(partner_rgb).all(|(sample, partner)| {
+                            (sample & !mask).abs_diff(partner & !mask) <= HYBRID_MAX_DISTANCE
+                        })
+                }
+                _ => false,
+            },
+        )
+        .collect();
+    Ok(indices
+        .iter()
+        .enumerate()
+        .filter(|&(_, &index)| swappable[usize::from(index)])
+        .map(|(position, _)| position)
+        .collect())
+}
+
+/// RGB entries of the palette of an indexed image.
+fn palette_entries<'a>(info: &'a png::Info<'static>) -> Result<&'a [u8], PngerError> {
+    info.palette
+        .as_deref()
+        .ok_or_else(|| PngerError::InvalidFormat("Indexed image without a palette".to_string()))
+}
+
+/// Checks whether PNG data carries a payload embedded with `options`.
+///
+/// Only the header is read and validated, see
+/// [`LSBEmbedder::contains_payload`](crate::strategy::lsb::LSBEmbedder::contains_payload).
+/// Images carrying an empty payload are reported as containing one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, contains_payload};
+///
+/// let png_data = std::fs::read("image.png")?;
+/// if contains_payload(&png_data, &EmbeddingOptions::linear())? {
+///     println!("Image carries a payload");
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the data is not a valid PNG image
+/// supported for embedding. A missing or corrupted header is not an error.
+pub fn contains_payload<P: AsRef<[u8]>>(
+    png_data: P,
+    options: &EmbeddingOptions,
+) -> Result<bool, PngerError> {
+    let mut image_data = read_carrier(png_data.as_ref(), options)?;
+    match &options.strategy {
+        Strategy::LSB(lsb_config) => Ok(LSBEmbedder::contains_payload(&mut image_data, lsb_config)),
+        Strategy::DCT(_) | Strategy::PVD(_) => Err(whole_image_only()),
+    }
+}
+
+/// Checks a password against a PNG image before extracting its payload.
+///
+/// Reads the header of the default bit plane only and compares its password
+/// verifier with the one derived from `password`, so a wrong password is
+/// reported immediately. For other bit indices, use
+/// [`LSBEmbedder::verify_password`](crate::strategy::lsb::LSBEmbedder::verify_password).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, extract_payload_from_bytes_with_options, verify_password};
+///
+/// let png_data = std::fs::read("image.png")?;
+/// if !verify_password(&png_data, "secret")? {
+///     eprintln!("wrong password");
+///     return Ok(());
+/// }
+/// let payload = extract_payload_from_bytes_with_options(
+///     &png_data,
+///     EmbeddingOptions::random_with_password("secret"),
+/// )?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - No valid header is found, or the payload was not embedded with a
+///   password (`PngerError::InvalidFormat`)
+pub fn verify_password<P: AsRef<[u8]>>(png_data: P, password: &str) -> Result<bool, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    let config = crate::strategy::lsb::LSBConfig::random().with_password(password.to_string());
+    LSBEmbedder::verify_password(&mut image_data, &config)
+}
+
+/// Lists the bit indices of a PNG image already holding a payload header.
+///
+/// Every bit plane is checked for a valid header in the default namespace.
+/// Linear headers are detected without credentials; headers scattered with
+/// a password-derived seed are only detected when `password` is given. See
+/// [`LSBEmbedder::occupied_bit_indices`](crate::strategy::lsb::LSBEmbedder::occupied_bit_indices).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::occupied_bit_indices;
+/// use pnger::strategy::lsb::BitIndex;
+///
+/// let png_data = std::fs::read("image.png")?;
+/// let occupied = occupied_bit_indices(&png_data, Some("secret"))?;
+/// let free = BitIndex::all().iter().find(|index| !occupied.contains(index));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the data is not a valid PNG image
+/// supported for embedding.
+pub fn occupied_bit_indices<P: AsRef<[u8]>>(
+    png_data: P,
+    password: Option<&str>,
+) -> Result<Vec<crate::strategy::lsb::BitIndex>, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    let config = crate::strategy::lsb::LSBConfig::random()
+        .with_password_if_some(password.map(str::to_string));
+    Ok(LSBEmbedder::occupied_bit_indices(&mut image_data, &config))
+}
+
+/// Reads and validates the payload header of a PNG image.
+///
+/// Decodes the image and parses the header stored by the default options,
+/// in bit 0 of the default namespace, checking its magic and checksum.
+/// Returns the header version, flags and declared payload size without
+/// extracting the payload. Headers scattered with a password-derived seed
+/// cannot be read without the seed; see
+/// [`LSBEmbedder::read_header`](crate::strategy::lsb::LSBEmbedder::read_header)
+/// for other bit indices and configurations, and [`describe_embedding`] for
+/// a summary of every recorded setting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::read_header;
+///
+/// let png_data = std::fs::read("output.png")?;
+/// let header = read_header(&png_data)?;
+/// println!("v{} header, {} payload bytes", header.version, header.payload_size);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - No header is found, or its checksum does not match
+///   (`PngerError::InvalidFormat`)
+pub fn read_header<P: AsRef<[u8]>>(
+    png_data: P,
+) -> Result<crate::strategy::lsb::HeaderInfo, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    LSBEmbedder::read_header(&mut image_data, &crate::strategy::lsb::LSBConfig::linear())
+}
+
+/// Describes the embedding of a PNG image from its payload header.
+///
+/// Bit planes are checked in ascending order for a valid header in the
+/// default namespace, and the first one found is summarized: pattern, bit
+/// index, whether the seed is embedded, payload size, header version and the
+/// header extensions present. No credentials are needed, so headers scattered
+/// with a password-derived seed are not found. The payload is not extracted.
+/// See [`LSBEmbedder::describe`](crate::strategy::lsb::LSBEmbedder::describe).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::describe_embedding;
+///
+/// let png_data = std::fs::read("output.png")?;
+/// println!("{}", describe_embedding(&png_data)?);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - No bit plane holds a valid header (`PngerError::InvalidFormat`)
+pub fn describe_embedding<P: AsRef<[u8]>>(
+    png_data: P,
+) -> Result<crate::strategy::lsb::EmbeddingSummary, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    crate::strategy::lsb::BitIndex::all()
+        .iter()
+        .find_map(|&bit_index| {
+            let config = crate::strategy::lsb::LSBConfig::random().with_bit_index(bit_index);
+            LSBEmbedder::describe(&mut image_data, &config).ok()
+        })
+        .ok_or_else(|| {
+            PngerError::InvalidFormat("No payload header found in any bit plane".to_string())
+        })
+}
+
+/// Extracts every payload of a PNG image, one per occupied bit index.
+///
+/// Scans the bit planes like [`occupied_bit_indices`] and extracts the
+/// payload of each plane holding a valid header, in ascending bit index
+/// order. Layers that cannot be extracted with `password` are skipped, such
+/// as password-derived layers when no password is given. With a wrong
+/// password, those layers are returned but their payload is garbage.
+/// Obfuscation is not reversed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::extract_all_layers;
+///
+/// let png_data = std::fs::read("layered.png")?;
+/// for (bit_index, payload) in extract_all_layers(&png_data, Some("secret"))? {
+///     println!("{bit_index:?}: {} bytes", payload.len());
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if the data is not a valid PNG image
+/// supported for embedding.
+pub fn extract_all_layers<P: AsRef<[u8]>>(
+    png_data: P,
+    password: Option<&str>,
+) -> Result<Vec<(crate::strategy::lsb::BitIndex, Vec<u8>)>, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    let config = crate::strategy::lsb::LSBConfig::random()
+        .with_password_if_some(password.map(str::to_string));
+
+    let layers = LSBEmbedder::occupied_bit_indices(&mut image_data, &config)
+        .into_iter()
+        .filter_map(|bit_index| {
+            let config = config.clone().with_bit_index(bit_index);
+            LSBEmbedder::extract(&mut image_data, &config)
+                .ok()
+                .map(|result| (bit_index, result.payload))
+        })
+        .collect();
+    Ok(layers)
+}
+
+/// Recovers the seed a random-pattern payload was embedded with.
+///
+/// Returns the seed stored in the header for auto-generated seeds, or the one
+/// derived from the options otherwise. Keep it in escrow to recover the
+/// payload with [`extract_with_external_seed`] if the image header's seed
+/// bytes are later damaged. See
+/// [`LSBEmbedder::export_seed`](crate::strategy::lsb::LSBEmbedder::export_seed).
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, export_seed};
+///
+/// let png_data = std::fs::read("image.png")?;
+/// let seed = export_seed(&png_data, &EmbeddingOptions::random())?;
+/// std::fs::write("image.seed", seed)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - No valid header is found, or the payload uses a linear pattern
+///   (`PngerError::InvalidFormat`)
+pub fn export_seed<P: AsRef<[u8]>>(
+    png_data: P,
+    options: &EmbeddingOptions,
+) -> Result<[u8; strategy::lsb::SEED_SIZE], PngerError> {
+    let mut image_data = read_carrier(png_data.as_ref(), options)?;
+    match &options.strategy {
+        Strategy::LSB(lsb_config) => LSBEmbedder::export_seed(&mut image_data, lsb_config),
+        Strategy::DCT(_) | Strategy::PVD(_) => Err(whole_image_only()),
+    }
+}
+
+/// Reads the raw seed stored in the header of an auto-seed embedding.
+///
+/// For forensic analysis: payloads embedded with an auto-generated seed
+/// store the 32-byte seed in their header, and this returns it without any
+/// option or password. Returns `None` when the header stores no seed, i.e.
+/// for linear, password and manual-seed embeddings. Only the header at bit 0
+/// with the default namespace is read; use
+/// [`LSBEmbedder::read_embedded_seed`](crate::strategy::lsb::LSBEmbedder::read_embedded_seed)
+/// for other bit indices or namespaces.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::read_embedded_seed;
+///
+/// let png_data = std::fs::read("image.png")?;
+/// match read_embedded_seed(&png_data)? {
+///     Some(seed) => println!("auto seed: {seed:02x?}"),
+///     None => println!("no seed stored in the header"),
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - No valid header is found (`PngerError::InvalidFormat`)
+pub fn read_embedded_seed<P: AsRef<[u8]>>(
+    png_data: P,
+) -> Result<Option<[u8; strategy::lsb::SEED_SIZE]>, PngerError> {
+    let options = EmbeddingOptions::random();
+    let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+    LSBEmbedder::read_embedded_seed(&mut image_data, &strategy::lsb::LSBConfig::random())
+}
+
+/// Extracts a random-pattern payload with an escrowed seed.
+///
+/// The seed stored in the header, if any, is ignored, so payloads remain
+/// recoverable when those bytes were overwritten. Obfuscation is not reversed;
+/// for obfuscated payloads, extract with options built from
+/// [`LSBConfig::with_external_seed`](crate::strategy::lsb::LSBConfig::with_external_seed)
+/// instead.
 ///
-/// ## Extract with Obfuscation
+/// # Examples
 ///
 /// ```no_run
-/// use pnger::{extract_payload_from_file_with_options, EmbeddingOptions, Strategy, Obfuscation};
-/// use pnger::strategy::lsb::LSBConfig;
-///
-/// let strategy = Strategy::LSB(LSBConfig::linear());
-/// let obfuscation = Obfuscation::Xor { key: b"encryption_key".to_vec() };
-/// let options = EmbeddingOptions::new_with_obfuscation(strategy, obfuscation);
+/// use pnger::extract_with_external_seed;
+/// use pnger::strategy::lsb::BitIndex;
 ///
-/// let payload = extract_payload_from_file_with_options("encrypted_image.png", options )?;
+/// let png_data = std::fs::read("damaged.png")?;
+/// let seed: [u8; 32] = std::fs::read("image.seed")?.try_into().unwrap();
+/// let payload = extract_with_external_seed(&png_data, seed, BitIndex::LSB)?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 ///
 /// # Errors
 ///
-/// This function will return an error if:
-/// - The PNG file cannot be read or doesn't exist
-/// - The file is not a valid PNG image
-/// - The extraction strategy doesn't match the embedding strategy
-/// - Password or seed information is incorrect
-/// - Obfuscation settings don't match those used during embedding
-/// - No embedded payload is found
-/// - File I/O operations fail
-pub fn extract_payload_from_file_with_options<P: AsRef<Path>>(
-    png_path: P,
-    options: EmbeddingOptions,
+/// This function will return an error if the data is not a valid PNG image or
+/// the extraction fails (see [`LSBEmbedder::extract`](crate::strategy::lsb::LSBEmbedder::extract)).
+pub fn extract_with_external_seed<P: AsRef<[u8]>>(
+    png_data: P,
+    seed: [u8; strategy::lsb::SEED_SIZE],
+    bit_index: strategy::lsb::BitIndex,
 ) -> Result<Vec<u8>, PngerError> {
-    let png_data = read_file(png_path)?;
-    extract_payload_from_bytes_with_options(&png_data, options)
+    let config = strategy::lsb::LSBConfig::random()
+        .with_external_seed(seed)
+        .with_bit_index(bit_index);
+    extract_payload_from_bytes_with_options(png_data, EmbeddingOptions::new(Strategy::LSB(config)))
 }
 
-/// Extracts a payload from PNG data in memory using the default embedding strategy.
-///
-/// This function operates entirely in memory, making it ideal for scenarios where
-/// you already have PNG data loaded or want to avoid file I/O operations. It uses
-/// the default LSB strategy with automatic pattern detection.
-///
-/// This is the core extraction function used internally by the file-based API and
-/// provides the foundation for all extraction operations.
-///
-/// # Returns
+/// Reads a payload from PNG data without parsing its header.
 ///
-/// Returns a `Vec<u8>` with the extracted payload data.
+/// Low-level escape hatch for recovery scenarios: reads `payload_size` bytes
+/// from the `bit_index` plane, skipping the first `body_offset` carrier
+/// bytes (the [`header_overhead`] of the original options). The pattern is
+/// linear when `seed` is `None` and random with that seed otherwise. Nothing
+/// is validated and obfuscation is not reversed, see
+/// [`LSBEmbedder::extract_raw`](crate::strategy::lsb::LSBEmbedder::extract_raw).
 ///
 /// # Examples
 ///
 /// ```no_run
-/// use pnger::extract_payload_from_bytes;
-///
-/// let png_data = std::fs::read("image_with_payload.png")?;
-/// let payload = extract_payload_from_bytes(&png_data)?;
+/// use pnger::extract_raw;
+/// use pnger::strategy::lsb::BitIndex;
 ///
-/// println!("Extracted payload: {}", String::from_utf8_lossy(&payload));
+/// let png_data = std::fs::read("damaged.png")?;
+/// let payload = extract_raw(&png_data, Some([7; 32]), BitIndex::Bit0, 1024, 64)?;
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
+///
 /// # Errors
 ///
 /// This function will return an error if:
-/// - The data is not valid PNG format
-/// - No embedded payload is found in the image
-/// - The embedded data is corrupted or incomplete
-/// - PNG decoding operations fail
-///
-/// # Performance Notes
-///
-/// - Faster than file-based operations (no I/O overhead)
-/// - Memory usage scales with PNG size
-/// - Consider memory constraints with very large images
-pub fn extract_payload_from_bytes<P: AsRef<[u8]>>(png_data: P) -> Result<Vec<u8>, PngerError> {
-    extract_payload_from_bytes_with_options(png_data, EmbeddingOptions::default())
+/// - The data is not a valid PNG image supported for embedding
+/// - `payload_size` bytes do not fit after `body_offset` carrier bytes
+///   (`PngerError::PayloadTooLarge`)
+pub fn extract_raw<P: AsRef<[u8]>>(
+    png_data: P,
+    seed: Option<[u8; strategy::lsb::SEED_SIZE]>,
+    bit_index: strategy::lsb::BitIndex,
+    payload_size: usize,
+    body_offset: usize,
+) -> Result<Vec<u8>, PngerError> {
+    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    LSBEmbedder::extract_raw(&mut image_data, seed, bit_index, payload_size, body_offset)
 }
 
-/// Extracts a payload from PNG data using custom embedding options.
-///
-/// This is the most flexible extraction function, providing full control over the
-/// extraction process while operating entirely in memory. It's the foundation for
-/// all other extraction functions and handles advanced scenarios like password
-/// protection and payload obfuscation.
-///
-/// # Returns
+/// Extracts headerless data terminated by `sentinel` from PNG data.
 ///
-/// Returns a `Vec<u8>` with the extracted payload data.
+/// For interoperability with tools that embed raw bits followed by a known
+/// byte sequence instead of a length: reads from the start of the carrier
+/// with the LSB settings of `options` and returns the bytes before the first
+/// occurrence of `sentinel`. Reading stops early at the sentinel and never
+/// goes past the carrier capacity, see
+/// [`LSBEmbedder::extract_until_sentinel`](crate::strategy::lsb::LSBEmbedder::extract_until_sentinel).
 ///
 /// # Examples
 ///
-/// ## Advanced Extraction with All Features
-///
 /// ```no_run
-/// use pnger::{extract_payload_from_bytes_with_options, EmbeddingOptions, Strategy, Obfuscation};
-/// use pnger::strategy::lsb::{LSBConfig, BitIndex};
+/// use pnger::{EmbeddingOptions, extract_until_sentinel};
 ///
-/// let png_data = std::fs::read("complex_image.png")?;
+/// let png_data = std::fs::read("from_other_tool.png")?;
+/// let message = extract_until_sentinel(&png_data, b"\0\0", EmbeddingOptions::linear())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 ///
-/// // Configure extraction to match embedding settings
-/// let strategy = Strategy::LSB(
-///     LSBConfig::random()
-///         .with_password("my_secret_password".to_string())
-///         .with_bit_index(BitIndex::Bit2)
-/// );
-/// let obfuscation = Obfuscation::Xor { key: b"encryption_key".to_vec() };
-/// let options = EmbeddingOptions::new_with_obfuscation(strategy, obfuscation);
+/// # Errors
 ///
-/// let payload = extract_payload_from_bytes_with_options(&png_data, options)?;
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - `sentinel` is empty or does not occur within the capacity (`PngerError::InvalidFormat`)
+/// - `options` use the DCT or PVD strategy, obfuscation, or a random pattern
+///   with an auto-generated seed (`PngerError::UnsupportedMode`)
+pub fn extract_until_sentinel<P: AsRef<[u8]>>(
+    png_data: P,
+    sentinel: &[u8],
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let Strategy::LSB(lsb_config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+    if options.obfuscation.is_some() {
+        return Err(PngerError::UnsupportedMode(
+            "Sentinel extraction reads raw data and cannot reverse obfuscation".to_string(),
+        ));
+    }
+    let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+    LSBEmbedder::extract_until_sentinel(&mut image_data, sentinel, lsb_config)
+}
+
+/// Extracts a payload from PNG data on a best-effort basis, with a
+/// [`Confidence`](crate::strategy::lsb::Confidence) report.
 ///
-/// // The payload is now decrypted and ready to use
-/// println!("Secret message: {}", String::from_utf8_lossy(&payload));
-/// # Ok::<(), Box<dyn std::error::Error>>(())
-/// ```
+/// For recovery tools working on damaged images: instead of failing, a
+/// header whose checksum does not match is still used, and a declared size
+/// larger than the image is truncated. The report tells which integrity
+/// checks failed, see
+/// [`LSBEmbedder::extract_with_confidence`](crate::strategy::lsb::LSBEmbedder::extract_with_confidence).
+/// Obfuscation is reversed as in [`extract_payload_from_bytes_with_options`].
 ///
-/// ## Batch Processing
+/// # Examples
 ///
-/// ```rust
-/// use pnger::{extract_payload_from_bytes_with_options, EmbeddingOptions, Strategy};
-/// use pnger::strategy::lsb::LSBConfig;
+/// ```no_run
+/// use pnger::{EmbeddingOptions, extract_with_confidence};
 ///
-/// fn process_images(images: Vec<Vec<u8>>) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-///     let mut results = Vec::new();
-///     for png_data in images {
-///         let strategy = Strategy::LSB(LSBConfig::linear());
-///         let options = EmbeddingOptions::new(strategy);
-///         let payload = extract_payload_from_bytes_with_options(&png_data, options)?;
-///         results.push(String::from_utf8(payload)?);
-///     }
-///     Ok(results)
+/// let png_data = std::fs::read("damaged.png")?;
+/// let (payload, confidence) = extract_with_confidence(&png_data, EmbeddingOptions::linear())?;
+/// if !confidence.is_high() {
+///     eprintln!("payload may be corrupted: {confidence:?}");
 /// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
 ///
 /// # Errors
 ///
 /// This function will return an error if:
-/// - The data is not valid PNG format
-/// - The extraction strategy doesn't match the embedding strategy
-/// - Password or cryptographic settings are incorrect
-/// - Obfuscation key doesn't match the one used during embedding
-/// - No embedded payload is found or data is corrupted
-/// - PNG decoding operations fail
+/// - The data is not a valid PNG image supported for embedding
+/// - No header is found in the image, whatever its checksum
+/// - `options` use the DCT or PVD strategy (`PngerError::UnsupportedMode`)
+/// - The obfuscation cannot be reversed on the extracted bytes
+pub fn extract_with_confidence<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<(Vec<u8>, strategy::lsb::Confidence), PngerError> {
+    let Strategy::LSB(lsb_config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+    let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+    let (result, confidence) = LSBEmbedder::extract_with_confidence(&mut image_data, lsb_config)?;
+    let payload = match options.obfuscation {
+        Some(obfuscation) => obfuscation::deobfuscate_payload(&result.payload, obfuscation)?,
+        None => result.payload,
+    };
+    Ok((payload, confidence))
+}
+
+/// Extracts a payload from PNG data chunk by chunk.
 ///
-/// # Security Considerations
+/// Instead of returning the payload, hands it to `on_chunk` in consecutive
+/// chunks of at most [`EXTRACT_CHUNK_SIZE`](crate::strategy::lsb::EXTRACT_CHUNK_SIZE)
+/// bytes, so large payloads can be written to disk or hashed without keeping
+/// a second copy in memory. The declared payload size is validated against
+/// the image before any chunk is emitted.
 ///
-/// - Always use the same password that was used during embedding
-/// - Obfuscation keys must match exactly (case-sensitive)
-/// - Failed extraction may indicate wrong credentials or corrupted data
-/// - Consider implementing retry logic with different parameters if needed
-pub fn extract_payload_from_bytes_with_options<P: AsRef<[u8]>>(
+/// XOR obfuscation is reversed chunk by chunk. Custom transforms need the
+/// whole payload, which is then buffered before being handed out in chunks.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn create_png() -> Vec<u8> {
+/// #     let mut png_data = Vec::new();
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     encoder.set_depth(png::BitDepth::Eight);
+/// #     let mut writer = encoder.write_header().unwrap();
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3]).unwrap();
+/// #     writer.finish().unwrap();
+/// #     png_data
+/// # }
+/// use pnger::{EmbeddingOptions, embed_payload_from_bytes_with_options, extract_payload_streaming};
+///
+/// let options = EmbeddingOptions::linear().with_xor_string("key");
+/// let png = embed_payload_from_bytes_with_options(create_png(), b"payload", options.clone())?;
+///
+/// let mut total = 0;
+/// extract_payload_streaming(&png, options, &mut |chunk| total += chunk.len())?;
+/// assert_eq!(total, 7);
+/// # Ok::<(), pnger::PngerError>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error in the same cases as
+/// [`extract_payload_from_bytes_with_options`]. No chunk is emitted when the
+/// header is invalid.
+pub fn extract_payload_streaming<P: AsRef<[u8]>>(
     png_data: P,
     options: EmbeddingOptions,
-) -> Result<Vec<u8>, PngerError> {
-    let (mut reader, _) = decode_png_info(png_data.as_ref())?;
-    let mut image_data = read_image_data(&mut reader)?;
+    on_chunk: &mut impl FnMut(&[u8]),
+) -> Result<(), PngerError> {
+    let mut image_data = read_carrier(png_data.as_ref(), &options)?;
+    let Strategy::LSB(lsb_config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+
+    match options.obfuscation {
+        None => {
+            LSBEmbedder::extract_streaming(&mut image_data, lsb_config, on_chunk)?;
+        }
+        Some(Obfuscation::Xor { key }) => {
+            let mut offset = 0;
+            LSBEmbedder::extract_streaming(&mut image_data, lsb_config, &mut |chunk| {
+                on_chunk(&obfuscation::xor_payload_at(chunk, &key, offset));
+                offset += chunk.len();
+            })?;
+        }
+        Some(obfuscation) => {
+            let mut payload = Vec::new();
+            LSBEmbedder::extract_streaming(&mut image_data, lsb_config, &mut |chunk| {
+                payload.extend_from_slice(chunk)
+            })?;
+            obfuscation::deobfuscate_payload(&payload, obfuscation)?
+                .chunks(strategy::lsb::EXTRACT_CHUNK_SIZE)
+                .for_each(on_chunk);
+        }
+    }
+    Ok(())
+}
 
+/// Extracts and deobfuscates a payload from raw image samples.
+fn extract_from_buffer(
+    image_data: &mut [u8],
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
     let payload_data = match options.strategy {
-        Strategy::LSB(lsb_config) => LSBEmbedder::extract(&mut image_data, &lsb_config)?.payload,
+        Strategy::LSB(lsb_config) => LSBEmbedder::extract(image_data, &lsb_config)?.payload,
+        Strategy::DCT(_) | Strategy::PVD(_) => return Err(whole_image_only()),
     };
 
     let final_payload = match options.obfuscation {
-        Some(obfuscation) => obfuscation::deobfuscate_payload(&payload_data, obfuscation),
+        Some(obfuscation) => obfuscation::deobfuscate_payload(&payload_data, obfuscation)?,
         None => payload_data,
     };
 
@@ -885,12 +2920,18 @@ pub fn embed_payload_from_file<P: AsRef<Path>, D: AsRef<[u8]>>(
 ///
 /// - Index 0 (LSB): Most common, good invisibility vs capacity trade-off
 /// - Higher indices: Less capacity, potentially more visible, but less predictable
+///
+/// # Memory Mapping
+///
+/// With the `mmap` feature, the cover file is memory-mapped instead of read
+/// into memory. It must not be modified while the call runs, see
+/// [`extract_payload_from_file_with_options`].
 pub fn embed_payload_from_file_with_options<P: AsRef<Path>, D: AsRef<[u8]>>(
     png_path: P,
     payload_data: D,
     options: EmbeddingOptions,
 ) -> Result<Vec<u8>, PngerError> {
-    let png_data = read_file(png_path)?;
+    let png_data = load_file(png_path)?;
     embed_payload_from_bytes_with_options(&png_data, payload_data, options)
 }
 
@@ -945,6 +2986,8 @@ pub fn embed_payload_from_file_with_options<P: AsRef<Path>, D: AsRef<[u8]>>(
 /// Practical capacity is lower due to header overhead:
 /// - Small images (< 100KB): ~60-80% of theoretical capacity
 /// - Large images (> 1MB): ~90-95% of theoretical capacity
+///
+/// [`capacity_efficiency`] computes the exact ratio for a given cover.
 pub fn embed_payload_from_bytes<P: AsRef<[u8]>, D: AsRef<[u8]>>(
     png_data: P,
     payload_data: D,
@@ -1080,22 +3123,337 @@ pub fn embed_payload_from_bytes_with_options<P: AsRef<[u8]>, D: AsRef<[u8]>>(
     payload_data: D,
     options: EmbeddingOptions,
 ) -> Result<Vec<u8>, PngerError> {
-    let (mut reader, info) = decode_png_info(png_data.as_ref())?;
-    let mut image_data = read_image_data(&mut reader)?;
-    let payload_data = match options.obfuscation {
-        Some(obfuscation) => &obfuscation::obfuscate_payload(payload_data, obfuscation),
-        _ => payload_data.as_ref(),
+    let (mut reader, info) = decode_carrier_png(png_data.as_ref(), &options)?;
+    let image_data = read_image_data(&mut reader)?;
+    embed_decoded(info, image_data, payload_data.as_ref(), &options)
+}
+
+/// Embeds a payload into a decoded cover and encodes the result as PNG.
+///
+/// The shared path of the APIs embedding into whole images: `info` and
+/// `image_data` are the decoded cover, already checked against `options`
+/// with [`check_carrier_format`]. Strict minimal diff is verified on the
+/// encoded output when enabled.
+fn embed_decoded(
+    mut info: png::Info<'static>,
+    mut image_data: Vec<u8>,
+    payload_data: &[u8],
+    options: &EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if options.strict_minimal_diff && !matches!(options.strategy, Strategy::LSB(_)) {
+        return Err(PngerError::UnsupportedMode(
+            "Strict minimal diff requires the LSB strategy".to_string(),
+        ));
+    }
+    let cover = if options.strict_minimal_diff {
+        Some((
+            carrier_of(&info, image_data.clone(), options)?,
+            image_data.clone(),
+        ))
+    } else {
+        None
+    };
+    embed_into_image_data(&mut info, &mut image_data, payload_data, options)?;
+    let output = encode_png_with_data(&info, &image_data, options.output_compression)?;
+    if let (Some((cover_carrier, cover_image_data)), Strategy::LSB(config)) =
+        (cover, &options.strategy)
+    {
+        check_minimal_diff(
+            &cover_carrier,
+            &cover_image_data,
+            &info,
+            &image_data,
+            &output,
+            config,
+            options,
+        )?;
+    }
+    Ok(output)
+}
+
+/// Embeds a payload and returns the changed bytes of the decoded image
+/// instead of a re-encoded PNG.
+///
+/// For patch-based distribution: each `(offset, value)` pair gives the new
+/// value of the byte at `offset` in the decoded pixel buffer of `png_data`,
+/// in increasing offset order. Applying the pairs to a fresh decode of the
+/// same cover and encoding the result gives an image the payload can be
+/// extracted from with the same options.
+///
+/// The delta applies to the decoded buffer (as produced by the `png` crate
+/// with no transformations), not to the bytes of the PNG file, whose
+/// compressed stream changes as a whole.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn create_png() -> Vec<u8> {
+/// #     let mut png_data = Vec::new();
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 32, 32);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     encoder.set_depth(png::BitDepth::Eight);
+/// #     let mut writer = encoder.write_header().unwrap();
+/// #     writer.write_image_data(&[0u8; 32 * 32 * 3]).unwrap();
+/// #     writer.finish().unwrap();
+/// #     png_data
+/// # }
+/// use pnger::{EmbeddingOptions, embed_payload_delta};
+///
+/// let delta = embed_payload_delta(create_png(), b"patch", EmbeddingOptions::linear())?;
+/// assert!(delta.iter().all(|&(offset, _)| offset < 32 * 32 * 3));
+/// # Ok::<(), pnger::PngerError>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - `options` use palette mode, which also changes the palette
+///   (`PngerError::UnsupportedMode`)
+/// - The embedding fails, as in [`embed_payload_from_bytes_with_options`]
+pub fn embed_payload_delta<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    png_data: P,
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<Vec<(usize, u8)>, PngerError> {
+    if options.palette_mode() {
+        return Err(PngerError::UnsupportedMode(
+            "Delta embedding does not support palette mode".to_string(),
+        ));
+    }
+    let (mut reader, mut info) = decode_carrier_png(png_data.as_ref(), &options)?;
+    let cover_image_data = read_image_data(&mut reader)?;
+    let mut image_data = cover_image_data.clone();
+    embed_into_image_data(&mut info, &mut image_data, payload_data.as_ref(), &options)?;
+    Ok(cover_image_data
+        .iter()
+        .zip(&image_data)
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(offset, (_, &after))| (offset, after))
+        .collect())
+}
+
+/// Embeds a payload unless the image already carries it, returning the
+/// image unchanged in that case.
+///
+/// For retry-prone pipelines where the same payload may be embedded twice
+/// into the same cover. The payload is embedded with `options` and a hash
+/// of it stored in the header, see
+/// [`LSBConfig::with_payload_hash`](crate::strategy::lsb::LSBConfig::with_payload_hash).
+/// When the header found with `options` already stores the hash of the
+/// (obfuscated) payload, embedding is skipped and `png_data` is returned as
+/// is. Obfuscations producing different bytes on each call, such as
+/// transforms with random nonces, never match and always re-embed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_if_changed};
+///
+/// let cover = std::fs::read("cover.png")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let first = embed_if_changed(&cover, b"job 42", options.clone())?;
+/// // A retry leaves the image as it is
+/// assert_eq!(embed_if_changed(&first, b"job 42", options)?, first);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - `options` use the DCT or PVD strategy (`PngerError::UnsupportedMode`)
+/// - Embedding fails, see [`embed_payload_from_bytes_with_options`]
+pub fn embed_if_changed<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    png_data: P,
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let options = options.with_payload_hash(true);
+    let Strategy::LSB(config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+    let png_data = png_data.as_ref();
+    let mut carrier = read_carrier(png_data, &options)?;
+    let payload = obfuscate(payload_data.as_ref(), &options)?;
+    if LSBEmbedder::holds_payload(&mut carrier, config, &payload) {
+        debug_event!("image already carries the payload, skipping the embedding");
+        return Ok(png_data.to_vec());
+    }
+    embed_payload_from_bytes_with_options(png_data, payload_data, options)
+}
+
+/// Embeds a payload into decoded image samples with the strategy of
+/// `options`, updating the palette of `info` in palette mode.
+fn embed_into_image_data(
+    info: &mut png::Info<'static>,
+    image_data: &mut [u8],
+    payload_data: &[u8],
+    options: &EmbeddingOptions,
+) -> Result<(), PngerError> {
+    match &options.strategy {
+        Strategy::LSB(_) => with_carrier(info, image_data, options, |carrier| {
+            embed_into_buffer(carrier, payload_data, options.clone())
+        }),
+        Strategy::DCT(dct_config) => {
+            let payload_data = obfuscate(payload_data, options)?;
+            let layout = strategy::png_layout(info)?;
+            DCTEmbedder::embed(image_data, layout, &payload_data, dct_config)
+        }
+        Strategy::PVD(pvd_config) => {
+            let payload_data = obfuscate(payload_data, options)?;
+            let layout = strategy::png_layout(info)?;
+            PVDEmbedder::embed(image_data, layout, &payload_data, pvd_config)
+        }
+    }
+}
+
+/// Checks that `output` differs from the cover only in the planned carrier
+/// bits, see [`EmbeddingOptions::strict_minimal_diff`].
+///
+/// `cover` is the carrier of the cover, `cover_image_data` and `image_data`
+/// are the samples before and after embedding, and `info` describes the
+/// embedded image.
+fn check_minimal_diff(
+    cover: &[u8],
+    cover_image_data: &[u8],
+    info: &png::Info<'static>,
+    image_data: &[u8],
+    output: &[u8],
+    config: &crate::strategy::lsb::LSBConfig,
+    options: &EmbeddingOptions,
+) -> Result<(), PngerError> {
+    let violation = |message: String| PngerError::PayloadError {
+        message: format!("strict minimal diff: {message}"),
+    };
+
+    // The encoder must store the embedded samples and palette exactly
+    let (mut reader, output_info) = decode_carrier_png(output, options)?;
+    if read_image_data(&mut reader)? != image_data || output_info.palette != info.palette {
+        return Err(violation(
+            "the PNG encoder altered the embedded image".to_string(),
+        ));
+    }
+
+    let mut stego = read_carrier(output, options)?;
+    let planned: HashSet<u32> = LSBEmbedder::embedded_indices(&mut stego, config)?
+        .into_iter()
+        .collect();
+    let mask = config.target_mask();
+    // Palette bytes lead the carrier in palette mode, the rest are samples
+    let palette_len = if options.palette_mode() {
+        palette_entries(info)?.len()
+    } else {
+        0
     };
+    let mut changed_samples = 0;
+    for (index, (before, after)) in cover.iter().zip(&stego).enumerate() {
+        let diff = before ^ after;
+        if diff == 0 {
+            continue;
+        }
+        if diff & !mask != 0 || !planned.contains(&(index as u32)) {
+            return Err(violation(format!(
+                "carrier byte {index} changed outside the planned bits"
+            )));
+        }
+        if index >= palette_len {
+            changed_samples += 1;
+        }
+    }
+
+    // Carrier samples are a subset of the image samples, so any other
+    // change is outside the carrier
+    let changed = cover_image_data
+        .iter()
+        .zip(image_data)
+        .filter(|(before, after)| before != after)
+        .count();
+    if changed != changed_samples {
+        return Err(violation(format!(
+            "{} image bytes outside the carrier changed",
+            changed - changed_samples
+        )));
+    }
+    Ok(())
+}
+
+/// Applies the obfuscation of `options`, if any, to a payload about to be
+/// embedded.
+///
+/// Capacity is checked on the result: custom transforms may grow the payload
+/// (a cipher adding a nonce and tag, for example), so a payload that fits as
+/// is can still fail with `PngerError::PayloadTooLarge`, whose `size` is then
+/// the obfuscated size. The size change is logged as well.
+fn obfuscate<'a>(
+    payload_data: &'a [u8],
+    options: &EmbeddingOptions,
+) -> Result<Cow<'a, [u8]>, PngerError> {
+    let Some(obfuscation) = options.obfuscation.clone() else {
+        return Ok(Cow::Borrowed(payload_data));
+    };
+    let obfuscated = obfuscation::obfuscate_payload(payload_data, obfuscation)?;
+    if obfuscated.len() != payload_data.len() {
+        debug_event!(
+            "obfuscation turned the {}-byte payload into {} bytes",
+            payload_data.len(),
+            obfuscated.len()
+        );
+    }
+    Ok(Cow::Owned(obfuscated))
+}
+
+/// Obfuscates and embeds a payload into raw image samples.
+fn embed_into_buffer(
+    image_data: &mut [u8],
+    payload_data: &[u8],
+    options: EmbeddingOptions,
+) -> Result<(), PngerError> {
+    let payload_data = obfuscate(payload_data, &options)?;
 
     match options.strategy {
         Strategy::LSB(lsb_config) => {
-            LSBEmbedder::embed(&mut image_data, payload_data, &lsb_config)?;
+            LSBEmbedder::embed(image_data, &payload_data, &lsb_config)?;
         }
+        Strategy::DCT(_) | Strategy::PVD(_) => return Err(whole_image_only()),
+    }
+    Ok(())
+}
+
+/// Error of the APIs working on raw carrier bytes, which the DCT and PVD
+/// strategies cannot use since they need the image geometry.
+fn whole_image_only() -> PngerError {
+    PngerError::UnsupportedMode(
+        "The DCT and PVD strategies only support whole-image embedding and extraction".to_string(),
+    )
+}
+
+/// The 8 bytes every PNG file starts with.
+pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Rejects data without the PNG signature before it reaches the decoder,
+/// whose errors are less clear for files of another format.
+///
+/// # Errors
+///
+/// Returns `PngerError::InvalidFormat` showing the first bytes of the data
+/// when they are not the PNG signature, including for data shorter than it.
+fn check_png_signature(png_data: &[u8]) -> Result<(), PngerError> {
+    if png_data.starts_with(&PNG_SIGNATURE) {
+        return Ok(());
     }
-    encode_png_with_data(&info, &image_data)
+    let first_bytes: Vec<String> = png_data
+        .iter()
+        .take(PNG_SIGNATURE.len())
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    Err(PngerError::InvalidFormat(format!(
+        "not a PNG: bad signature (first bytes: [{}])",
+        first_bytes.join(" ")
+    )))
 }
 
-type DecodedPngInfo<'a> = Result<(png::Reader<Cursor<&'a [u8]>>, png::Info<'a>), PngerError>;
+type DecodedPngInfo<'a> = Result<(png::Reader<Cursor<&'a [u8]>>, png::Info<'static>), PngerError>;
 
 /// Decodes PNG data and extracts format information.
 ///
@@ -1110,16 +3468,73 @@ type DecodedPngInfo<'a> = Result<(png::Reader<Cursor<&'a [u8]>>, png::Info<'a>),
 /// # Errors
 ///
 /// This function will return an error if:
-/// - The data is not valid PNG format
+/// - The data does not start with the PNG signature (`PngerError::InvalidFormat`)
 /// - PNG headers are corrupted or malformed
 /// - Unsupported PNG variants or extensions
-fn decode_png_info(png_data: &[u8]) -> DecodedPngInfo {
+/// - The color type and bit depth cannot carry a payload (`PngerError::UnsupportedMode`)
+fn decode_png_info(png_data: &[u8]) -> DecodedPngInfo<'_> {
+    let (reader, info) = decode_any_png(png_data)?;
+    validate_color_format(&info)?;
+    Ok((reader, info))
+}
+
+/// Decodes PNG data of any color type and bit depth.
+///
+/// # Errors
+///
+/// This function will return an error if the data does not start with the
+/// PNG signature (`PngerError::InvalidFormat`) or its headers cannot be
+/// decoded.
+fn decode_any_png(png_data: &[u8]) -> DecodedPngInfo<'_> {
+    check_png_signature(png_data)?;
     let decoder = png::Decoder::new(Cursor::new(png_data));
     let reader = decoder.read_info()?;
     let info = reader.info().clone();
+    debug_event!(
+        "decoded {}x{} PNG ({:?}, {:?}, interlaced: {})",
+        info.width,
+        info.height,
+        info.color_type,
+        info.bit_depth,
+        info.interlaced
+    );
+    Ok((reader, info))
+}
+
+/// Decodes PNG data for the carrier selected by `options`.
+///
+/// Works like [`decode_png_info`], except that palette mode accepts indexed
+/// images only.
+///
+/// # Errors
+///
+/// This function will return an error if the data is not a valid PNG image,
+/// or if the image type does not match palette mode (`PngerError::UnsupportedMode`).
+fn decode_carrier_png<'a>(png_data: &'a [u8], options: &EmbeddingOptions) -> DecodedPngInfo<'a> {
+    let (reader, info) = decode_any_png(png_data)?;
+    check_carrier_format(&info, options)?;
     Ok((reader, info))
 }
 
+/// Checks that a decoded image can carry a payload with `options`: an
+/// indexed image in palette mode, a format accepted by
+/// [`validate_color_format`] otherwise.
+///
+/// # Errors
+///
+/// Returns `PngerError::UnsupportedMode` when the image type does not match.
+fn check_carrier_format(info: &png::Info, options: &EmbeddingOptions) -> Result<(), PngerError> {
+    if !options.palette_mode() {
+        return validate_color_format(info);
+    }
+    if info.color_type != png::ColorType::Indexed {
+        return Err(PngerError::UnsupportedMode(
+            "Palette mode requires an indexed image".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Reads raw pixel data from a PNG reader into memory.
 ///
 /// This function extracts the raw image pixel data that will be used for
@@ -1147,6 +3562,10 @@ fn read_image_data(reader: &mut png::Reader<Cursor<&[u8]>>) -> Result<Vec<u8>, P
 /// This function takes modified image data (after embedding operations) and
 /// reconstructs a valid PNG file with the same format characteristics as the
 /// original image.
+/// Adam7-interlaced images are the exception: they are written back
+/// non-interlaced, with the same pixels, see [`setup_png_encoder`].
+/// The compression level of the original is kept unless `compression`
+/// overrides it.
 ///
 /// # Returns
 ///
@@ -1156,11 +3575,33 @@ fn read_image_data(reader: &mut png::Reader<Cursor<&[u8]>>) -> Result<Vec<u8>, P
 ///
 /// This function will return an error if:
 /// - PNG encoding operations fail
-/// - Image data size doesn't match expected dimensions
+/// - Image data size doesn't match expected dimensions (`PngerError::PayloadError`)
 /// - Memory allocation or buffer operations fail
-fn encode_png_with_data(info: &png::Info, image_data: &[u8]) -> Result<Vec<u8>, PngerError> {
+fn encode_png_with_data(
+    info: &png::Info,
+    image_data: &[u8],
+    compression: Option<png::Compression>,
+) -> Result<Vec<u8>, PngerError> {
+    // A buffer resized by an embedding bug would otherwise fail deep in the
+    // encoder with an unhelpful message
+    let expected_len = (info.raw_row_length() - 1) * info.height as usize;
+    if image_data.len() != expected_len {
+        return Err(PngerError::PayloadError {
+            message: format!(
+                "Image buffer holds {} bytes but a {}x{} image needs {expected_len}; \
+                 the embedding changed the buffer size",
+                image_data.len(),
+                info.width,
+                info.height
+            ),
+        });
+    }
+
     let mut writer_buffer = BufWriter::new(Vec::new());
-    let encoder = setup_png_encoder(info, &mut writer_buffer)?;
+    let mut encoder = setup_png_encoder(info, &mut writer_buffer)?;
+    if let Some(compression) = compression {
+        encoder.set_compression(compression);
+    }
 
     let mut writer = encoder.write_header()?;
     writer.write_image_data(image_data)?;
@@ -0,0 +1,58 @@
+//! Library diagnostics behind the `logging` feature.
+//!
+//! With the feature enabled, decoding, capacity checks, header and body
+//! embedding emit `debug` records with the `pnger` target through the `log`
+//! crate. Any `log` backend (`env_logger`, `tracing-log`, ...) can collect
+//! them, e.g. `RUST_LOG=pnger=debug`. Without the feature, the events expand
+//! to nothing and their arguments are never evaluated.
+
+/// Emits a `debug` record with the `pnger` target when the `logging` feature
+/// is enabled.
+macro_rules! debug_event {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "logging")]
+        log::debug!(target: "pnger", $($arg)+);
+    };
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod tests {
+    use crate::strategy::lsb::{LSBConfig, LSBEmbedder};
+    use std::sync::Mutex;
+
+    static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == "pnger"
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                RECORDS.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn test_embed_emits_debug_events() {
+        static LOGGER: CaptureLogger = CaptureLogger;
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let mut image_data = vec![0u8; 4096];
+        LSBEmbedder::embed(&mut image_data, b"logged payload", &LSBConfig::linear()).unwrap();
+
+        let records = RECORDS.lock().unwrap();
+        assert!(
+            records
+                .iter()
+                .any(|record| record.contains("embedding 14-byte body"))
+        );
+        assert!(records.iter().any(|record| record.contains("header")));
+    }
+}
@@ -1,11 +1,14 @@
 mod cli;
 
 use anyhow::{Context, Result};
-use pnger::{embed_payload_from_file_with_options, extract_payload_from_file_with_options};
+use pnger::{
+    embed_payload_from_file_with_options, extract_payload_from_file_with_options,
+    verify_embedded_payload,
+};
 use std::fs;
 use std::io::{self, Write};
 
-use crate::cli::Cli;
+use crate::cli::{Cli, Command};
 
 macro_rules! log {
     ($level:ident($($arg:tt)+)) => {
@@ -16,7 +19,7 @@ macro_rules! log {
 fn embed_payload(args: &Cli, payload_data: &[u8]) -> Result<Vec<u8>> {
     let options = args.get_options()?;
     embed_payload_from_file_with_options(
-        args.input
+        args.input()
             .to_str()
             .context("Input file path contains invalid UTF-8")?,
         payload_data,
@@ -28,7 +31,7 @@ fn embed_payload(args: &Cli, payload_data: &[u8]) -> Result<Vec<u8>> {
 fn extract_payload(args: &Cli) -> Result<Vec<u8>> {
     let options = args.get_options()?;
     extract_payload_from_file_with_options(
-        args.input
+        args.input()
             .to_str()
             .context("Input file path contains invalid UTF-8")?,
         options,
@@ -36,6 +39,19 @@ fn extract_payload(args: &Cli) -> Result<Vec<u8>> {
     .context("Failed to extract payload from PNG")
 }
 
+fn verify_payload(args: &Cli, result: &[u8], payload_data: &[u8]) -> Result<()> {
+    let options = args.get_options()?;
+    verify_embedded_payload(result, payload_data, options)
+        .context("Verification of the embedded payload failed")?;
+    // Keep stdout clean when it carries the image
+    if args.raw {
+        eprintln!("verification OK");
+    } else {
+        println!("verification OK");
+    }
+    Ok(())
+}
+
 fn write_result(args: &Cli, result: &[u8]) -> Result<()> {
     if let Some(output_path) = args.output.as_ref() {
         fs::write(output_path, result)
@@ -56,19 +72,27 @@ fn main() -> Result<()> {
     env_logger::init();
 
     let args = Cli::parse_and_validate()?;
+    if let Some(Command::EmbedBatch(batch)) = &args.command {
+        return batch.run();
+    }
+
     let result = if args.extract {
-        log!(info("Extracting payload from {:?}", args.input));
+        log!(info("Extracting payload from {:?}", args.input()));
         extract_payload(&args)?
     } else {
         log!(info(
             "Embedding payload of {:?} into {:?}",
             args.payload,
-            args.input
+            args.input()
         ));
         let payload_file = &args.payload.clone().expect("payload has to be specified");
         let payload_data = fs::read(payload_file)
             .with_context(|| format!("Failed to read payload file '{payload_file:?}'"))?;
-        embed_payload(&args, &payload_data)?
+        let result = embed_payload(&args, &payload_data)?;
+        if args.verify {
+            verify_payload(&args, &result, &payload_data)?;
+        }
+        result
     };
     write_result(&args, &result)?;
     Ok(())
@@ -5,7 +5,10 @@
 //! before embedding, making it harder to detect and analyze even if the steganographic
 //! data is discovered.
 //!
-//! For now, only XOR encryption is supported. More encryption methods will be added in the future.
+//! XOR encryption is built in. Other ciphers can be plugged in by implementing
+//! [`PayloadTransform`] and using [`Obfuscation::Custom`], or, for AEAD
+//! ciphers taking a nonce, [`NoncedTransform`] and
+//! [`Obfuscation::derived_nonce`].
 //!
 //! ## XOR Encryption
 //!
@@ -32,8 +35,162 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use rand::{RngCore, SeedableRng};
+
+use crate::{error::PngerError, strategy::lsb::SEED_SIZE};
+
+/// Size in bytes of the keys generated by [`Obfuscation::xor_random`]
+pub const RANDOM_XOR_KEY_SIZE: usize = 32;
+
+/// Size in bytes of the nonces passed to a [`NoncedTransform`], the nonce
+/// size of AES-GCM and ChaCha20-Poly1305.
+pub const NONCE_SIZE: usize = 12;
+
+/// ChaCha stream deriving nonces from a seed, distinct from the streams the
+/// LSB strategy draws from the same seed
+const NONCE_STREAM: u64 = 6;
+
+/// A reversible payload transformation supplied by the user.
+///
+/// Implement this trait to obfuscate payloads with your own cipher, then wrap
+/// it in [`Obfuscation::Custom`]. `forward` runs before embedding and
+/// `backward` after extraction; `backward(forward(data))` must return `data`.
+/// The transformed payload may differ in size from the original.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use pnger::{EmbeddingOptions, Obfuscation, PngerError};
+/// use pnger::obfuscation::PayloadTransform;
+///
+/// struct Reverse;
+///
+/// impl PayloadTransform for Reverse {
+///     fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+///         Ok(data.iter().rev().copied().collect())
+///     }
+///
+///     fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+///         self.forward(data)
+///     }
+/// }
+///
+/// let mut options = EmbeddingOptions::linear();
+/// options.set_obfuscation(Some(Obfuscation::Custom(Arc::new(Reverse))));
+/// ```
+pub trait PayloadTransform: Send + Sync {
+    /// Transforms the payload before embedding.
+    ///
+    /// # Errors
+    ///
+    /// Implementations should return `PngerError::PayloadError` when the
+    /// payload cannot be transformed.
+    fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError>;
+
+    /// Reverses [`forward`](Self::forward) after extraction.
+    ///
+    /// # Errors
+    ///
+    /// Implementations should return `PngerError::PayloadError` when the
+    /// extracted data cannot be restored, for example on authentication failure.
+    fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError>;
+}
+
+/// A payload transformation taking a nonce, such as an AEAD cipher.
+///
+/// Wrapped by [`Obfuscation::derived_nonce`], which derives the nonce from
+/// the embedding seed and a counter, so that the transformed payload does
+/// not need to carry it. `backward(nonce, forward(nonce, data))` must return
+/// `data`.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::PngerError;
+/// use pnger::obfuscation::{NONCE_SIZE, NoncedTransform};
+///
+/// // Stand-in for an AEAD cipher: XOR with the nonce
+/// struct NonceXor;
+///
+/// impl NoncedTransform for NonceXor {
+///     fn forward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+///         Ok(data.iter().zip(nonce.iter().cycle()).map(|(a, b)| a ^ b).collect())
+///     }
+///
+///     fn backward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+///         self.forward(nonce, data)
+///     }
+/// }
+/// ```
+pub trait NoncedTransform: Send + Sync {
+    /// Transforms the payload before embedding, using `nonce`.
+    ///
+    /// # Errors
+    ///
+    /// Implementations should return `PngerError::PayloadError` when the
+    /// payload cannot be transformed.
+    fn forward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError>;
+
+    /// Reverses [`forward`](Self::forward) after extraction, using the same
+    /// `nonce`.
+    ///
+    /// # Errors
+    ///
+    /// Implementations should return `PngerError::PayloadError` when the
+    /// extracted data cannot be restored, for example on authentication failure.
+    fn backward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError>;
+}
+
+/// Derives the nonce of embedding number `counter` under `seed`.
+///
+/// The first 8 bytes are drawn from a ChaCha20 stream keyed by `seed`, the
+/// last 4 hold `counter` in big-endian order: nonces derived from the same
+/// seed with different counters always differ.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::obfuscation::derive_nonce;
+///
+/// let seed = [7u8; 32];
+/// assert_eq!(derive_nonce(&seed, 1), derive_nonce(&seed, 1));
+/// assert_ne!(derive_nonce(&seed, 1), derive_nonce(&seed, 2));
+/// assert_eq!(derive_nonce(&seed, 2)[8..], 2u32.to_be_bytes());
+/// ```
+pub fn derive_nonce(seed: &[u8; SEED_SIZE], counter: u32) -> [u8; NONCE_SIZE] {
+    let mut rng = rand_chacha::ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(NONCE_STREAM);
+    let mut nonce = [0; NONCE_SIZE];
+    rng.fill_bytes(&mut nonce[..NONCE_SIZE - 4]);
+    nonce[NONCE_SIZE - 4..].copy_from_slice(&counter.to_be_bytes());
+    nonce
+}
+
+// Applies a nonced transform with a fixed nonce
+struct DerivedNonce {
+    transform: Arc<dyn NoncedTransform>,
+    nonce: [u8; NONCE_SIZE],
+}
+
+impl PayloadTransform for DerivedNonce {
+    fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+        self.transform.forward(&self.nonce, data)
+    }
+
+    fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+        self.transform.backward(&self.nonce, data)
+    }
+}
+
 /// Enumeration of available payload obfuscation methods.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub enum Obfuscation {
     /// XOR-based obfuscation using a repeating key.
     ///
@@ -64,6 +221,143 @@ pub enum Obfuscation {
         /// The same key must be used for both obfuscation and deobfuscation.
         key: Vec<u8>,
     },
+
+    /// User-supplied transformation, such as a certified cipher.
+    ///
+    /// The same transform must be provided for embedding and extraction.
+    /// See [`PayloadTransform`] for an example.
+    Custom(Arc<dyn PayloadTransform>),
+}
+
+impl Obfuscation {
+    /// XOR obfuscation with a freshly generated random key.
+    ///
+    /// The key is [`RANDOM_XOR_KEY_SIZE`] bytes from the operating system's
+    /// cryptographically secure generator. It is needed again for extraction,
+    /// so read it back from the [`Xor`](Self::Xor) variant and store it, or
+    /// use [`EmbeddingOptions::with_random_xor`](crate::EmbeddingOptions::with_random_xor)
+    /// which returns it directly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::Obfuscation;
+    ///
+    /// let Obfuscation::Xor { key } = Obfuscation::xor_random() else {
+    ///     unreachable!()
+    /// };
+    /// assert_eq!(key.len(), pnger::obfuscation::RANDOM_XOR_KEY_SIZE);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operating system random number generator is unavailable.
+    pub fn xor_random() -> Self {
+        Self::Xor {
+            key: random_xor_key(),
+        }
+    }
+
+    /// Custom obfuscation with `transform`, its nonce derived from `seed`
+    /// and `counter` instead of being stored, see [`derive_nonce`].
+    ///
+    /// An AEAD cipher usually prepends its [`NONCE_SIZE`]-byte nonce to the
+    /// ciphertext; deriving it saves those bytes of capacity. Extraction
+    /// needs the same seed and counter.
+    ///
+    /// **Nonce reuse:** an AEAD nonce must never encrypt two different
+    /// payloads under the same key. Reusing one reveals the XOR of both
+    /// plaintexts, and with AES-GCM or ChaCha20-Poly1305 lets an attacker
+    /// forge ciphertexts. The derived nonce only depends on `seed` and
+    /// `counter`: only use it with a seed unique to the image, such as a
+    /// per-image password or manual seed, or increment `counter` for every
+    /// payload embedded with the same seed and key. When that cannot be
+    /// guaranteed, let the transform store a random nonce instead.
+    ///
+    /// [`EmbeddingOptions::with_derived_nonce`](crate::EmbeddingOptions::with_derived_nonce)
+    /// takes the seed from the options.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use pnger::{Obfuscation, PngerError};
+    /// use pnger::obfuscation::{NONCE_SIZE, NoncedTransform};
+    ///
+    /// struct NonceXor;
+    ///
+    /// impl NoncedTransform for NonceXor {
+    ///     fn forward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+    ///         Ok(data.iter().zip(nonce.iter().cycle()).map(|(a, b)| a ^ b).collect())
+    ///     }
+    ///
+    ///     fn backward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+    ///         self.forward(nonce, data)
+    ///     }
+    /// }
+    ///
+    /// let obfuscation = Obfuscation::derived_nonce(Arc::new(NonceXor), &[7; 32], 1);
+    /// ```
+    pub fn derived_nonce(
+        transform: Arc<dyn NoncedTransform>,
+        seed: &[u8; SEED_SIZE],
+        counter: u32,
+    ) -> Self {
+        Self::Custom(Arc::new(DerivedNonce {
+            transform,
+            nonce: derive_nonce(seed, counter),
+        }))
+    }
+}
+
+/// Generates a random key of [`RANDOM_XOR_KEY_SIZE`] bytes.
+pub(crate) fn random_xor_key() -> Vec<u8> {
+    let mut key = vec![0u8; RANDOM_XOR_KEY_SIZE];
+    getrandom::fill(&mut key).expect("operating system random number generator failed");
+    key
+}
+
+impl Default for Obfuscation {
+    /// XOR obfuscation with a random key, see [`Obfuscation::xor_random`].
+    fn default() -> Self {
+        Self::xor_random()
+    }
+}
+
+impl fmt::Debug for Obfuscation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Obfuscation::Xor { key } => f.debug_struct("Xor").field("key", key).finish(),
+            Obfuscation::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// XOR obfuscations are equal when their keys are. Custom transforms cannot
+/// be compared by behavior, so they are equal only when they share the same
+/// [`Arc`] allocation.
+impl PartialEq for Obfuscation {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Obfuscation::Xor { key }, Obfuscation::Xor { key: other }) => key == other,
+            (Obfuscation::Custom(transform), Obfuscation::Custom(other)) => {
+                Arc::ptr_eq(transform, other)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Obfuscation {}
+
+impl Hash for Obfuscation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Obfuscation::Xor { key } => key.hash(state),
+            Obfuscation::Custom(transform) => Arc::as_ptr(transform).cast::<()>().hash(state),
+        }
+    }
 }
 
 /// Obfuscates payload data using the specified obfuscation method.
@@ -74,14 +368,15 @@ pub enum Obfuscation {
 ///
 /// # Returns
 ///
-/// Returns the obfuscated payload data. The output size is identical to the
-/// input size for all current obfuscation methods.
+/// Returns the obfuscated payload data. XOR obfuscation keeps the input size,
+/// custom transforms may not.
 pub(crate) fn obfuscate_payload<P: AsRef<[u8]>>(
     payload_data: P,
     obfuscation: Obfuscation,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, PngerError> {
     match obfuscation {
-        Obfuscation::Xor { key } => xor_payload(payload_data, &key),
+        Obfuscation::Xor { key } => Ok(xor_payload(payload_data, &key)),
+        Obfuscation::Custom(transform) => transform.forward(payload_data.as_ref()),
     }
 }
 
@@ -103,9 +398,10 @@ pub(crate) fn obfuscate_payload<P: AsRef<[u8]>>(
 pub(crate) fn deobfuscate_payload<P: AsRef<[u8]>>(
     payload_data: P,
     obfuscation: Obfuscation,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, PngerError> {
     match obfuscation {
-        Obfuscation::Xor { key } => xor_payload(payload_data, &key),
+        Obfuscation::Xor { key } => Ok(xor_payload(payload_data, &key)),
+        Obfuscation::Custom(transform) => transform.backward(payload_data.as_ref()),
     }
 }
 
@@ -134,6 +430,25 @@ pub(crate) fn xor_payload<P: AsRef<[u8]>, K: AsRef<[u8]>>(payload_data: P, key:
         .collect()
 }
 
+/// XORs a slice starting `offset` bytes into the payload, so that a payload
+/// processed in consecutive slices matches [`xor_payload`] on the whole.
+pub(crate) fn xor_payload_at<P: AsRef<[u8]>, K: AsRef<[u8]>>(
+    payload_data: P,
+    key: K,
+    offset: usize,
+) -> Vec<u8> {
+    let key = key.as_ref();
+    if key.is_empty() {
+        return payload_data.as_ref().to_vec();
+    }
+    payload_data
+        .as_ref()
+        .iter()
+        .zip(key.iter().cycle().skip(offset % key.len()))
+        .map(|(byte, key_byte)| byte ^ key_byte)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,6 +571,35 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_xor_random_generates_distinct_keys() {
+        let key = |obfuscation| match obfuscation {
+            Obfuscation::Xor { key } => key,
+            Obfuscation::Custom(_) => panic!("Expected XOR obfuscation"),
+        };
+        let first = key(Obfuscation::xor_random());
+        let second = key(Obfuscation::default());
+
+        assert_eq!(first.len(), RANDOM_XOR_KEY_SIZE);
+        assert_eq!(second.len(), RANDOM_XOR_KEY_SIZE);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_xor_payload_at_matches_whole_payload() {
+        let payload: Vec<u8> = (0..100).collect();
+        let key = b"odd key";
+        let whole = xor_payload(&payload, key);
+
+        let mut offset = 0;
+        let mut pieces = Vec::new();
+        for chunk in payload.chunks(9) {
+            pieces.extend(xor_payload_at(chunk, key, offset));
+            offset += chunk.len();
+        }
+        assert_eq!(pieces, whole);
+    }
+
     #[test]
     fn test_xor_payload_large_data() {
         let payload = vec![0x42; 1000]; // 1KB of 0x42
@@ -272,4 +616,128 @@ mod tests {
         let decrypted = xor_payload(&result, key);
         assert_eq!(decrypted, payload);
     }
+
+    struct Rot13;
+
+    impl PayloadTransform for Rot13 {
+        fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+            Ok(data
+                .iter()
+                .map(|&byte| match byte {
+                    b'a'..=b'z' => (byte - b'a' + 13) % 26 + b'a',
+                    b'A'..=b'Z' => (byte - b'A' + 13) % 26 + b'A',
+                    _ => byte,
+                })
+                .collect())
+        }
+
+        fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+            self.forward(data)
+        }
+    }
+
+    #[test]
+    fn test_custom_transform_roundtrip() {
+        let payload = b"Hello, World!";
+        let obfuscation = Obfuscation::Custom(Arc::new(Rot13));
+
+        let obfuscated = obfuscate_payload(payload, obfuscation.clone()).unwrap();
+        assert_eq!(obfuscated, b"Uryyb, Jbeyq!");
+        assert_eq!(
+            deobfuscate_payload(&obfuscated, obfuscation.clone()).unwrap(),
+            payload
+        );
+
+        // The transform is applied around the embedding itself
+        let mut options = crate::EmbeddingOptions::linear();
+        options.set_obfuscation(Some(obfuscation));
+        let mut image_data = vec![0u8; 1000];
+        crate::embed_into_buffer(&mut image_data, payload, options.clone()).unwrap();
+
+        let raw =
+            crate::extract_from_buffer(&mut image_data.clone(), crate::EmbeddingOptions::linear())
+                .unwrap();
+        assert_eq!(raw, b"Uryyb, Jbeyq!");
+        assert_eq!(
+            crate::extract_from_buffer(&mut image_data, options).unwrap(),
+            payload
+        );
+    }
+
+    // Stand-in for an AEAD cipher: XOR with the nonce, then a 4-byte tag
+    // authenticating the nonce and ciphertext
+    struct NonceXorTag;
+
+    impl NoncedTransform for NonceXorTag {
+        fn forward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+            let mut sealed = xor_payload(data, nonce);
+            let tag = crc32fast::hash(&[&nonce[..], &sealed].concat());
+            sealed.extend_from_slice(&tag.to_be_bytes());
+            Ok(sealed)
+        }
+
+        fn backward(&self, nonce: &[u8; NONCE_SIZE], data: &[u8]) -> Result<Vec<u8>, PngerError> {
+            let (sealed, tag) = data.split_at(data.len().saturating_sub(4));
+            if crc32fast::hash(&[&nonce[..], sealed].concat()).to_be_bytes() != tag {
+                return Err(PngerError::PayloadError {
+                    message: "authentication failed".to_string(),
+                });
+            }
+            Ok(xor_payload(sealed, nonce))
+        }
+    }
+
+    #[test]
+    fn test_derived_nonce_roundtrip() {
+        let payload = b"sealed without a stored nonce";
+        let seeded = crate::EmbeddingOptions::new(crate::Strategy::LSB(
+            crate::strategy::lsb::LSBConfig::random().with_seed([3; SEED_SIZE]),
+        ));
+        let with_counter = |counter| {
+            seeded
+                .clone()
+                .with_derived_nonce(Arc::new(NonceXorTag), counter)
+                .unwrap()
+        };
+
+        let mut image_data = vec![0u8; 2000];
+        crate::embed_into_buffer(&mut image_data, payload, with_counter(1)).unwrap();
+        assert_eq!(
+            crate::extract_from_buffer(&mut image_data.clone(), with_counter(1)).unwrap(),
+            payload
+        );
+
+        // Only the tag is added, the nonce is not stored
+        let raw = crate::extract_from_buffer(&mut image_data.clone(), seeded.clone()).unwrap();
+        assert_eq!(raw.len(), payload.len() + 4);
+
+        // The next counter seals the same payload under another nonce, which
+        // does not open the first one
+        let mut next = vec![0u8; 2000];
+        crate::embed_into_buffer(&mut next, payload, with_counter(2)).unwrap();
+        assert_ne!(
+            crate::extract_from_buffer(&mut next, seeded.clone()).unwrap(),
+            raw
+        );
+        assert!(matches!(
+            crate::extract_from_buffer(&mut image_data, with_counter(2)),
+            Err(PngerError::PayloadError { .. })
+        ));
+
+        // A seed never yields the same nonce for two counters
+        let nonces: std::collections::HashSet<_> = (0..1000)
+            .map(|counter| derive_nonce(&[3; SEED_SIZE], counter))
+            .collect();
+        assert_eq!(nonces.len(), 1000);
+
+        for options in [
+            crate::EmbeddingOptions::linear(),
+            crate::EmbeddingOptions::random(),
+        ] {
+            assert!(matches!(
+                options.with_derived_nonce(Arc::new(NonceXorTag), 1),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+        }
+    }
 }
@@ -0,0 +1,38 @@
+//! Common imports in one line.
+//!
+//! `use pnger::prelude::*;` brings in the options and error types used by
+//! almost every program, along with the embedding and extraction functions
+//! taking [`EmbeddingOptions`]. Specialized APIs (frames, regions, splitting,
+//! analysis) and the strategy configurations other than [`LSBConfig`] are
+//! left out to keep glob imports from clashing with user code; import them
+//! from the crate root or [`strategy`](crate::strategy).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pnger::prelude::*;
+//!
+//! # let mut cover = Vec::new();
+//! # let mut encoder = png::Encoder::new(&mut cover, 32, 32);
+//! # encoder.set_color(png::ColorType::Rgb);
+//! # encoder.set_depth(png::BitDepth::Eight);
+//! # let mut writer = encoder.write_header().unwrap();
+//! # writer.write_image_data(&[128; 32 * 32 * 3]).unwrap();
+//! # writer.finish().unwrap();
+//! let config = LSBConfig::random()
+//!     .with_password("secret".to_string())
+//!     .with_bit_index(BitIndex::Bit1);
+//! let options = EmbeddingOptions::new(Strategy::LSB(config)).with_xor_key(b"key".to_vec());
+//!
+//! let stego = embed_payload_from_bytes_with_options(&cover, b"prelude", options.clone())?;
+//! let payload = extract_payload_from_bytes_with_options(&stego, options)?;
+//! assert_eq!(payload, b"prelude");
+//! # Ok::<(), PngerError>(())
+//! ```
+
+pub use crate::strategy::lsb::{BitIndex, LSBConfig};
+pub use crate::{
+    EmbeddingOptions, Obfuscation, PngerError, Strategy, embed_payload_from_bytes_with_options,
+    embed_payload_from_file_with_options, extract_payload_from_bytes_with_options,
+    extract_payload_from_file_with_options,
+};
@@ -0,0 +1,563 @@
+//! Embedding confined to a rectangle of the image.
+//!
+//! [`embed_payload_in_region`] only modifies the pixels of a [`Region`]: the
+//! samples of the rectangle, row by row, form the carrier of a regular LSB
+//! embedding. The carrier starts with a 12-byte region header (a magic, the
+//! region width and height) so [`extract_payload_from_region`] can find the
+//! rectangle without being told where it is: it scans the image for the
+//! magic, which lies at the top-left corner of the region.
+//!
+//! [`embed_payload_in_rows`] is the simpler case of a band of full-width
+//! scanlines, a contiguous slice of the row-major pixel buffer. The range is
+//! recorded in the payload header instead, and
+//! [`extract_payload_from_rows`] finds it by reading a header at the start of
+//! each row.
+
+use std::ops::Range;
+
+use crate::{
+    EmbeddingOptions, PngerError, Strategy, decode_png_info, embed_into_buffer,
+    encode_png_with_data, extract_from_buffer, read_image_data, strategy::lsb::LSBEmbedder,
+    utils::with_color_samples, whole_image_only,
+};
+
+const REGION_MAGIC: [u8; 4] = *b"PRGN";
+// Magic, then the region width and height as big-endian u32
+const REGION_HEADER_SIZE: usize = REGION_MAGIC.len() + 4 + 4;
+// Leading part of the region header that must lie in the first row of the
+// region, so the width is known before reading the following rows
+const REGION_LOCATOR_SIZE: usize = REGION_MAGIC.len() + 4;
+
+/// A rectangle of the image, in pixels.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::Region;
+///
+/// // Bottom-right 100x100 corner of a 640x480 image
+/// let region = Region::new(540, 380, 100, 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// Column of the left edge.
+    pub x: u32,
+    /// Row of the top edge.
+    pub y: u32,
+    /// Width of the rectangle.
+    pub width: u32,
+    /// Height of the rectangle.
+    pub height: u32,
+}
+
+impl Region {
+    /// Creates the rectangle of `width` x `height` pixels whose top-left
+    /// corner is at (`x`, `y`).
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn check_fits(&self, info: &png::Info) -> Result<(), PngerError> {
+        let fits = self.width > 0
+            && self.height > 0
+            && self
+                .x
+                .checked_add(self.width)
+                .is_some_and(|right| right <= info.width)
+            && self
+                .y
+                .checked_add(self.height)
+                .is_some_and(|bottom| bottom <= info.height);
+        if !fits {
+            return Err(PngerError::UnsupportedMode(format!(
+                "Region {}x{} at ({}, {}) does not fit in the {}x{} image",
+                self.width, self.height, self.x, self.y, info.width, info.height
+            )));
+        }
+        Ok(())
+    }
+
+    // Image bytes of the region, row by row
+    fn gather(&self, image_data: &[u8], info: &png::Info) -> Vec<u8> {
+        self.row_ranges(info)
+            .flat_map(|range| image_data[range].iter().copied())
+            .collect()
+    }
+
+    fn scatter(&self, image_data: &mut [u8], info: &png::Info, region_data: &[u8]) {
+        let mut rows = region_data.chunks_exact(self.width as usize * info.bytes_per_pixel());
+        for (range, row) in self.row_ranges(info).zip(&mut rows) {
+            image_data[range].copy_from_slice(row);
+        }
+    }
+
+    fn row_ranges(&self, info: &png::Info) -> impl Iterator<Item = std::ops::Range<usize>> {
+        let pixel_bytes = info.bytes_per_pixel();
+        let stride = info.width as usize * pixel_bytes;
+        let start = self.x as usize * pixel_bytes;
+        let len = self.width as usize * pixel_bytes;
+        (self.y as usize..(self.y + self.height) as usize)
+            .map(move |row| row * stride + start..row * stride + start + len)
+    }
+}
+
+/// Embeds a payload into the pixels of `region` only.
+///
+/// Pixels outside the rectangle are left untouched. The payload is embedded
+/// with `options` into the region samples, after a region header recording
+/// the rectangle size. The capacity is that of an image the size of the
+/// region, minus the 96 carrier bytes of the region header, and the region
+/// must be wide enough for the first 64 of them to fit in its first row
+/// (22 pixels for RGB images).
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, Region, embed_payload_in_region, extract_payload_from_region};
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 64, 64);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[128u8; 64 * 64 * 3])?;
+/// # }
+///
+/// let options = EmbeddingOptions::linear();
+/// let region = Region::new(32, 32, 32, 32);
+/// let embedded = embed_payload_in_region(&png_data, b"corner", region, options.clone())?;
+///
+/// assert_eq!(extract_payload_from_region(&embedded, options)?, b"corner");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - The region does not fit in the image or is too narrow
+///   (`PngerError::UnsupportedMode`)
+/// - `options` select palette mode or a strategy other than LSB
+///   (`PngerError::UnsupportedMode`)
+/// - The payload does not fit in the region
+pub fn embed_payload_in_region<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    png_data: P,
+    payload_data: D,
+    region: Region,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let bit = region_bit(&options)?;
+    let (mut reader, info) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    region.check_fits(&info)?;
+    let alpha = options.alpha_layout(&info);
+    if (region.width as usize) * carrier_pixel_bytes(&info, alpha.is_some())
+        < REGION_LOCATOR_SIZE * 8
+    {
+        return Err(PngerError::UnsupportedMode(format!(
+            "Region must be at least {} pixels wide for this image",
+            (REGION_LOCATOR_SIZE * 8).div_ceil(carrier_pixel_bytes(&info, alpha.is_some()))
+        )));
+    }
+
+    let mut region_data = region.gather(&image_data, &info);
+    with_color_samples(&mut region_data, alpha, |carrier| {
+        if carrier.len() < REGION_HEADER_SIZE * 8 {
+            return Err(PngerError::InsufficientCapacity);
+        }
+        let mut header = REGION_MAGIC.to_vec();
+        header.extend_from_slice(&region.width.to_be_bytes());
+        header.extend_from_slice(&region.height.to_be_bytes());
+        write_bits(carrier, &header, bit);
+        embed_into_buffer(
+            &mut carrier[REGION_HEADER_SIZE * 8..],
+            payload_data.as_ref(),
+            options.clone(),
+        )
+    })?;
+    region.scatter(&mut image_data, &info, &region_data);
+    encode_png_with_data(&info, &image_data, options.output_compression)
+}
+
+/// Extracts a payload embedded by [`embed_payload_in_region`].
+///
+/// The region is found by scanning the image for its header, so only the
+/// `options` used for embedding are needed. Regions whose header is found
+/// but whose payload cannot be extracted are skipped.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - `options` select palette mode or a strategy other than LSB
+///   (`PngerError::UnsupportedMode`)
+/// - No region header is found (`PngerError::InvalidFormat`), or the error
+///   of the last region whose payload could not be extracted
+pub fn extract_payload_from_region<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    let bit = region_bit(&options)?;
+    let (mut reader, info) = decode_png_info(png_data.as_ref())?;
+    let image_data = read_image_data(&mut reader)?;
+    let alpha = options.alpha_layout(&info);
+    let pixel_bytes = carrier_pixel_bytes(&info, alpha.is_some());
+
+    let mut last_error = None;
+    for y in 0..info.height {
+        let mut row = Region::new(0, y, info.width, 1).gather(&image_data, &info);
+        if let Some(alpha) = alpha {
+            row = alpha.color_samples(&row);
+        }
+        for x in 0..info.width {
+            let start = x as usize * pixel_bytes;
+            let Some(locator) = row.get(start..start + REGION_LOCATOR_SIZE * 8) else {
+                break;
+            };
+            if !has_magic(locator, bit) {
+                continue;
+            }
+            let width = read_u32(&locator[REGION_MAGIC.len() * 8..], bit);
+            let candidate = Region::new(x, y, width, info.height - y);
+            if candidate.check_fits(&info).is_err() {
+                continue;
+            }
+
+            let mut region_data = candidate.gather(&image_data, &info);
+            if let Some(alpha) = alpha {
+                region_data = alpha.color_samples(&region_data);
+            }
+            let height = read_u32(&region_data[REGION_LOCATOR_SIZE * 8..], bit);
+            if height == 0 || height > candidate.height {
+                continue;
+            }
+            region_data.truncate(width as usize * height as usize * pixel_bytes);
+            if region_data.len() < REGION_HEADER_SIZE * 8 {
+                continue;
+            }
+            match extract_from_buffer(&mut region_data[REGION_HEADER_SIZE * 8..], options.clone()) {
+                Ok(payload) => return Ok(payload),
+                Err(error) => last_error = Some(error),
+            }
+        }
+    }
+    Err(last_error
+        .unwrap_or_else(|| PngerError::InvalidFormat("No region header found".to_string())))
+}
+
+/// Embeds a payload into the scanlines `rows` only.
+///
+/// Rows `rows.start` to `rows.end` (excluded), at full width, form the
+/// carrier of a regular embedding with `options`; other rows are left
+/// untouched. The range is recorded in the payload header, see
+/// [`LSBConfig::with_row_range`](crate::strategy::lsb::LSBConfig::with_row_range).
+/// The capacity is that of an image with the width of the cover and the
+/// height of the range.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::{EmbeddingOptions, embed_payload_in_rows, extract_payload_from_rows};
+/// # let mut png_data = Vec::new();
+/// # {
+/// #     let mut encoder = png::Encoder::new(&mut png_data, 64, 64);
+/// #     encoder.set_color(png::ColorType::Rgb);
+/// #     let mut writer = encoder.write_header()?;
+/// #     writer.write_image_data(&[128u8; 64 * 64 * 3])?;
+/// # }
+///
+/// let options = EmbeddingOptions::random_with_password("secret");
+/// let embedded = embed_payload_in_rows(&png_data, b"bottom rows", 48..64, options.clone())?;
+///
+/// let (rows, payload) = extract_payload_from_rows(&embedded, options)?;
+/// assert_eq!((rows, payload.as_slice()), (48..64, &b"bottom rows"[..]));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - `rows` is empty, including a start past its end, or extends past the
+///   last row (`PngerError::UnsupportedMode`)
+/// - `options` select palette mode or a strategy other than LSB
+///   (`PngerError::UnsupportedMode`)
+/// - The payload does not fit in the rows
+pub fn embed_payload_in_rows<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    png_data: P,
+    payload_data: D,
+    rows: Range<u32>,
+    mut options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    region_bit(&options)?;
+    let (mut reader, info) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    if rows.is_empty() {
+        return Err(PngerError::UnsupportedMode(format!(
+            "Row range {}..{} is empty, its start must be below its end",
+            rows.start, rows.end
+        )));
+    }
+    if rows.end > info.height {
+        return Err(PngerError::UnsupportedMode(format!(
+            "Rows {}..{} do not fit in the {}-row image",
+            rows.start, rows.end, info.height
+        )));
+    }
+
+    let row_bytes = row_bytes(&info);
+    let band = rows.start as usize * row_bytes..rows.end as usize * row_bytes;
+    if let Strategy::LSB(config) = &mut options.strategy {
+        *config = std::mem::take(config).with_row_range(rows);
+    }
+    let alpha = options.alpha_layout(&info);
+    let compression = options.output_compression;
+    with_color_samples(&mut image_data[band], alpha, |carrier| {
+        embed_into_buffer(carrier, payload_data.as_ref(), options)
+    })?;
+    encode_png_with_data(&info, &image_data, compression)
+}
+
+/// Extracts a payload embedded by [`embed_payload_in_rows`].
+///
+/// Rows are tried from the top: the first one starting a header that
+/// records a range beginning at that row gives the rows to extract from.
+/// Returns the range along with the payload. Ranges whose payload cannot be
+/// extracted are skipped.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data is not a valid PNG image supported for embedding
+/// - `options` select palette mode or a strategy other than LSB
+///   (`PngerError::UnsupportedMode`)
+/// - No header recording a row range is found (`PngerError::InvalidFormat`),
+///   or the error of the last range whose payload could not be extracted
+pub fn extract_payload_from_rows<P: AsRef<[u8]>>(
+    png_data: P,
+    options: EmbeddingOptions,
+) -> Result<(Range<u32>, Vec<u8>), PngerError> {
+    region_bit(&options)?;
+    let Strategy::LSB(config) = &options.strategy else {
+        return Err(whole_image_only());
+    };
+    let (mut reader, info) = decode_png_info(png_data.as_ref())?;
+    let mut image_data = read_image_data(&mut reader)?;
+    let alpha = options.alpha_layout(&info);
+    let row_bytes = row_bytes(&info);
+
+    let mut last_error = None;
+    for y in 0..info.height {
+        // The header starts the carrier, whatever the number of rows
+        let recorded = with_color_samples(
+            &mut image_data[y as usize * row_bytes..],
+            alpha,
+            |carrier| LSBEmbedder::row_range(carrier, config),
+        );
+        let rows = match recorded {
+            Ok(Some(rows)) if rows.start == y && rows.end <= info.height && !rows.is_empty() => {
+                rows
+            }
+            _ => continue,
+        };
+        let band = rows.start as usize * row_bytes..rows.end as usize * row_bytes;
+        match with_color_samples(&mut image_data[band], alpha, |carrier| {
+            extract_from_buffer(carrier, options.clone())
+        }) {
+            Ok(payload) => return Ok((rows, payload)),
+            Err(error) => last_error = Some(error),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        PngerError::InvalidFormat("No header recording a row range found".to_string())
+    }))
+}
+
+// Bytes of one scanline of the decoded image
+fn row_bytes(info: &png::Info) -> usize {
+    info.width as usize * info.bytes_per_pixel()
+}
+
+// Bit position used by the region header: the target bit of the LSB options
+fn region_bit(options: &EmbeddingOptions) -> Result<u8, PngerError> {
+    match &options.strategy {
+        Strategy::LSB(_) if options.palette_mode() => Err(PngerError::UnsupportedMode(
+            "Region embedding does not support palette mode".to_string(),
+        )),
+        Strategy::LSB(config) => Ok(config.bit_index().position()),
+        Strategy::DCT(_) | Strategy::PVD(_) => Err(whole_image_only()),
+    }
+}
+
+// Carrier bytes per pixel, without the alpha samples when they are skipped
+fn carrier_pixel_bytes(info: &png::Info, skip_alpha: bool) -> usize {
+    let bytes_per_sample = usize::from(info.bit_depth as u8).div_ceil(8);
+    (info.color_type.samples() - usize::from(skip_alpha)) * bytes_per_sample
+}
+
+// Writes `bytes` into bit `bit` of the carrier, least significant bit first
+fn write_bits(carrier: &mut [u8], bytes: &[u8], bit: u8) {
+    let bits = bytes
+        .iter()
+        .flat_map(|byte| (0..8).map(move |shift| (byte >> shift) & 1));
+    for (sample, value) in carrier.iter_mut().zip(bits) {
+        *sample = (*sample & !(1 << bit)) | (value << bit);
+    }
+}
+
+fn read_byte(carrier: &[u8], bit: u8) -> u8 {
+    carrier[..8]
+        .iter()
+        .enumerate()
+        .fold(0, |byte, (shift, sample)| {
+            byte | (((sample >> bit) & 1) << shift)
+        })
+}
+
+fn read_u32(carrier: &[u8], bit: u8) -> u32 {
+    u32::from_be_bytes(std::array::from_fn(|i| read_byte(&carrier[i * 8..], bit)))
+}
+
+// Compares byte by byte, so most positions are rejected after 8 samples
+fn has_magic(carrier: &[u8], bit: u8) -> bool {
+    REGION_MAGIC
+        .iter()
+        .enumerate()
+        .all(|(i, &expected)| read_byte(&carrier[i * 8..], bit) == expected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::gradient_png;
+
+    fn decode(png_data: &[u8]) -> (png::Info<'static>, Vec<u8>) {
+        let (mut reader, info) = decode_png_info(png_data).unwrap();
+        (info, read_image_data(&mut reader).unwrap())
+    }
+
+    #[test]
+    fn test_region_roundtrip_leaves_outside_untouched() {
+        let region = Region::new(70, 50, 30, 30);
+        for (color_type, options) in [
+            (png::ColorType::Rgb, EmbeddingOptions::linear()),
+            (
+                png::ColorType::Rgb,
+                EmbeddingOptions::random_with_password("region").with_xor_string("key"),
+            ),
+            (
+                png::ColorType::Rgba,
+                EmbeddingOptions::linear().with_alpha_preserved(),
+            ),
+        ] {
+            let png_data = gradient_png(100, 80, color_type);
+            let payload = b"watermark in the bottom-right corner";
+            let embedded =
+                embed_payload_in_region(&png_data, payload, region, options.clone()).unwrap();
+
+            let (info, original) = decode(&png_data);
+            let (_, modified) = decode(&embedded);
+            let inside: Vec<_> = region.row_ranges(&info).collect();
+            let changed = original
+                .iter()
+                .zip(&modified)
+                .enumerate()
+                .filter(|(_, (before, after))| before != after)
+                .map(|(index, _)| index);
+            let mut changes = 0;
+            for index in changed {
+                assert!(inside.iter().any(|range| range.contains(&index)));
+                changes += 1;
+            }
+            assert!(changes > 0);
+
+            assert_eq!(
+                extract_payload_from_region(&embedded, options).unwrap(),
+                payload
+            );
+        }
+    }
+
+    #[test]
+    fn test_rows_roundtrip_leaves_other_rows_untouched() {
+        for (color_type, options) in [
+            (png::ColorType::Rgb, EmbeddingOptions::linear()),
+            (
+                png::ColorType::Rgb,
+                EmbeddingOptions::random_with_password("rows").with_xor_string("key"),
+            ),
+            (
+                png::ColorType::Rgba,
+                EmbeddingOptions::random().with_alpha_preserved(),
+            ),
+        ] {
+            let png_data = gradient_png(40, 30, color_type);
+            let payload = b"between rows 12 and 20";
+            let embedded =
+                embed_payload_in_rows(&png_data, payload, 12..20, options.clone()).unwrap();
+
+            let (info, original) = decode(&png_data);
+            let (_, modified) = decode(&embedded);
+            let row_bytes = row_bytes(&info);
+            let band = 12 * row_bytes..20 * row_bytes;
+            assert_eq!(original[..band.start], modified[..band.start]);
+            assert_eq!(original[band.end..], modified[band.end..]);
+            assert_ne!(original[band.clone()], modified[band]);
+
+            assert_eq!(
+                extract_payload_from_rows(&embedded, options).unwrap(),
+                (12..20, payload.to_vec())
+            );
+        }
+
+        let png_data = gradient_png(40, 30, png::ColorType::Rgb);
+        let options = EmbeddingOptions::linear();
+        for (rows, message) in [
+            (5..5, "Row range 5..5 is empty"),
+            (Range { start: 20, end: 12 }, "Row range 20..12 is empty"),
+            (20..31, "Rows 20..31 do not fit in the 30-row image"),
+        ] {
+            assert!(matches!(
+                embed_payload_in_rows(&png_data, b"x", rows, options.clone()),
+                Err(PngerError::UnsupportedMode(error)) if error.contains(message)
+            ));
+        }
+        assert!(matches!(
+            extract_payload_from_rows(&png_data, options),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_region_errors() {
+        let png_data = gradient_png(64, 64, png::ColorType::Rgb);
+        let options = EmbeddingOptions::linear();
+        for region in [
+            Region::new(60, 0, 10, 10),
+            Region::new(0, 0, 0, 10),
+            Region::new(0, 0, 10, 10),
+        ] {
+            assert!(matches!(
+                embed_payload_in_region(&png_data, b"x", region, options.clone()),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+        }
+        assert!(matches!(
+            embed_payload_in_region(
+                &png_data,
+                [0; 400],
+                Region::new(0, 0, 32, 32),
+                options.clone()
+            ),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+        assert!(matches!(
+            extract_payload_from_region(&png_data, options),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+}
@@ -0,0 +1,86 @@
+//! Removal of privacy-sensitive ancillary chunks.
+//!
+//! Photos often carry camera details, GPS coordinates or timestamps in
+//! ancillary chunks. Those chunks survive as-is in the cover when they are not
+//! stripped, and can identify the author of an image carrying a payload.
+
+use crate::{PngerError, decode_any_png, encode_png_with_data, read_image_data};
+
+/// Re-encodes a PNG image without its metadata chunks.
+///
+/// The `eXIf`, `tEXt`, `iTXt`, `zTXt` and `tIME` chunks are dropped, along
+/// with any other ancillary chunk not required to render the image. The pixel
+/// data and the chunks affecting its interpretation (palette, transparency,
+/// gamma, chromaticities, sRGB, ICC profile and physical dimensions) are
+/// preserved.
+///
+/// Unlike embedding, any valid PNG can be sanitized, including indexed and
+/// low bit depth images.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{embed_payload_from_bytes, sanitize_metadata};
+///
+/// let photo = std::fs::read("photo.png")?;
+/// let clean_cover = sanitize_metadata(&photo)?;
+/// let output = embed_payload_from_bytes(&clean_cover, b"secret")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The data does not start with the PNG signature (`PngerError::InvalidFormat`)
+/// - The data is not a valid PNG image
+/// - Re-encoding fails
+pub fn sanitize_metadata<P: AsRef<[u8]>>(png_data: P) -> Result<Vec<u8>, PngerError> {
+    let (mut reader, info) = decode_any_png(png_data.as_ref())?;
+    let image_data = read_image_data(&mut reader)?;
+    encode_png_with_data(&info, &image_data, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, io::Cursor};
+
+    use super::*;
+
+    fn contains_chunk(png_data: &[u8], chunk_type: &[u8; 4]) -> bool {
+        png_data.windows(4).any(|window| window == chunk_type)
+    }
+
+    #[test]
+    fn test_sanitize_removes_exif() {
+        let image_data: Vec<u8> = (0..32 * 32 * 3).map(|i| (i % 251) as u8).collect();
+        let mut info = png::Info::with_size(32, 32);
+        info.color_type = png::ColorType::Rgb;
+        info.bit_depth = png::BitDepth::Eight;
+        info.exif_metadata = Some(Cow::Owned(b"Exif\0\0camera serial 1234".to_vec()));
+
+        let mut png_data = Vec::new();
+        let mut encoder = png::Encoder::with_info(&mut png_data, info).unwrap();
+        encoder
+            .add_text_chunk("Author".to_string(), "Jane".to_string())
+            .unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&image_data).unwrap();
+        writer.finish().unwrap();
+        assert!(contains_chunk(&png_data, b"eXIf"));
+        assert!(contains_chunk(&png_data, b"tEXt"));
+
+        let sanitized = sanitize_metadata(&png_data).unwrap();
+        assert!(!contains_chunk(&sanitized, b"eXIf"));
+        assert!(!contains_chunk(&sanitized, b"tEXt"));
+
+        let decoder = png::Decoder::new(Cursor::new(sanitized.as_slice()));
+        let mut reader = decoder.read_info().unwrap();
+        assert!(reader.info().exif_metadata.is_none());
+        assert_eq!(read_image_data(&mut reader).unwrap(), image_data);
+
+        // Other formats are rejected before decoding, like everywhere else
+        let error = sanitize_metadata(b"GIF89a not a png").unwrap_err();
+        assert!(matches!(error, PngerError::InvalidFormat(_)));
+        assert!(error.to_string().contains("bad signature"), "{error}");
+    }
+}
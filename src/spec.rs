@@ -0,0 +1,245 @@
+//! Compact option specs, such as `lsb:random:bit1:xor=mykey`.
+//!
+//! Parsed by [`EmbeddingOptions::try_from`], for tools taking their
+//! configuration from a command-line flag or an environment variable.
+//!
+//! # Grammar
+//!
+//! ```text
+//! spec     = strategy *(":" token)
+//! strategy = "lsb" | "dct" | "pvd"
+//! token    = "linear" | "random"            ; LSB pattern, linear by default
+//!          | "bit0" ... "bit7"              ; LSB bit index, bit0 by default
+//!          | "password=" text               ; LSB password, implies random
+//!          | "xor=" text                    ; XOR obfuscation key
+//!          | "xorhex=" 1*(2HEXDIG)          ; XOR obfuscation key, hex-encoded
+//!          | "alpha"                        ; preserve the alpha channel
+//! ```
+//!
+//! Tokens are case-sensitive, may appear in any order, and each at most once.
+//! Values run to the next `:`, so they cannot contain one: keys holding a
+//! `:`, or any byte that is not text, go through `xorhex=`, which takes the
+//! key bytes as hex digits (`xorhex=613a62` is the key `a:b`). `xor=` and
+//! `xorhex=` set the same key, so a spec holds at most one of them.
+
+use crate::{
+    EmbeddingOptions, PngerError, Strategy,
+    strategy::{
+        dct::DCTConfig,
+        lsb::{BitIndex, LSBConfig},
+        pvd::PVDConfig,
+    },
+};
+
+/// Tokens of a spec, before they are applied.
+#[derive(Default)]
+struct SpecTokens<'a> {
+    random: Option<bool>,
+    bit_index: Option<BitIndex>,
+    password: Option<&'a str>,
+    xor_key: Option<Vec<u8>>,
+    preserve_alpha: bool,
+}
+
+impl<'a> SpecTokens<'a> {
+    fn parse(tokens: impl Iterator<Item = &'a str>) -> Result<Self, PngerError> {
+        let mut parsed = Self::default();
+        for token in tokens {
+            let duplicate = match token.split_once('=') {
+                Some(("password", value)) => {
+                    parsed.password.replace(non_empty(token, value)?).is_some()
+                }
+                Some(("xor", value)) => parsed
+                    .xor_key
+                    .replace(non_empty(token, value)?.as_bytes().to_vec())
+                    .is_some(),
+                Some(("xorhex", value)) => parsed
+                    .xor_key
+                    .replace(decode_hex(token, non_empty(token, value)?)?)
+                    .is_some(),
+                Some(_) => return Err(invalid(format!("unknown option '{token}'"))),
+                None => match token {
+                    "linear" | "random" => parsed.random.replace(token == "random").is_some(),
+                    "alpha" => std::mem::replace(&mut parsed.preserve_alpha, true),
+                    _ => match token.strip_prefix("bit").map(str::parse::<u8>) {
+                        Some(Ok(index)) => {
+                            let index = BitIndex::try_from(index).map_err(|message| {
+                                invalid(format!("bad bit index '{token}': {message}"))
+                            })?;
+                            parsed.bit_index.replace(index).is_some()
+                        }
+                        _ => return Err(invalid(format!("unknown token '{token}'"))),
+                    },
+                },
+            };
+            if duplicate {
+                return Err(invalid(format!(
+                    "'{token}' conflicts with an earlier token"
+                )));
+            }
+        }
+        Ok(parsed)
+    }
+
+    // Fails on the first token the strategy does not use
+    fn reject_lsb_tokens(&self, strategy: &str) -> Result<(), PngerError> {
+        let lsb_token = if self.random.is_some() {
+            Some("linear/random")
+        } else if self.bit_index.is_some() {
+            Some("bitN")
+        } else if self.password.is_some() {
+            Some("password")
+        } else {
+            None
+        };
+        match lsb_token {
+            Some(token) => Err(invalid(format!(
+                "'{token}' only applies to the lsb strategy, not {strategy}"
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Parses a compact spec, see the [module documentation](self).
+pub(crate) fn parse(spec: &str) -> Result<EmbeddingOptions, PngerError> {
+    let mut tokens = spec.split(':');
+    let strategy = tokens.next().unwrap_or_default();
+    let parsed = SpecTokens::parse(tokens)?;
+    let strategy = match strategy {
+        "lsb" => {
+            let random = parsed.random.unwrap_or(parsed.password.is_some());
+            let mut config = if random {
+                LSBConfig::random()
+            } else {
+                LSBConfig::linear()
+            };
+            if let Some(password) = parsed.password {
+                config = config.try_with_password(password.to_string())?;
+            }
+            Strategy::LSB(config.with_bit_index(parsed.bit_index.unwrap_or(BitIndex::LSB)))
+        }
+        "dct" => {
+            parsed.reject_lsb_tokens(strategy)?;
+            Strategy::DCT(DCTConfig::new())
+        }
+        "pvd" => {
+            parsed.reject_lsb_tokens(strategy)?;
+            Strategy::PVD(PVDConfig::new())
+        }
+        "" => return Err(invalid("missing strategy".to_string())),
+        _ => {
+            return Err(invalid(format!(
+                "unknown strategy '{strategy}', expected lsb, dct or pvd"
+            )));
+        }
+    };
+
+    let mut options = EmbeddingOptions::new(strategy);
+    if let Some(key) = parsed.xor_key {
+        options = options.with_xor_key(key);
+    }
+    if parsed.preserve_alpha {
+        options = options.with_alpha_preserved();
+    }
+    Ok(options)
+}
+
+fn non_empty<'a>(token: &str, value: &'a str) -> Result<&'a str, PngerError> {
+    if value.is_empty() {
+        return Err(invalid(format!("'{token}' needs a value")));
+    }
+    Ok(value)
+}
+
+fn decode_hex(token: &str, value: &str) -> Result<Vec<u8>, PngerError> {
+    let bad_digits = || invalid(format!("'{token}' needs pairs of hex digits"));
+    if value.len() % 2 != 0 || !value.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(bad_digits());
+    }
+    Ok((0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).expect("checked hex digits"))
+        .collect())
+}
+
+fn invalid(message: String) -> PngerError {
+    PngerError::InvalidFormat(format!("Invalid options spec: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_specs() {
+        let options = EmbeddingOptions::try_from("lsb:random:bit1:xor=mykey").unwrap();
+        let expected = EmbeddingOptions::new(Strategy::LSB(
+            LSBConfig::random().with_bit_index(BitIndex::Bit1),
+        ))
+        .with_xor_string("mykey");
+        assert!(crate::explain_mismatch(&options, &expected).is_empty());
+
+        let options = EmbeddingOptions::try_from("lsb:password=secret:alpha").unwrap();
+        let expected = EmbeddingOptions::random_with_password("secret").with_alpha_preserved();
+        assert!(crate::explain_mismatch(&options, &expected).is_empty());
+
+        let options = EmbeddingOptions::try_from("lsb").unwrap();
+        assert!(crate::explain_mismatch(&options, &EmbeddingOptions::linear()).is_empty());
+
+        // Keys holding a ':' are hex-encoded
+        let options = EmbeddingOptions::try_from("lsb:xorhex=613A62:bit2").unwrap();
+        let expected = EmbeddingOptions::new(Strategy::LSB(
+            LSBConfig::linear().with_bit_index(BitIndex::Bit2),
+        ))
+        .with_xor_string("a:b");
+        assert!(crate::explain_mismatch(&options, &expected).is_empty());
+        assert!(matches!(
+            options.obfuscation,
+            Some(crate::Obfuscation::Xor { ref key }) if key == b"a:b"
+        ));
+
+        assert!(matches!(
+            EmbeddingOptions::try_from("dct:xor=k").unwrap().strategy,
+            Strategy::DCT(_)
+        ));
+        assert!(matches!(
+            EmbeddingOptions::try_from("pvd").unwrap().strategy,
+            Strategy::PVD(_)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_specs() {
+        for (spec, message) in [
+            ("", "missing strategy"),
+            ("jpeg:random", "unknown strategy 'jpeg'"),
+            ("lsb:rnd", "unknown token 'rnd'"),
+            ("lsb:bit8", "bad bit index 'bit8'"),
+            ("lsb:key=value", "unknown option 'key=value'"),
+            ("lsb:xor=", "'xor=' needs a value"),
+            ("lsb:xorhex=6", "'xorhex=6' needs pairs of hex digits"),
+            ("lsb:xorhex=+a", "'xorhex=+a' needs pairs of hex digits"),
+            (
+                "lsb:xor=k:xorhex=6b",
+                "'xorhex=6b' conflicts with an earlier token",
+            ),
+            (
+                "lsb:random:linear",
+                "'linear' conflicts with an earlier token",
+            ),
+            ("lsb:bit1:bit2", "'bit2' conflicts with an earlier token"),
+            (
+                "lsb:linear:password=x",
+                "A password requires a random pattern",
+            ),
+            (
+                "dct:bit2",
+                "'bitN' only applies to the lsb strategy, not dct",
+            ),
+        ] {
+            let error = EmbeddingOptions::try_from(spec).unwrap_err().to_string();
+            assert!(error.contains(message), "{spec:?}: {error}");
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Splitting payloads too large for a single image across a pool of covers.
+//!
+//! [`embed_payload_auto_split`] fills the covers in order, each up to its
+//! capacity, and describes the result in a [`Manifest`]. Reassembly with
+//! [`extract_payload_with_manifest`] needs only the manifest and the stego
+//! images. With the `json` feature, the manifest converts to and from JSON
+//! for external tooling.
+
+use crate::{
+    EmbeddingOptions, PngerError, embed_payload_from_bytes_with_options,
+    extract_payload_from_bytes_with_options, payload_capacity,
+};
+
+/// Version of the manifest layout written by this release.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Description of a payload split across several images.
+///
+/// `parts` are in payload order: concatenating the payloads extracted from
+/// the images of each part, in order, restores the original payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manifest {
+    /// Manifest layout version, [`MANIFEST_VERSION`] for this release.
+    pub version: u32,
+    /// Size in bytes of the whole payload.
+    pub total_size: u64,
+    /// Payload chunks, one per stego image.
+    pub parts: Vec<ManifestPart>,
+}
+
+/// Payload chunk embedded into one image of a split.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifestPart {
+    /// Index of the cover in the pool given to [`embed_payload_auto_split`].
+    pub image_index: usize,
+    /// Offset of the chunk in the payload.
+    pub offset: u64,
+    /// Size in bytes of the chunk.
+    pub size: u64,
+}
+
+#[cfg(feature = "json")]
+impl Manifest {
+    /// Serializes the manifest as pretty-printed JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::{MANIFEST_VERSION, Manifest};
+    ///
+    /// let manifest = Manifest { version: MANIFEST_VERSION, total_size: 0, parts: Vec::new() };
+    /// assert_eq!(Manifest::from_json(&manifest.to_json()).unwrap(), manifest);
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("manifest fields serialize to JSON")
+    }
+
+    /// Parses a manifest written by [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: `json` is not a valid manifest
+    pub fn from_json(json: &str) -> Result<Self, PngerError> {
+        serde_json::from_str(json)
+            .map_err(|err| PngerError::InvalidFormat(format!("Invalid split manifest: {err}")))
+    }
+}
+
+/// Embeds a payload across a pool of PNG covers, as many as needed.
+///
+/// Covers are filled in pool order, each with as many payload bytes as its
+/// capacity allows with `options`; covers too small for a header are
+/// skipped, and unneeded ones left out. Returns the stego images, one per
+/// manifest part and in the same order, with the [`Manifest`] describing
+/// the split.
+///
+/// Each image holds a standalone embedding, so obfuscation restarts for each
+/// chunk. Capacities assume obfuscation keeps the payload size, which holds
+/// for XOR.
+///
+/// # Examples
+///
+/// ```no_run
+/// use pnger::{EmbeddingOptions, embed_payload_auto_split, extract_payload_with_manifest};
+///
+/// let covers = [std::fs::read("a.png")?, std::fs::read("b.png")?, std::fs::read("c.png")?];
+/// let payload = std::fs::read("archive.zip")?;
+/// let options = EmbeddingOptions::random_with_password("secret");
+///
+/// let (images, manifest) = embed_payload_auto_split(&covers, &payload, options.clone())?;
+/// let restored = extract_payload_with_manifest(&images, &manifest, options)?;
+/// assert_eq!(restored, payload);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// # Errors
+/// - `PngerError::PayloadTooLarge`: The pool cannot hold the whole payload
+/// - Any error of [`embed_payload_from_bytes_with_options`], such as a cover
+///   that is not a valid PNG
+pub fn embed_payload_auto_split<P: AsRef<[u8]>, D: AsRef<[u8]>>(
+    pngs: &[P],
+    payload_data: D,
+    options: EmbeddingOptions,
+) -> Result<(Vec<Vec<u8>>, Manifest), PngerError> {
+    let payload_data = payload_data.as_ref();
+    let mut images = Vec::new();
+    let mut parts = Vec::new();
+    let mut offset = 0;
+    for (image_index, png_data) in pngs.iter().enumerate() {
+        if offset == payload_data.len() {
+            break;
+        }
+        let capacity = payload_capacity(png_data.as_ref(), &options)?;
+        let size = capacity.min(payload_data.len() - offset);
+        if size == 0 {
+            continue;
+        }
+        let chunk = &payload_data[offset..offset + size];
+        images.push(embed_payload_from_bytes_with_options(
+            png_data,
+            chunk,
+            options.clone(),
+        )?);
+        parts.push(ManifestPart {
+            image_index,
+            offset: offset as u64,
+            size: size as u64,
+        });
+        offset += size;
+    }
+    if offset < payload_data.len() {
+        return Err(PngerError::PayloadTooLarge {
+            size: payload_data.len(),
+            capacity: offset,
+        });
+    }
+
+    let manifest = Manifest {
+        version: MANIFEST_VERSION,
+        total_size: payload_data.len() as u64,
+        parts,
+    };
+    Ok((images, manifest))
+}
+
+/// Reassembles a payload split by [`embed_payload_auto_split`].
+///
+/// `pngs` are the stego images in manifest order, one per part. Each chunk
+/// is checked against the size recorded in the manifest.
+///
+/// # Errors
+/// - `PngerError::InvalidFormat`: Unknown manifest version, image count
+///   differing from the part count, or a chunk, or the reassembled payload,
+///   differing in size from the manifest
+/// - Any error of [`extract_payload_from_bytes_with_options`]
+pub fn extract_payload_with_manifest<P: AsRef<[u8]>>(
+    pngs: &[P],
+    manifest: &Manifest,
+    options: EmbeddingOptions,
+) -> Result<Vec<u8>, PngerError> {
+    if manifest.version != MANIFEST_VERSION {
+        return Err(PngerError::InvalidFormat(format!(
+            "Unsupported split manifest version {}",
+            manifest.version
+        )));
+    }
+    if pngs.len() != manifest.parts.len() {
+        return Err(PngerError::InvalidFormat(format!(
+            "Split manifest lists {} parts, got {} images",
+            manifest.parts.len(),
+            pngs.len()
+        )));
+    }
+
+    let mut payload = Vec::new();
+    for (part, png_data) in manifest.parts.iter().zip(pngs) {
+        let chunk = extract_payload_from_bytes_with_options(png_data, options.clone())?;
+        if part.offset != payload.len() as u64 || part.size != chunk.len() as u64 {
+            return Err(PngerError::InvalidFormat(format!(
+                "Image {} holds {} bytes at offset {}, the manifest expects {} at offset {}",
+                part.image_index,
+                chunk.len(),
+                payload.len(),
+                part.size,
+                part.offset
+            )));
+        }
+        payload.extend_from_slice(&chunk);
+    }
+    if payload.len() as u64 != manifest.total_size {
+        return Err(PngerError::InvalidFormat(format!(
+            "Reassembled {} bytes, the manifest expects {}",
+            payload.len(),
+            manifest.total_size
+        )));
+    }
+    Ok(payload)
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_json_roundtrip() {
+        let manifest = Manifest {
+            version: MANIFEST_VERSION,
+            total_size: 300,
+            parts: vec![
+                ManifestPart {
+                    image_index: 0,
+                    offset: 0,
+                    size: 200,
+                },
+                ManifestPart {
+                    image_index: 2,
+                    offset: 200,
+                    size: 100,
+                },
+            ],
+        };
+        let json = manifest.to_json();
+        assert!(json.contains("\"image_index\": 2"));
+        assert_eq!(Manifest::from_json(&json).unwrap(), manifest);
+        assert!(matches!(
+            Manifest::from_json("{\"version\": 1}"),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+}
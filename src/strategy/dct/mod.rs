@@ -0,0 +1,603 @@
+//! Experimental DCT-domain steganography, resilient to JPEG re-encoding.
+//!
+//! Instead of pixel bits, this strategy modifies the frequency content of the
+//! image: the luminance of each 8x8 pixel block (the JPEG block size) goes
+//! through a discrete cosine transform, and four mid-frequency coefficients
+//! per block carry one bit each by quantization index modulation (QIM). A
+//! coefficient is moved to the nearest multiple of the quantization step for
+//! a 0 bit, or to the nearest odd multiple of half the step for a 1 bit; the
+//! extractor reads back which lattice the coefficient is closest to.
+//!
+//! ## Robustness
+//!
+//! JPEG quantization moves each coefficient by at most half its quantization
+//! table entry. Bits survive as long as that error stays below a quarter of
+//! the step: with the default step of 24, luminance re-encoding at quality 75
+//! or above is expected to preserve the payload. Lower JPEG qualities need a
+//! larger step, at the cost of more visible changes. Resizing, cropping (which
+//! shifts the block grid), rotation or filtering destroy the payload.
+//! Saturated regions (near black or white) cannot absorb the changes:
+//! embedding fails rather than producing an unreadable image.
+//!
+//! ## Capacity
+//!
+//! Each complete 8x8 block carries 4 bits, partial blocks at the right and
+//! bottom edges are unused, and an 8-byte header (payload length and CRC32)
+//! comes first. A 256x256 image holds 504 payload bytes, see
+//! [`DCTConfig::capacity`].
+//!
+//! ## Limitations
+//!
+//! The block order is fixed and nothing is keyed: combine with an
+//! [`Obfuscation`](crate::Obfuscation) for confidentiality. Only 8-bit images
+//! are supported, and the alpha channel is left untouched. The strategy works
+//! on whole images, so it is only available through
+//! [`embed_payload_from_bytes_with_options`](crate::embed_payload_from_bytes_with_options),
+//! [`extract_payload_from_bytes_with_options`](crate::extract_payload_from_bytes_with_options)
+//! and their file-based counterparts, or directly through [`DCTEmbedder`].
+
+use std::f64::consts::PI;
+
+use crate::PngerError;
+use crate::strategy::lsb::PixelLayout;
+
+/// Side, in pixels, of the square blocks transformed by the DCT.
+pub const BLOCK_SIZE: usize = 8;
+
+/// Size in bytes of the header preceding the payload: its length and CRC32.
+pub const HEADER_SIZE: usize = 8;
+
+/// Default quantization step of the embedding coefficients.
+pub const DEFAULT_QUANTIZATION_STEP: u16 = 24;
+
+// Smallest step whose lattices stay apart after pixel rounding
+const MIN_QUANTIZATION_STEP: u16 = 4;
+
+// Mid-frequency coefficients carrying one bit each, as (row, column) in the
+// block. Low enough to survive JPEG quantization at moderate quality, high
+// enough for the changes to blend into textured content.
+const COEFFICIENTS: [(usize, usize); 4] = [(1, 2), (2, 1), (2, 2), (0, 3)];
+
+// Passes over a block until rounding and clamping of the pixels no longer
+// move its coefficients off their lattice
+const MAX_PASSES: usize = 4;
+
+type Block = [[f64; BLOCK_SIZE]; BLOCK_SIZE];
+
+/// Configuration of the experimental DCT strategy.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::{Strategy, dct::DCTConfig};
+///
+/// // Larger step: survives stronger JPEG compression, more visible
+/// let strategy = Strategy::DCT(DCTConfig::new().with_quantization_step(40));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DCTConfig {
+    quantization_step: u16,
+}
+
+impl Default for DCTConfig {
+    fn default() -> Self {
+        Self {
+            quantization_step: DEFAULT_QUANTIZATION_STEP,
+        }
+    }
+}
+
+impl DCTConfig {
+    /// Creates a configuration with the default quantization step.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the quantization step of the embedding coefficients.
+    ///
+    /// Bits survive coefficient errors below a quarter of the step, so a
+    /// larger step tolerates stronger JPEG compression but changes pixels
+    /// more (by up to about `step / 8` per coefficient). Extraction must use
+    /// the same step. Steps below 4 are rejected when embedding or extracting.
+    pub fn with_quantization_step(mut self, step: u16) -> Self {
+        self.quantization_step = step;
+        self
+    }
+
+    /// Quantization step of the embedding coefficients.
+    pub fn quantization_step(&self) -> u16 {
+        self.quantization_step
+    }
+
+    /// Largest payload, in bytes, that fits in a `width` x `height` image.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::dct::DCTConfig;
+    ///
+    /// assert_eq!(DCTConfig::capacity(256, 256), 504);
+    /// assert_eq!(DCTConfig::capacity(16, 16), 0);
+    /// ```
+    pub fn capacity(width: usize, height: usize) -> usize {
+        (slot_count(width, height) / 8).saturating_sub(HEADER_SIZE)
+    }
+
+    pub(crate) fn step(&self) -> Result<f64, PngerError> {
+        if self.quantization_step < MIN_QUANTIZATION_STEP {
+            return Err(PngerError::UnsupportedMode(format!(
+                "DCT quantization step {} is below the minimum of {MIN_QUANTIZATION_STEP}",
+                self.quantization_step
+            )));
+        }
+        Ok(f64::from(self.quantization_step))
+    }
+}
+
+/// DCT-domain embedding and extraction on raw 8-bit pixel buffers.
+pub struct DCTEmbedder;
+
+impl DCTEmbedder {
+    /// Embed `payload` in the luminance DCT coefficients of `pixels`.
+    ///
+    /// `layout` describes the buffer: 1 or 2 channels for grayscale (with
+    /// alpha), 3 or 4 for RGB (with alpha). For color images, each pixel's
+    /// luminance change is applied equally to its R, G and B samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::dct::{DCTConfig, DCTEmbedder};
+    /// use pnger::strategy::lsb::PixelLayout;
+    ///
+    /// let layout = PixelLayout::packed(64, 64, 1);
+    /// let mut pixels: Vec<u8> = (0..64 * 64).map(|i| 96 + (i * 7 % 64) as u8).collect();
+    /// let config = DCTConfig::new();
+    ///
+    /// DCTEmbedder::embed(&mut pixels, layout, b"robust", &config).unwrap();
+    /// assert_eq!(DCTEmbedder::extract(&pixels, layout, &config).unwrap(), b"robust");
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Unsupported channel count or
+    ///   quantization step
+    /// - `PngerError::InvalidFormat`: `layout` does not fit in `pixels`
+    /// - `PngerError::PayloadTooLarge`: The payload exceeds
+    ///   [`DCTConfig::capacity`], or the image cannot hold the header
+    /// - `PngerError::PayloadError`: A block is too saturated to carry its bits
+    pub fn embed(
+        pixels: &mut [u8],
+        layout: PixelLayout,
+        payload: &[u8],
+        config: &DCTConfig,
+    ) -> Result<(), PngerError> {
+        let step = config.step()?;
+        check_layout(pixels, layout)?;
+        let header_fits = header_fits(layout.width, layout.height);
+        let capacity = if header_fits {
+            DCTConfig::capacity(layout.width, layout.height)
+        } else {
+            0
+        };
+        let too_large = || PngerError::PayloadTooLarge {
+            size: payload.len(),
+            capacity,
+        };
+        if !header_fits || payload.len() > capacity {
+            return Err(too_large());
+        }
+
+        let payload_len = u32::try_from(payload.len()).map_err(|_| too_large())?;
+        let mut message = Vec::with_capacity(HEADER_SIZE + payload.len());
+        message.extend_from_slice(&payload_len.to_be_bytes());
+        message.extend_from_slice(&checksum(&payload_len.to_be_bytes(), payload).to_be_bytes());
+        message.extend_from_slice(payload);
+        let bits: Vec<u8> = message
+            .iter()
+            .flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1))
+            .collect();
+
+        let basis = dct_basis();
+        for (block_bits, (x, y)) in bits.chunks(COEFFICIENTS.len()).zip(block_origins(layout)) {
+            embed_block(pixels, layout, (x, y), block_bits, step, &basis)?;
+        }
+        Ok(())
+    }
+
+    /// Extract a payload embedded by [`embed`](Self::embed).
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Unsupported channel count or
+    ///   quantization step
+    /// - `PngerError::InvalidFormat`: `layout` does not fit in `pixels`, or
+    ///   no intact payload is found (none embedded, a different step, or
+    ///   changes beyond the robustness limits)
+    pub fn extract(
+        pixels: &[u8],
+        layout: PixelLayout,
+        config: &DCTConfig,
+    ) -> Result<Vec<u8>, PngerError> {
+        let step = config.step()?;
+        check_layout(pixels, layout)?;
+        let basis = dct_basis();
+
+        let bits: Vec<u8> = block_origins(layout)
+            .flat_map(|origin| {
+                let coefficients = forward_dct(&read_luma(pixels, layout, origin), &basis);
+                COEFFICIENTS.map(|(u, v)| extract_bit(coefficients[u][v], step))
+            })
+            .collect();
+        let bytes: Vec<u8> = bits
+            .chunks_exact(8)
+            .map(|bits| {
+                bits.iter()
+                    .enumerate()
+                    .fold(0, |byte, (i, bit)| byte | (bit << i))
+            })
+            .collect();
+
+        let (header, body) = bytes.split_at(HEADER_SIZE.min(bytes.len()));
+        let [l0, l1, l2, l3, c0, c1, c2, c3] = header.try_into().map_err(|_| {
+            PngerError::InvalidFormat("Image too small for a DCT payload header".to_string())
+        })?;
+        let payload_len = u32::from_be_bytes([l0, l1, l2, l3]) as usize;
+        let payload = body.get(..payload_len).ok_or_else(|| {
+            PngerError::InvalidFormat(format!(
+                "DCT header declares a {payload_len}-byte payload but the image only holds {} bytes",
+                body.len()
+            ))
+        })?;
+        if checksum(&[l0, l1, l2, l3], payload) != u32::from_be_bytes([c0, c1, c2, c3]) {
+            return Err(PngerError::InvalidFormat(
+                "DCT payload checksum mismatch: no payload, a different quantization step, \
+                 or changes beyond the robustness limits"
+                    .to_string(),
+            ));
+        }
+        Ok(payload.to_vec())
+    }
+}
+
+// CRC32 of the length field and the payload. Covering the length keeps an
+// unmodified, all-zero bit stream from reading as a valid empty payload.
+fn checksum(length: &[u8], payload: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(length);
+    hasher.update(payload);
+    hasher.finalize()
+}
+
+/// Whether a `width` x `height` image holds the header, and so an empty
+/// payload.
+pub(crate) fn header_fits(width: usize, height: usize) -> bool {
+    slot_count(width, height) >= HEADER_SIZE * 8
+}
+
+// Number of coefficients carrying a bit in a `width` x `height` image
+fn slot_count(width: usize, height: usize) -> usize {
+    (width / BLOCK_SIZE) * (height / BLOCK_SIZE) * COEFFICIENTS.len()
+}
+
+fn check_layout(pixels: &[u8], layout: PixelLayout) -> Result<(), PngerError> {
+    if !(1..=4).contains(&layout.channels) {
+        return Err(PngerError::UnsupportedMode(format!(
+            "DCT strategy supports 1 to 4 channels, not {}",
+            layout.channels
+        )));
+    }
+    layout.validate(pixels.len())
+}
+
+// Top-left pixels of the complete blocks, in row-major order
+fn block_origins(layout: PixelLayout) -> impl Iterator<Item = (usize, usize)> {
+    let columns = layout.width / BLOCK_SIZE;
+    (0..layout.height / BLOCK_SIZE)
+        .flat_map(move |row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| (column * BLOCK_SIZE, row * BLOCK_SIZE))
+}
+
+fn embed_block(
+    pixels: &mut [u8],
+    layout: PixelLayout,
+    origin: (usize, usize),
+    bits: &[u8],
+    step: f64,
+    basis: &Block,
+) -> Result<(), PngerError> {
+    let decodes = |coefficients: &Block| {
+        COEFFICIENTS
+            .iter()
+            .zip(bits)
+            .all(|(&(u, v), &bit)| extract_bit(coefficients[u][v], step) == bit)
+    };
+
+    for pass in 0..MAX_PASSES {
+        let coefficients = forward_dct(&read_luma(pixels, layout, origin), basis);
+        // The first pass always quantizes, to center coefficients that
+        // decode by chance but without margin
+        if pass > 0 && decodes(&coefficients) {
+            return Ok(());
+        }
+
+        let mut delta = [[0.0; BLOCK_SIZE]; BLOCK_SIZE];
+        for (&(u, v), &bit) in COEFFICIENTS.iter().zip(bits) {
+            delta[u][v] = quantize(coefficients[u][v], bit, step) - coefficients[u][v];
+        }
+        add_luma(pixels, layout, origin, &inverse_dct(&delta, basis));
+    }
+
+    if decodes(&forward_dct(&read_luma(pixels, layout, origin), basis)) {
+        Ok(())
+    } else {
+        Err(PngerError::PayloadError {
+            message: format!(
+                "Block at ({}, {}) is too saturated to carry DCT bits",
+                origin.0, origin.1
+            ),
+        })
+    }
+}
+
+// Nearest point of the lattice encoding `bit`: multiples of `step` for 0,
+// shifted by half a step for 1
+fn quantize(coefficient: f64, bit: u8, step: f64) -> f64 {
+    let offset = f64::from(bit) * step / 2.0;
+    ((coefficient - offset) / step).round() * step + offset
+}
+
+// Bit of the lattice closest to `coefficient`
+fn extract_bit(coefficient: f64, step: f64) -> u8 {
+    let remainder = coefficient.rem_euclid(step);
+    u8::from((remainder - step / 2.0).abs() < step / 4.0)
+}
+
+// Luminance of the block at `origin`
+fn read_luma(pixels: &[u8], layout: PixelLayout, origin: (usize, usize)) -> Block {
+    let mut block = [[0.0; BLOCK_SIZE]; BLOCK_SIZE];
+    for (y, row) in block.iter_mut().enumerate() {
+        for (x, luma) in row.iter_mut().enumerate() {
+            let i = pixel_offset(layout, origin, x, y);
+            *luma = if layout.channels >= 3 {
+                0.299 * f64::from(pixels[i])
+                    + 0.587 * f64::from(pixels[i + 1])
+                    + 0.114 * f64::from(pixels[i + 2])
+            } else {
+                f64::from(pixels[i])
+            };
+        }
+    }
+    block
+}
+
+// Adds `delta` to the luminance of the block at `origin`. For color pixels,
+// adding the same value to R, G and B changes the luminance by that value.
+fn add_luma(pixels: &mut [u8], layout: PixelLayout, origin: (usize, usize), delta: &Block) {
+    let color_channels = if layout.channels >= 3 { 3 } else { 1 };
+    for (y, row) in delta.iter().enumerate() {
+        for (x, &change) in row.iter().enumerate() {
+            let i = pixel_offset(layout, origin, x, y);
+            for sample in &mut pixels[i..i + color_channels] {
+                *sample = (f64::from(*sample) + change).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+    }
+}
+
+fn pixel_offset(layout: PixelLayout, origin: (usize, usize), x: usize, y: usize) -> usize {
+    (origin.1 + y) * layout.row_stride + (origin.0 + x) * layout.channels
+}
+
+// Orthonormal DCT-II basis, as used by JPEG: `basis[k][n]` weighs sample `n`
+// in coefficient `k`
+fn dct_basis() -> Block {
+    let mut basis = [[0.0; BLOCK_SIZE]; BLOCK_SIZE];
+    for (k, row) in basis.iter_mut().enumerate() {
+        let scale = if k == 0 { (1.0 / 8.0f64).sqrt() } else { 0.5 };
+        for (n, weight) in row.iter_mut().enumerate() {
+            *weight = scale * ((2 * n + 1) as f64 * k as f64 * PI / 16.0).cos();
+        }
+    }
+    basis
+}
+
+// B * X * B^T
+fn forward_dct(block: &Block, basis: &Block) -> Block {
+    transform(block, |i, j, values| {
+        (0..BLOCK_SIZE)
+            .flat_map(|y| (0..BLOCK_SIZE).map(move |x| (y, x)))
+            .map(|(y, x)| basis[i][y] * values[y][x] * basis[j][x])
+            .sum()
+    })
+}
+
+// B^T * F * B
+fn inverse_dct(coefficients: &Block, basis: &Block) -> Block {
+    transform(coefficients, |y, x, values| {
+        (0..BLOCK_SIZE)
+            .flat_map(|u| (0..BLOCK_SIZE).map(move |v| (u, v)))
+            .map(|(u, v)| basis[u][y] * values[u][v] * basis[v][x])
+            .sum()
+    })
+}
+
+fn transform(values: &Block, entry: impl Fn(usize, usize, &Block) -> f64) -> Block {
+    let mut output = [[0.0; BLOCK_SIZE]; BLOCK_SIZE];
+    for (i, row) in output.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = entry(i, j, values);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // JPEG luminance and chrominance quantization tables at quality 50
+    const LUMINANCE_TABLE: [[f64; 8]; 8] = [
+        [16., 11., 10., 16., 24., 40., 51., 61.],
+        [12., 12., 14., 19., 26., 58., 60., 55.],
+        [14., 13., 16., 24., 40., 57., 69., 56.],
+        [14., 17., 22., 29., 51., 87., 80., 62.],
+        [18., 22., 37., 56., 68., 109., 103., 77.],
+        [24., 35., 55., 64., 81., 104., 113., 92.],
+        [49., 64., 78., 87., 103., 121., 120., 101.],
+        [72., 92., 95., 98., 112., 100., 103., 99.],
+    ];
+    const CHROMINANCE_TABLE: [[f64; 8]; 8] = [
+        [17., 18., 24., 47., 99., 99., 99., 99.],
+        [18., 21., 26., 66., 99., 99., 99., 99.],
+        [24., 26., 56., 99., 99., 99., 99., 99.],
+        [47., 66., 99., 99., 99., 99., 99., 99.],
+        [99., 99., 99., 99., 99., 99., 99., 99.],
+        [99., 99., 99., 99., 99., 99., 99., 99.],
+        [99., 99., 99., 99., 99., 99., 99., 99.],
+        [99., 99., 99., 99., 99., 99., 99., 99.],
+    ];
+
+    fn textured_rgb(width: usize, height: usize) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let wave = 40.0 * (x as f64 * 0.3).sin() * (y as f64 * 0.2).cos();
+                let noise = ((x * 7 + y * 13) % 9) as f64;
+                [
+                    (128.0 + wave + noise) as u8,
+                    (110.0 - wave / 2.0 + noise) as u8,
+                    (140.0 + wave / 3.0) as u8,
+                ]
+            })
+            .collect()
+    }
+
+    // Round-trips an RGB image through the lossy steps of a baseline JPEG
+    // encoder at `quality`: YCbCr conversion, DCT quantization, rounding
+    fn simulate_jpeg(pixels: &mut [u8], layout: PixelLayout, quality: u32) {
+        let scale = if quality < 50 {
+            5000.0 / f64::from(quality)
+        } else {
+            200.0 - 2.0 * f64::from(quality)
+        } / 100.0;
+        let quantizer = |table: &[[f64; 8]; 8]| table.map(|row| row.map(|q| (q * scale).max(1.0)));
+        let tables = [
+            quantizer(&LUMINANCE_TABLE),
+            quantizer(&CHROMINANCE_TABLE),
+            quantizer(&CHROMINANCE_TABLE),
+        ];
+        let basis = dct_basis();
+
+        for origin in block_origins(layout) {
+            let positions = (0..8).flat_map(|y| (0..8).map(move |x| (y, x)));
+            let mut planes = [[[0.0; 8]; 8]; 3];
+            for (y, x) in positions.clone() {
+                let i = pixel_offset(layout, origin, x, y);
+                let [r, g, b] = [0, 1, 2].map(|c| f64::from(pixels[i + c]));
+                planes[0][y][x] = 0.299 * r + 0.587 * g + 0.114 * b;
+                planes[1][y][x] = 128.0 - 0.168736 * r - 0.331264 * g + 0.5 * b;
+                planes[2][y][x] = 128.0 + 0.5 * r - 0.418688 * g - 0.081312 * b;
+            }
+            for (plane, table) in planes.iter_mut().zip(&tables) {
+                let mut coefficients = forward_dct(plane, &basis);
+                for (row, table_row) in coefficients.iter_mut().zip(table) {
+                    for (coefficient, q) in row.iter_mut().zip(table_row) {
+                        *coefficient = (*coefficient / q).round() * q;
+                    }
+                }
+                *plane = inverse_dct(&coefficients, &basis);
+            }
+            for (y, x) in positions {
+                let i = pixel_offset(layout, origin, x, y);
+                let [luma, cb, cr] = planes.map(|plane| plane[y][x]);
+                let rgb = [
+                    luma + 1.402 * (cr - 128.0),
+                    luma - 0.344136 * (cb - 128.0) - 0.714136 * (cr - 128.0),
+                    luma + 1.772 * (cb - 128.0),
+                ];
+                for (c, value) in rgb.into_iter().enumerate() {
+                    pixels[i + c] = value.round().clamp(0.0, 255.0) as u8;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dct_roundtrip() {
+        let basis = dct_basis();
+        let block = read_luma(&textured_rgb(8, 8), PixelLayout::packed(8, 8, 3), (0, 0));
+        let restored = inverse_dct(&forward_dct(&block, &basis), &basis);
+        for (row, restored_row) in block.iter().zip(&restored) {
+            for (value, restored_value) in row.iter().zip(restored_row) {
+                assert!((value - restored_value).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_embed_extract_rgba_keeps_alpha() {
+        let layout = PixelLayout::packed(64, 48, 4);
+        let mut pixels: Vec<u8> = textured_rgb(64, 48)
+            .chunks(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 77])
+            .collect();
+        let config = DCTConfig::new();
+        let payload = b"frequency domain";
+
+        DCTEmbedder::embed(&mut pixels, layout, payload, &config).unwrap();
+        assert_eq!(
+            DCTEmbedder::extract(&pixels, layout, &config).unwrap(),
+            payload
+        );
+        assert!(pixels.iter().skip(3).step_by(4).all(|&alpha| alpha == 77));
+    }
+
+    #[test]
+    fn test_payload_survives_jpeg_quantization() {
+        let layout = PixelLayout::packed(128, 128, 3);
+        let mut pixels = textured_rgb(128, 128);
+        let config = DCTConfig::new();
+        let payload = b"survives lossy re-encoding";
+
+        DCTEmbedder::embed(&mut pixels, layout, payload, &config).unwrap();
+        simulate_jpeg(&mut pixels, layout, 75);
+        assert_eq!(
+            DCTEmbedder::extract(&pixels, layout, &config).unwrap(),
+            payload
+        );
+
+        // Much stronger compression exceeds the default step
+        simulate_jpeg(&mut pixels, layout, 10);
+        assert!(matches!(
+            DCTEmbedder::extract(&pixels, layout, &config),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_capacity_and_errors() {
+        let layout = PixelLayout::packed(64, 32, 3);
+        let mut pixels = textured_rgb(64, 32);
+        let config = DCTConfig::new();
+        assert_eq!(DCTConfig::capacity(64, 32), 8);
+
+        assert!(matches!(
+            DCTEmbedder::embed(&mut pixels, layout, &[0; 9], &config),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+        assert!(matches!(
+            DCTEmbedder::extract(&pixels, layout, &config),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        DCTEmbedder::embed(&mut pixels, layout, &[0xA5; 8], &config).unwrap();
+        assert_eq!(
+            DCTEmbedder::extract(&pixels, layout, &config).unwrap(),
+            [0xA5; 8]
+        );
+
+        let tiny = DCTConfig::new().with_quantization_step(2);
+        assert!(matches!(
+            DCTEmbedder::embed(&mut pixels, layout, b"", &tiny),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+}
@@ -156,6 +156,47 @@ impl BitIndex {
             BitIndex::Bit7 => 7,
         }
     }
+
+    /// Packs a set of bit indices into a bitmask.
+    ///
+    /// Bit `n` of the mask is set when `BitIndex` `n` is in `indices`, so a
+    /// set of bit planes fits in a single byte, for example in a header.
+    /// Order and duplicates do not matter.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::BitIndex;
+    ///
+    /// assert_eq!(BitIndex::mask(&[BitIndex::Bit0, BitIndex::Bit2]), 0b0000_0101);
+    /// assert_eq!(BitIndex::mask(&[BitIndex::Bit7, BitIndex::Bit7]), 0b1000_0000);
+    /// assert_eq!(BitIndex::mask(&[]), 0);
+    /// ```
+    pub fn mask(indices: &[BitIndex]) -> u8 {
+        indices
+            .iter()
+            .fold(0, |mask, &bit_index| mask | 1 << bit_index.position())
+    }
+
+    /// Unpacks a bitmask built by [`mask`](Self::mask) into bit indices.
+    ///
+    /// Returns the indices of the set bits, from [`Bit0`](Self::Bit0) up.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::BitIndex;
+    ///
+    /// assert_eq!(BitIndex::from_mask(0b1000_0010), [BitIndex::Bit1, BitIndex::Bit7]);
+    /// assert!(BitIndex::from_mask(0).is_empty());
+    /// ```
+    pub fn from_mask(mask: u8) -> Vec<BitIndex> {
+        Self::all()
+            .iter()
+            .copied()
+            .filter(|bit_index| mask & 1 << bit_index.position() != 0)
+            .collect()
+    }
 }
 
 /// Convert `BitIndex` to u8 for use with low-level bit manipulation functions.
@@ -302,6 +343,31 @@ mod tests {
         assert_eq!(format!("{}", BitIndex::LSB), "Bit0");
     }
 
+    #[test]
+    fn test_mask_roundtrip() {
+        for mask in 0..=u8::MAX {
+            let indices = BitIndex::from_mask(mask);
+            assert_eq!(indices.len(), mask.count_ones() as usize);
+            assert_eq!(BitIndex::mask(&indices), mask);
+        }
+    }
+
+    #[test]
+    fn test_mask_bit_positions() {
+        for &bit_index in BitIndex::all() {
+            let mask = BitIndex::mask(&[bit_index]);
+            assert_eq!(mask, 1 << bit_index.position());
+            assert_eq!(BitIndex::from_mask(mask), [bit_index]);
+        }
+        assert_eq!(BitIndex::mask(&[BitIndex::LSB]), 0b0000_0001);
+        assert_eq!(BitIndex::mask(&[BitIndex::Bit7]), 0b1000_0000);
+        assert_eq!(
+            BitIndex::mask(&[BitIndex::Bit3, BitIndex::Bit1, BitIndex::Bit3]),
+            0b0000_1010
+        );
+        assert_eq!(BitIndex::from_mask(u8::MAX), BitIndex::all());
+    }
+
     #[test]
     fn test_round_trip_conversion() {
         // Test that converting to u8 and back gives the same result
@@ -9,12 +9,18 @@ pub enum CryptoError {
     KeyDerivation(String),
     #[error("Invalid seed length: expected {expected} bytes, got {0}", expected = SEED_SIZE)]
     InvalidSeedLength(usize),
+    #[error("Key derivation requests {requested_kib} KiB of memory, above the {max_mib} MiB limit")]
+    KdfMemoryLimit { requested_kib: u32, max_mib: u32 },
 }
 
+/// Argon2 memory cost of password-derived seeds, in KiB.
+pub const KDF_MEMORY_KIB: u32 = argon2::Params::DEFAULT_M_COST;
+
 /// Crypto mode determines how the seed is generated
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum CryptoMode {
     /// Auto-generate random seed (will be embedded in PNG)
+    #[default]
     Auto,
     /// Derive seed from password using Argon2 (nothing embedded)
     Password(String),
@@ -22,12 +28,6 @@ pub enum CryptoMode {
     Manual([u8; SEED_SIZE]),
 }
 
-impl Default for CryptoMode {
-    fn default() -> Self {
-        Self::Auto
-    }
-}
-
 #[derive(Debug, Default, Clone)]
 pub struct CryptoParams {
     pub mode: CryptoMode,
@@ -57,6 +57,30 @@ impl CryptoParams {
     }
 }
 
+// Seed returned by `generate_random_seed` instead of system randomness, so
+// tests can assert the exact output of auto-seed embeddings
+#[cfg(test)]
+thread_local! {
+    static FORCED_SEED: std::cell::Cell<Option<[u8; SEED_SIZE]>> =
+        const { std::cell::Cell::new(None) };
+}
+
+/// Runs `f` with auto-generated seeds replaced by `seed` on this thread.
+#[cfg(test)]
+pub(super) fn with_forced_seed<R>(seed: [u8; SEED_SIZE], f: impl FnOnce() -> R) -> R {
+    // Restores system randomness even if `f` panics
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            FORCED_SEED.with(|forced| forced.set(None));
+        }
+    }
+
+    FORCED_SEED.with(|forced| forced.set(Some(seed)));
+    let _reset = Reset;
+    f()
+}
+
 #[derive(Debug)]
 pub struct CryptoContext {
     pub seed: [u8; SEED_SIZE],
@@ -78,18 +102,53 @@ impl CryptoContext {
     }
 
     pub fn generate_random_seed() -> Result<[u8; SEED_SIZE], CryptoError> {
+        #[cfg(test)]
+        if let Some(seed) = FORCED_SEED.with(std::cell::Cell::get) {
+            return Ok(seed);
+        }
         Self::generate_random_bytes::<SEED_SIZE>()
     }
 
     /// Derive seed from password using Argon2 with built-in salt
-    pub fn derive_seed_from_password(password: &str) -> Result<[u8; SEED_SIZE], CryptoError> {
-        use argon2::Argon2;
+    ///
+    /// Fails without allocating when the KDF memory exceeds `max_memory_mib`.
+    pub fn derive_seed_from_password(
+        password: &str,
+        max_memory_mib: Option<u32>,
+    ) -> Result<[u8; SEED_SIZE], CryptoError> {
+        Self::derive_seed_with_memory(password, KDF_MEMORY_KIB, max_memory_mib)
+    }
+
+    /// Derive seed from password using Argon2 with `memory_kib` KiB of memory
+    pub fn derive_seed_with_memory(
+        password: &str,
+        memory_kib: u32,
+        max_memory_mib: Option<u32>,
+    ) -> Result<[u8; SEED_SIZE], CryptoError> {
+        use argon2::{Algorithm, Argon2, Params, Version};
+
+        // Checked first, so oversized parameters never reach the allocator
+        if let Some(max_mib) =
+            max_memory_mib.filter(|&max| u64::from(memory_kib) > u64::from(max) * 1024)
+        {
+            return Err(CryptoError::KdfMemoryLimit {
+                requested_kib: memory_kib,
+                max_mib,
+            });
+        }
 
         // Built-in salt ensures reproducibility without storing salt
         let salt = b"pnger_steganography_salt_v1_____"; // 32 bytes
         let mut seed = [0u8; SEED_SIZE];
 
-        let argon2 = Argon2::default();
+        let params = Params::new(
+            memory_kib,
+            Params::DEFAULT_T_COST,
+            Params::DEFAULT_P_COST,
+            None,
+        )
+        .map_err(|e| CryptoError::KeyDerivation(e.to_string()))?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
         argon2
             .hash_password_into(password.as_bytes(), salt, &mut seed)
             .map_err(|e| CryptoError::KeyDerivation(e.to_string()))?;
@@ -104,7 +163,7 @@ impl CryptoContext {
                 (seed, true)
             }
             CryptoMode::Password(password) => {
-                let seed = Self::derive_seed_from_password(&password)?;
+                let seed = Self::derive_seed_from_password(&password, None)?;
                 (seed, false)
             }
             CryptoMode::Manual(seed) => (seed, false),
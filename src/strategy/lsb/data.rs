@@ -1,93 +1,281 @@
-use crate::PayloadSize;
+use crate::strategy::lsb::shuffle;
 use crate::strategy::lsb::utils::{embed_bit, extract_bit};
-use crate::strategy::lsb::{BitIndex, RuntimePattern};
-use rand::SeedableRng;
-use rand::seq::SliceRandom;
+use crate::strategy::lsb::{BitIndex, BitOrder, EXTRACT_CHUNK_SIZE, RuntimePattern, WriteMode};
+use crate::{PayloadSize, PngerError};
+use rand::{Rng, SeedableRng};
 
 pub(super) struct BodyEmbedder<'a> {
     target_bit_index: BitIndex,
+    channel_bits: Option<Vec<BitIndex>>,
+    bit_order: BitOrder,
+    write_mode: WriteMode,
     index: usize,
     indices: Vec<PayloadSize>,
     bytes: &'a mut [u8],
 }
 
 impl<'a> BodyEmbedder<'a> {
+    /// Creates an embedder over the `candidates` carrier indices, ordered
+    /// according to `pattern`.
     pub fn new(
         bytes: &'a mut [u8],
+        candidates: Vec<PayloadSize>,
         pattern: &RuntimePattern,
         bit_index: BitIndex,
         payload_len: usize,
     ) -> Self {
-        let mut ordered_indices: Vec<u32> = (0..bytes.len()).map(|i| i as u32).collect();
-        let indices = match &pattern {
-            RuntimePattern::Linear => ordered_indices,
+        let indices = Self::order_candidates(candidates, pattern, payload_len);
+        Self::from_indices(bytes, indices, bit_index)
+    }
+
+    /// Orders the `candidates` carrier indices according to `pattern`. Random
+    /// patterns only keep the indices needed for `payload_len` bytes.
+    pub fn order_candidates(
+        mut candidates: Vec<PayloadSize>,
+        pattern: &RuntimePattern,
+        payload_len: usize,
+    ) -> Vec<PayloadSize> {
+        match &pattern {
+            RuntimePattern::Linear => candidates,
             RuntimePattern::Random { seed, .. } => {
                 let mut rng = rand_chacha::ChaCha20Rng::from_seed(*seed);
-                let (shuffled, _) = ordered_indices.partial_shuffle(&mut rng, payload_len * 8);
-                shuffled.to_vec()
+                // Requests beyond the candidates are clamped by the shuffle
+                let amount = payload_len.saturating_mul(8);
+                shuffle::partial_shuffle(&mut candidates, &mut rng, amount).to_vec()
             }
-        };
+        }
+    }
 
+    /// Creates an embedder visiting exactly `indices`, in order.
+    pub fn from_indices(
+        bytes: &'a mut [u8],
+        indices: Vec<PayloadSize>,
+        bit_index: BitIndex,
+    ) -> Self {
         Self {
             target_bit_index: bit_index,
+            channel_bits: None,
+            bit_order: BitOrder::LsbFirst,
+            write_mode: WriteMode::Overwrite,
             index: 0,
             indices,
             bytes,
         }
     }
 
-    pub fn embed_payload(&mut self, payload: &[u8]) {
-        let mut indices = self.indices.clone();
-        indices.truncate(payload.len() * 8);
-        payload.iter().for_each(|byte| self.write_u8(*byte));
+    /// Targets `channel_bits[i % channel_bits.len()]` in carrier byte `i`
+    /// instead of the single bit index, when set.
+    pub fn with_channel_bits(mut self, channel_bits: Option<Vec<BitIndex>>) -> Self {
+        self.channel_bits = channel_bits;
+        self
+    }
+
+    /// Embeds and extracts the bits of each byte in `bit_order`.
+    pub fn with_bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self
     }
 
-    pub fn extract_payload(&mut self, size: usize) -> Vec<u8> {
-        let mut indices = self.indices.clone();
-        indices.truncate(size * 8);
-        let mut payload = Vec::with_capacity(size);
-        for _ in 0..size {
-            payload.push(self.read_u8());
+    /// Writes payload bits according to `write_mode`. Reading is the same
+    /// in every mode.
+    pub fn with_write_mode(mut self, write_mode: WriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
+    }
+
+    // Position within the byte of its `n`-th embedded bit
+    const fn bit_position(&self, n: u8) -> u8 {
+        match self.bit_order {
+            BitOrder::LsbFirst => n,
+            BitOrder::MsbFirst => 7 - n,
         }
-        payload
     }
 
-    pub fn write_u8(&mut self, byte: u8) {
-        let target_bit = self.target_bit_index;
+    fn target_bit(&self, image_index: usize) -> BitIndex {
+        match &self.channel_bits {
+            Some(bits) => bits[image_index % bits.len()],
+            None => self.target_bit_index,
+        }
+    }
 
-        for bit_pos in 0..8 {
-            assert!(
-                (self.index < self.indices.len()),
-                "LSB index {} is out of bounds (max: {}). Payload too large for available capacity.",
-                self.index,
-                self.indices.len()
-            );
+    /// Embeds `payload` at the current position.
+    ///
+    /// Fails with `PngerError::PayloadTooLarge` when the remaining indices
+    /// cannot hold it; bytes written before that point stay modified.
+    pub fn embed_payload(&mut self, payload: &[u8]) -> Result<(), PngerError> {
+        payload.iter().try_for_each(|byte| self.write_u8(*byte))
+    }
 
-            let image_index = self.indices[self.index] as usize;
-            let bit = (byte >> bit_pos) & 1;
+    /// Extracts `size` bytes from the current position.
+    ///
+    /// Fails with `PngerError::InvalidFormat` when the remaining indices do
+    /// not hold `size` bytes. The buffer grows by at most
+    /// [`EXTRACT_CHUNK_SIZE`] bytes at a time as bytes are read, so a size
+    /// declared by an untrusted header is never allocated up front.
+    pub fn extract_payload(&mut self, size: usize) -> Result<Vec<u8>, PngerError> {
+        let mut payload = Vec::new();
+        while payload.len() < size {
+            let chunk = (size - payload.len()).min(EXTRACT_CHUNK_SIZE);
+            payload.reserve_exact(chunk);
+            for _ in 0..chunk {
+                payload.push(self.read_u8()?);
+            }
+        }
+        Ok(payload)
+    }
+
+    /// Embeds `copies` consecutive copies of the payload.
+    pub fn embed_redundant(&mut self, payload: &[u8], copies: usize) -> Result<(), PngerError> {
+        for _ in 0..copies {
+            self.embed_payload(payload)?;
+        }
+        Ok(())
+    }
+
+    /// Extracts `copies` consecutive copies of a `size`-byte payload and
+    /// recovers each bit by majority vote. Ties resolve to 0.
+    pub fn extract_redundant(&mut self, size: usize, copies: usize) -> Result<Vec<u8>, PngerError> {
+        self.extract_redundant_range(0, size, size, copies)
+    }
+
+    /// Extracts `len` bytes at `offset` within each of `copies` consecutive
+    /// `copy_size`-byte copies, recovering each bit by majority vote.
+    pub fn extract_redundant_range(
+        &mut self,
+        offset: usize,
+        len: usize,
+        copy_size: usize,
+        copies: usize,
+    ) -> Result<Vec<u8>, PngerError> {
+        let copies = copies.max(1);
+        let extracted: Vec<Vec<u8>> = (0..copies)
+            .map(|copy| {
+                self.index = (copy * copy_size + offset) * 8;
+                self.extract_payload(len)
+            })
+            .collect::<Result<_, _>>()?;
+        if copies == 1 {
+            return Ok(extracted.into_iter().next().unwrap_or_default());
+        }
+
+        Ok((0..len)
+            .map(|i| {
+                (0..8).fold(0u8, |byte, bit_pos| {
+                    let ones = extracted
+                        .iter()
+                        .filter(|copy| (copy[i] >> bit_pos) & 1 == 1)
+                        .count();
+                    if ones * 2 > copies {
+                        byte | (1 << bit_pos)
+                    } else {
+                        byte
+                    }
+                })
+            })
+            .collect())
+    }
+
+    /// Writes random target bits into every carrier byte that is neither in
+    /// `reserved` nor already written by this embedder.
+    pub fn randomize_unused(&mut self, reserved: &[PayloadSize], rng: &mut impl Rng) {
+        let mut used = vec![false; self.bytes.len()];
+        reserved
+            .iter()
+            .chain(&self.indices[..self.index])
+            .for_each(|&index| used[index as usize] = true);
+
+        for image_index in (0..self.bytes.len()).filter(|&index| !used[index]) {
+            let target_bit = self.target_bit(image_index);
+            let bit = u8::from(rng.random::<bool>());
             self.bytes[image_index] = embed_bit(target_bit, self.bytes[image_index], bit);
-            self.index += 1;
         }
     }
 
-    pub fn read_u8(&mut self) -> u8 {
-        let target_bit = self.target_bit_index;
-        let mut byte = 0u8;
+    pub fn write_u8(&mut self, byte: u8) -> Result<(), PngerError> {
+        if self.indices.len().saturating_sub(self.index) < 8 {
+            return Err(PngerError::PayloadTooLarge {
+                size: self.index / 8 + 1,
+                capacity: self.indices.len() / 8,
+            });
+        }
 
         for bit_pos in 0..8 {
-            assert!(
-                (self.index < self.indices.len()),
-                "LSB index {} is out of bounds (max: {}). Extraction beyond available data.",
-                self.index,
+            let image_index = self.indices[self.index] as usize;
+            let bit = (byte >> self.bit_position(bit_pos)) & 1;
+            if bit == 1 || self.write_mode == WriteMode::Overwrite {
+                let target_bit = self.target_bit(image_index);
+                self.bytes[image_index] = embed_bit(target_bit, self.bytes[image_index], bit);
+            }
+            self.index += 1;
+        }
+        Ok(())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, PngerError> {
+        if self.indices.len().saturating_sub(self.index) < 8 {
+            return Err(PngerError::InvalidFormat(format!(
+                "LSB index {} is out of bounds (max: {}), extraction beyond available data",
+                self.index + 8,
                 self.indices.len()
-            );
+            )));
+        }
 
+        let mut byte = 0u8;
+        for bit_pos in 0..8 {
             let image_index = self.indices[self.index] as usize;
-            let bit = extract_bit(target_bit, self.bytes[image_index]);
-            byte |= (bit & 1) << bit_pos;
+            let bit = extract_bit(self.target_bit(image_index), self.bytes[image_index]);
+            byte |= (bit & 1) << self.bit_position(bit_pos);
             self.index += 1;
         }
 
-        byte
+        Ok(byte)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embed_beyond_capacity_errors() {
+        let mut bytes = [0u8; 20];
+        let mut embedder =
+            BodyEmbedder::from_indices(&mut bytes, (0..20).collect(), BitIndex::Bit0);
+        embedder.embed_payload(b"ab").unwrap();
+        assert!(matches!(
+            embedder.embed_payload(b"c"),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+        assert!(matches!(
+            embedder.embed_redundant(b"x", 2),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_beyond_data_errors() {
+        let mut bytes = [0xFFu8; 20];
+        let mut embedder =
+            BodyEmbedder::from_indices(&mut bytes, (0..20).collect(), BitIndex::Bit0);
+        assert_eq!(embedder.extract_payload(2).unwrap(), [0xFF, 0xFF]);
+        assert!(matches!(
+            embedder.extract_payload(1),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(embedder.extract_redundant(2, 2).is_err());
+        assert_eq!(embedder.extract_redundant(1, 2).unwrap(), [0xFF]);
+    }
+
+    #[test]
+    fn test_extract_huge_size_allocates_incrementally() {
+        // Allocating the declared size up front would abort the process
+        let mut bytes = [0u8; 20];
+        let mut embedder =
+            BodyEmbedder::from_indices(&mut bytes, (0..20).collect(), BitIndex::Bit0);
+        assert!(matches!(
+            embedder.extract_payload(1 << 50),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(embedder.extract_redundant(1 << 50, 3).is_err());
     }
 }
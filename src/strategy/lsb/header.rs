@@ -1,11 +1,20 @@
 use binrw::BinRead;
 use crc32fast::Hasher;
-use std::io::{Cursor, Read, Write};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::{
+    io::{Cursor, Read, Write},
+    ops::Range,
+};
 use thiserror::Error;
 
 use crate::{
-    PayloadSize, PngerError,
-    strategy::lsb::{RuntimeConfig, RuntimePattern, SEED_SIZE},
+    PayloadSize, PngerError, TextEncoding,
+    strategy::lsb::{
+        BitIndex, BitOrder, EmbeddingPattern, HeaderChecksum, LSBConfig, MAGIC_SIZE, Parity,
+        RandomConfig, RuntimeConfig, RuntimePattern, SEED_SIZE, SeedSource, WireFormat, WriteMode,
+        data::BodyEmbedder,
+    },
 };
 
 #[derive(Debug, Error)]
@@ -19,12 +28,21 @@ pub(super) enum HeaderError {
     #[error("Invalid magic number")]
     InvalidMagic,
 
+    #[error("Seed flagged but data too short for it")]
+    TruncatedSeed,
+
     #[error("CRC mismatch: expected {expected:08x}, found {found:08x}")]
-    CrcMismatch { expected: u32, found: u32 },
+    CrcMismatch { expected: u64, found: u64 },
+
+    #[error("Unknown header checksum algorithm: {0}")]
+    UnknownChecksum(u8),
 
     #[error("Unsupported version: {0}")]
     UnsupportedVersion(u8),
 
+    #[error("Header extensions take {0} bytes, above the {max} byte limit", max = u16::MAX)]
+    ExtensionsTooLarge(usize),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 }
@@ -41,33 +59,93 @@ impl From<HeaderError> for PngerError {
             HeaderError::InvalidMagic => {
                 PngerError::InvalidFormat("Invalid header magic".to_string())
             }
+            HeaderError::TruncatedSeed => {
+                PngerError::InvalidFormat("seed flagged but image too small for seed".to_string())
+            }
             HeaderError::CrcMismatch { expected, found } => PngerError::InvalidFormat(format!(
                 "Header CRC mismatch: expected {expected:08x}, found {found:08x}"
             )),
+            HeaderError::UnknownChecksum(id) => {
+                PngerError::InvalidFormat(format!("Unknown header checksum algorithm: {id}"))
+            }
             HeaderError::UnsupportedVersion(v) => {
                 PngerError::InvalidFormat(format!("Unsupported header version: {v}"))
             }
+            HeaderError::ExtensionsTooLarge(len) => PngerError::InvalidFormat(format!(
+                "Header extensions take {len} bytes, above the {} byte limit",
+                u16::MAX
+            )),
             HeaderError::Io(io_err) => PngerError::FileIo(io_err),
         }
     }
 }
 
-// Header constants
-const MAGIC: &[u8; 4] = b"PNGR";
-const VERSION: u8 = 1;
+// Header constants. Version 1 headers were raw bytes at the start of the
+// carrier, see the `legacy` module: headers in the bit plane start at 2.
+const VERSION: u8 = 2;
+// Version of headers carrying an extension region. Writers only use it when
+// extensions are present, so headers without extensions stay readable by
+// version 2 readers.
+const EXTENDED_VERSION: u8 = 3;
 
 // Header field sizes
-const MAGIC_SIZE: usize = 4;
 const VERSION_SIZE: usize = 1;
 const FLAGS_SIZE: usize = 1;
 const PAYLOAD_SIZE_SIZE: usize = 4;
 const CRC32_SIZE: usize = 4;
+const REDUNDANCY_SIZE: usize = 1;
+const METADATA_LEN_SIZE: usize = 2;
+const CHANNEL_BITS_SIZE: usize = 2;
+const EXTENSION_LEN_SIZE: usize = 2;
+// Type and length preceding each extension value
+const EXTENSION_ENTRY_HEADER_SIZE: usize = 1 + 2;
+
+// Extension types
+const EXTENSION_BIT_ORDER: u8 = 1;
+const EXTENSION_PAYLOAD_TAG: u8 = 2;
+const EXTENSION_PASSWORD_VERIFIER: u8 = 3;
+const EXTENSION_PAYLOAD_LENGTH: u8 = 4;
+const EXTENSION_TEXT_ENCODING: u8 = 5;
+const EXTENSION_CHECKSUM: u8 = 6;
+const EXTENSION_INDEXED_HYBRID: u8 = 7;
+const EXTENSION_SKIP_SATURATED: u8 = 8;
+const EXTENSION_FRAME_INDEX: u8 = 9;
+const EXTENSION_LENGTH_BOUND_SEED: u8 = 10;
+const EXTENSION_PARITY: u8 = 11;
+const EXTENSION_WRITE_MODE: u8 = 12;
+const EXTENSION_DIFFUSION_STRIDE: u8 = 13;
+const EXTENSION_ROW_RANGE: u8 = 14;
+const EXTENSION_PAYLOAD_HASH: u8 = 15;
+
+// Size of the upper half of a CRC-64, stored in the checksum extension
+const CHECKSUM_HIGH_SIZE: usize = 4;
+
+// Size of the payload hash, a CRC-64 stored as a big-endian digest
+const PAYLOAD_HASH_SIZE: usize = 8;
+
+// Size of the password verifier: enough to reject a mistyped password, short
+// enough not to speed up offline guessing beyond the Argon2 cost
+const PASSWORD_VERIFIER_SIZE: usize = 4;
 
-// Fixed header size (always present)
-const FIXED_HEADER_SIZE: usize =
+/// Size in bytes of the header fields present in every embedding: magic,
+/// version, flags, a 4-byte payload size and the CRC32 checksum.
+pub const FIXED_HEADER_SIZE: usize =
     MAGIC_SIZE + VERSION_SIZE + FLAGS_SIZE + PAYLOAD_SIZE_SIZE + CRC32_SIZE;
 
-// Header flags (simplified)
+// Bootstrap section, always stored linearly at the start of the carrier so
+// readers can locate the header and learn its layout
+pub(super) const BOOTSTRAP_SIZE: usize = MAGIC_SIZE + VERSION_SIZE + FLAGS_SIZE;
+
+// ChaCha stream used to derive scattered header positions, kept distinct from
+// the body permutation stream
+const HEADER_STREAM: u64 = 1;
+// ChaCha stream used to derive the password verifier (2 is the noise stream)
+const VERIFIER_STREAM: u64 = 3;
+
+/// Flags byte of the payload header, recording how the payload was
+/// embedded.
+///
+/// Returned in [`HeaderInfo::flags`](super::HeaderInfo::flags).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, BinRead)]
 pub struct HeaderFlags(u8);
 
@@ -75,104 +153,621 @@ bitflags::bitflags! {
     impl HeaderFlags: u8 {
         const RANDOM_PATTERN = 0b0000_0001;  // 0=Linear, 1=Random
         const SEED_EMBEDDED = 0b0000_0010;   // 1=Seed is embedded in header
+        const SCATTERED_HEADER = 0b0000_0100; // 1=Size and CRC stored at seed-derived positions
+        const REDUNDANT = 0b0000_1000;       // 1=Redundancy factor stored after the seed
+        const HAS_METADATA = 0b0010_0000;    // 1=Metadata length stored after the seed, metadata precedes the body
+        const LITTLE_ENDIAN = 0b0100_0000;   // 1=Multi-byte fields stored little-endian
+        const CHANNEL_BITS = 0b1000_0000;    // 1=Per-channel bit indices stored after the seed
+    }
+}
+
+// Puts big-endian `bytes` in the byte order selected by `flags`. Reversing is
+// its own inverse, so this also converts wire bytes back to big-endian.
+fn wire_order<const N: usize>(flags: HeaderFlags, mut bytes: [u8; N]) -> [u8; N] {
+    if flags.contains(HeaderFlags::LITTLE_ENDIAN) {
+        bytes.reverse();
+    }
+    bytes
+}
+
+// Size of the optional seed section
+const fn seed_section_size(flags: HeaderFlags) -> usize {
+    if flags.contains(HeaderFlags::SEED_EMBEDDED) {
+        SEED_SIZE
+    } else {
+        0
+    }
+}
+
+// Size of the optional fields stored after the seed
+const fn trailing_section_size(flags: HeaderFlags) -> usize {
+    (if flags.contains(HeaderFlags::REDUNDANT) {
+        REDUNDANCY_SIZE
+    } else {
+        0
+    }) + (if flags.contains(HeaderFlags::HAS_METADATA) {
+        METADATA_LEN_SIZE
+    } else {
+        0
+    }) + if flags.contains(HeaderFlags::CHANNEL_BITS) {
+        CHANNEL_BITS_SIZE
+    } else {
+        0
+    }
+}
+
+// Packs 1 to MAX_CHANNELS bit indices: the channel count minus one on the low
+// 2 bits, then 3 bits per channel
+fn pack_channel_bits(channel_bits: &[BitIndex]) -> u16 {
+    channel_bits
+        .iter()
+        .enumerate()
+        .fold((channel_bits.len() - 1) as u16, |packed, (i, bit)| {
+            packed | (u16::from(u8::from(*bit)) << (2 + 3 * i))
+        })
+}
+
+fn unpack_channel_bits(packed: u16) -> Vec<BitIndex> {
+    let count = usize::from(packed & 0b11) + 1;
+    (0..count)
+        .map(|i| {
+            let position = ((packed >> (2 + 3 * i)) & 0b111) as u8;
+            BitIndex::try_from(position).expect("3-bit value is a valid bit index")
+        })
+        .collect()
+}
+
+// Size of the fields moved to seed-derived carrier positions when the header
+// is scattered: payload size, checksum and trailing fields
+const fn scattered_section_size(flags: HeaderFlags) -> usize {
+    PAYLOAD_SIZE_SIZE + CRC32_SIZE + trailing_section_size(flags)
+}
+
+// Total size of a version 2 header described by `flags`
+const fn header_size_for_flags(flags: HeaderFlags) -> usize {
+    FIXED_HEADER_SIZE + seed_section_size(flags) + trailing_section_size(flags)
+}
+
+// Size of the bootstrap section. Extended headers store the extension length
+// right after the flags, so readers know the full header size upfront.
+const fn bootstrap_size(version: u8) -> usize {
+    if version >= EXTENDED_VERSION {
+        BOOTSTRAP_SIZE + EXTENSION_LEN_SIZE
+    } else {
+        BOOTSTRAP_SIZE
+    }
+}
+
+// Size of the extension length field and the extension region
+const fn extension_section_size(version: u8, extension_len: u16) -> usize {
+    if version >= EXTENDED_VERSION {
+        EXTENSION_LEN_SIZE + extension_len as usize
+    } else {
+        0
+    }
+}
+
+// Total header size, including the extension section of extended headers
+const fn header_size_for(flags: HeaderFlags, version: u8, extension_len: u16) -> usize {
+    header_size_for_flags(flags) + extension_section_size(version, extension_len)
+}
+
+/// Entry of the header extension region.
+///
+/// Extensions are stored after all other header fields as TLV entries: a
+/// type byte, a 2-byte length in the header byte order, then the value.
+/// Features needing new header fields get an extension type instead of a
+/// version bump; readers look up the types they know and skip the others by
+/// their length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct HeaderExtension {
+    pub kind: u8,
+    pub value: Vec<u8>,
+}
+
+// Extensions recording the options of `config` that have no header flag.
+//
+// `password_seed` is the seed derived from the password of password configs.
+// Without it, the password verifier is zeroed, which is enough for sizing.
+pub(super) fn config_extensions(
+    config: &LSBConfig,
+    password_seed: Option<&[u8; SEED_SIZE]>,
+) -> Vec<HeaderExtension> {
+    let mut extensions = Vec::new();
+    if config.bit_order == BitOrder::MsbFirst {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_BIT_ORDER,
+            value: vec![1],
+        });
+    }
+    if let Some(tag) = config.payload_tag {
+        // Multi-byte values follow the header byte order
+        let value = match config.wire_format {
+            WireFormat::BigEndian => tag.to_be_bytes(),
+            WireFormat::LittleEndian => tag.to_le_bytes(),
+        };
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_PAYLOAD_TAG,
+            value: value.to_vec(),
+        });
+    }
+    if let Some(index) = config.frame_index {
+        let value = match config.wire_format {
+            WireFormat::BigEndian => index.to_be_bytes(),
+            WireFormat::LittleEndian => index.to_le_bytes(),
+        };
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_FRAME_INDEX,
+            value: value.to_vec(),
+        });
+    }
+    if let Some(rows) = &config.row_range {
+        let value = match config.wire_format {
+            WireFormat::BigEndian => [rows.start.to_be_bytes(), rows.end.to_be_bytes()],
+            WireFormat::LittleEndian => [rows.start.to_le_bytes(), rows.end.to_le_bytes()],
+        };
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_ROW_RANGE,
+            value: value.concat(),
+        });
+    }
+    if let Some(encoding) = config.text_encoding {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_TEXT_ENCODING,
+            value: vec![encoding.id()],
+        });
+    }
+    if let EmbeddingPattern::Random(RandomConfig {
+        seed_source: SeedSource::Password(_),
+        ..
+    }) = &config.pattern
+    {
+        let verifier = password_seed.map_or([0; PASSWORD_VERIFIER_SIZE], password_verifier);
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_PASSWORD_VERIFIER,
+            value: verifier.to_vec(),
+        });
+    }
+    // Kept last so the copy sits at the end of the header, away from the
+    // primary field. The value is filled in with the payload size when the
+    // header is built.
+    if config.indexed_hybrid {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_INDEXED_HYBRID,
+            value: vec![1],
+        });
+    }
+    if config.skip_saturated {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_SKIP_SATURATED,
+            value: vec![1],
+        });
+    }
+    if let EmbeddingPattern::Random(RandomConfig {
+        length_bound_seed: true,
+        ..
+    }) = &config.pattern
+    {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_LENGTH_BOUND_SEED,
+            value: vec![1],
+        });
+    }
+    if let Some(parity) = config.parity {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_PARITY,
+            value: vec![parity.id()],
+        });
+    }
+    if config.write_mode == WriteMode::Or {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_WRITE_MODE,
+            value: vec![1],
+        });
+    }
+    // Random patterns ignore the stride, see `RuntimeConfig::from_config`
+    if let (Some(stride), EmbeddingPattern::Linear) = (config.diffusion_stride, &config.pattern) {
+        let value = match config.wire_format {
+            WireFormat::BigEndian => stride.to_be_bytes(),
+            WireFormat::LittleEndian => stride.to_le_bytes(),
+        };
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_DIFFUSION_STRIDE,
+            value: value.to_vec(),
+        });
+    }
+    // OR-ed bodies are always hashed, as extraction has no other way to tell
+    // whether set bits crept into them
+    if config.payload_hash || config.write_mode == WriteMode::Or {
+        // Filled in with the payload, see `fill_payload_hash`
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_PAYLOAD_HASH,
+            value: vec![0; PAYLOAD_HASH_SIZE],
+        });
+    }
+    if config.header_checksum != HeaderChecksum::Crc32 {
+        // The upper half of a CRC-64 is filled in when the header is built
+        let mut value = vec![config.header_checksum.id()];
+        if config.header_checksum == HeaderChecksum::Crc64 {
+            value.extend_from_slice(&[0; CHECKSUM_HIGH_SIZE]);
+        }
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_CHECKSUM,
+            value,
+        });
+    }
+    if config.redundant_length {
+        extensions.push(HeaderExtension {
+            kind: EXTENSION_PAYLOAD_LENGTH,
+            value: vec![0; PAYLOAD_SIZE_SIZE],
+        });
+    }
+    extensions
+}
+
+// Verifier proving knowledge of a password-derived `seed`: the start of a
+// dedicated ChaCha stream keyed by the seed, so it reveals nothing about the
+// seed itself
+fn password_verifier(seed: &[u8; SEED_SIZE]) -> [u8; PASSWORD_VERIFIER_SIZE] {
+    let mut rng = ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(VERIFIER_STREAM);
+    let mut verifier = [0; PASSWORD_VERIFIER_SIZE];
+    rng.fill_bytes(&mut verifier);
+    verifier
+}
+
+// Size of the extension section written for `extensions`
+fn extensions_size(extensions: &[HeaderExtension]) -> usize {
+    if extensions.is_empty() {
+        return 0;
+    }
+    EXTENSION_LEN_SIZE
+        + extensions
+            .iter()
+            .map(|extension| EXTENSION_ENTRY_HEADER_SIZE + extension.value.len())
+            .sum::<usize>()
+}
+
+// Serializes extensions as TLV entries
+fn encode_extensions(
+    extensions: &[HeaderExtension],
+    flags: HeaderFlags,
+) -> Result<Vec<u8>, HeaderError> {
+    let mut encoded = Vec::new();
+    for extension in extensions {
+        let len = u16::try_from(extension.value.len())
+            .map_err(|_| HeaderError::ExtensionsTooLarge(extension.value.len()))?;
+        encoded.push(extension.kind);
+        encoded.extend_from_slice(&wire_order(flags, len.to_be_bytes()));
+        encoded.extend_from_slice(&extension.value);
+    }
+    if encoded.len() > usize::from(u16::MAX) {
+        return Err(HeaderError::ExtensionsTooLarge(encoded.len()));
+    }
+    Ok(encoded)
+}
+
+// Parses TLV entries, keeping unknown types so callers can skip them
+fn parse_extensions(
+    mut data: &[u8],
+    flags: HeaderFlags,
+) -> Result<Vec<HeaderExtension>, HeaderError> {
+    let mut extensions = Vec::new();
+    while !data.is_empty() {
+        let (entry_header, rest) = data
+            .split_at_checked(EXTENSION_ENTRY_HEADER_SIZE)
+            .ok_or(HeaderError::InsufficientData)?;
+        let len = u16::from_be_bytes(wire_order(flags, [entry_header[1], entry_header[2]]));
+        let (value, rest) = rest
+            .split_at_checked(usize::from(len))
+            .ok_or(HeaderError::InsufficientData)?;
+        extensions.push(HeaderExtension {
+            kind: entry_header[0],
+            value: value.to_vec(),
+        });
+        data = rest;
+    }
+    Ok(extensions)
+}
+
+// Position of the value of the first extension of type `kind` in `data`
+fn find_extension(mut data: &[u8], flags: HeaderFlags, kind: u8) -> Option<Range<usize>> {
+    let mut offset = 0;
+    while data.len() >= EXTENSION_ENTRY_HEADER_SIZE {
+        let len = usize::from(u16::from_be_bytes(wire_order(flags, [data[1], data[2]])));
+        let start = offset + EXTENSION_ENTRY_HEADER_SIZE;
+        if data.len() < EXTENSION_ENTRY_HEADER_SIZE + len {
+            return None;
+        }
+        if data[0] == kind {
+            return Some(start..start + len);
+        }
+        data = &data[EXTENSION_ENTRY_HEADER_SIZE + len..];
+        offset = start + len;
+    }
+    None
+}
+
+const fn crc32c_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0x82F6_3B78
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc64_table() -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xC96C_5795_D787_0F42
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
     }
+    table
+}
+
+static CRC32C_TABLE: [u32; 256] = crc32c_table();
+static CRC64_TABLE: [u64; 256] = crc64_table();
+
+// CRC-32C (Castagnoli, reflected)
+fn crc32c(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC32C_TABLE[usize::from(crc as u8 ^ byte)] ^ (crc >> 8)
+    })
+}
+
+// CRC-64/XZ (ECMA-182 polynomial, reflected)
+fn crc64(data: &[u8]) -> u64 {
+    !data.iter().fold(!0u64, |crc, &byte| {
+        CRC64_TABLE[usize::from(crc as u8 ^ byte)] ^ (crc >> 8)
+    })
 }
 
 // Fixed header structure
 #[derive(Debug, BinRead)]
 #[br(big)]
-#[br(magic = b"PNGR")]
 pub struct FixedHeader {
-    #[br(assert(version == VERSION))]
+    pub magic: [u8; MAGIC_SIZE],
     pub version: u8,
     pub flags: HeaderFlags,
+    #[br(
+        if(version >= EXTENDED_VERSION),
+        is_little = flags.contains(HeaderFlags::LITTLE_ENDIAN)
+    )]
+    pub extension_len: u16,
+    #[br(is_little = flags.contains(HeaderFlags::LITTLE_ENDIAN))]
     pub payload_size: PayloadSize,
+    #[br(is_little = flags.contains(HeaderFlags::LITTLE_ENDIAN))]
     pub crc32: u32,
 }
 
 impl FixedHeader {
-    pub fn read_from_bytes(data: &[u8]) -> Result<Self, HeaderError> {
-        if data.len() < FIXED_HEADER_SIZE {
-            return Err(HeaderError::InsufficientData);
-        }
-
-        let mut cursor = Cursor::new(data);
-        let header = FixedHeader::read_be(&mut cursor).map_err(|e| match e {
-            binrw::Error::AssertFail { .. } => HeaderError::UnsupportedVersion(VERSION),
-            binrw::Error::BadMagic { .. } => HeaderError::InvalidMagic,
+    fn read_fields(cursor: &mut Cursor<&[u8]>) -> Result<Self, HeaderError> {
+        FixedHeader::read_be(cursor).map_err(|e| match e {
             binrw::Error::Io(io_err) => HeaderError::Io(io_err),
             _ => HeaderError::InsufficientData,
-        })?;
+        })
+    }
 
-        header.validate()?;
-        Ok(header)
+    /// Payload size field as stored in the header.
+    fn payload_size_bytes(&self) -> [u8; PAYLOAD_SIZE_SIZE] {
+        wire_order(self.flags, self.payload_size.to_be_bytes())
     }
 
-    pub const fn calculate_total_header_size(&self) -> usize {
-        FIXED_HEADER_SIZE
-            + if self.flags.contains(HeaderFlags::SEED_EMBEDDED) {
-                SEED_SIZE
-            } else {
-                0
-            }
+    /// Extension length field as stored in extended headers.
+    fn extension_len_bytes(&self) -> Vec<u8> {
+        if self.version >= EXTENDED_VERSION {
+            wire_order(self.flags, self.extension_len.to_be_bytes()).to_vec()
+        } else {
+            Vec::new()
+        }
     }
 
-    fn prepare_crc_data(&self) -> [u8; 6] {
-        let mut data = [0u8; VERSION_SIZE + FLAGS_SIZE + PAYLOAD_SIZE_SIZE];
-        data[0] = self.version;
-        data[1] = self.flags.bits();
-        data[2..6].copy_from_slice(&self.payload_size.to_be_bytes());
+    // The checksum covers the extension region, which follows the other fields
+    fn prepare_crc_data(&self, extensions: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(
+            VERSION_SIZE + FLAGS_SIZE + EXTENSION_LEN_SIZE + PAYLOAD_SIZE_SIZE + extensions.len(),
+        );
+        data.push(self.version);
+        data.push(self.flags.bits());
+        data.extend_from_slice(&self.extension_len_bytes());
+        data.extend_from_slice(&self.payload_size_bytes());
+        data.extend_from_slice(extensions);
         data
     }
 
-    fn calculate_crc(&self) -> u32 {
-        let mut hasher = Hasher::new();
-        hasher.update(&self.prepare_crc_data());
-        hasher.finalize()
+    // Checksum algorithm recorded in `extensions`, with the position of the
+    // upper half of a CRC-64
+    fn checksum_algorithm(
+        &self,
+        extensions: &[u8],
+    ) -> Result<(HeaderChecksum, Option<Range<usize>>), HeaderError> {
+        let Some(value) = find_extension(extensions, self.flags, EXTENSION_CHECKSUM) else {
+            return Ok((HeaderChecksum::Crc32, None));
+        };
+        let id = *extensions
+            .get(value.start)
+            .ok_or(HeaderError::InsufficientData)?;
+        let algorithm = HeaderChecksum::from_id(id).ok_or(HeaderError::UnknownChecksum(id))?;
+        let high = value.start + 1..value.end;
+        match (algorithm, high.len()) {
+            (HeaderChecksum::Crc64, CHECKSUM_HIGH_SIZE) => Ok((algorithm, Some(high))),
+            (HeaderChecksum::Crc32 | HeaderChecksum::Crc32c, 0) => Ok((algorithm, None)),
+            _ => Err(HeaderError::InsufficientData),
+        }
+    }
+
+    // The upper half of a CRC-64 lives in the covered extension region, so it
+    // is zeroed before computing the checksum
+    fn calculate_crc(&self, extensions: &[u8]) -> Result<u64, HeaderError> {
+        let (algorithm, high) = self.checksum_algorithm(extensions)?;
+        let mut data = self.prepare_crc_data(extensions);
+        if let Some(high) = high {
+            let offset = data.len() - extensions.len();
+            data[offset + high.start..offset + high.end].fill(0);
+        }
+        Ok(match algorithm {
+            HeaderChecksum::Crc32 => {
+                let mut hasher = Hasher::new();
+                hasher.update(&data);
+                u64::from(hasher.finalize())
+            }
+            HeaderChecksum::Crc32c => u64::from(crc32c(&data)),
+            HeaderChecksum::Crc64 => crc64(&data),
+        })
+    }
+
+    // Checksum stored in the header: the CRC field, plus the upper half of a
+    // CRC-64 stored in its extension
+    fn stored_crc(&self, extensions: &[u8]) -> Result<u64, HeaderError> {
+        let (_, high) = self.checksum_algorithm(extensions)?;
+        let Some(high) = high else {
+            return Ok(u64::from(self.crc32));
+        };
+        let mut bytes = [0; CHECKSUM_HIGH_SIZE];
+        bytes.copy_from_slice(&extensions[high]);
+        let high = u32::from_be_bytes(wire_order(self.flags, bytes));
+        Ok(u64::from(high) << 32 | u64::from(self.crc32))
+    }
+
+    fn validate_identity(&self, magic: &[u8; MAGIC_SIZE]) -> Result<(), HeaderError> {
+        if &self.magic != magic {
+            return Err(HeaderError::InvalidMagic);
+        }
+        if !(VERSION..=EXTENDED_VERSION).contains(&self.version) {
+            return Err(HeaderError::UnsupportedVersion(self.version));
+        }
+        Ok(())
+    }
+
+    fn validate_crc(&self, extensions: &[u8]) -> Result<(), HeaderError> {
+        let expected = self.calculate_crc(extensions)?;
+        let found = self.stored_crc(extensions)?;
+        if found != expected {
+            return Err(HeaderError::CrcMismatch { expected, found });
+        }
+        Ok(())
     }
 
-    fn validate(&self) -> Result<(), HeaderError> {
-        if self.crc32 != self.calculate_crc() {
-            return Err(HeaderError::CrcMismatch {
-                expected: self.calculate_crc(),
-                found: self.crc32,
-            });
+    // Validates the checksum, recovering from a corrupted copy of the payload
+    // size when the header stores two. The copy is the last extension, so a
+    // mismatch is resolved by trying each value in place of the other.
+    fn validate_crc_with_length_copy(&mut self, extensions: &[u8]) -> Result<(), HeaderError> {
+        let Err(error) = self.validate_crc(extensions) else {
+            return Ok(());
+        };
+        let has_copy = parse_extensions(extensions, self.flags).is_ok_and(|parsed| {
+            parsed.last().is_some_and(|extension| {
+                extension.kind == EXTENSION_PAYLOAD_LENGTH
+                    && extension.value.len() == PAYLOAD_SIZE_SIZE
+            })
+        });
+        if !has_copy {
+            return Err(error);
+        }
+        let copy_start = extensions.len() - PAYLOAD_SIZE_SIZE;
+        let mut copy = [0; PAYLOAD_SIZE_SIZE];
+        copy.copy_from_slice(&extensions[copy_start..]);
+        let copy = PayloadSize::from_be_bytes(wire_order(self.flags, copy));
+
+        // Corrupted primary field: the checksum holds with the copy
+        let primary = self.payload_size;
+        self.payload_size = copy;
+        if self.validate_crc(extensions).is_ok() {
+            debug_event!("payload size {primary} corrupted, recovered {copy} from its copy");
+            return Ok(());
         }
+        self.payload_size = primary;
+
+        // Corrupted copy: the checksum holds with the primary value
+        let mut repaired = extensions.to_vec();
+        repaired[copy_start..].copy_from_slice(&wire_order(self.flags, primary.to_be_bytes()));
+        self.validate_crc(&repaired).map_err(|_| error)?;
+        debug_event!("payload size copy corrupted, kept primary value {primary}");
         Ok(())
     }
 }
 
-// Complete header with optional seed
+// Complete header with optional seed, redundancy factor, metadata length
+// and extensions
 #[derive(Debug)]
 pub struct CompleteHeader {
     pub fixed: FixedHeader,
     pub seed: Option<[u8; 32]>,
+    pub redundancy: Option<u8>,
+    pub metadata_len: Option<u16>,
+    pub channel_bits: Option<u16>,
+    pub extensions: Vec<HeaderExtension>,
+    // Whether the checksum matched; only false for headers read leniently
+    pub crc_valid: bool,
 }
 
 impl CompleteHeader {
-    pub fn read_from_bytes(data: &[u8]) -> Result<Self, HeaderError> {
+    pub fn read_from_bytes(data: &[u8], magic: &[u8; MAGIC_SIZE]) -> Result<Self, HeaderError> {
+        Self::parse(data, magic, false)
+    }
+
+    // Reads a header whose checksum may not match, recording the mismatch in
+    // `crc_valid` instead of failing. Extensions that cannot be parsed are
+    // dropped when the checksum does not hold.
+    pub fn read_from_bytes_lenient(
+        data: &[u8],
+        magic: &[u8; MAGIC_SIZE],
+    ) -> Result<Self, HeaderError> {
+        Self::parse(data, magic, true)
+    }
+
+    fn parse(data: &[u8], magic: &[u8; MAGIC_SIZE], lenient: bool) -> Result<Self, HeaderError> {
         if data.len() < FIXED_HEADER_SIZE {
             return Err(HeaderError::InsufficientData);
         }
 
         let mut cursor = Cursor::new(data);
-        let fixed = FixedHeader::read_be(&mut cursor).map_err(|e| match e {
-            binrw::Error::AssertFail { .. } => HeaderError::UnsupportedVersion(VERSION),
-            binrw::Error::BadMagic { .. } => HeaderError::InvalidMagic,
-            binrw::Error::Io(io_err) => HeaderError::Io(io_err),
-            _ => HeaderError::InsufficientData,
-        })?;
+        let mut fixed = FixedHeader::read_fields(&mut cursor)?;
+        fixed.validate_identity(magic)?;
+        // The seed follows the fixed fields: data cut within it is reported
+        // as such, a hint of a truncated transfer
+        if fixed.flags.contains(HeaderFlags::SEED_EMBEDDED)
+            && data.len() < cursor.position() as usize + SEED_SIZE
+        {
+            return Err(HeaderError::TruncatedSeed);
+        }
 
-        fixed.validate()?;
+        // The extension region closes the header
+        let extension_start = header_size_for(fixed.flags, fixed.version, 0);
+        let extension_bytes = data
+            .get(extension_start..extension_start + usize::from(fixed.extension_len))
+            .ok_or(HeaderError::InsufficientData)?;
+        let crc_valid = match fixed.validate_crc_with_length_copy(extension_bytes) {
+            Ok(()) => true,
+            Err(HeaderError::CrcMismatch { .. }) if lenient => false,
+            Err(error) => return Err(error),
+        };
 
         // Read seed if present
         let seed = if fixed.flags.contains(HeaderFlags::SEED_EMBEDDED) {
-            let required_pos = (cursor.position() as usize)
-                .checked_add(SEED_SIZE)
-                .ok_or(HeaderError::InsufficientData)?;
-            if data.len() < required_pos {
-                return Err(HeaderError::InsufficientData);
-            }
             let mut seed_bytes = [0u8; SEED_SIZE];
             cursor.read_exact(&mut seed_bytes)?;
             Some(seed_bytes)
@@ -180,12 +775,661 @@ impl CompleteHeader {
             None
         };
 
-        Ok(Self { fixed, seed })
+        // Read redundancy factor if present
+        let redundancy = if fixed.flags.contains(HeaderFlags::REDUNDANT) {
+            let mut factor = [0u8; REDUNDANCY_SIZE];
+            cursor
+                .read_exact(&mut factor)
+                .map_err(|_| HeaderError::InsufficientData)?;
+            Some(factor[0])
+        } else {
+            None
+        };
+
+        // Read metadata length if present
+        let metadata_len = if fixed.flags.contains(HeaderFlags::HAS_METADATA) {
+            let mut len = [0u8; METADATA_LEN_SIZE];
+            cursor
+                .read_exact(&mut len)
+                .map_err(|_| HeaderError::InsufficientData)?;
+            Some(u16::from_be_bytes(wire_order(fixed.flags, len)))
+        } else {
+            None
+        };
+
+        // Read per-channel bit indices if present
+        let channel_bits = if fixed.flags.contains(HeaderFlags::CHANNEL_BITS) {
+            let mut packed = [0u8; CHANNEL_BITS_SIZE];
+            cursor
+                .read_exact(&mut packed)
+                .map_err(|_| HeaderError::InsufficientData)?;
+            Some(u16::from_be_bytes(wire_order(fixed.flags, packed)))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            seed,
+            redundancy,
+            metadata_len,
+            channel_bits,
+            extensions: match parse_extensions(extension_bytes, fixed.flags) {
+                Ok(extensions) => extensions,
+                Err(_) if !crc_valid => Vec::new(),
+                Err(error) => return Err(error),
+            },
+            fixed,
+            crc_valid,
+        })
     }
 
     pub const fn header_size(&self) -> usize {
-        FIXED_HEADER_SIZE + if self.seed.is_some() { SEED_SIZE } else { 0 }
+        header_size_for(
+            self.fixed.flags,
+            self.fixed.version,
+            self.fixed.extension_len,
+        )
+    }
+
+    /// Number of payload copies stored in the body.
+    pub fn redundancy(&self) -> u8 {
+        self.redundancy.unwrap_or(1).max(1)
+    }
+
+    /// Number of metadata bytes stored ahead of the payload in each copy.
+    pub fn metadata_len(&self) -> usize {
+        self.metadata_len.map_or(0, usize::from)
+    }
+
+    /// Bit index targeted in each channel, if stored per channel.
+    pub fn channel_bits(&self) -> Option<Vec<BitIndex>> {
+        self.channel_bits.map(unpack_channel_bits)
+    }
+
+    /// Types of the extensions present, in header order.
+    pub fn extension_kinds(&self) -> Vec<u8> {
+        self.extensions
+            .iter()
+            .map(|extension| extension.kind)
+            .collect()
+    }
+
+    // Value of the first extension of type `kind`, if any
+    fn extension(&self, kind: u8) -> Option<&[u8]> {
+        self.extensions
+            .iter()
+            .find(|extension| extension.kind == kind)
+            .map(|extension| extension.value.as_slice())
+    }
+
+    /// Whether the carrier includes pixel indices, see
+    /// [`LSBConfig::indexed_hybrid`].
+    pub fn indexed_hybrid(&self) -> bool {
+        self.extension(EXTENSION_INDEXED_HYBRID).is_some()
+    }
+
+    /// Whether near-saturated carrier bytes are left out of the body, see
+    /// [`LSBConfig::with_skip_saturated`].
+    pub fn skip_saturated(&self) -> bool {
+        self.extension(EXTENSION_SKIP_SATURATED).is_some()
+    }
+
+    /// Whether the body is shuffled with a seed bound to the payload length,
+    /// see [`LSBConfig::with_length_bound_seed`].
+    pub fn length_bound_seed(&self) -> bool {
+        self.extension(EXTENSION_LENGTH_BOUND_SEED).is_some()
+    }
+
+    /// Carrier bytes holding the payload, from the parity extension if
+    /// present, see [`LSBConfig::with_parity`].
+    pub fn parity(&self) -> Result<Option<Parity>, PngerError> {
+        self.extension(EXTENSION_PARITY)
+            .map(|value| match value {
+                &[id] => Parity::from_id(id).ok_or_else(|| {
+                    PngerError::InvalidFormat(format!("Unknown carrier parity {id:#04x}"))
+                }),
+                value => Err(PngerError::InvalidFormat(format!(
+                    "Parity extension holds {} bytes, expected 1",
+                    value.len()
+                ))),
+            })
+            .transpose()
+    }
+
+    /// Order of the payload bits, from the bit order extension if present.
+    pub fn bit_order(&self) -> Result<BitOrder, PngerError> {
+        let Some(value) = self.extension(EXTENSION_BIT_ORDER) else {
+            return Ok(BitOrder::LsbFirst);
+        };
+        match value {
+            [0] => Ok(BitOrder::LsbFirst),
+            [1] => Ok(BitOrder::MsbFirst),
+            value => Err(PngerError::InvalidFormat(format!(
+                "Unknown payload bit order {value:02x?}"
+            ))),
+        }
+    }
+
+    /// How the body bits were written, from the write mode extension if
+    /// present.
+    pub fn write_mode(&self) -> Result<WriteMode, PngerError> {
+        let Some(value) = self.extension(EXTENSION_WRITE_MODE) else {
+            return Ok(WriteMode::Overwrite);
+        };
+        match value {
+            [0] => Ok(WriteMode::Overwrite),
+            [1] => Ok(WriteMode::Or),
+            value => Err(PngerError::InvalidFormat(format!(
+                "Unknown body write mode {value:02x?}"
+            ))),
+        }
+    }
+
+    /// Image rows holding the payload, from the row range extension if
+    /// present.
+    pub fn row_range(&self) -> Result<Option<Range<u32>>, PngerError> {
+        self.extension(EXTENSION_ROW_RANGE)
+            .map(|value| {
+                let bytes: [u8; 8] = value.try_into().map_err(|_| {
+                    PngerError::InvalidFormat(format!(
+                        "Row range extension holds {} bytes, expected 8",
+                        value.len()
+                    ))
+                })?;
+                let [s0, s1, s2, s3, e0, e1, e2, e3] = bytes;
+                let start = u32::from_be_bytes(wire_order(self.fixed.flags, [s0, s1, s2, s3]));
+                let end = u32::from_be_bytes(wire_order(self.fixed.flags, [e0, e1, e2, e3]));
+                Ok(start..end)
+            })
+            .transpose()
+    }
+
+    /// Distance between consecutive body bits of a linear pattern, from the
+    /// diffusion stride extension if present, see
+    /// [`LSBConfig::with_diffusion_stride`].
+    pub fn diffusion_stride(&self) -> Result<Option<u32>, PngerError> {
+        self.extension(EXTENSION_DIFFUSION_STRIDE)
+            .map(|value| {
+                let bytes: [u8; 4] = value.try_into().map_err(|_| {
+                    PngerError::InvalidFormat(format!(
+                        "Diffusion stride extension holds {} bytes, expected 4",
+                        value.len()
+                    ))
+                })?;
+                match u32::from_be_bytes(wire_order(self.fixed.flags, bytes)) {
+                    0 => Err(PngerError::InvalidFormat(
+                        "Diffusion stride extension holds a zero stride".to_string(),
+                    )),
+                    stride => Ok(stride),
+                }
+            })
+            .transpose()
+    }
+
+    /// Checks `seed` against the password verifier extension.
+    ///
+    /// Returns `None` when the header has no verifier, i.e. the payload was
+    /// not embedded with a password.
+    pub fn password_matches(&self, seed: &[u8; SEED_SIZE]) -> Option<bool> {
+        self.extension(EXTENSION_PASSWORD_VERIFIER)
+            .map(|verifier| verifier == password_verifier(seed))
+    }
+
+    /// Checks `payload` against the payload hash extension.
+    ///
+    /// Returns `None` when the header has no payload hash.
+    pub fn payload_hash_matches(&self, payload: &[u8]) -> Option<bool> {
+        self.extension(EXTENSION_PAYLOAD_HASH)
+            .map(|hash| hash == crc64(payload).to_be_bytes())
+    }
+
+    /// Application tag, from the payload tag extension if present.
+    pub fn payload_tag(&self) -> Result<Option<u16>, PngerError> {
+        self.extension(EXTENSION_PAYLOAD_TAG)
+            .map(|value| {
+                let bytes: [u8; 2] = value.try_into().map_err(|_| {
+                    PngerError::InvalidFormat(format!(
+                        "Payload tag extension holds {} bytes, expected 2",
+                        value.len()
+                    ))
+                })?;
+                Ok(u16::from_be_bytes(wire_order(self.fixed.flags, bytes)))
+            })
+            .transpose()
+    }
+
+    /// Animation frame holding the payload, from the frame index extension
+    /// if present.
+    pub fn frame_index(&self) -> Result<Option<u32>, PngerError> {
+        self.extension(EXTENSION_FRAME_INDEX)
+            .map(|value| {
+                let bytes: [u8; 4] = value.try_into().map_err(|_| {
+                    PngerError::InvalidFormat(format!(
+                        "Frame index extension holds {} bytes, expected 4",
+                        value.len()
+                    ))
+                })?;
+                Ok(u32::from_be_bytes(wire_order(self.fixed.flags, bytes)))
+            })
+            .transpose()
+    }
+
+    /// Character encoding of a text payload, from the text encoding
+    /// extension if present.
+    pub fn text_encoding(&self) -> Result<Option<TextEncoding>, PngerError> {
+        self.extension(EXTENSION_TEXT_ENCODING)
+            .map(|value| match value {
+                &[id] => TextEncoding::from_id(id).ok_or_else(|| {
+                    PngerError::InvalidFormat(format!("Unknown text encoding {id:#04x}"))
+                }),
+                value => Err(PngerError::InvalidFormat(format!(
+                    "Text encoding extension holds {} bytes, expected 1",
+                    value.len()
+                ))),
+            })
+            .transpose()
+    }
+
+    /// Serializes the header in carrier order.
+    ///
+    /// A linear header is written field by field. A scattered header keeps the
+    /// bootstrap and the optional seed first, followed by the scattered fields.
+    /// Fields stored after the seed always come last, extensions at the very
+    /// end.
+    fn to_carrier_bytes(&self) -> Result<Vec<u8>, HeaderError> {
+        let mut cursor = Cursor::new(Vec::with_capacity(self.header_size()));
+
+        cursor.write_all(&self.fixed.magic)?;
+        cursor.write_all(&[self.fixed.version])?;
+        cursor.write_all(&[self.fixed.flags.bits()])?;
+        cursor.write_all(&self.fixed.extension_len_bytes())?;
+
+        if self.fixed.flags.contains(HeaderFlags::SCATTERED_HEADER) {
+            if let Some(seed) = &self.seed {
+                cursor.write_all(seed)?;
+            }
+            cursor.write_all(&self.fixed.payload_size_bytes())?;
+            cursor.write_all(&wire_order(
+                self.fixed.flags,
+                self.fixed.crc32.to_be_bytes(),
+            ))?;
+        } else {
+            cursor.write_all(&self.fixed.payload_size_bytes())?;
+            cursor.write_all(&wire_order(
+                self.fixed.flags,
+                self.fixed.crc32.to_be_bytes(),
+            ))?;
+            if let Some(seed) = &self.seed {
+                cursor.write_all(seed)?;
+            }
+        }
+
+        if let Some(factor) = self.redundancy {
+            cursor.write_all(&[factor])?;
+        }
+        if let Some(len) = self.metadata_len {
+            cursor.write_all(&wire_order(self.fixed.flags, len.to_be_bytes()))?;
+        }
+        if let Some(packed) = self.channel_bits {
+            cursor.write_all(&wire_order(self.fixed.flags, packed.to_be_bytes()))?;
+        }
+        cursor.write_all(&encode_extensions(&self.extensions, self.fixed.flags)?)?;
+
+        Ok(cursor.into_inner())
+    }
+}
+
+/// Placement of the header bits within the carrier bytes.
+///
+/// Linear headers occupy the first `header_size * 8` carrier bytes. Scattered
+/// headers keep the bootstrap (and embedded seed) there, and spread the
+/// remaining fields over seed-derived positions that the body must skip.
+#[derive(Debug)]
+pub(super) struct HeaderLayout {
+    indices: Vec<u32>,
+    body_start: usize,
+}
+
+impl HeaderLayout {
+    fn linear(header_size: usize) -> Self {
+        let body_start = header_size * 8;
+        Self {
+            indices: (0..body_start as u32).collect(),
+            body_start,
+        }
+    }
+
+    fn scattered(
+        linear_size: usize,
+        scattered_size: usize,
+        seed: &[u8; SEED_SIZE],
+        carrier_len: usize,
+    ) -> Result<Self, HeaderError> {
+        let body_start = linear_size * 8;
+        let required = body_start + scattered_size * 8;
+        if carrier_len < required {
+            return Err(HeaderError::InsufficientSpace(required, carrier_len));
+        }
+
+        let mut rng = ChaCha20Rng::from_seed(*seed);
+        rng.set_stream(HEADER_STREAM);
+        let mut indices: Vec<u32> = (0..body_start as u32).collect();
+        indices.extend(
+            rand::seq::index::sample(&mut rng, carrier_len - body_start, scattered_size * 8)
+                .into_iter()
+                .map(|i| (body_start + i) as u32),
+        );
+
+        Ok(Self {
+            indices,
+            body_start,
+        })
+    }
+
+    /// Carrier indices holding the header bits.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Number of header bytes stored in the carrier.
+    pub fn header_size(&self) -> usize {
+        self.indices.len() / 8
+    }
+
+    /// Carrier indices available to the payload body, in ascending order.
+    pub fn body_candidates(&self, carrier_len: usize) -> Vec<u32> {
+        let mut reserved = self.indices[self.body_start..].to_vec();
+        reserved.sort_unstable();
+        (self.body_start as u32..carrier_len as u32)
+            .filter(|index| reserved.binary_search(index).is_err())
+            .collect()
+    }
+}
+
+/// Reads `count` header bytes stored linearly from the start of the carrier.
+fn read_linear(
+    bytes: &mut [u8],
+    bit_index: BitIndex,
+    count: usize,
+) -> Result<Vec<u8>, HeaderError> {
+    if bytes.len() < count * 8 {
+        return Err(HeaderError::InsufficientData);
+    }
+    let indices = (0..(count * 8) as u32).collect();
+    let mut reader = BodyEmbedder::from_indices(bytes, indices, bit_index);
+    (0..count)
+        .map(|_| reader.read_u8().map_err(|_| HeaderError::InsufficientData))
+        .collect()
+}
+
+/// Reads the header from the carrier bit plane selected by `config`.
+///
+/// Returns the validated header, the runtime pattern reconstructed from the
+/// header flags and user configuration, and the carrier layout of the header.
+pub(super) fn read_header(
+    bytes: &mut [u8],
+    config: &LSBConfig,
+) -> Result<(CompleteHeader, RuntimePattern, HeaderLayout), PngerError> {
+    read_header_with(bytes, config, false)
+}
+
+/// Reads the header like [`read_header`], but tolerates a checksum mismatch
+/// when `lenient`, recording it in [`CompleteHeader::crc_valid`].
+pub(super) fn read_header_with(
+    bytes: &mut [u8],
+    config: &LSBConfig,
+    lenient: bool,
+) -> Result<(CompleteHeader, RuntimePattern, HeaderLayout), PngerError> {
+    let parse = if lenient {
+        CompleteHeader::read_from_bytes_lenient
+    } else {
+        CompleteHeader::read_from_bytes
+    };
+    let (flags, version, extension_len) = read_bootstrap(bytes, config)?;
+    debug_event!(
+        "read v{version} header bootstrap, flags {flags:?}, {extension_len}-byte extensions"
+    );
+
+    // Report a carrier cut within the embedded seed before reading past it.
+    // The seed follows the bootstrap in scattered headers, and the rest of
+    // the fixed fields in linear ones.
+    let seed_end = if flags.contains(HeaderFlags::SCATTERED_HEADER) {
+        bootstrap_size(version) + SEED_SIZE
+    } else {
+        bootstrap_size(version) + FIXED_HEADER_SIZE - BOOTSTRAP_SIZE + SEED_SIZE
+    };
+    if flags.contains(HeaderFlags::SEED_EMBEDDED) && bytes.len() < seed_end * 8 {
+        return Err(HeaderError::TruncatedSeed.into());
+    }
+
+    if !flags.contains(HeaderFlags::SCATTERED_HEADER) {
+        let layout = HeaderLayout::linear(header_size_for(flags, version, extension_len));
+        let header = parse(
+            &read_linear(bytes, config.bit_index, layout.header_size())?,
+            &config.magic,
+        )?;
+        let pattern = RuntimePattern::from_header_and_config(&header, config)?;
+        return Ok((header, pattern, layout));
+    }
+
+    // Bootstrap and embedded seed are linear, the rest depends on the seed
+    let bootstrap_size = bootstrap_size(version);
+    let linear_size = bootstrap_size + seed_section_size(flags);
+    let scattered_size = scattered_section_size(flags) + usize::from(extension_len);
+    let linear = read_linear(bytes, config.bit_index, linear_size)?;
+    let embedded_seed = linear[bootstrap_size..].try_into().ok();
+
+    let pattern = RuntimePattern::from_flags_and_config(flags, embedded_seed, config)?;
+    let RuntimePattern::Random { seed, .. } = &pattern else {
+        return Err(PngerError::InvalidFormat(
+            "Scattered header requires a random pattern".to_string(),
+        ));
+    };
+    let layout = HeaderLayout::scattered(linear_size, scattered_size, seed, bytes.len())
+        .map_err(|_| HeaderError::InsufficientData)?;
+
+    let scattered_indices = layout.indices[layout.body_start..].to_vec();
+    let mut reader = BodyEmbedder::from_indices(bytes, scattered_indices, config.bit_index);
+    let scattered = (0..scattered_size)
+        .map(|_| reader.read_u8())
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    // Reassemble the logical header: fixed fields, the seed, then trailing
+    // fields and extensions
+    let fixed_tail = FIXED_HEADER_SIZE - BOOTSTRAP_SIZE;
+    let mut logical = linear[..bootstrap_size].to_vec();
+    logical.extend_from_slice(&scattered[..fixed_tail]);
+    logical.extend_from_slice(&linear[bootstrap_size..]);
+    logical.extend_from_slice(&scattered[fixed_tail..]);
+    let header = parse(&logical, &config.magic)?;
+
+    Ok((header, pattern, layout))
+}
+
+/// Reads the bootstrap section, returning the flags, version and extension
+/// length needed to locate the rest of the header.
+/// Checks the seed of `config` against the password verifier of the header.
+///
+/// Only the header is read. Errors when no header is found or it carries no
+/// verifier.
+pub(super) fn verify_password(bytes: &mut [u8], config: &LSBConfig) -> Result<bool, PngerError> {
+    let (flags, _, _) = read_bootstrap(bytes, config)?;
+    let (header, pattern, _) = match read_header(bytes, config) {
+        Ok(read) => read,
+        // Scattered fields are located with the password-derived seed, so
+        // with a wrong password they fail their checksum
+        Err(_) if flags.contains(HeaderFlags::SCATTERED_HEADER) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let verified = match &pattern {
+        RuntimePattern::Random { seed, .. } => header.password_matches(seed),
+        RuntimePattern::Linear => None,
+    };
+    verified.ok_or_else(|| {
+        PngerError::InvalidFormat("Payload was not embedded with a password".to_string())
+    })
+}
+
+/// Fills the payload hash extension of `extensions`, if any, with the hash
+/// of `payload`.
+pub(super) fn fill_payload_hash(extensions: &mut [HeaderExtension], payload: &[u8]) {
+    if let Some(extension) = extensions
+        .iter_mut()
+        .find(|extension| extension.kind == EXTENSION_PAYLOAD_HASH)
+    {
+        extension.value = crc64(payload).to_be_bytes().to_vec();
+    }
+}
+
+/// Checks whether the header stores the hash of `payload`, see
+/// [`LSBConfig::with_payload_hash`].
+///
+/// The header must also be readable with the pattern and password of
+/// `config`, so a payload embedded with other credentials never matches.
+pub(super) fn payload_hash_matches(bytes: &mut [u8], config: &LSBConfig, payload: &[u8]) -> bool {
+    let Ok((header, pattern, _)) = read_header(bytes, config) else {
+        return false;
+    };
+    let same_pattern = match &pattern {
+        RuntimePattern::Random { seed, .. } => {
+            matches!(config.pattern, EmbeddingPattern::Random(_))
+                && header.password_matches(seed) != Some(false)
+        }
+        RuntimePattern::Linear => matches!(config.pattern, EmbeddingPattern::Linear),
+    };
+    same_pattern
+        && header.fixed.payload_size == payload.len() as PayloadSize
+        && header.extension(EXTENSION_PAYLOAD_HASH) == Some(&crc64(payload).to_be_bytes()[..])
+}
+
+/// Reads the seed stored in the header, if the header embeds one.
+///
+/// Only the header is read, and validated when it embeds a seed.
+pub(super) fn read_embedded_seed(
+    bytes: &mut [u8],
+    config: &LSBConfig,
+) -> Result<Option<[u8; SEED_SIZE]>, PngerError> {
+    let (flags, _, _) = read_bootstrap(bytes, config)?;
+    if !flags.contains(HeaderFlags::SEED_EMBEDDED) {
+        return Ok(None);
+    }
+    let (header, _, _) = read_header(bytes, config)?;
+    Ok(header.seed)
+}
+
+/// Reads the header without reconstructing the pattern, returning it with
+/// its size.
+///
+/// Linear headers are parsed whatever the seed source of `config`, so the
+/// header of a password embedding is read without the password. Scattered
+/// headers are located with the seed of `config`.
+pub(super) fn read_header_fields(
+    bytes: &mut [u8],
+    config: &LSBConfig,
+) -> Result<(CompleteHeader, usize), PngerError> {
+    let (flags, version, extension_len) = read_bootstrap(bytes, config)?;
+    if flags.contains(HeaderFlags::SCATTERED_HEADER) {
+        let (header, _, layout) = read_header(bytes, config)?;
+        return Ok((header, layout.header_size()));
+    }
+    let header_size = header_size_for(flags, version, extension_len);
+    let header = CompleteHeader::read_from_bytes(
+        &read_linear(bytes, config.bit_index, header_size)?,
+        &config.magic,
+    )?;
+    Ok((header, header_size))
+}
+
+fn read_bootstrap(
+    bytes: &mut [u8],
+    config: &LSBConfig,
+) -> Result<(HeaderFlags, u8, u16), HeaderError> {
+    let bootstrap = read_linear(bytes, config.bit_index, BOOTSTRAP_SIZE)?;
+    if bootstrap[..MAGIC_SIZE] != config.magic[..] {
+        return Err(HeaderError::InvalidMagic);
+    }
+    let version = bootstrap[MAGIC_SIZE];
+    let flags = HeaderFlags::from_bits_retain(bootstrap[MAGIC_SIZE + VERSION_SIZE]);
+    if version < EXTENDED_VERSION {
+        return Ok((flags, version, 0));
+    }
+
+    let extended = read_linear(bytes, config.bit_index, bootstrap_size(version))?;
+    let len_bytes = [extended[BOOTSTRAP_SIZE], extended[BOOTSTRAP_SIZE + 1]];
+    Ok((
+        flags,
+        version,
+        u16::from_be_bytes(wire_order(flags, len_bytes)),
+    ))
+}
+
+// Flags describing the optional header fields and encoding
+fn option_flags(
+    redundancy: u8,
+    has_metadata: bool,
+    wire_format: WireFormat,
+    has_channel_bits: bool,
+) -> HeaderFlags {
+    let mut flags = HeaderFlags::empty();
+    if redundancy > 1 {
+        flags |= HeaderFlags::REDUNDANT;
+    }
+    if has_metadata {
+        flags |= HeaderFlags::HAS_METADATA;
+    }
+    if wire_format == WireFormat::LittleEndian {
+        flags |= HeaderFlags::LITTLE_ENDIAN;
     }
+    if has_channel_bits {
+        flags |= HeaderFlags::CHANNEL_BITS;
+    }
+    flags
+}
+
+/// Header size written for `config`, for payloads up to `u32::MAX` bytes.
+///
+/// Computed from the configuration alone, without generating or deriving the
+/// seed.
+pub(super) fn header_size_for_config(config: &LSBConfig) -> usize {
+    let mut flags = option_flags(
+        config.redundancy,
+        config.metadata.is_some(),
+        config.wire_format,
+        config.channel_bits.is_some(),
+    );
+
+    if let EmbeddingPattern::Random(random_config) = &config.pattern {
+        flags |= HeaderFlags::RANDOM_PATTERN;
+        if matches!(random_config.seed_source, SeedSource::Auto) {
+            flags |= HeaderFlags::SEED_EMBEDDED;
+        }
+        if !random_config.header_linear {
+            flags |= HeaderFlags::SCATTERED_HEADER;
+        }
+    }
+
+    header_size_for_flags(flags) + extensions_size(&config_extensions(config, None))
+}
+
+/// Checks whether a valid header (magic and CRC) is stored in the carrier bit
+/// plane selected by `config`.
+///
+/// Linear headers are validated without the seed, so any seed source works.
+/// Scattered headers can only be located with the seed the config provides.
+pub(super) fn header_present(bytes: &mut [u8], config: &LSBConfig) -> bool {
+    let Ok((flags, version, extension_len)) = read_bootstrap(bytes, config) else {
+        return false;
+    };
+
+    if flags.contains(HeaderFlags::SCATTERED_HEADER) {
+        return read_header(bytes, config).is_ok();
+    }
+    read_linear(
+        bytes,
+        config.bit_index,
+        header_size_for(flags, version, extension_len),
+    )
+    .is_ok_and(|data| CompleteHeader::read_from_bytes(&data, &config.magic).is_ok())
 }
 
 // Header embedder for writing headers
@@ -199,76 +1443,452 @@ impl<'a> HeaderEmbedder<'a> {
         Self { bytes, config }
     }
 
-    pub fn embed(&mut self, payload_size: u32) -> Result<usize, HeaderError> {
-        let header = self.build_header(payload_size);
-        let required_size = header.header_size();
+    pub fn embed(&mut self, payload_size: PayloadSize) -> Result<HeaderLayout, HeaderError> {
+        let (header, layout) = Self::plan(&self.config, self.bytes.len(), payload_size)?;
+        debug_event!(
+            "embedding {}-byte v{} header, flags {:?}",
+            header.header_size(),
+            header.fixed.version,
+            header.fixed.flags
+        );
+        self.write_header(&header, &layout)?;
+        Ok(layout)
+    }
+
+    /// Builds the header for `payload_size` and locates it in a carrier of
+    /// `carrier_len` bytes, without writing anything.
+    pub fn plan(
+        config: &RuntimeConfig,
+        carrier_len: usize,
+        payload_size: PayloadSize,
+    ) -> Result<(CompleteHeader, HeaderLayout), HeaderError> {
+        let header = Self::build_header(config, payload_size)?;
+        let required_size = header.header_size() * 8;
 
-        if self.bytes.len() < required_size {
-            return Err(HeaderError::InsufficientSpace(
-                required_size,
-                self.bytes.len(),
-            ));
+        if carrier_len < required_size {
+            return Err(HeaderError::InsufficientSpace(required_size, carrier_len));
         }
 
-        self.write_header(&header)
-    }
+        let layout = match &config.pattern {
+            RuntimePattern::Random {
+                seed,
+                scatter_header: true,
+                ..
+            } => {
+                let linear_size =
+                    bootstrap_size(header.fixed.version) + seed_section_size(header.fixed.flags);
+                HeaderLayout::scattered(
+                    linear_size,
+                    header.header_size() - linear_size,
+                    seed,
+                    carrier_len,
+                )?
+            }
+            _ => HeaderLayout::linear(header.header_size()),
+        };
 
-    fn build_header(&self, payload_size: u32) -> CompleteHeader {
-        let mut flags = HeaderFlags::empty();
-        let mut embedded_seed = None;
+        Ok((header, layout))
+    }
 
-        if let RuntimePattern::Random { seed, embed_seed } = &self.config.pattern {
-            flags |= HeaderFlags::RANDOM_PATTERN;
-            if *embed_seed {
-                flags |= HeaderFlags::SEED_EMBEDDED;
-                embedded_seed = Some(*seed);
-            }
-        }
+    fn build_header(
+        config: &RuntimeConfig,
+        payload_size: PayloadSize,
+    ) -> Result<CompleteHeader, HeaderError> {
+        let flags = Self::header_flags(config);
+        let embedded_seed = match &config.pattern {
+            RuntimePattern::Random {
+                seed,
+                embed_seed: true,
+                ..
+            } => Some(*seed),
+            _ => None,
+        };
+        let redundancy = flags
+            .contains(HeaderFlags::REDUNDANT)
+            .then_some(config.redundancy);
+        let metadata_len = config
+            .metadata
+            .as_ref()
+            .map(|metadata| metadata.len() as u16);
+        let channel_bits = config.channel_bits.as_deref().map(pack_channel_bits);
+        let mut extensions: Vec<HeaderExtension> = config
+            .extensions
+            .iter()
+            .map(|extension| match extension.kind {
+                EXTENSION_PAYLOAD_LENGTH => HeaderExtension {
+                    kind: EXTENSION_PAYLOAD_LENGTH,
+                    value: wire_order(flags, payload_size.to_be_bytes()).to_vec(),
+                },
+                _ => extension.clone(),
+            })
+            .collect();
+        let encoded_extensions = encode_extensions(&extensions, flags)?;
 
         let mut fixed = FixedHeader {
-            version: VERSION,
+            magic: config.magic,
+            version: if encoded_extensions.is_empty() {
+                VERSION
+            } else {
+                EXTENDED_VERSION
+            },
             flags,
+            extension_len: encoded_extensions.len() as u16,
             payload_size,
             crc32: 0,
         };
-        fixed.crc32 = fixed.calculate_crc();
+        let crc = fixed.calculate_crc(&encoded_extensions)?;
+        fixed.crc32 = crc as u32;
+        if let Some(extension) = extensions.iter_mut().find(|extension| {
+            extension.kind == EXTENSION_CHECKSUM && extension.value.len() > CHECKSUM_HIGH_SIZE
+        }) {
+            extension.value[1..]
+                .copy_from_slice(&wire_order(flags, ((crc >> 32) as u32).to_be_bytes()));
+        }
 
-        CompleteHeader {
+        Ok(CompleteHeader {
             fixed,
             seed: embedded_seed,
+            redundancy,
+            metadata_len,
+            channel_bits,
+            extensions,
+            crc_valid: true,
+        })
+    }
+
+    fn header_flags(config: &RuntimeConfig) -> HeaderFlags {
+        let mut flags = option_flags(
+            config.redundancy,
+            config.metadata.is_some(),
+            config.wire_format,
+            config.channel_bits.is_some(),
+        );
+
+        if let RuntimePattern::Random {
+            embed_seed,
+            scatter_header,
+            ..
+        } = &config.pattern
+        {
+            flags |= HeaderFlags::RANDOM_PATTERN;
+            if *embed_seed {
+                flags |= HeaderFlags::SEED_EMBEDDED;
+            }
+            if *scatter_header {
+                flags |= HeaderFlags::SCATTERED_HEADER;
+            }
         }
+
+        flags
     }
 
-    fn write_header(&mut self, header: &CompleteHeader) -> Result<usize, HeaderError> {
-        let mut cursor = Cursor::new(&mut self.bytes[..]);
+    fn write_header(
+        &mut self,
+        header: &CompleteHeader,
+        layout: &HeaderLayout,
+    ) -> Result<(), HeaderError> {
+        let data = header.to_carrier_bytes()?;
+        let mut writer =
+            BodyEmbedder::from_indices(self.bytes, layout.indices.clone(), self.config.bit_index);
+        data.iter().try_for_each(|byte| {
+            writer
+                .write_u8(*byte)
+                .map_err(|_| HeaderError::InsufficientSpace(data.len() * 8, layout.indices.len()))
+        })
+    }
 
-        // Write fixed header
-        cursor.write_all(MAGIC)?;
-        cursor.write_all(&[header.fixed.version])?;
-        cursor.write_all(&[header.fixed.flags.bits()])?;
-        cursor.write_all(&header.fixed.payload_size.to_be_bytes())?;
-        cursor.write_all(&header.fixed.crc32.to_be_bytes())?;
+    pub fn required_size(config: &RuntimeConfig) -> usize {
+        header_size_for_flags(Self::header_flags(config)) + extensions_size(&config.extensions)
+    }
+}
 
-        // Write seed if present
-        if let Some(seed) = &header.seed {
-            cursor.write_all(seed)?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::lsb::{DEFAULT_MAGIC, LSBEmbedder};
+
+    #[test]
+    fn test_truncated_embedded_seed() {
+        let mut image_data = vec![0u8; 2000];
+        LSBEmbedder::embed(&mut image_data, b"cut short", &LSBConfig::random()).unwrap();
+
+        let cut = FIXED_HEADER_SIZE + SEED_SIZE - 1;
+        let header_bytes = read_linear(&mut image_data, BitIndex::LSB, cut).unwrap();
+        assert!(matches!(
+            CompleteHeader::read_from_bytes(&header_bytes, &DEFAULT_MAGIC),
+            Err(HeaderError::TruncatedSeed)
+        ));
+
+        // Carriers cut within the seed are reported the same way, wherever
+        // the header layout puts it
+        let cut = FIXED_HEADER_SIZE + SEED_SIZE / 2;
+        for config in [
+            LSBConfig::random(),
+            LSBConfig::random().with_scattered_header(),
+        ] {
+            let mut image_data = vec![0u8; 2000];
+            LSBEmbedder::embed(&mut image_data, b"cut short", &config).unwrap();
+            match LSBEmbedder::extract(&mut image_data[..cut * 8], &config) {
+                Err(PngerError::InvalidFormat(message)) => {
+                    assert_eq!(message, "seed flagged but image too small for seed");
+                }
+                other => panic!("Expected InvalidFormat, got {other:?}"),
+            }
         }
+    }
+
+    #[test]
+    fn test_scattered_header_not_contiguous() {
+        let seed = [7u8; SEED_SIZE];
+        let payload = b"Hello, World!";
+
+        let mut linear_image = vec![0u8; 1000];
+        let linear_config = LSBConfig::random().with_seed(seed);
+        LSBEmbedder::embed(&mut linear_image, payload, &linear_config).unwrap();
+
+        let mut scattered_image = vec![0u8; 1000];
+        let scattered_config = LSBConfig::random().with_seed(seed).with_scattered_header();
+        LSBEmbedder::embed(&mut scattered_image, payload, &scattered_config).unwrap();
+
+        // Only the magic stays at offset 0
+        assert_eq!(
+            linear_image[..MAGIC_SIZE * 8],
+            scattered_image[..MAGIC_SIZE * 8]
+        );
+
+        // The fixed header can no longer be read contiguously from offset 0
+        let linear_bytes =
+            read_linear(&mut linear_image, BitIndex::LSB, FIXED_HEADER_SIZE).unwrap();
+        assert!(CompleteHeader::read_from_bytes(&linear_bytes, &DEFAULT_MAGIC).is_ok());
+        let scattered_bytes =
+            read_linear(&mut scattered_image, BitIndex::LSB, FIXED_HEADER_SIZE).unwrap();
+        assert!(CompleteHeader::read_from_bytes(&scattered_bytes, &DEFAULT_MAGIC).is_err());
 
-        Ok(cursor.position() as usize)
+        // The scattered positions leave gaps for the body right after the bootstrap
+        let layout = HeaderLayout::scattered(
+            BOOTSTRAP_SIZE,
+            scattered_section_size(HeaderFlags::empty()),
+            &seed,
+            1000,
+        )
+        .unwrap();
+        let candidates = layout.body_candidates(1000);
+        assert_eq!(
+            candidates.len(),
+            1000 - (BOOTSTRAP_SIZE + PAYLOAD_SIZE_SIZE + CRC32_SIZE) * 8
+        );
+        assert!(
+            candidates
+                .iter()
+                .any(|&index| (index as usize) < FIXED_HEADER_SIZE * 8)
+        );
     }
 
-    pub const fn required_size(config: &RuntimeConfig) -> usize {
-        FIXED_HEADER_SIZE
-            + if matches!(
-                config.pattern,
-                RuntimePattern::Random {
-                    embed_seed: true,
-                    ..
-                }
-            ) {
-                SEED_SIZE
-            } else {
-                0
+    #[test]
+    fn test_wire_format_roundtrip() {
+        let payload = b"wire format payload";
+        let metadata = crate::strategy::lsb::PayloadMetadata::new("a.txt", "text/plain");
+
+        for wire_format in [WireFormat::BigEndian, WireFormat::LittleEndian] {
+            for lsb_config in [
+                LSBConfig::linear(),
+                LSBConfig::random()
+                    .with_seed([3u8; SEED_SIZE])
+                    .with_scattered_header(),
+            ] {
+                let lsb_config = lsb_config
+                    .with_wire_format(wire_format)
+                    .with_payload_metadata(metadata.clone());
+                let mut carrier = vec![0u8; 2000];
+                LSBEmbedder::embed(&mut carrier, payload, &lsb_config).unwrap();
+
+                let (header, _, _) = read_header(&mut carrier, &lsb_config).unwrap();
+                assert_eq!(
+                    header.fixed.flags.contains(HeaderFlags::LITTLE_ENDIAN),
+                    wire_format == WireFormat::LittleEndian
+                );
+                let result = LSBEmbedder::extract(&mut carrier, &lsb_config).unwrap();
+                assert_eq!(result.payload, payload);
+                assert_eq!(result.metadata, Some(metadata.clone()));
             }
+        }
+
+        // The payload size is laid out in the selected byte order
+        let mut carrier = vec![0u8; 1000];
+        let config = LSBConfig::linear().with_wire_format(WireFormat::LittleEndian);
+        LSBEmbedder::embed(&mut carrier, payload, &config).unwrap();
+        let header = read_linear(&mut carrier, BitIndex::LSB, FIXED_HEADER_SIZE).unwrap();
+        let size_bytes = &header[BOOTSTRAP_SIZE..BOOTSTRAP_SIZE + PAYLOAD_SIZE_SIZE];
+        assert_eq!(size_bytes, (payload.len() as u32).to_le_bytes());
+    }
+
+    #[test]
+    fn test_extension_parsing_skips_by_length() {
+        let flags = HeaderFlags::empty();
+        let extensions = [
+            HeaderExtension {
+                kind: 0xEE,
+                value: vec![1, 2, 3],
+            },
+            HeaderExtension {
+                kind: 0x01,
+                value: vec![],
+            },
+            HeaderExtension {
+                kind: 0x7F,
+                value: vec![9; 300],
+            },
+        ];
+        let encoded = encode_extensions(&extensions, flags).unwrap();
+        assert_eq!(encoded.len(), 3 * EXTENSION_ENTRY_HEADER_SIZE + 303);
+        assert_eq!(parse_extensions(&encoded, flags).unwrap(), extensions);
+
+        // A reader only interested in type 0x7F finds it past unknown entries
+        let parsed = parse_extensions(&encoded, flags).unwrap();
+        let known = parsed.iter().find(|extension| extension.kind == 0x7F);
+        assert_eq!(known.unwrap().value, [9; 300]);
+
+        // Entries running past the region are rejected
+        assert!(parse_extensions(&encoded[..encoded.len() - 1], flags).is_err());
+        assert!(parse_extensions(&[0xEE, 0x00], flags).is_err());
+    }
+
+    #[test]
+    fn test_extended_header_roundtrip() {
+        let extensions = vec![
+            HeaderExtension {
+                kind: 0xFE,
+                value: b"unknown to this reader".to_vec(),
+            },
+            HeaderExtension {
+                kind: 0xFD,
+                value: vec![42],
+            },
+        ];
+
+        for lsb_config in [
+            LSBConfig::linear().with_wire_format(WireFormat::LittleEndian),
+            LSBConfig::random()
+                .with_seed([5u8; SEED_SIZE])
+                .with_redundancy(2),
+            LSBConfig::random().with_scattered_header(),
+        ] {
+            let mut carrier = vec![0u8; 2000];
+            let mut config = RuntimeConfig::from_config(&lsb_config).unwrap();
+            config.extensions = extensions.clone();
+            let required = HeaderEmbedder::required_size(&config);
+            let layout = HeaderEmbedder::new(&mut carrier, config).embed(10).unwrap();
+            assert_eq!(layout.header_size(), required);
+
+            let (header, _, read_layout) = read_header(&mut carrier, &lsb_config).unwrap();
+            assert_eq!(header.fixed.version, EXTENDED_VERSION);
+            assert_eq!(header.extensions, extensions);
+            assert_eq!(read_layout.header_size(), required);
+            assert!(header_present(&mut carrier, &lsb_config));
+
+            // The body follows the extension region
+            let result = LSBEmbedder::extract(&mut carrier, &lsb_config).unwrap();
+            assert_eq!(result.payload, [0u8; 10]);
+        }
+
+        // Extensions are covered by the checksum
+        let mut carrier = vec![0u8; 2000];
+        let mut config = RuntimeConfig::from_config(&LSBConfig::linear()).unwrap();
+        config.extensions = extensions;
+        HeaderEmbedder::new(&mut carrier, config).embed(10).unwrap();
+        let extension_start = FIXED_HEADER_SIZE + EXTENSION_LEN_SIZE;
+        carrier[(extension_start + EXTENSION_ENTRY_HEADER_SIZE) * 8] ^= 1;
+        assert!(read_header(&mut carrier, &LSBConfig::linear()).is_err());
+    }
+
+    #[test]
+    fn test_headers_without_extensions_use_version_two() {
+        let mut carrier = vec![0u8; 1000];
+        LSBEmbedder::embed(&mut carrier, b"v1", &LSBConfig::linear()).unwrap();
+        let (header, _, layout) = read_header(&mut carrier, &LSBConfig::linear()).unwrap();
+        assert_eq!(header.fixed.version, VERSION);
+        assert!(header.extensions.is_empty());
+        assert_eq!(layout.header_size(), FIXED_HEADER_SIZE);
+    }
+
+    #[test]
+    fn test_channel_bits_packing() {
+        for channel_bits in [
+            vec![BitIndex::Bit7],
+            vec![BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit1],
+            vec![
+                BitIndex::Bit3,
+                BitIndex::Bit0,
+                BitIndex::Bit7,
+                BitIndex::Bit5,
+            ],
+        ] {
+            assert_eq!(
+                unpack_channel_bits(pack_channel_bits(&channel_bits)),
+                channel_bits
+            );
+        }
+    }
+
+    #[test]
+    fn test_header_size_for_config_matches_embedding() {
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::random(),
+            LSBConfig::random()
+                .with_seed([1u8; SEED_SIZE])
+                .with_scattered_header()
+                .with_redundancy(3),
+            LSBConfig::linear()
+                .with_payload_metadata(crate::strategy::lsb::PayloadMetadata::new("a", "b"))
+                .with_channel_bit_indices([BitIndex::Bit0, BitIndex::Bit1]),
+        ] {
+            let mut carrier = vec![0u8; 2000];
+            let result = LSBEmbedder::embed(&mut carrier, b"payload", &config).unwrap();
+            assert_eq!(header_size_for_config(&config), result.header_size);
+        }
+    }
+
+    #[test]
+    fn test_header_checksum_algorithms() {
+        assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+        assert_eq!(crc64(b"123456789"), 0x995D_C9BB_DF19_39FA);
+
+        for checksum in [
+            HeaderChecksum::Crc32,
+            HeaderChecksum::Crc32c,
+            HeaderChecksum::Crc64,
+        ] {
+            for wire_format in [WireFormat::BigEndian, WireFormat::LittleEndian] {
+                let config = LSBConfig::linear()
+                    .with_header_checksum(checksum)
+                    .with_wire_format(wire_format);
+                let mut carrier = vec![0u8; 2000];
+                let result = LSBEmbedder::embed(&mut carrier, b"payload", &config).unwrap();
+                let (header, _, _) = read_header(&mut carrier, &LSBConfig::linear()).unwrap();
+                assert_eq!(header.fixed.payload_size, 7);
+
+                // Flip the low bit of the payload size
+                let version = header.fixed.version;
+                let size_bit = (bootstrap_size(version) + PAYLOAD_SIZE_SIZE - 1) * 8;
+                let size_bit = match wire_format {
+                    WireFormat::BigEndian => size_bit,
+                    WireFormat::LittleEndian => size_bit - (PAYLOAD_SIZE_SIZE - 1) * 8,
+                };
+                let mut corrupted = carrier.clone();
+                corrupted[size_bit] ^= 1;
+                let error = read_header(&mut corrupted, &LSBConfig::linear()).unwrap_err();
+                assert!(
+                    error.to_string().contains("CRC mismatch"),
+                    "{checksum:?} {wire_format:?}: {error}"
+                );
+
+                // The last header bit is in the checksum extension, if any,
+                // which holds the upper half of a CRC-64
+                let mut corrupted = carrier.clone();
+                corrupted[result.header_size * 8 - 1] ^= 1;
+                assert!(read_header(&mut corrupted, &LSBConfig::linear()).is_err());
+            }
+        }
     }
 }
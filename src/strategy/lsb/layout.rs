@@ -0,0 +1,148 @@
+use crate::error::PngerError;
+
+/// Memory layout of a raw pixel buffer with optional row padding.
+///
+/// Decoders and graphics APIs often pad each row to an alignment boundary, so
+/// a row occupies `row_stride` bytes of which only `width * channels` hold
+/// pixel samples. [`LSBEmbedder::embed_with_layout`](super::LSBEmbedder::embed_with_layout)
+/// uses the layout to embed into the sample bytes only, leaving the padding
+/// untouched and out of the capacity.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::PixelLayout;
+///
+/// // 10 RGBA pixels per row, padded to 48 bytes
+/// let layout = PixelLayout {
+///     width: 10,
+///     height: 4,
+///     channels: 4,
+///     row_stride: 48,
+/// };
+/// assert_eq!(layout.row_len(), 40);
+/// assert_eq!(layout.sample_count(), 160);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelLayout {
+    /// Width of the image in pixels.
+    pub width: usize,
+    /// Height of the image in rows.
+    pub height: usize,
+    /// Number of one-byte samples per pixel.
+    pub channels: usize,
+    /// Distance in bytes between the starts of consecutive rows.
+    pub row_stride: usize,
+}
+
+impl PixelLayout {
+    /// Layout of a tightly packed buffer, without row padding.
+    pub const fn packed(width: usize, height: usize, channels: usize) -> Self {
+        Self {
+            width,
+            height,
+            channels,
+            row_stride: width * channels,
+        }
+    }
+
+    /// Number of sample bytes in each row, excluding padding.
+    pub const fn row_len(&self) -> usize {
+        self.width * self.channels
+    }
+
+    /// Total number of sample bytes, excluding padding.
+    pub const fn sample_count(&self) -> usize {
+        self.row_len() * self.height
+    }
+
+    /// Checks that the layout is consistent and fits in a buffer of `len` bytes.
+    ///
+    /// The last row does not need to be followed by padding.
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: The stride is shorter than a row, or
+    ///   the buffer is too small for the layout
+    pub fn validate(&self, len: usize) -> Result<(), PngerError> {
+        if self.row_stride < self.row_len() {
+            return Err(PngerError::InvalidFormat(format!(
+                "row stride {} is shorter than a row of {} bytes",
+                self.row_stride,
+                self.row_len()
+            )));
+        }
+        let required = match self.height {
+            0 => Some(0),
+            height => (height - 1)
+                .checked_mul(self.row_stride)
+                .and_then(|padded| padded.checked_add(self.row_len())),
+        }
+        .ok_or_else(|| PngerError::InvalidFormat("pixel layout overflows".to_string()))?;
+        if len < required {
+            return Err(PngerError::InvalidFormat(format!(
+                "pixel buffer of {len} bytes is smaller than its layout ({required} bytes)"
+            )));
+        }
+        Ok(())
+    }
+
+    fn rows<'a>(&self, data: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+        let row_len = self.row_len();
+        data.chunks(self.row_stride.max(1))
+            .take(self.height)
+            .map(move |row| &row[..row_len])
+    }
+
+    /// Copies the sample bytes of `data`, dropping row padding.
+    pub(super) fn gather(&self, data: &[u8]) -> Vec<u8> {
+        self.rows(data).flatten().copied().collect()
+    }
+
+    /// Writes sample bytes produced by [`gather`](Self::gather) back in place.
+    pub(super) fn scatter(&self, data: &mut [u8], samples: &[u8]) {
+        let row_len = self.row_len();
+        data.chunks_mut(self.row_stride.max(1))
+            .take(self.height)
+            .zip(samples.chunks(row_len.max(1)))
+            .for_each(|(row, samples)| row[..row_len].copy_from_slice(samples));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        let layout = PixelLayout {
+            width: 2,
+            height: 3,
+            channels: 3,
+            row_stride: 9,
+        };
+        // Two padded rows plus one unpadded last row
+        assert!(layout.validate(24).is_ok());
+        assert!(layout.validate(23).is_err());
+
+        let short_stride = PixelLayout {
+            row_stride: 5,
+            ..layout
+        };
+        assert!(short_stride.validate(100).is_err());
+    }
+
+    #[test]
+    fn test_gather_scatter_skips_padding() {
+        let layout = PixelLayout {
+            width: 1,
+            height: 2,
+            channels: 2,
+            row_stride: 3,
+        };
+        let mut data = vec![1, 2, 0xAA, 3, 4];
+        assert_eq!(layout.gather(&data), [1, 2, 3, 4]);
+
+        layout.scatter(&mut data, &[5, 6, 7, 8]);
+        assert_eq!(data, [5, 6, 0xAA, 7, 8]);
+    }
+}
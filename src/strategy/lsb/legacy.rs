@@ -0,0 +1,147 @@
+//! Reader for the original header layout.
+//!
+//! The first releases wrote a version 1 header as raw bytes over the start
+//! of the carrier: magic, version, flags, big-endian payload size, CRC32 and
+//! the seed when embedded, followed by a body using the carrier bytes after
+//! it. Headers now live in the target bit plane like the payload and start
+//! at version 2, so these images no longer have a header where current
+//! readers look for one. Extraction falls back to this module when the bit
+//! plane holds no header.
+
+use crate::{
+    PngerError,
+    strategy::lsb::{
+        DEFAULT_MAGIC, ExtractResult, LSBConfig, MAGIC_SIZE, RuntimePattern, SEED_SIZE,
+        data::BodyEmbedder, header::HeaderFlags,
+    },
+};
+
+const VERSION: u8 = 1;
+
+// Magic, version, flags, payload size and CRC32
+const FIXED_HEADER_SIZE: usize = 14;
+
+// Flags known to the original layout
+const FLAGS: HeaderFlags = HeaderFlags::RANDOM_PATTERN.union(HeaderFlags::SEED_EMBEDDED);
+
+/// Extracts the payload of an image embedded with the original layout.
+///
+/// Returns `None` when the carrier does not start with a raw version 1
+/// header carrying the default magic and a valid checksum, so that the
+/// caller reports its own error. The layout knew a single magic, so
+/// configurations using another namespace never match.
+pub(super) fn extract(
+    image_data: &mut [u8],
+    config: &LSBConfig,
+) -> Option<Result<ExtractResult, PngerError>> {
+    let fixed = image_data.get(..FIXED_HEADER_SIZE)?;
+    let (magic, fields) = fixed.split_at(MAGIC_SIZE);
+    let (checked, crc) = fields.split_at(fields.len() - 4);
+    let flags = HeaderFlags::from_bits_retain(checked[1]);
+    if config.magic != DEFAULT_MAGIC
+        || magic != DEFAULT_MAGIC
+        || checked[0] != VERSION
+        || !FLAGS.contains(flags)
+        || crc32fast::hash(checked).to_be_bytes() != crc
+    {
+        return None;
+    }
+    let payload_size = u32::from_be_bytes([checked[2], checked[3], checked[4], checked[5]]);
+    debug_event!("read original layout header, flags {flags:?}, {payload_size}-byte payload");
+    Some(extract_body(
+        image_data,
+        config,
+        flags,
+        payload_size as usize,
+    ))
+}
+
+fn extract_body(
+    image_data: &mut [u8],
+    config: &LSBConfig,
+    flags: HeaderFlags,
+    payload_size: usize,
+) -> Result<ExtractResult, PngerError> {
+    let seed_was_embedded = flags.contains(HeaderFlags::SEED_EMBEDDED);
+    let header_size = FIXED_HEADER_SIZE + if seed_was_embedded { SEED_SIZE } else { 0 };
+    let embedded_seed = if seed_was_embedded {
+        let seed = image_data
+            .get(FIXED_HEADER_SIZE..header_size)
+            .ok_or_else(|| {
+                PngerError::InvalidFormat("seed flagged but image too small for seed".to_string())
+            })?;
+        Some(<[u8; SEED_SIZE]>::try_from(seed).expect("seed slice has the seed size"))
+    } else {
+        None
+    };
+    let pattern = RuntimePattern::from_flags_and_config(flags, embedded_seed, config)?;
+
+    // The body used every carrier byte after the header
+    let body = &mut image_data[header_size..];
+    let available = body.len() / 8;
+    if payload_size > available {
+        return Err(PngerError::InvalidFormat(format!(
+            "Header declares a {payload_size}-byte body but the image only holds {available} bytes"
+        )));
+    }
+    let candidates = (0..body.len() as u32).collect();
+    let payload = BodyEmbedder::new(body, candidates, &pattern, config.bit_index, payload_size)
+        .extract_payload(payload_size)?;
+
+    Ok(ExtractResult {
+        payload,
+        header_size,
+        seed_was_embedded,
+        metadata: None,
+        tag: None,
+        frame_index: None,
+        row_range: None,
+        text_encoding: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::lsb::LSBEmbedder;
+
+    // Carriers embedded by the original code with `LSBEmbedder::embed` and
+    // the payload `b"legacy"`, starting from `(i * 37 % 251) as u8`
+    const LINEAR_FIXTURE: [u8; 80] = [
+        80, 78, 71, 82, 1, 0, 0, 0, 0, 6, 147, 253, 215, 51, 16, 52, 91, 127, 164, 201, 239, 24,
+        61, 98, 135, 172, 208, 247, 33, 68, 107, 143, 181, 216, 2, 41, 77, 114, 151, 188, 224, 10,
+        48, 85, 123, 158, 197, 233, 18, 56, 92, 131, 167, 204, 241, 26, 64, 101, 139, 175, 213,
+        248, 35, 72, 109, 146, 183, 220, 6, 43, 80, 117, 154, 191, 228, 14, 51, 88, 125, 162,
+    ];
+    const RANDOM_FIXTURE: [u8; 120] = [
+        80, 78, 71, 82, 1, 3, 0, 0, 0, 6, 212, 93, 173, 227, 224, 148, 152, 190, 69, 3, 177, 234,
+        181, 37, 124, 243, 38, 140, 46, 206, 225, 247, 183, 169, 101, 154, 21, 97, 155, 24, 222,
+        254, 221, 90, 130, 142, 197, 233, 19, 56, 93, 130, 167, 204, 241, 26, 64, 100, 138, 175,
+        213, 249, 35, 73, 108, 146, 183, 220, 7, 42, 80, 117, 155, 191, 228, 15, 50, 88, 124, 162,
+        199, 236, 22, 59, 97, 132, 170, 207, 245, 30, 66, 104, 141, 179, 214, 1, 38, 75, 112, 149,
+        187, 222, 8, 47, 82, 121, 157, 194, 230, 17, 55, 91, 129, 165, 202, 239, 25, 62, 99, 136,
+    ];
+
+    #[test]
+    fn test_original_layout_still_extracts() {
+        let mut carrier = LINEAR_FIXTURE;
+        let result = LSBEmbedder::extract(&mut carrier, &LSBConfig::linear()).unwrap();
+        assert_eq!(result.payload, b"legacy");
+        assert_eq!(result.header_size, FIXED_HEADER_SIZE);
+        assert!(!result.seed_was_embedded);
+
+        let mut carrier = RANDOM_FIXTURE;
+        let result = LSBEmbedder::extract(&mut carrier, &LSBConfig::random()).unwrap();
+        assert_eq!(result.payload, b"legacy");
+        assert_eq!(result.header_size, FIXED_HEADER_SIZE + SEED_SIZE);
+        assert!(result.seed_was_embedded);
+
+        // A damaged raw header is not taken for one
+        let mut carrier = LINEAR_FIXTURE;
+        carrier[9] ^= 1;
+        assert!(extract(&mut carrier, &LSBConfig::linear()).is_none());
+        assert!(LSBEmbedder::extract(&mut carrier, &LSBConfig::linear()).is_err());
+        let mut carrier = LINEAR_FIXTURE;
+        assert!(extract(&mut carrier, &LSBConfig::linear().with_namespace(*b"APP1")).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::PngerError;
+
+/// Maximum length in bytes of each UTF-8 metadata field.
+pub const MAX_METADATA_FIELD_LEN: usize = u8::MAX as usize;
+
+/// Descriptive information stored alongside an embedded payload.
+///
+/// Lets the extracting side recover the original filename and content type
+/// of the payload. Each field is stored as a length-prefixed UTF-8 string of
+/// at most [`MAX_METADATA_FIELD_LEN`] bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, PayloadMetadata};
+///
+/// let mut image = vec![0u8; 2000];
+/// let config = LSBConfig::linear()
+///     .with_payload_metadata(PayloadMetadata::new("notes.txt", "text/plain"));
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+/// let metadata = result.metadata.unwrap();
+/// assert_eq!(metadata.filename, "notes.txt");
+/// assert_eq!(metadata.mime_type, "text/plain");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PayloadMetadata {
+    /// Original filename of the payload.
+    pub filename: String,
+    /// MIME type of the payload, such as `application/pdf`.
+    pub mime_type: String,
+}
+
+impl PayloadMetadata {
+    /// Creates payload metadata from a filename and a MIME type.
+    pub fn new(filename: impl Into<String>, mime_type: impl Into<String>) -> Self {
+        Self {
+            filename: filename.into(),
+            mime_type: mime_type.into(),
+        }
+    }
+
+    /// Serializes the metadata as two length-prefixed strings.
+    pub(super) fn to_bytes(&self) -> Result<Vec<u8>, PngerError> {
+        let mut data = Vec::with_capacity(2 + self.filename.len() + self.mime_type.len());
+        for field in [&self.filename, &self.mime_type] {
+            let len = u8::try_from(field.len()).map_err(|_| PngerError::PayloadError {
+                message: format!(
+                    "Metadata field is {} bytes long, the maximum is {MAX_METADATA_FIELD_LEN}",
+                    field.len()
+                ),
+            })?;
+            data.push(len);
+            data.extend_from_slice(field.as_bytes());
+        }
+        Ok(data)
+    }
+
+    /// Parses metadata serialized by [`to_bytes`](Self::to_bytes).
+    pub(super) fn from_bytes(data: &[u8]) -> Result<Self, PngerError> {
+        let invalid = || PngerError::InvalidFormat("Invalid payload metadata".to_string());
+
+        let mut rest = data;
+        let mut fields = Vec::with_capacity(2);
+        for _ in 0..2 {
+            let (&len, tail) = rest.split_first().ok_or_else(invalid)?;
+            let field = tail.get(..usize::from(len)).ok_or_else(invalid)?;
+            fields.push(String::from_utf8(field.to_vec()).map_err(|_| invalid())?);
+            rest = &tail[usize::from(len)..];
+        }
+        if !rest.is_empty() {
+            return Err(invalid());
+        }
+
+        let mime_type = fields.pop().ok_or_else(invalid)?;
+        let filename = fields.pop().ok_or_else(invalid)?;
+        Ok(Self {
+            filename,
+            mime_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_encoding() {
+        let metadata = PayloadMetadata::new("résumé.pdf", "application/pdf");
+        let bytes = metadata.to_bytes().unwrap();
+        assert_eq!(PayloadMetadata::from_bytes(&bytes).unwrap(), metadata);
+
+        // Truncated or padded data is rejected
+        assert!(PayloadMetadata::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(PayloadMetadata::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_err());
+
+        // Fields longer than a length byte can describe are rejected
+        let too_long = PayloadMetadata::new("a".repeat(MAX_METADATA_FIELD_LEN + 1), "");
+        assert!(matches!(
+            too_long.to_bytes(),
+            Err(PngerError::PayloadError { .. })
+        ));
+    }
+}
@@ -34,17 +34,56 @@ pub const ARGON2_TIME_COST: u32 = 2;
 /// Argon2 parallelism parameter (threads)
 pub const ARGON2_PARALLELISM: u32 = 1;
 
+/// Size of the header magic identifying the embedding namespace
+pub const MAGIC_SIZE: usize = 4;
+
+/// Default header magic, shared by all applications using the default namespace
+pub const DEFAULT_MAGIC: [u8; MAGIC_SIZE] = *b"PNGR";
+
+/// Maximum chunk size, in bytes, passed to [`LSBEmbedder::extract_streaming`] callbacks.
+///
+/// Extraction buffers also grow by at most this many bytes at a time, so an
+/// untrusted declared size is never allocated before its bytes are read.
+pub const EXTRACT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Maximum number of channels accepted by [`LSBConfig::with_channel_bit_indices`]
+pub const MAX_CHANNELS: usize = 4;
+
 mod bit_index;
 #[doc(hidden)]
 pub mod crypto;
 mod data;
 mod header;
+mod layout;
+mod legacy;
+mod metadata;
+mod shuffle;
 #[doc(hidden)]
 pub mod utils;
 
 pub use bit_index::BitIndex;
+pub use header::{FIXED_HEADER_SIZE, HeaderFlags};
+pub use layout::PixelLayout;
+pub use metadata::{MAX_METADATA_FIELD_LEN, PayloadMetadata};
+
+use crate::{PayloadSize, error::PngerError, strategy::lsb::data::BodyEmbedder};
+use rand::SeedableRng;
+
+/// ChaCha stream drawing the noise written by [`LSBConfig::with_lsb_noise`],
+/// distinct from the streams ordering the body and scattering the header
+const NOISE_STREAM: u64 = 2;
+
+/// ChaCha stream drawing the keystream of [`LSBConfig::with_chaff`], masking
+/// the whole target bit plane
+const CHAFF_STREAM: u64 = 4;
 
-use crate::{error::PngerError, strategy::lsb::data::BodyEmbedder};
+/// ChaCha stream deriving the body seed of [`LSBConfig::with_length_bound_seed`]
+/// from the pattern seed and the payload length
+const LENGTH_SEED_STREAM: u64 = 5;
+
+/// Bit plane entropy, in bits per bit, above which [`LSBConfig::auto_pattern`]
+/// considers the cover noisy
+const NOISY_PLANE_ENTROPY: f64 = 0.95;
 
 /// Configuration for LSB (Least Significant Bit) steganography strategy.
 ///
@@ -86,10 +125,35 @@ use crate::{error::PngerError, strategy::lsb::data::BodyEmbedder};
 /// | Random  | Slower| High     | Low           |
 ///
 /// Choose linear patterns for speed, random patterns for security.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LSBConfig {
     bit_index: BitIndex,
     pattern: EmbeddingPattern,
+    redundancy: u8,
+    magic: [u8; MAGIC_SIZE],
+    metadata: Option<PayloadMetadata>,
+    max_payload: Option<usize>,
+    max_kdf_memory: Option<u32>,
+    wire_format: WireFormat,
+    channel_bits: Option<Vec<BitIndex>>,
+    lsb_noise: bool,
+    chaff: bool,
+    skip_saturated: bool,
+    auto_pattern: bool,
+    fail_if_occupied: bool,
+    header_checksum: HeaderChecksum,
+    bit_order: BitOrder,
+    palette_mode: bool,
+    indexed_hybrid: bool,
+    payload_tag: Option<u16>,
+    frame_index: Option<u32>,
+    row_range: Option<std::ops::Range<u32>>,
+    text_encoding: Option<crate::TextEncoding>,
+    redundant_length: bool,
+    parity: Option<Parity>,
+    write_mode: WriteMode,
+    diffusion_stride: Option<u32>,
+    payload_hash: bool,
 }
 
 /// Embedding pattern configuration for LSB steganography.
@@ -141,7 +205,7 @@ pub struct LSBConfig {
 /// let random_password = LSBConfig::random()
 ///     .with_password("my_secret".to_string());
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EmbeddingPattern {
     /// Sequential embedding from top-left to bottom-right.
     ///
@@ -175,16 +239,30 @@ impl Default for EmbeddingPattern {
 /// The seed is critical for extraction - without the correct seed,
 /// the payload cannot be recovered. Choose the seed source based on
 /// your security and convenience requirements.
-#[derive(Debug, Clone)]
+///
+/// # Header Placement
+///
+/// Random patterns only scatter the body by default: the header is written
+/// linearly at the start of the carrier, where extraction finds it without
+/// searching, and the body is shuffled over the remaining carrier bytes.
+/// This balances speed and security. The header can instead be scattered
+/// over seed-derived positions, see [`LSBConfig::with_header_linear`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct RandomConfig {
     seed_source: SeedSource,
+    header_linear: bool,
+    seed_overrides_header: bool,
+    length_bound_seed: bool,
 }
 
 impl Default for RandomConfig {
-    /// Random configuration with automatic seed generation.
+    /// Random configuration with automatic seed generation and linear header.
     fn default() -> Self {
         Self {
             seed_source: SeedSource::default(),
+            header_linear: true,
+            seed_overrides_header: false,
+            length_bound_seed: false,
         }
     }
 }
@@ -220,7 +298,25 @@ impl Default for RandomConfig {
 /// let manual_config = LSBConfig::random()
 ///     .with_seed(manual_seed);
 /// ```
-#[derive(Debug, Clone)]
+///
+/// The enum is non-exhaustive: the `TestOnly` variant only exists with the
+/// `testing` feature, which any crate of a dependency graph can enable, so
+/// matches outside this crate need a wildcard arm:
+///
+/// ```rust
+/// use pnger::strategy::lsb::SeedSource;
+///
+/// fn seed_location(source: &SeedSource) -> &'static str {
+///     match source {
+///         SeedSource::Auto => "in the image",
+///         SeedSource::Password(_) | SeedSource::Manual(_) => "with the user",
+///         _ => "elsewhere",
+///     }
+/// }
+/// assert_eq!(seed_location(&SeedSource::Auto), "in the image");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum SeedSource {
     /// Auto-generate cryptographically secure random seed.
     ///
@@ -282,6 +378,43 @@ pub enum SeedSource {
     /// **Best for:** Advanced users, testing, integration with
     /// existing key management systems.
     Manual([u8; SEED_SIZE]),
+
+    /// Fixed, publicly known seed for deterministic tests and examples.
+    ///
+    /// **How it works:**
+    /// - Uses [`TEST_SEED`] for every embedding and extraction
+    /// - No seed data is stored in the image
+    /// - Logs a warning whenever the seed is used
+    ///
+    /// The variant only exists with the `testing` feature (or in this
+    /// crate's own unit tests), so a weak seed cannot reach a build that
+    /// does not opt in explicitly:
+    ///
+    #[cfg_attr(feature = "testing", doc = "```rust")]
+    #[cfg_attr(not(feature = "testing"), doc = "```compile_fail")]
+    /// use pnger::strategy::lsb::SeedSource;
+    ///
+    /// let _source = SeedSource::TestOnly;
+    /// ```
+    ///
+    /// **Best for:** Unit tests and reproducible examples. Never use it to
+    /// protect real data.
+    #[cfg(any(test, feature = "testing"))]
+    TestOnly,
+}
+
+/// Seed used by [`SeedSource::TestOnly`]. Anyone can reproduce it.
+#[cfg(any(test, feature = "testing"))]
+pub const TEST_SEED: [u8; SEED_SIZE] = [42u8; SEED_SIZE];
+
+impl SeedSource {
+    /// Returns the test seed, warning that it provides no secrecy.
+    #[cfg(any(test, feature = "testing"))]
+    fn test_seed() -> [u8; SEED_SIZE] {
+        #[cfg(feature = "log")]
+        log::warn!("using SeedSource::TestOnly; the embedding pattern is publicly known");
+        TEST_SEED
+    }
 }
 
 impl Default for SeedSource {
@@ -291,6 +424,217 @@ impl Default for SeedSource {
     }
 }
 
+/// Byte order of the multi-byte header fields.
+///
+/// The payload size, checksum and metadata length are stored big-endian by
+/// default. Tools reading the header with little-endian integers can embed
+/// with [`WireFormat::LittleEndian`] instead. The byte order is recorded in
+/// the header flags, so extraction detects it without any configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, WireFormat};
+///
+/// let mut image = vec![0u8; 1000];
+/// let config = LSBConfig::linear().with_wire_format(WireFormat::LittleEndian);
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+/// assert_eq!(result.payload, b"payload");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WireFormat {
+    /// Big-endian header fields, the historical format.
+    #[default]
+    BigEndian,
+
+    /// Little-endian header fields.
+    LittleEndian,
+}
+
+/// Order in which the bits of each payload byte are embedded.
+///
+/// Payload bytes are embedded least significant bit first by default. Some
+/// tools expect the most significant bit first; embed with
+/// [`BitOrder::MsbFirst`] to exchange payloads with them. A non-default
+/// order is recorded in a header extension, so extraction detects it
+/// without any configuration. The header itself is always stored LSB-first.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{BitOrder, LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 1000];
+/// let config = LSBConfig::linear().with_bit_order(BitOrder::MsbFirst);
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+/// assert_eq!(result.payload, b"payload");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BitOrder {
+    /// Least significant bit of each byte first, the historical order.
+    #[default]
+    LsbFirst,
+
+    /// Most significant bit of each byte first.
+    MsbFirst,
+}
+
+/// How payload bits are written into the carrier body.
+///
+/// Embedding replaces the target bit of each carrier byte by default. With
+/// [`WriteMode::Or`], payload bits are OR-ed into the carrier instead: zero
+/// bits leave the carrier untouched, and body bits are only ever set, for
+/// append-style marking where each embedding keeps the marks of the previous
+/// ones. The header is always overwritten, since it must read back exactly.
+///
+/// Extraction returns the bits found in the carrier, so an OR-ed payload
+/// only reads back over body bits that are clear wherever it has a zero bit:
+/// embedding fails with `PngerError::InvalidFormat` otherwise. OR-ed
+/// payloads record their hash, and
+/// [`LSBEmbedder::extract_with_confidence`] reports bits set after embedding
+/// as an unverified payload. The mode is recorded in a header extension, and
+/// extraction refuses OR-ed payloads unless its own configuration uses
+/// [`WriteMode::Or`] too.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, WriteMode};
+///
+/// let config = LSBConfig::linear().with_write_mode(WriteMode::Or);
+/// let mut image = vec![0u8; 1000];
+/// LSBEmbedder::embed(&mut image, &[0b0000_0101], &config).unwrap();
+/// LSBEmbedder::embed(&mut image, &[0b0011_0101], &config).unwrap();
+///
+/// let result = LSBEmbedder::extract(&mut image, &config).unwrap();
+/// assert_eq!(result.payload, [0b0011_0101]);
+///
+/// // Clearing the earlier marks is refused
+/// assert!(LSBEmbedder::embed(&mut image, &[0b0011_0000], &config).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WriteMode {
+    /// Replace the target bit with the payload bit, the historical mode.
+    #[default]
+    Overwrite,
+
+    /// Set the target bit for one payload bits, leave it as is for zero bits.
+    Or,
+}
+
+/// Checksum protecting the header fields.
+///
+/// The checksum is stored in the 4-byte CRC field of the header. Algorithms
+/// other than CRC32 are recorded in a header extension, which also holds the
+/// upper half of the 64-bit checksum, so extraction picks the right one
+/// without configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{HeaderChecksum, LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 1000];
+/// let config = LSBConfig::linear().with_header_checksum(HeaderChecksum::Crc64);
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+/// assert_eq!(result.payload, b"payload");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HeaderChecksum {
+    /// CRC-32 (IEEE), the historical checksum, readable by every version.
+    #[default]
+    Crc32,
+
+    /// CRC-32C (Castagnoli), with better error detection on short data.
+    Crc32c,
+
+    /// CRC-64/XZ, stored across the CRC field and the header extension.
+    Crc64,
+}
+
+impl HeaderChecksum {
+    // Identifier stored in the header extension
+    const fn id(self) -> u8 {
+        match self {
+            Self::Crc32 => 0,
+            Self::Crc32c => 1,
+            Self::Crc64 => 2,
+        }
+    }
+
+    const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Crc32),
+            1 => Some(Self::Crc32c),
+            2 => Some(Self::Crc64),
+            _ => None,
+        }
+    }
+}
+
+/// Carrier bytes used by [`LSBConfig::with_parity`].
+///
+/// Payloads embedded into the even carrier bytes leave the odd ones
+/// untouched, and the other way around, so two payloads can share a bit
+/// plane, or the untouched half can serve as a reference.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, Parity};
+///
+/// let mut image = vec![0u8; 2000];
+/// let even = LSBConfig::linear().with_parity(Parity::Even);
+/// let odd = LSBConfig::linear().with_parity(Parity::Odd);
+/// LSBEmbedder::embed(&mut image, b"even", &even).unwrap();
+/// LSBEmbedder::embed(&mut image, b"odd", &odd).unwrap();
+///
+/// assert_eq!(LSBEmbedder::extract(&mut image, &even).unwrap().payload, b"even");
+/// assert_eq!(LSBEmbedder::extract(&mut image, &odd).unwrap().payload, b"odd");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Parity {
+    /// Carrier bytes 0, 2, 4, ...
+    Even,
+
+    /// Carrier bytes 1, 3, 5, ...
+    Odd,
+}
+
+impl Parity {
+    // Identifier stored in the header extension
+    const fn id(self) -> u8 {
+        match self {
+            Self::Even => 0,
+            Self::Odd => 1,
+        }
+    }
+
+    const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Even),
+            1 => Some(Self::Odd),
+            _ => None,
+        }
+    }
+
+    // First carrier byte of this parity
+    const fn offset(self) -> usize {
+        self.id() as usize
+    }
+
+    // Number of carrier bytes of this parity in a `carrier_len`-byte carrier
+    const fn carrier_len(self, carrier_len: usize) -> usize {
+        (carrier_len + 1 - self.offset()) / 2
+    }
+}
+
 // Builder pattern implementations for LSBConfig
 impl LSBConfig {
     /// Create a new LSB configuration with linear embedding pattern.
@@ -318,6 +662,31 @@ impl LSBConfig {
         Self {
             bit_index: BitIndex::LSB,
             pattern: EmbeddingPattern::Linear,
+            redundancy: 1,
+            magic: DEFAULT_MAGIC,
+            metadata: None,
+            max_payload: None,
+            max_kdf_memory: None,
+            wire_format: WireFormat::BigEndian,
+            channel_bits: None,
+            lsb_noise: false,
+            chaff: false,
+            skip_saturated: false,
+            auto_pattern: false,
+            fail_if_occupied: false,
+            header_checksum: HeaderChecksum::Crc32,
+            bit_order: BitOrder::LsbFirst,
+            palette_mode: false,
+            indexed_hybrid: false,
+            payload_tag: None,
+            frame_index: None,
+            row_range: None,
+            text_encoding: None,
+            redundant_length: false,
+            parity: None,
+            write_mode: WriteMode::Overwrite,
+            diffusion_stride: None,
+            payload_hash: false,
         }
     }
 
@@ -358,8 +727,82 @@ impl LSBConfig {
             bit_index: BitIndex::LSB,
             pattern: EmbeddingPattern::Random(RandomConfig {
                 seed_source: SeedSource::Auto,
+                header_linear: true,
+                seed_overrides_header: false,
+                length_bound_seed: false,
             }),
+            redundancy: 1,
+            magic: DEFAULT_MAGIC,
+            metadata: None,
+            max_payload: None,
+            max_kdf_memory: None,
+            wire_format: WireFormat::BigEndian,
+            channel_bits: None,
+            lsb_noise: false,
+            chaff: false,
+            skip_saturated: false,
+            auto_pattern: false,
+            fail_if_occupied: false,
+            header_checksum: HeaderChecksum::Crc32,
+            bit_order: BitOrder::LsbFirst,
+            palette_mode: false,
+            indexed_hybrid: false,
+            payload_tag: None,
+            frame_index: None,
+            row_range: None,
+            text_encoding: None,
+            redundant_length: false,
+            parity: None,
+            write_mode: WriteMode::Overwrite,
+            diffusion_stride: None,
+            payload_hash: false,
+        }
+    }
+
+    /// Create a configuration picking its pattern from the cover.
+    ///
+    /// The bit plane of the carrier is measured just before embedding. When it
+    /// already looks like noise, where changes are hard to detect whatever
+    /// the pattern, the faster linear pattern is used; smooth covers, where
+    /// linear changes stand out, get a random pattern with an auto-generated
+    /// seed. The chosen pattern is recorded in the header, so this same
+    /// configuration, or the default one, extracts either.
+    ///
+    /// Capacity checks assume the random pattern, whose embedded seed takes
+    /// more room. Setting a password or seed keeps the pattern random, since
+    /// falling back to linear would drop it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// // A flat cover gets a random pattern, and so an embedded seed
+    /// let mut image = vec![128u8; 4000];
+    /// let result = LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::auto_pattern()).unwrap();
+    /// assert!(result.seed_embedded);
+    /// ```
+    pub const fn auto_pattern() -> Self {
+        let mut config = Self::random();
+        config.auto_pattern = true;
+        config
+    }
+
+    /// Pattern `auto_pattern` resolves to for `carrier`.
+    fn resolve_auto_pattern(&self, carrier: &[u8]) -> Self {
+        let mut config = self.clone();
+        config.auto_pattern = false;
+        let seedless = matches!(
+            &config.pattern,
+            EmbeddingPattern::Random(RandomConfig {
+                seed_source: SeedSource::Auto,
+                ..
+            })
+        );
+        if seedless && bit_plane_entropy(carrier, config.bit_index) >= NOISY_PLANE_ENTROPY {
+            config.pattern = EmbeddingPattern::Linear;
         }
+        config
     }
 
     /// Create a const random configuration with manual seed.
@@ -381,7 +824,35 @@ impl LSBConfig {
             bit_index: BitIndex::LSB,
             pattern: EmbeddingPattern::Random(RandomConfig {
                 seed_source: SeedSource::Manual(seed),
+                header_linear: true,
+                seed_overrides_header: false,
+                length_bound_seed: false,
             }),
+            redundancy: 1,
+            magic: DEFAULT_MAGIC,
+            metadata: None,
+            max_payload: None,
+            max_kdf_memory: None,
+            wire_format: WireFormat::BigEndian,
+            channel_bits: None,
+            lsb_noise: false,
+            chaff: false,
+            skip_saturated: false,
+            auto_pattern: false,
+            fail_if_occupied: false,
+            header_checksum: HeaderChecksum::Crc32,
+            bit_order: BitOrder::LsbFirst,
+            palette_mode: false,
+            indexed_hybrid: false,
+            payload_tag: None,
+            frame_index: None,
+            row_range: None,
+            text_encoding: None,
+            redundant_length: false,
+            parity: None,
+            write_mode: WriteMode::Overwrite,
+            diffusion_stride: None,
+            payload_hash: false,
         }
     }
 
@@ -421,654 +892,3229 @@ impl LSBConfig {
         self
     }
 
-    /// Set password for random pattern seed derivation.
-    ///
-    /// Configures the random pattern to derive its seed from the provided
-    /// password using Argon2id key derivation. This provides strong security
-    /// without storing sensitive data in the image.
-    ///
-    /// **Note:** Only works with random patterns. Calling this on a linear
-    /// configuration has no effect.
+    /// Store the payload `copies` times for robustness.
     ///
-    /// # Parameters
-    /// - `password`: Password string for seed derivation
+    /// Each copy occupies its own set of carrier bytes, following the
+    /// configured pattern. On extraction every payload bit is decided by a
+    /// majority vote across the copies, so the payload survives as long as
+    /// fewer than half of the copies are damaged at any given bit. The factor
+    /// is recorded in the header, extraction does not need to know it.
     ///
-    /// # Security Features
-    /// - Uses Argon2id for password-based key derivation
-    /// - Resistant to rainbow table attacks
-    /// - Configurable work factors for brute force resistance
-    /// - No password or seed data stored in the image
+    /// Capacity is divided by `copies`. A factor of 0 is treated as 1 (no
+    /// redundancy). Odd factors are recommended since ties resolve to 0.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBConfig, BitIndex};
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// let config = LSBConfig::random()
-    ///     .with_password("my_secure_password".to_string());
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::linear().with_redundancy(3);
+    /// LSBEmbedder::embed(&mut image, b"robust", &config).unwrap();
     ///
-    /// // Can be chained with other options
-    /// let config = LSBConfig::random()
-    ///     .with_password("secret".to_string())
-    ///     .with_bit_index(BitIndex::Bit1);
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"robust");
     /// ```
-    ///
-    /// # Password Guidelines
-    /// - Use strong, unique passwords
-    /// - Minimum 12 characters recommended
-    /// - Include mix of letters, numbers, symbols
-    /// - Store passwords securely
-    pub fn with_password(mut self, password: String) -> Self {
-        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
-            config.seed_source = SeedSource::Password(password);
-        }
+    pub fn with_redundancy(mut self, copies: u8) -> Self {
+        self.redundancy = copies.max(1);
         self
     }
 
-    /// Set manual 32-byte seed for random pattern.
-    ///
-    /// Provides direct control over the pseudorandom seed used for
-    /// embedding pattern generation. This is for advanced use cases
-    /// where you need deterministic behavior or integration with
-    /// existing key management systems.
+    /// Use a custom header magic for domain separation.
     ///
-    /// **Note:** Only works with random patterns. Calling this on a linear
-    /// configuration has no effect.
-    ///
-    /// # Parameters
-    /// - `seed`: Exactly 32 bytes of seed data
+    /// The magic marks the start of the header and defaults to
+    /// [`DEFAULT_MAGIC`] (`PNGR`). Applications using their own namespace only
+    /// recognize payloads embedded with the same magic, so images produced by
+    /// one application are not reported as carrying a payload by tools
+    /// looking for another namespace.
     ///
-    /// # Security Considerations
-    /// - **Seed quality is critical** - use cryptographically secure randomness
-    /// - Poor quality seeds reduce security significantly
-    /// - Same seed always produces same embedding pattern
-    /// - Seed must be stored/transmitted securely
+    /// Extraction must use the same namespace as embedding.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::LSBConfig;
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// // Generate a secure seed (example - use proper CSPRNG in practice)
-    /// let seed = [42u8; 32]; // Don't use this in production!
+    /// let mut image = vec![0u8; 1000];
+    /// let config = LSBConfig::linear().with_namespace(*b"ACME");
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
     ///
-    /// let config = LSBConfig::random().with_seed(seed);
+    /// // The default namespace does not recognize the payload
+    /// assert!(LSBEmbedder::extract(&mut image, &LSBConfig::linear()).is_err());
+    /// assert!(LSBEmbedder::extract(&mut image, &config).is_ok());
     /// ```
-    ///
-    /// # Use Cases
-    /// - Testing with reproducible results
-    /// - Integration with existing key management
-    /// - Custom seed derivation schemes
-    /// - Compliance with specific randomness requirements
-    pub fn with_seed(mut self, seed: [u8; SEED_SIZE]) -> Self {
-        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
-            config.seed_source = SeedSource::Manual(seed);
-        }
+    pub fn with_namespace(mut self, magic: [u8; MAGIC_SIZE]) -> Self {
+        self.magic = magic;
         self
     }
 
-    /// Conditionally set password if provided (CLI helper).
+    /// Store a filename and MIME type alongside the payload.
     ///
-    /// Convenience method for CLI applications where password might be
-    /// optional. Only applies the password if `Some(password)` is provided.
+    /// The metadata is embedded ahead of the payload (and repeated with it
+    /// when redundancy is enabled), so it consumes capacity like payload
+    /// bytes: 2 bytes plus the length of both strings. It is returned in
+    /// [`ExtractResult::metadata`] without any extraction-side configuration.
     ///
-    /// # Parameters
-    /// - `password`: Optional password string
+    /// Embedding fails with `PngerError::PayloadError` if either field is
+    /// longer than [`MAX_METADATA_FIELD_LEN`] bytes.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::LSBConfig;
-    ///
-    /// let user_password: Option<String> = Some("secret".to_string());
-    /// let config = LSBConfig::random().with_password_if_some(user_password);
+    /// use pnger::strategy::lsb::{LSBConfig, PayloadMetadata};
     ///
-    /// let no_password: Option<String> = None;
-    /// let config = LSBConfig::random().with_password_if_some(no_password);
-    /// // This config will still use auto-generated seed
+    /// let config = LSBConfig::random()
+    ///     .with_payload_metadata(PayloadMetadata::new("report.pdf", "application/pdf"));
     /// ```
-    pub fn with_password_if_some(self, password: Option<String>) -> Self {
-        match password {
-            Some(pwd) => self.with_password(pwd),
-            None => self,
-        }
+    pub fn with_payload_metadata(mut self, metadata: PayloadMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
     }
 
-    /// Conditionally set seed if provided (CLI helper).
-    ///
-    /// Convenience method for CLI applications where seed might be
-    /// optional. Only applies the seed if `Some(seed)` is provided.
+    /// Store a 2-byte application tag in the header.
     ///
-    /// # Parameters
-    /// - `seed`: Optional 32-byte seed array
+    /// A lighter alternative to [`with_payload_metadata`](Self::with_payload_metadata)
+    /// for routing payloads of several types through a single extractor.
+    /// The tag is stored in a header extension, taking 5 header bytes (40
+    /// carrier bytes) plus the extension length, and is returned in
+    /// [`ExtractResult::tag`] without any extraction-side configuration.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::LSBConfig;
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// let user_seed: Option<[u8; 32]> = Some([1u8; 32]);
-    /// let config = LSBConfig::random().with_seed_if_some(user_seed);
+    /// let mut image = vec![0u8; 1000];
+    /// let config = LSBConfig::linear().with_payload_tag(0x1234);
+    /// LSBEmbedder::embed(&mut image, b"invoice", &config).unwrap();
     ///
-    /// let no_seed: Option<[u8; 32]> = None;
-    /// let config = LSBConfig::random().with_seed_if_some(no_seed);
-    /// // This config will still use auto-generated seed
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.tag, Some(0x1234));
     /// ```
-    pub fn with_seed_if_some(self, seed: Option<[u8; SEED_SIZE]>) -> Self {
-        match seed {
-            Some(seed) => self.with_seed(seed),
-            None => self,
-        }
+    pub fn with_payload_tag(mut self, tag: u16) -> Self {
+        self.payload_tag = Some(tag);
+        self
     }
 
-    /// Get the configured bit index.
-    ///
-    /// Returns the `BitIndex` that will be modified during embedding operations.
+    /// Record the animation frame holding the payload in the header.
     ///
-    /// # Returns
-    /// `BitIndex` variant representing the target bit position.
+    /// Set by [`embed_payload_into_frame`](crate::embed_payload_into_frame)
+    /// for APNG covers, so extraction can tell which frame was meant to
+    /// carry the payload. The index is returned in
+    /// [`ExtractResult::frame_index`] and has no other effect.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBConfig, BitIndex};
-    ///
-    /// let config = LSBConfig::linear().with_bit_index(BitIndex::Bit2);
-    /// assert_eq!(config.bit_index(), BitIndex::Bit2);
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// // Using the LSB alias
-    /// let config = LSBConfig::linear().with_bit_index(BitIndex::LSB);
-    /// assert_eq!(config.bit_index(), BitIndex::Bit0);
+    /// let mut frame = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut frame, b"payload", &LSBConfig::linear().with_frame_index(2)).unwrap();
+    /// let result = LSBEmbedder::extract(&mut frame, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.frame_index, Some(2));
     /// ```
-    pub fn bit_index(&self) -> BitIndex {
-        self.bit_index
+    pub fn with_frame_index(mut self, index: u32) -> Self {
+        self.frame_index = Some(index);
+        self
     }
 
-    /// Get a reference to the embedding pattern configuration.
+    /// Record the image rows holding the payload in the header.
     ///
-    /// Returns the pattern type (Linear or Random) along with its
-    /// associated configuration options.
+    /// Set by [`embed_payload_in_rows`](crate::embed_payload_in_rows), whose
+    /// carrier is the scanlines `rows` only, so extraction can tell which
+    /// rows to read. The range is returned in [`ExtractResult::row_range`]
+    /// and has no other effect.
     ///
-    /// # Returns
-    /// Reference to the `EmbeddingPattern` enum variant.
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut rows = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut rows, b"payload", &LSBConfig::linear().with_row_range(4..9)).unwrap();
+    /// let result = LSBEmbedder::extract(&mut rows, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.row_range, Some(4..9));
+    /// ```
+    pub fn with_row_range(mut self, rows: std::ops::Range<u32>) -> Self {
+        self.row_range = Some(rows);
+        self
+    }
+
+    /// Record the character encoding of a text payload in the header.
+    ///
+    /// The encoding is stored in a header extension, taking 4 header bytes
+    /// (32 carrier bytes) plus the extension length, and is returned in
+    /// [`ExtractResult::text_encoding`]. The payload itself is not converted:
+    /// use [`embed_text_from_bytes`](crate::embed_text_from_bytes) to encode
+    /// a string and record its encoding in one step.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBConfig, EmbeddingPattern};
+    /// use pnger::TextEncoding;
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// let config = LSBConfig::linear();
-    /// match config.pattern() {
-    ///     EmbeddingPattern::Linear => println!("Using linear pattern"),
-    ///     EmbeddingPattern::Random(_) => println!("Using random pattern"),
-    /// }
+    /// let mut image = vec![0u8; 1000];
+    /// let text = TextEncoding::Latin1.encode("café").unwrap();
+    /// let config = LSBConfig::linear().with_text_encoding(TextEncoding::Latin1);
+    /// LSBEmbedder::embed(&mut image, &text, &config).unwrap();
+    ///
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.text_encoding, Some(TextEncoding::Latin1));
     /// ```
-    pub fn pattern(&self) -> &EmbeddingPattern {
-        &self.pattern
+    pub fn with_text_encoding(mut self, encoding: crate::TextEncoding) -> Self {
+        self.text_encoding = Some(encoding);
+        self
     }
-}
 
-impl Default for LSBConfig {
-    /// Creates a default LSB configuration using random pattern.
+    /// Store a second copy of the payload length at the end of the header.
     ///
-    /// The default provides a good balance of security and convenience:
-    /// - Random embedding pattern for better security
-    /// - Auto-generated seed (embedded in image header)
-    /// - Bit index 0 (least significant bit)
+    /// The payload length is otherwise a single header field, and a
+    /// corrupted length fails the header checksum even when the body is
+    /// intact. With this option, a copy is kept in a header extension; when
+    /// the checksum fails, extraction tries each copy in place of the other
+    /// and keeps the one the checksum confirms. The copy takes 7 header
+    /// bytes (56 carrier bytes) plus the extension length, and extraction
+    /// needs no configuration to use it.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use pnger::strategy::lsb::LSBConfig;
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// let config = LSBConfig::default();
-    /// // Equivalent to:
-    /// let config = LSBConfig::random();
+    /// let mut image = vec![0u8; 1000];
+    /// let config = LSBConfig::linear().with_redundant_length(true);
+    /// LSBEmbedder::embed(&mut image, b"resilient", &config).unwrap();
+    ///
+    /// // Corrupt the low byte of the primary length field
+    /// image[11 * 8] ^= 1;
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"resilient");
     /// ```
-    fn default() -> Self {
-        Self::random()
+    pub fn with_redundant_length(mut self, enabled: bool) -> Self {
+        self.redundant_length = enabled;
+        self
     }
-}
 
-// Internal runtime configuration for optimized implementation
-#[derive(Debug, Clone)]
-pub(crate) struct RuntimeConfig {
-    bit_index: BitIndex,
-    pattern: RuntimePattern,
-}
+    /// Cap the payload size extraction will accept.
+    ///
+    /// The payload size comes from the image header, which is untrusted when
+    /// processing images from unknown sources. With a cap, extraction rejects
+    /// headers declaring more than `max_bytes` bytes with
+    /// `PngerError::InvalidFormat` before reading or allocating anything for
+    /// the payload. Has no effect on embedding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// LSBEmbedder::embed(&mut image, &[0u8; 100], &LSBConfig::linear()).unwrap();
+    ///
+    /// let capped = LSBConfig::linear().with_max_payload(64);
+    /// assert!(LSBEmbedder::extract(&mut image, &capped).is_err());
+    /// ```
+    pub fn with_max_payload(mut self, max_bytes: usize) -> Self {
+        self.max_payload = Some(max_bytes);
+        self
+    }
 
-#[derive(Debug, Clone)]
-pub(crate) enum RuntimePattern {
-    Linear,
-    Random {
-        seed: [u8; SEED_SIZE],
-        embed_seed: bool,
-    },
-}
+    /// Cap the memory the password key derivation may use, in MiB.
+    ///
+    /// Password seeds are derived with Argon2id using
+    /// [`KDF_MEMORY_KIB`](crypto::KDF_MEMORY_KIB) KiB of memory (19 MiB).
+    /// Once KDF parameters can come from image headers, a hostile image
+    /// could request enough memory to exhaust the host. With a cap, any
+    /// derivation needing more than `mib` MiB fails with
+    /// `PngerError::CryptoError` before allocating anything.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::PngerError;
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::random().with_password("secret".to_string());
+    ///
+    /// let result = LSBEmbedder::embed(&mut image, b"payload", &config.clone().with_max_kdf_memory(8));
+    /// assert!(matches!(result, Err(PngerError::CryptoError(_))));
+    /// assert!(LSBEmbedder::embed(&mut image, b"payload", &config.with_max_kdf_memory(64)).is_ok());
+    /// ```
+    pub fn with_max_kdf_memory(mut self, mib: u32) -> Self {
+        self.max_kdf_memory = Some(mib);
+        self
+    }
 
-impl RuntimeConfig {
-    /// Convert from user-facing `LSBConfig` to internal `RuntimeConfig`
-    fn from_config(config: &LSBConfig) -> Result<Self, PngerError> {
-        let pattern = match &config.pattern {
-            EmbeddingPattern::Linear => RuntimePattern::Linear,
-            EmbeddingPattern::Random(random_config) => {
-                let (seed, embed_seed) = match &random_config.seed_source {
-                    SeedSource::Auto => {
-                        let seed = crypto::CryptoContext::generate_random_seed()
-                            .map_err(|e| PngerError::CryptoError(e.to_string()))?;
-                        (seed, true)
-                    }
-                    SeedSource::Password(password) => {
-                        let seed = crypto::CryptoContext::derive_seed_from_password(password)
-                            .map_err(|e| PngerError::CryptoError(e.to_string()))?;
-                        (seed, false)
-                    }
-                    SeedSource::Manual(seed) => (*seed, false),
-                };
+    /// Set the byte order of the header fields.
+    ///
+    /// Defaults to [`WireFormat::BigEndian`]. The choice is recorded in the
+    /// header, so extraction reads either format regardless of this setting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, WireFormat};
+    ///
+    /// let config = LSBConfig::random().with_wire_format(WireFormat::LittleEndian);
+    /// assert_eq!(config.wire_format(), WireFormat::LittleEndian);
+    /// ```
+    pub fn with_wire_format(mut self, wire_format: WireFormat) -> Self {
+        self.wire_format = wire_format;
+        self
+    }
 
-                RuntimePattern::Random { seed, embed_seed }
-            }
-        };
+    /// Set the order in which the bits of each payload byte are embedded.
+    ///
+    /// Defaults to [`BitOrder::LsbFirst`]. Any other order is recorded in a
+    /// header extension, which adds a few bytes to the header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{BitOrder, LSBConfig};
+    ///
+    /// let config = LSBConfig::random().with_bit_order(BitOrder::MsbFirst);
+    /// assert_eq!(config.bit_order(), BitOrder::MsbFirst);
+    /// ```
+    pub fn with_bit_order(mut self, bit_order: BitOrder) -> Self {
+        self.bit_order = bit_order;
+        self
+    }
 
-        Ok(RuntimeConfig {
-            bit_index: config.bit_index,
-            pattern,
-        })
+    /// Set how payload bits are written into the carrier body.
+    ///
+    /// Defaults to [`WriteMode::Overwrite`]. [`WriteMode::Or`] is recorded in
+    /// a header extension and must be set for extraction as well; see
+    /// [`WriteMode`] for what extraction returns in that mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, WriteMode};
+    ///
+    /// let config = LSBConfig::linear().with_write_mode(WriteMode::Or);
+    /// assert_eq!(config.write_mode(), WriteMode::Or);
+    /// ```
+    pub fn with_write_mode(mut self, write_mode: WriteMode) -> Self {
+        self.write_mode = write_mode;
+        self
     }
-}
 
-impl RuntimePattern {
-    /// Creates a `RuntimePattern` by analyzing the image header and user config.
-    fn from_header_and_config(
-        header: &header::CompleteHeader,
-        config: &LSBConfig,
-    ) -> Result<Self, PngerError> {
-        if header
-            .fixed
-            .flags
-            .contains(header::HeaderFlags::RANDOM_PATTERN)
-        {
-            // It's a random pattern, so we just need to get the seed.
-            let seed = Self::reconstruct_seed(header, config)?;
-            let seed_was_embedded = header
-                .fixed
-                .flags
-                .contains(header::HeaderFlags::SEED_EMBEDDED);
+    /// Set the checksum protecting the header.
+    ///
+    /// Defaults to [`HeaderChecksum::Crc32`]. Any other algorithm is recorded
+    /// in a header extension, which adds a few bytes to the header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{HeaderChecksum, LSBConfig};
+    ///
+    /// let config = LSBConfig::random().with_header_checksum(HeaderChecksum::Crc32c);
+    /// assert_eq!(config.header_checksum(), HeaderChecksum::Crc32c);
+    /// ```
+    pub fn with_header_checksum(mut self, checksum: HeaderChecksum) -> Self {
+        self.header_checksum = checksum;
+        self
+    }
 
-            Ok(RuntimePattern::Random {
-                seed,
-                embed_seed: seed_was_embedded,
-            })
-        } else {
-            // It's a linear pattern.
-            Ok(RuntimePattern::Linear)
-        }
+    /// Target a different bit in each channel.
+    ///
+    /// Channel samples are interleaved in the carrier, so carrier byte `i`
+    /// gets bit `indices[i % N]`. For 8-bit images `N` should be the number
+    /// of channels (3 for RGB, 4 for RGBA), pairing each index with a channel;
+    /// for example `[Bit0, Bit1, Bit1]` uses the least significant bit of red
+    /// but the second bit of green and blue. For 16-bit images each sample
+    /// spans two carrier bytes, high byte first.
+    ///
+    /// The mapping is recorded in the header, so extraction only needs the
+    /// [`bit_index`](Self::with_bit_index) the header itself is stored in.
+    /// Embedding fails with `PngerError::UnsupportedMode` unless `N` is
+    /// between 1 and [`MAX_CHANNELS`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{BitIndex, LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 3000];
+    /// let config = LSBConfig::linear()
+    ///     .with_channel_bit_indices([BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit1]);
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+    ///
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"payload");
+    /// ```
+    pub fn with_channel_bit_indices<const N: usize>(mut self, indices: [BitIndex; N]) -> Self {
+        self.channel_bits = Some(indices.to_vec());
+        self
     }
 
-    /// Reconstructs the seed from the header or user config.
-    fn reconstruct_seed(
-        header: &header::CompleteHeader,
-        config: &LSBConfig,
-    ) -> Result<[u8; SEED_SIZE], PngerError> {
-        let seed_was_embedded = header
-            .fixed
-            .flags
-            .contains(header::HeaderFlags::SEED_EMBEDDED);
+    /// Set password for random pattern seed derivation.
+    ///
+    /// Configures the random pattern to derive its seed from the provided
+    /// password using Argon2id key derivation. This provides strong security
+    /// without storing sensitive data in the image.
+    ///
+    /// **Note:** Only works with random patterns. Calling this on a linear
+    /// configuration has no effect; use
+    /// [`try_with_password`](Self::try_with_password) to get an error instead.
+    ///
+    /// # Parameters
+    /// - `password`: Password string for seed derivation
+    ///
+    /// # Security Features
+    /// - Uses Argon2id for password-based key derivation
+    /// - Resistant to rainbow table attacks
+    /// - Configurable work factors for brute force resistance
+    /// - No password or seed data stored in the image
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, BitIndex};
+    ///
+    /// let config = LSBConfig::random()
+    ///     .with_password("my_secure_password".to_string());
+    ///
+    /// // Can be chained with other options
+    /// let config = LSBConfig::random()
+    ///     .with_password("secret".to_string())
+    ///     .with_bit_index(BitIndex::Bit1);
+    /// ```
+    ///
+    /// # Password Guidelines
+    /// - Use strong, unique passwords
+    /// - Minimum 12 characters recommended
+    /// - Include mix of letters, numbers, symbols
+    /// - Store passwords securely
+    pub fn with_password(mut self, password: String) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.seed_source = SeedSource::Password(password);
+        }
+        self
+    }
 
-        if seed_was_embedded {
-            // Use embedded seed
-            header.seed.ok_or_else(|| {
-                PngerError::InvalidFormat("Seed embedded flag set but no seed data".to_string())
-            })
-        } else {
-            // Use user-provided seed source
-            match &config.pattern {
-                EmbeddingPattern::Random(random_config) => match &random_config.seed_source {
-                    SeedSource::Password(password) => {
-                        crypto::CryptoContext::derive_seed_from_password(password)
-                            .map_err(|e| PngerError::CryptoError(e.to_string()))
-                    }
-                    SeedSource::Manual(seed) => Ok(*seed),
-                    SeedSource::Auto => Err(PngerError::InvalidFormat(
-                        "Auto seed source but no seed embedded".to_string(),
-                    )),
-                },
-                EmbeddingPattern::Linear => Err(PngerError::InvalidFormat(
-                    "Linear pattern expected but random pattern found".to_string(),
-                )),
-            }
+    /// Set password for random pattern seed derivation, rejecting linear
+    /// configurations.
+    ///
+    /// Same as [`with_password`](Self::with_password), except that a linear
+    /// configuration, which a password cannot protect, is an error instead of
+    /// being silently left unprotected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// assert!(LSBConfig::random().try_with_password("secret".to_string()).is_ok());
+    /// assert!(LSBConfig::linear().try_with_password("secret".to_string()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: The configuration uses a linear
+    ///   pattern
+    pub fn try_with_password(self, password: String) -> Result<Self, PngerError> {
+        if matches!(self.pattern, EmbeddingPattern::Linear) {
+            return Err(PngerError::UnsupportedMode(
+                "A password requires a random pattern; linear embedding is not protected"
+                    .to_string(),
+            ));
         }
+        Ok(self.with_password(password))
     }
-}
 
-/// High-level LSB steganography operations.
-///
-/// This struct provides the main API for embedding and extracting payloads
-/// using LSB steganography. It encapsulates the complexity of header management,
-/// pattern selection, and cryptographic operations.
-///
-/// # Design Philosophy
-///
-/// The `LSBEmbedder` uses a stateless design where all configuration is provided
-/// through the `LSBConfig` parameter. This ensures thread safety and makes the
-/// API predictable and testable.
-///
-/// # Examples
-///
-/// ## Basic Embedding and Extraction
-/// ```rust
-/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
-///
-/// let mut image_data = vec![0u8; 1000]; // Your image data
-/// let payload = b"Hello, World!";
-///
-/// // Embed with linear pattern
-/// let result = LSBEmbedder::embed(&mut image_data, payload, &LSBConfig::linear());
-/// assert!(result.is_ok());
-///
-/// // Extract the payload
-/// let result = LSBEmbedder::extract(&mut image_data, &LSBConfig::linear());
-/// assert_eq!(result.unwrap().payload, payload);
-/// ```
-///
-/// ## Security-Focused Usage
-/// ```rust
-/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig, BitIndex};
-///
-/// let mut image_data = vec![0u8; 1000];
-/// let payload = b"Secret message";
-/// let password = "my_secure_password";
-///
-/// // Embed with password protection
-/// let config = LSBConfig::random().with_password(password.to_string());
-/// LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
-///
-/// // Extract with same password
-/// let config = LSBConfig::random().with_password(password.to_string());
-/// let result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
-/// assert_eq!(result.payload, payload);
-/// ```
-pub struct LSBEmbedder;
+    /// Set manual 32-byte seed for random pattern.
+    ///
+    /// Provides direct control over the pseudorandom seed used for
+    /// embedding pattern generation. This is for advanced use cases
+    /// where you need deterministic behavior or integration with
+    /// existing key management systems.
+    ///
+    /// **Note:** Only works with random patterns. Calling this on a linear
+    /// configuration has no effect.
+    ///
+    /// # Parameters
+    /// - `seed`: Exactly 32 bytes of seed data
+    ///
+    /// # Security Considerations
+    /// - **Seed quality is critical** - use cryptographically secure randomness
+    /// - Poor quality seeds reduce security significantly
+    /// - Same seed always produces same embedding pattern
+    /// - Seed must be stored/transmitted securely
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// // Generate a secure seed (example - use proper CSPRNG in practice)
+    /// let seed = [42u8; 32]; // Don't use this in production!
+    ///
+    /// let config = LSBConfig::random().with_seed(seed);
+    /// ```
+    ///
+    /// # Use Cases
+    /// - Testing with reproducible results
+    /// - Integration with existing key management
+    /// - Custom seed derivation schemes
+    /// - Compliance with specific randomness requirements
+    pub fn with_seed(mut self, seed: [u8; SEED_SIZE]) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.seed_source = SeedSource::Manual(seed);
+        }
+        self
+    }
 
-/// Result of a successful embedding operation.
-///
-/// Contains metadata about the embedding operation that can be useful
-/// for analysis, optimization, or user feedback.
-///
-/// # Fields
-///
-/// - `bytes_used`: Total bytes of image data modified (header + payload)
-/// - `header_size`: Bytes used for the steganography header
-/// - `seed_embedded`: Whether the random seed was stored in the image
-///
-/// # Examples
-///
-/// ```rust
-/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
-///
-/// let mut image = vec![0u8; 1000];
-/// let result = LSBEmbedder::embed(&mut image, b"test", &LSBConfig::linear()).unwrap();
-///
-/// println!("Embedded payload using {} bytes", result.bytes_used);
-/// println!("Header size: {} bytes", result.header_size);
-/// println!("Seed embedded: {}", result.seed_embedded);
-/// ```
-#[derive(Debug, Clone)]
-pub struct EmbedResult {
-    /// Total number of image bytes modified during embedding.
+    /// Use the publicly known [`TEST_SEED`] for random pattern.
     ///
-    /// This includes both header bytes and payload bytes. Each bit of
-    /// payload data requires modifying one bit in the image, so a 100-byte
-    /// payload requires modifying 800 image bytes (plus header overhead).
-    pub bytes_used: usize,
+    /// Only available with the `testing` feature. Prefer this over
+    /// `with_seed([42u8; 32])` in tests so that the weak seed cannot be
+    /// shipped by accident. Has no effect for linear patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "testing")]
+    /// # {
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::random().with_test_seed();
+    /// let mut image = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut image, b"test", &config).unwrap();
+    /// assert_eq!(LSBEmbedder::extract(&mut image, &config).unwrap().payload, b"test");
+    /// # }
+    /// ```
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_test_seed(mut self) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.seed_source = SeedSource::TestOnly;
+        }
+        self
+    }
 
-    /// Number of bytes used for the steganography header.
+    /// Randomize the target bits of the carrier bytes left unused.
     ///
-    /// The header contains metadata needed for extraction including:
-    /// - Payload size
-    /// - Embedding pattern flags
-    /// - Random seed (if auto-generated)
-    /// - Checksum for integrity verification
-    pub header_size: usize,
+    /// A small payload only changes part of the bit plane, leaving the rest
+    /// at its original, often non-random, values. The boundary between both
+    /// regions is easy to find statistically. With noise enabled, every
+    /// carrier byte not holding header or payload bits gets a random target
+    /// bit, so the bit plane looks uniformly random.
+    ///
+    /// Noise is drawn from the embedding seed for random patterns, and from
+    /// system randomness for linear ones. Extraction is not affected, but the
+    /// whole bit plane is modified, which makes the embedding easier to
+    /// detect by comparison with the original image.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::random().with_password("secret".to_string()).with_lsb_noise(true);
+    /// let mut image = vec![0u8; 4000];
+    /// LSBEmbedder::embed(&mut image, b"small", &config).unwrap();
+    /// assert_eq!(LSBEmbedder::extract(&mut image, &config).unwrap().payload, b"small");
+    /// ```
+    pub fn with_lsb_noise(mut self, enabled: bool) -> Self {
+        self.lsb_noise = enabled;
+        self
+    }
 
-    /// Whether the random seed was embedded in the image header.
+    /// XOR the whole target bit plane with a keystream derived from the seed.
     ///
-    /// - `true`: Auto-generated seed stored in image (`SeedSource::Auto`)
-    /// - `false`: Password or manual seed used (no seed storage needed)
-    pub seed_embedded: bool,
-}
+    /// Unlike payload obfuscation, which only transforms the payload bytes,
+    /// chaff covers every carrier byte, header included, after embedding. The
+    /// bit plane then looks uniformly random and the header magic cannot be
+    /// searched for without the seed. Extraction removes the keystream again
+    /// before reading anything.
+    ///
+    /// The seed must be known at extraction, so chaff requires a random
+    /// pattern with a password or manual seed, and a single bit index: auto
+    /// seeds are stored in the masked header, and per-channel bit indices are
+    /// only recorded there. As with [`with_lsb_noise`](Self::with_lsb_noise),
+    /// the whole bit plane is modified.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::random().with_seed([9; 32]).with_chaff(true);
+    /// let mut image = vec![0u8; 4000];
+    /// LSBEmbedder::embed(&mut image, b"masked", &config).unwrap();
+    /// assert_eq!(LSBEmbedder::extract(&mut image, &config).unwrap().payload, b"masked");
+    /// ```
+    pub fn with_chaff(mut self, enabled: bool) -> Self {
+        self.chaff = enabled;
+        self
+    }
 
-/// Result of a successful extraction operation.
-///
-/// Contains the extracted payload and metadata about the extraction
-/// process that can be useful for verification or analysis.
-///
-/// # Fields
-///
-/// - `payload`: The extracted payload data
-/// - `header_size`: Size of the steganography header that was read
-/// - `seed_was_embedded`: Whether the image contained an embedded seed
-///
-/// # Examples
-///
-/// ```rust
-/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
-///
-/// let mut image = vec![0u8; 1000];
-///
-/// // First embed a payload
-/// LSBEmbedder::embed(&mut image, b"test payload", &LSBConfig::linear()).unwrap();
-///
-/// // Then extract it
-/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
-/// println!("Extracted {} bytes", result.payload.len());
-/// println!("Header was {} bytes", result.header_size);
-/// println!("Had embedded seed: {}", result.seed_was_embedded);
-/// ```
-#[derive(Debug, Clone)]
-pub struct ExtractResult {
-    /// The extracted payload data.
+    /// Keep payload bits out of near-saturated carrier bytes.
+    ///
+    /// Flipping bits of pure black or pure white areas can be visible on
+    /// some displays, and is a known steganalysis target. When enabled, body
+    /// carrier bytes whose bits other than the target one are all 0 or all 1
+    /// (0 and 1, or 254 and 255, for the LSB) are skipped. Embedding only
+    /// flips the target bit, so extraction finds the same bytes excluded.
+    /// The setting is recorded in the header, which itself stays at its
+    /// usual positions.
+    ///
+    /// Capacity drops by the share of saturated bytes in the body region: a
+    /// cover that is 30% pure black holds about 30% less. [`max_capacity`](Self::max_capacity)
+    /// does not see the image and ignores the exclusion, and
+    /// [`LSBEmbedder::planned_indices`] fails with this option for the same
+    /// reason.
     ///
-    /// This is the original data that was embedded in the image,
-    /// recovered bit-by-bit from the modified pixels.
-    pub payload: Vec<u8>,
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::random().with_seed([5; 32]).with_skip_saturated(true);
+    /// let mut image: Vec<u8> = (0..4000).map(|i| if i % 3 == 0 { 255 } else { 128 }).collect();
+    /// LSBEmbedder::embed(&mut image, b"mid-range only", &config).unwrap();
+    /// assert!(image.iter().step_by(3).skip(200).all(|&byte| byte == 255));
+    /// assert_eq!(LSBEmbedder::extract(&mut image, &config).unwrap().payload, b"mid-range only");
+    /// ```
+    pub fn with_skip_saturated(mut self, enabled: bool) -> Self {
+        self.skip_saturated = enabled;
+        self
+    }
+
+    /// Embed only into the even, or only into the odd, carrier bytes.
+    ///
+    /// Header and body are laid out over every other carrier byte, as if
+    /// the carrier were the bytes of `parity` alone; the others are never
+    /// modified, noise and chaff included. Capacity is halved accordingly,
+    /// and [`max_capacity`](Self::max_capacity) accounts for it. The parity
+    /// is recorded in a header extension, but extraction must use the same
+    /// parity to find the header in the first place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, Parity};
+    ///
+    /// let config = LSBConfig::linear().with_parity(Parity::Odd);
+    /// let mut image = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut image, b"odd only", &config).unwrap();
+    /// assert!(image.iter().step_by(2).all(|&byte| byte == 0));
+    /// assert_eq!(LSBEmbedder::extract(&mut image, &config).unwrap().payload, b"odd only");
+    /// ```
+    pub fn with_parity(mut self, parity: Parity) -> Self {
+        self.parity = Some(parity);
+        self
+    }
+
+    /// Spread the bits of each body byte `stride` carrier bytes apart in
+    /// linear patterns.
+    ///
+    /// A linear body otherwise writes each byte into 8 consecutive carrier
+    /// bytes, a localized signature. With a stride, the body visits every
+    /// `stride`-th candidate byte first, then every `stride`-th byte from the
+    /// next one, and so on: consecutive bits land `stride` bytes apart, at no
+    /// cost beyond reordering the candidates. The header stays linear and
+    /// records the stride, so extraction needs no configuration. Random
+    /// patterns already spread the body and ignore the stride. A stride of 1
+    /// leaves the body unchanged; 0 is rejected when embedding.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::linear().with_diffusion_stride(7);
+    /// LSBEmbedder::embed(&mut image, b"spread out", &config).unwrap();
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"spread out");
+    /// ```
+    pub fn with_diffusion_stride(mut self, stride: u32) -> Self {
+        self.diffusion_stride = Some(stride);
+        self
+    }
+
+    /// Store a hash of the payload in the header.
+    ///
+    /// The 8-byte CRC-64 of the payload, as passed to
+    /// [`LSBEmbedder::embed`], is recorded in a header extension, so
+    /// [`LSBEmbedder::holds_payload`] can tell whether an image already
+    /// carries a given payload from its header alone. The hash guards against
+    /// accidental re-embedding in retry-prone pipelines, see
+    /// [`embed_if_changed`](crate::embed_if_changed); it is not a
+    /// cryptographic digest and does not protect against tampering.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::linear().with_payload_hash(true);
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+    /// assert!(LSBEmbedder::holds_payload(&mut image, &config, b"payload"));
+    /// assert!(!LSBEmbedder::holds_payload(&mut image, &config, b"other"));
+    /// ```
+    pub fn with_payload_hash(mut self, enabled: bool) -> Self {
+        self.payload_hash = enabled;
+        self
+    }
+
+    /// Refuse to embed over an existing payload.
+    ///
+    /// Embedding twice at the same bit index silently overwrites the first
+    /// payload. When enabled, the target bit plane is checked for a valid
+    /// header first, and the embedding fails without modifying anything if
+    /// one is found. Detection uses this configuration, so headers from
+    /// another namespace, or scattered with another seed, go unnoticed; see
+    /// [`LSBEmbedder::occupied_bit_indices`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::linear().fail_if_occupied(true);
+    /// LSBEmbedder::embed(&mut image, b"first", &config).unwrap();
+    /// assert!(LSBEmbedder::embed(&mut image, b"second", &config).is_err());
+    /// ```
+    pub fn fail_if_occupied(mut self, enabled: bool) -> Self {
+        self.fail_if_occupied = enabled;
+        self
+    }
+
+    /// Embed into the palette of indexed PNG images.
+    ///
+    /// Indexed images are rejected by default, since changing the low bits
+    /// of their pixel indices would point them to unrelated colors. In
+    /// palette mode, the LSBs of the palette RGB entries carry the payload
+    /// instead: the indices, and so the image structure, stay intact while
+    /// each color shifts subtly. Only indexed images are accepted, and
+    /// extraction must use palette mode as well.
+    ///
+    /// The carrier is the palette itself, so capacity is tiny: at most 768
+    /// bytes (256 RGB entries), from which the header is taken first. Use
+    /// [`max_capacity`](Self::max_capacity) with three times the palette
+    /// length to size payloads. This flag only selects the carrier of PNG
+    /// images; [`LSBEmbedder`] itself works on any buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let config = LSBConfig::linear().palette_mode();
+    /// assert!(config.is_palette_mode());
+    /// assert_eq!(config.max_capacity(256 * 3), 82);
+    /// ```
+    pub fn palette_mode(mut self) -> Self {
+        self.palette_mode = true;
+        self
+    }
+
+    /// Bits of each carrier byte an embedding may modify.
+    pub(crate) fn target_mask(&self) -> u8 {
+        match &self.channel_bits {
+            Some(channel_bits) => BitIndex::mask(channel_bits),
+            None => BitIndex::mask(&[self.bit_index]),
+        }
+    }
+
+    /// Check whether the palette of indexed images carries the payload.
+    ///
+    /// See [`palette_mode`](Self::palette_mode).
+    pub fn is_palette_mode(&self) -> bool {
+        self.palette_mode
+    }
+
+    /// Embed into both the palette and the pixel indices of indexed PNG
+    /// images.
+    ///
+    /// An advanced form of [`palette_mode`](Self::palette_mode) for larger
+    /// payloads. The carrier is the palette, followed by the indices whose
+    /// target bit can flip safely: those pointing to one of two palette
+    /// entries that differ only by the target bit and whose colors, with
+    /// that bit masked, are at most 8 apart on each channel (with the same
+    /// transparency). Flipping the bit swaps the pixel to its near-identical
+    /// partner, so colors only shift subtly. Embedding the palette never
+    /// changes which indices are safe, so extraction finds the same carrier.
+    ///
+    /// Capacity depends on the palette: images whose palette has no close
+    /// pairs hold no more than in palette mode. Only 8-bit indices are
+    /// supported. The hybrid layout is recorded in the header, and
+    /// extraction must use this mode as well.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let config = LSBConfig::random().with_password("secret".to_string()).indexed_hybrid();
+    /// assert!(config.is_palette_mode());
+    /// assert!(config.is_indexed_hybrid());
+    /// ```
+    pub fn indexed_hybrid(mut self) -> Self {
+        self.palette_mode = true;
+        self.indexed_hybrid = true;
+        self
+    }
+
+    /// Check whether the palette and the safe pixel indices of indexed images
+    /// carry the payload.
+    ///
+    /// See [`indexed_hybrid`](Self::indexed_hybrid).
+    pub fn is_indexed_hybrid(&self) -> bool {
+        self.indexed_hybrid
+    }
+
+    /// Use an externally stored seed for extraction, ignoring any seed
+    /// embedded in the header.
+    ///
+    /// Recovers payloads whose embedded seed bytes were damaged, using a seed
+    /// escrowed earlier with [`LSBEmbedder::export_seed`]. The header checksum
+    /// does not cover the seed, so the rest of the header still validates.
+    /// For embedding, this behaves like [`with_seed`](Self::with_seed).
+    ///
+    /// **Note:** Only works with random patterns. Calling this on a linear
+    /// configuration has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// LSBEmbedder::embed(&mut image, b"escrowed", &LSBConfig::random()).unwrap();
+    /// let seed = LSBEmbedder::export_seed(&mut image, &LSBConfig::random()).unwrap();
+    ///
+    /// let config = LSBConfig::random().with_external_seed(seed);
+    /// let result = LSBEmbedder::extract(&mut image, &config).unwrap();
+    /// assert_eq!(result.payload, b"escrowed");
+    /// ```
+    pub fn with_external_seed(mut self, seed: [u8; SEED_SIZE]) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.seed_source = SeedSource::Manual(seed);
+            config.seed_overrides_header = true;
+        }
+        self
+    }
+
+    /// Scatter the header over seed-derived carrier positions.
+    ///
+    /// By default the header is written linearly at the start of the carrier,
+    /// which is the most predictable region for detection. With this option,
+    /// only a small bootstrap (magic, version, flags and the auto-generated seed
+    /// if any) stays at the start; the payload size and checksum are spread over
+    /// positions derived from the seed, and the body skips them.
+    ///
+    /// **Note:** Only works with random patterns. Calling this on a linear
+    /// configuration has no effect.
+    ///
+    /// # Tradeoffs
+    /// - The bootstrap still marks the image as carrying a header
+    /// - Extraction requires the seed before the header can be validated,
+    ///   so a wrong password is reported as a corrupted header
+    /// - Needs 64 additional carrier bytes beyond the bootstrap to be available
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let config = LSBConfig::random()
+    ///     .with_password("secret".to_string())
+    ///     .with_scattered_header();
+    /// ```
+    pub fn with_scattered_header(self) -> Self {
+        self.with_header_linear(false)
+    }
+
+    /// Choose between a linear and a scattered header for random patterns.
+    ///
+    /// `true`, the default, writes the header contiguously at the start of
+    /// the carrier while the body stays scattered. `false` is equivalent to
+    /// [`with_scattered_header`](Self::with_scattered_header). Has no effect
+    /// for linear patterns, whose header is always linear.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let config = LSBConfig::random().with_header_linear(false);
+    /// assert!(!config.is_header_linear());
+    /// assert!(LSBConfig::random().is_header_linear());
+    /// ```
+    pub fn with_header_linear(mut self, header_linear: bool) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.header_linear = header_linear;
+        }
+        self
+    }
+
+    /// Check whether the header is written linearly at the start of the
+    /// carrier.
+    ///
+    /// Always `true` for linear patterns.
+    pub fn is_header_linear(&self) -> bool {
+        match &self.pattern {
+            EmbeddingPattern::Linear => true,
+            EmbeddingPattern::Random(config) => config.header_linear,
+        }
+    }
+
+    /// Bind the body permutation of random patterns to the payload length.
+    ///
+    /// Payloads embedded with the same password (or manual seed) share their
+    /// body permutation, so comparing two images reveals the positions both
+    /// payloads use. When enabled, the body is shuffled with a seed derived
+    /// from the pattern seed and the payload length, giving payloads of
+    /// different sizes unrelated positions. The header, read before the
+    /// length is known, keeps the pattern seed.
+    ///
+    /// The choice is recorded in the header, so extraction does not need it.
+    /// Has no effect for linear patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::random()
+    ///     .with_password("secret".to_string())
+    ///     .with_length_bound_seed(true);
+    /// let mut image = vec![0u8; 2000];
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+    ///
+    /// let extract_config = LSBConfig::random().with_password("secret".to_string());
+    /// let result = LSBEmbedder::extract(&mut image, &extract_config).unwrap();
+    /// assert_eq!(result.payload, b"payload");
+    /// ```
+    pub fn with_length_bound_seed(mut self, enabled: bool) -> Self {
+        if let EmbeddingPattern::Random(ref mut config) = self.pattern {
+            config.length_bound_seed = enabled;
+        }
+        self
+    }
+
+    /// Conditionally set password if provided (CLI helper).
+    ///
+    /// Convenience method for CLI applications where password might be
+    /// optional. Only applies the password if `Some(password)` is provided.
+    ///
+    /// # Parameters
+    /// - `password`: Optional password string
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let user_password: Option<String> = Some("secret".to_string());
+    /// let config = LSBConfig::random().with_password_if_some(user_password);
+    ///
+    /// let no_password: Option<String> = None;
+    /// let config = LSBConfig::random().with_password_if_some(no_password);
+    /// // This config will still use auto-generated seed
+    /// ```
+    pub fn with_password_if_some(self, password: Option<String>) -> Self {
+        match password {
+            Some(pwd) => self.with_password(pwd),
+            None => self,
+        }
+    }
+
+    /// Conditionally set seed if provided (CLI helper).
+    ///
+    /// Convenience method for CLI applications where seed might be
+    /// optional. Only applies the seed if `Some(seed)` is provided.
+    ///
+    /// # Parameters
+    /// - `seed`: Optional 32-byte seed array
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let user_seed: Option<[u8; 32]> = Some([1u8; 32]);
+    /// let config = LSBConfig::random().with_seed_if_some(user_seed);
+    ///
+    /// let no_seed: Option<[u8; 32]> = None;
+    /// let config = LSBConfig::random().with_seed_if_some(no_seed);
+    /// // This config will still use auto-generated seed
+    /// ```
+    pub fn with_seed_if_some(self, seed: Option<[u8; SEED_SIZE]>) -> Self {
+        match seed {
+            Some(seed) => self.with_seed(seed),
+            None => self,
+        }
+    }
+
+    /// Get the configured bit index.
+    ///
+    /// Returns the `BitIndex` that will be modified during embedding operations.
+    ///
+    /// # Returns
+    /// `BitIndex` variant representing the target bit position.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, BitIndex};
+    ///
+    /// let config = LSBConfig::linear().with_bit_index(BitIndex::Bit2);
+    /// assert_eq!(config.bit_index(), BitIndex::Bit2);
+    ///
+    /// // Using the LSB alias
+    /// let config = LSBConfig::linear().with_bit_index(BitIndex::LSB);
+    /// assert_eq!(config.bit_index(), BitIndex::Bit0);
+    /// ```
+    pub fn bit_index(&self) -> BitIndex {
+        self.bit_index
+    }
+
+    /// Get the configured redundancy factor.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// assert_eq!(LSBConfig::linear().redundancy(), 1);
+    /// assert_eq!(LSBConfig::linear().with_redundancy(3).redundancy(), 3);
+    /// ```
+    pub fn redundancy(&self) -> u8 {
+        self.redundancy
+    }
+
+    /// Get the configured header magic.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{DEFAULT_MAGIC, LSBConfig};
+    ///
+    /// assert_eq!(LSBConfig::linear().namespace(), DEFAULT_MAGIC);
+    /// assert_eq!(LSBConfig::linear().with_namespace(*b"ACME").namespace(), *b"ACME");
+    /// ```
+    pub fn namespace(&self) -> [u8; MAGIC_SIZE] {
+        self.magic
+    }
+
+    /// Number of carrier bytes taken by the header.
+    ///
+    /// Each header byte is stored in the target bit of 8 carrier bytes, so
+    /// this is 8 times the header size: [`FIXED_HEADER_SIZE`] plus the
+    /// optional fields the configuration enables (such as the [`SEED_SIZE`]
+    /// bytes of an auto-generated seed).
+    ///
+    /// Payload metadata, if any, is stored in the body and takes capacity in
+    /// addition to this overhead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{FIXED_HEADER_SIZE, LSBConfig, SEED_SIZE};
+    ///
+    /// assert_eq!(LSBConfig::linear().header_overhead(), FIXED_HEADER_SIZE * 8);
+    /// assert_eq!(
+    ///     LSBConfig::random().header_overhead(),
+    ///     (FIXED_HEADER_SIZE + SEED_SIZE) * 8
+    /// );
+    /// ```
+    pub fn header_overhead(&self) -> usize {
+        header::header_size_for_config(self) * 8
+    }
+
+    /// Largest payload, in bytes, that fits in `carrier_len` carrier bytes.
+    ///
+    /// This is the exact capacity: a payload of this size embeds, one more
+    /// byte fails with [`PngerError::PayloadTooLarge`]. After the
+    /// [`header_overhead`](Self::header_overhead), which includes the header
+    /// extensions recording non-default settings, each payload byte needs 8
+    /// carrier bytes per copy, and the metadata, if any, is stored in every
+    /// copy. With a [`parity`](Self::with_parity), only the carrier bytes of
+    /// that parity count. Since payloads are whole bytes, up to
+    /// `8 * copies - 1` trailing carrier bytes may be left unused; they are
+    /// never modified.
+    ///
+    /// With [`with_skip_saturated`](Self::with_skip_saturated), this is only an
+    /// upper bound: the saturated body bytes it excludes depend on the cover,
+    /// which this method does not see.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let config = LSBConfig::linear();
+    /// let carrier_len = config.header_overhead() + 8 * 100 + 7;
+    /// assert_eq!(config.max_capacity(carrier_len), 100);
+    ///
+    /// let mut image = vec![0u8; carrier_len];
+    /// assert!(LSBEmbedder::embed(&mut image, &[0xAB; 100], &config).is_ok());
+    /// assert!(LSBEmbedder::embed(&mut image, &[0xAB; 101], &config).is_err());
+    /// ```
+    pub fn max_capacity(&self, carrier_len: usize) -> usize {
+        let metadata_len = self
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.to_bytes().ok())
+            .map_or(0, |bytes| bytes.len());
+        let carrier_len = self
+            .parity
+            .map_or(carrier_len, |parity| parity.carrier_len(carrier_len));
+        let body_bytes = carrier_len.saturating_sub(self.header_overhead()) / 8;
+        (body_bytes / usize::from(self.redundancy.max(1))).saturating_sub(metadata_len)
+    }
+
+    /// Get the configured header byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, WireFormat};
+    ///
+    /// assert_eq!(LSBConfig::linear().wire_format(), WireFormat::BigEndian);
+    /// ```
+    pub fn wire_format(&self) -> WireFormat {
+        self.wire_format
+    }
+
+    /// Get the configured payload bit order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{BitOrder, LSBConfig};
+    ///
+    /// assert_eq!(LSBConfig::linear().bit_order(), BitOrder::LsbFirst);
+    /// ```
+    pub fn bit_order(&self) -> BitOrder {
+        self.bit_order
+    }
+
+    /// Get the configured body write mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, WriteMode};
+    ///
+    /// assert_eq!(LSBConfig::linear().write_mode(), WriteMode::Overwrite);
+    /// ```
+    pub fn write_mode(&self) -> WriteMode {
+        self.write_mode
+    }
+
+    /// Get the checksum protecting the header.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{HeaderChecksum, LSBConfig};
+    ///
+    /// assert_eq!(LSBConfig::linear().header_checksum(), HeaderChecksum::Crc32);
+    /// ```
+    pub fn header_checksum(&self) -> HeaderChecksum {
+        self.header_checksum
+    }
+
+    /// Get a reference to the embedding pattern configuration.
+    ///
+    /// Returns the pattern type (Linear or Random) along with its
+    /// associated configuration options.
+    ///
+    /// # Returns
+    /// Reference to the `EmbeddingPattern` enum variant.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, EmbeddingPattern};
+    ///
+    /// let config = LSBConfig::linear();
+    /// match config.pattern() {
+    ///     EmbeddingPattern::Linear => println!("Using linear pattern"),
+    ///     EmbeddingPattern::Random(_) => println!("Using random pattern"),
+    /// }
+    /// ```
+    pub fn pattern(&self) -> &EmbeddingPattern {
+        &self.pattern
+    }
+
+    /// Check that extraction with this configuration can succeed at all.
+    ///
+    /// Catches combinations no image can satisfy, before decoding anything:
+    /// - Chaff without a password or manual seed, or with channel bit
+    ///   indices: the chaff keystream must be derived before the header is
+    ///   read, so an auto-generated seed cannot be used
+    /// - A password whose key derivation exceeds the
+    ///   [`with_max_kdf_memory`](Self::with_max_kdf_memory) limit
+    /// - Channel bit indices outside 1 to 4 entries
+    ///
+    /// Manual seeds are 32-byte arrays, so their length needs no check.
+    /// Settings that depend on the image, such as an auto-generated seed
+    /// the header turns out not to embed, are only detected by extraction.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// assert!(LSBConfig::random().with_password("secret".to_string()).validate_for_extraction().is_ok());
+    /// assert!(LSBConfig::random().with_chaff(true).validate_for_extraction().is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Chaff cannot be removed, or invalid
+    ///   channel bit indices
+    /// - `PngerError::CryptoError`: The key derivation memory limit is below
+    ///   the Argon2 memory cost
+    pub fn validate_for_extraction(&self) -> Result<(), PngerError> {
+        check_channel_bits(self.channel_bits.as_deref())?;
+
+        let seed_source = match &self.pattern {
+            EmbeddingPattern::Random(random_config) => Some(&random_config.seed_source),
+            EmbeddingPattern::Linear => None,
+        };
+        if self.chaff
+            && (self.channel_bits.is_some() || matches!(seed_source, None | Some(SeedSource::Auto)))
+        {
+            return Err(chaff_unsupported());
+        }
+
+        if let (Some(SeedSource::Password(_)), Some(max_mib)) = (seed_source, self.max_kdf_memory) {
+            if u64::from(crypto::KDF_MEMORY_KIB) > u64::from(max_mib) * 1024 {
+                return Err(PngerError::CryptoError(
+                    crypto::CryptoError::KdfMemoryLimit {
+                        requested_kib: crypto::KDF_MEMORY_KIB,
+                        max_mib,
+                    }
+                    .to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the pattern seed when it is known before embedding, i.e. for
+    /// random patterns with a password or manual seed.
+    pub(crate) fn known_seed(&self) -> Result<Option<[u8; SEED_SIZE]>, PngerError> {
+        let EmbeddingPattern::Random(random_config) = &self.pattern else {
+            return Ok(None);
+        };
+        match &random_config.seed_source {
+            SeedSource::Auto => Ok(None),
+            SeedSource::Password(password) => {
+                crypto::CryptoContext::derive_seed_from_password(password, self.max_kdf_memory)
+                    .map(Some)
+                    .map_err(|e| PngerError::CryptoError(e.to_string()))
+            }
+            SeedSource::Manual(seed) => Ok(Some(*seed)),
+            #[cfg(any(test, feature = "testing"))]
+            SeedSource::TestOnly => Ok(Some(SeedSource::test_seed())),
+        }
+    }
+
+    /// Differences with `extract` that prevent extracting a payload embedded
+    /// with `self`, as human-readable sentences.
+    ///
+    /// Settings recorded in the header (redundancy, channel bits, bit order)
+    /// and the pattern of payloads whose seed is embedded are not compared,
+    /// since extraction picks them up from the image.
+    pub(crate) fn extraction_mismatches(&self, extract: &LSBConfig) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        if self.bit_index != extract.bit_index {
+            mismatches.push(format!(
+                "bit index: embedded in bit {}, extracting from bit {}",
+                self.bit_index.position(),
+                extract.bit_index.position()
+            ));
+        }
+        if self.magic != extract.magic {
+            mismatches.push("namespace: the header magics differ".to_string());
+        }
+        if self.is_header_linear() != extract.is_header_linear() {
+            let placement = |linear| if linear { "linear" } else { "scattered" };
+            mismatches.push(format!(
+                "header: embedded {}, extracting {}",
+                placement(self.is_header_linear()),
+                placement(extract.is_header_linear())
+            ));
+        }
+        if self.indexed_hybrid != extract.indexed_hybrid {
+            mismatches.push(format!(
+                "indexed hybrid mode: {} when embedding, {} when extracting",
+                on_off(self.indexed_hybrid),
+                on_off(extract.indexed_hybrid)
+            ));
+        }
+        if self.palette_mode != extract.palette_mode {
+            mismatches.push(format!(
+                "palette mode: {} when embedding, {} when extracting",
+                on_off(self.palette_mode),
+                on_off(extract.palette_mode)
+            ));
+        }
+        if self.chaff != extract.chaff {
+            mismatches.push(format!(
+                "chaff: {} when embedding, {} when extracting",
+                on_off(self.chaff),
+                on_off(extract.chaff)
+            ));
+        }
+
+        // Only seeds known to the user must be provided again
+        let EmbeddingPattern::Random(embed_random) = &self.pattern else {
+            return mismatches;
+        };
+        let EmbeddingPattern::Random(extract_random) = &extract.pattern else {
+            if !matches!(embed_random.seed_source, SeedSource::Auto) {
+                mismatches.push(format!(
+                    "pattern: embedded with a random pattern ({}), extracting with a linear pattern",
+                    embed_random.seed_source.describe()
+                ));
+            }
+            return mismatches;
+        };
+        match (&embed_random.seed_source, &extract_random.seed_source) {
+            (SeedSource::Auto, _) => {}
+            (SeedSource::Password(embed), SeedSource::Password(extract)) => {
+                if embed != extract {
+                    mismatches.push("password: the passwords differ".to_string());
+                }
+            }
+            (SeedSource::Manual(embed), SeedSource::Manual(extract)) => {
+                if embed != extract {
+                    mismatches.push("seed: the manual seeds differ".to_string());
+                }
+            }
+            #[cfg(any(test, feature = "testing"))]
+            (SeedSource::TestOnly, SeedSource::TestOnly) => {}
+            (embed, extract) => mismatches.push(format!(
+                "seed source: embedded with {}, extracting with {}",
+                embed.describe(),
+                extract.describe()
+            )),
+        }
+        mismatches
+    }
+}
+
+const fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
+
+impl SeedSource {
+    // Kind of seed source, without the secret it holds
+    const fn describe(&self) -> &'static str {
+        match self {
+            SeedSource::Auto => "an embedded seed",
+            SeedSource::Password(_) => "a password",
+            SeedSource::Manual(_) => "a manual seed",
+            #[cfg(any(test, feature = "testing"))]
+            SeedSource::TestOnly => "the test seed",
+        }
+    }
+}
+
+impl Default for LSBConfig {
+    /// Creates a default LSB configuration using random pattern.
+    ///
+    /// The default provides a good balance of security and convenience:
+    /// - Random embedding pattern for better security
+    /// - Auto-generated seed (embedded in image header)
+    /// - Bit index 0 (least significant bit)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBConfig;
+    ///
+    /// let config = LSBConfig::default();
+    /// // Equivalent to:
+    /// let config = LSBConfig::random();
+    /// ```
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+// Internal runtime configuration for optimized implementation
+#[derive(Debug, Clone)]
+pub(crate) struct RuntimeConfig {
+    bit_index: BitIndex,
+    pattern: RuntimePattern,
+    redundancy: u8,
+    magic: [u8; MAGIC_SIZE],
+    metadata: Option<Vec<u8>>,
+    wire_format: WireFormat,
+    channel_bits: Option<Vec<BitIndex>>,
+    lsb_noise: bool,
+    chaff: Option<[u8; SEED_SIZE]>,
+    skip_saturated: bool,
+    length_bound_seed: bool,
+    bit_order: BitOrder,
+    write_mode: WriteMode,
+    diffusion_stride: Option<u32>,
+    extensions: Vec<header::HeaderExtension>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum RuntimePattern {
+    Linear,
+    Random {
+        seed: [u8; SEED_SIZE],
+        embed_seed: bool,
+        scatter_header: bool,
+    },
+}
+
+impl RuntimeConfig {
+    /// Convert from user-facing `LSBConfig` to internal `RuntimeConfig`
+    fn from_config(config: &LSBConfig) -> Result<Self, PngerError> {
+        let pattern = match &config.pattern {
+            EmbeddingPattern::Linear => RuntimePattern::Linear,
+            EmbeddingPattern::Random(random_config) => {
+                let (seed, embed_seed) = match &random_config.seed_source {
+                    SeedSource::Auto => {
+                        let seed = crypto::CryptoContext::generate_random_seed()
+                            .map_err(|e| PngerError::CryptoError(e.to_string()))?;
+                        (seed, true)
+                    }
+                    SeedSource::Password(password) => {
+                        let seed = crypto::CryptoContext::derive_seed_from_password(
+                            password,
+                            config.max_kdf_memory,
+                        )
+                        .map_err(|e| PngerError::CryptoError(e.to_string()))?;
+                        (seed, false)
+                    }
+                    SeedSource::Manual(seed) => (*seed, false),
+                    #[cfg(any(test, feature = "testing"))]
+                    SeedSource::TestOnly => (SeedSource::test_seed(), false),
+                };
+
+                RuntimePattern::Random {
+                    seed,
+                    embed_seed,
+                    scatter_header: !random_config.header_linear,
+                }
+            }
+        };
+
+        check_channel_bits(config.channel_bits.as_deref())?;
+
+        if config.diffusion_stride == Some(0) {
+            return Err(PngerError::InvalidFormat(
+                "The diffusion stride must be at least 1".to_string(),
+            ));
+        }
+        // Random patterns already spread the body
+        let diffusion_stride = match &pattern {
+            RuntimePattern::Linear => config.diffusion_stride,
+            RuntimePattern::Random { .. } => None,
+        };
+
+        let chaff = if config.chaff {
+            match &pattern {
+                RuntimePattern::Random {
+                    seed,
+                    embed_seed: false,
+                    ..
+                } if config.channel_bits.is_none() => Some(*seed),
+                _ => return Err(chaff_unsupported()),
+            }
+        } else {
+            None
+        };
+
+        // Only password configs use the seed, for their verifier
+        let extensions = match &pattern {
+            RuntimePattern::Random { seed, .. } => header::config_extensions(config, Some(seed)),
+            RuntimePattern::Linear => header::config_extensions(config, None),
+        };
+
+        Ok(RuntimeConfig {
+            bit_index: config.bit_index,
+            pattern,
+            redundancy: config.redundancy,
+            magic: config.magic,
+            metadata: config
+                .metadata
+                .as_ref()
+                .map(PayloadMetadata::to_bytes)
+                .transpose()?,
+            wire_format: config.wire_format,
+            channel_bits: config.channel_bits.clone(),
+            lsb_noise: config.lsb_noise,
+            chaff,
+            skip_saturated: config.skip_saturated,
+            length_bound_seed: matches!(
+                &config.pattern,
+                EmbeddingPattern::Random(RandomConfig {
+                    length_bound_seed: true,
+                    ..
+                })
+            ),
+            bit_order: config.bit_order,
+            write_mode: config.write_mode,
+            diffusion_stride,
+            extensions,
+        })
+    }
+}
+
+impl RuntimePattern {
+    /// Pattern ordering the body of a `payload_len`-byte payload, see
+    /// [`LSBConfig::with_length_bound_seed`]: random patterns switch to a
+    /// seed derived from their own seed and the length when `bound`.
+    fn for_body(&self, payload_len: PayloadSize, bound: bool) -> Self {
+        match self {
+            RuntimePattern::Random {
+                seed,
+                embed_seed,
+                scatter_header,
+            } if bound => {
+                use rand::RngCore;
+
+                // Each length reads its own 32-byte block of the stream
+                let mut rng = rand_chacha::ChaCha20Rng::from_seed(*seed);
+                rng.set_stream(LENGTH_SEED_STREAM);
+                rng.set_word_pos(u128::from(payload_len) * (SEED_SIZE / 4) as u128);
+                let mut body_seed = [0; SEED_SIZE];
+                rng.fill_bytes(&mut body_seed);
+                RuntimePattern::Random {
+                    seed: body_seed,
+                    embed_seed: *embed_seed,
+                    scatter_header: *scatter_header,
+                }
+            }
+            pattern => pattern.clone(),
+        }
+    }
+
+    /// Creates a `RuntimePattern` by analyzing the image header and user config.
+    fn from_header_and_config(
+        header: &header::CompleteHeader,
+        config: &LSBConfig,
+    ) -> Result<Self, PngerError> {
+        Self::from_flags_and_config(header.fixed.flags, header.seed, config)
+    }
+
+    /// Creates a `RuntimePattern` from header flags, the embedded seed (if any)
+    /// and user config.
+    fn from_flags_and_config(
+        flags: header::HeaderFlags,
+        embedded_seed: Option<[u8; SEED_SIZE]>,
+        config: &LSBConfig,
+    ) -> Result<Self, PngerError> {
+        if flags.contains(header::HeaderFlags::RANDOM_PATTERN) {
+            // It's a random pattern, so we just need to get the seed.
+            let seed = Self::reconstruct_seed(flags, embedded_seed, config)?;
+
+            Ok(RuntimePattern::Random {
+                seed,
+                embed_seed: flags.contains(header::HeaderFlags::SEED_EMBEDDED),
+                scatter_header: flags.contains(header::HeaderFlags::SCATTERED_HEADER),
+            })
+        } else {
+            // It's a linear pattern.
+            Ok(RuntimePattern::Linear)
+        }
+    }
+
+    /// Reconstructs the seed from the header or user config.
+    fn reconstruct_seed(
+        flags: header::HeaderFlags,
+        embedded_seed: Option<[u8; SEED_SIZE]>,
+        config: &LSBConfig,
+    ) -> Result<[u8; SEED_SIZE], PngerError> {
+        if let EmbeddingPattern::Random(RandomConfig {
+            seed_source: SeedSource::Manual(seed),
+            seed_overrides_header: true,
+            ..
+        }) = &config.pattern
+        {
+            // External seed takes precedence over the embedded one
+            Ok(*seed)
+        } else if flags.contains(header::HeaderFlags::SEED_EMBEDDED) {
+            // Use embedded seed
+            embedded_seed.ok_or_else(|| {
+                PngerError::InvalidFormat("Seed embedded flag set but no seed data".to_string())
+            })
+        } else {
+            // Use user-provided seed source
+            match &config.pattern {
+                EmbeddingPattern::Random(random_config) => match &random_config.seed_source {
+                    SeedSource::Password(password) => {
+                        crypto::CryptoContext::derive_seed_from_password(
+                            password,
+                            config.max_kdf_memory,
+                        )
+                        .map_err(|e| PngerError::CryptoError(e.to_string()))
+                    }
+                    SeedSource::Manual(seed) => Ok(*seed),
+                    #[cfg(any(test, feature = "testing"))]
+                    SeedSource::TestOnly => Ok(SeedSource::test_seed()),
+                    SeedSource::Auto => Err(PngerError::InvalidFormat(
+                        "Auto seed source but no seed embedded".to_string(),
+                    )),
+                },
+                EmbeddingPattern::Linear => Err(PngerError::InvalidFormat(
+                    "Linear pattern expected but random pattern found".to_string(),
+                )),
+            }
+        }
+    }
+}
+
+/// High-level LSB steganography operations.
+///
+/// This struct provides the main API for embedding and extracting payloads
+/// using LSB steganography. It encapsulates the complexity of header management,
+/// pattern selection, and cryptographic operations.
+///
+/// # Design Philosophy
+///
+/// The `LSBEmbedder` uses a stateless design where all configuration is provided
+/// through the `LSBConfig` parameter. This ensures thread safety and makes the
+/// API predictable and testable.
+///
+/// # Examples
+///
+/// ## Basic Embedding and Extraction
+/// ```rust
+/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+///
+/// let mut image_data = vec![0u8; 1000]; // Your image data
+/// let payload = b"Hello, World!";
+///
+/// // Embed with linear pattern
+/// let result = LSBEmbedder::embed(&mut image_data, payload, &LSBConfig::linear());
+/// assert!(result.is_ok());
+///
+/// // Extract the payload
+/// let result = LSBEmbedder::extract(&mut image_data, &LSBConfig::linear());
+/// assert_eq!(result.unwrap().payload, payload);
+/// ```
+///
+/// ## Security-Focused Usage
+/// ```rust
+/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig, BitIndex};
+///
+/// let mut image_data = vec![0u8; 1000];
+/// let payload = b"Secret message";
+/// let password = "my_secure_password";
+///
+/// // Embed with password protection
+/// let config = LSBConfig::random().with_password(password.to_string());
+/// LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+///
+/// // Extract with same password
+/// let config = LSBConfig::random().with_password(password.to_string());
+/// let result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+/// assert_eq!(result.payload, payload);
+/// ```
+pub struct LSBEmbedder;
+
+/// Result of a successful embedding operation.
+///
+/// Contains metadata about the embedding operation that can be useful
+/// for analysis, optimization, or user feedback.
+///
+/// # Fields
+///
+/// - `bytes_used`: Total bytes of image data modified (header + payload)
+/// - `header_size`: Bytes used for the steganography header
+/// - `seed_embedded`: Whether the random seed was stored in the image
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+///
+/// let mut image = vec![0u8; 1000];
+/// let result = LSBEmbedder::embed(&mut image, b"test", &LSBConfig::linear()).unwrap();
+///
+/// println!("Embedded payload using {} bytes", result.bytes_used);
+/// println!("Header size: {} bytes", result.header_size);
+/// println!("Seed embedded: {}", result.seed_embedded);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmbedResult {
+    /// Total number of image bytes modified during embedding.
+    ///
+    /// This includes both header bytes and payload bytes. Each bit of
+    /// header or payload data requires modifying one bit in the image, so a
+    /// 100-byte payload requires modifying 800 image bytes (plus header overhead).
+    pub bytes_used: usize,
+
+    /// Number of bytes used for the steganography header.
+    ///
+    /// The header is stored in the same bit plane as the payload, so it
+    /// occupies `header_size * 8` carrier bytes. It contains metadata needed
+    /// for extraction including:
+    /// - Payload size
+    /// - Embedding pattern flags
+    /// - Random seed (if auto-generated)
+    /// - Checksum for integrity verification
+    pub header_size: usize,
+
+    /// Whether the random seed was embedded in the image header.
+    ///
+    /// - `true`: Auto-generated seed stored in image (`SeedSource::Auto`)
+    /// - `false`: Password or manual seed used (no seed storage needed)
+    pub seed_embedded: bool,
+
+    /// Time spent in each embedding phase.
+    #[cfg(feature = "instrument")]
+    pub timing: EmbedTiming,
+}
+
+/// Duration of the phases of an embedding operation.
+///
+/// Only available with the `instrument` feature, so regular builds don't pay
+/// for reading the clock. Useful to compare the cost of patterns and seed
+/// sources: password-derived seeds spend most of their time in `config`
+/// (Argon2), random patterns in `body` (index shuffling).
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 10_000];
+/// let result = LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::random()).unwrap();
+/// println!("header: {:?}, body: {:?}", result.timing.header, result.timing.body);
+/// ```
+#[cfg(feature = "instrument")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbedTiming {
+    /// Configuration resolution, including seed generation or derivation.
+    pub config: std::time::Duration,
+
+    /// Header construction and embedding.
+    pub header: std::time::Duration,
+
+    /// Payload ordering and embedding.
+    pub body: std::time::Duration,
+}
+
+/// Result of a successful extraction operation.
+///
+/// Contains the extracted payload and metadata about the extraction
+/// process that can be useful for verification or analysis.
+///
+/// # Fields
+///
+/// - `payload`: The extracted payload data
+/// - `header_size`: Size of the steganography header that was read
+/// - `seed_was_embedded`: Whether the image contained an embedded seed
+/// - `metadata`: Filename and MIME type stored with the payload, if any
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+///
+/// let mut image = vec![0u8; 1000];
+///
+/// // First embed a payload
+/// LSBEmbedder::embed(&mut image, b"test payload", &LSBConfig::linear()).unwrap();
+///
+/// // Then extract it
+/// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+/// println!("Extracted {} bytes", result.payload.len());
+/// println!("Header was {} bytes", result.header_size);
+/// println!("Had embedded seed: {}", result.seed_was_embedded);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtractResult {
+    /// The extracted payload data.
+    ///
+    /// This is the original data that was embedded in the image,
+    /// recovered bit-by-bit from the modified pixels.
+    pub payload: Vec<u8>,
+
+    /// Size of the steganography header that was read.
+    ///
+    /// Indicates how many bytes of metadata were stored alongside the
+    /// payload. Like the payload, each header byte occupies 8 carrier bytes,
+    /// except in images embedded with the original layout, whose header
+    /// takes the first `header_size` carrier bytes as is.
+    pub header_size: usize,
+
+    /// Whether the image contained an embedded random seed.
+    ///
+    /// - `true`: Seed was read from the image header (auto-generated)
+    /// - `false`: Seed was derived from password or provided manually
+    pub seed_was_embedded: bool,
+
+    /// Filename and MIME type stored alongside the payload, if any.
+    ///
+    /// Present when the payload was embedded with
+    /// [`LSBConfig::with_payload_metadata`].
+    pub metadata: Option<PayloadMetadata>,
+
+    /// Application tag stored in the header, if any.
+    ///
+    /// Present when the payload was embedded with
+    /// [`LSBConfig::with_payload_tag`].
+    pub tag: Option<u16>,
+
+    /// Animation frame the payload was embedded into, if recorded.
+    ///
+    /// Present when the payload was embedded with
+    /// [`LSBConfig::with_frame_index`].
+    pub frame_index: Option<u32>,
+
+    /// Image rows the payload was embedded into, if recorded.
+    ///
+    /// Present when the payload was embedded with
+    /// [`LSBConfig::with_row_range`].
+    pub row_range: Option<std::ops::Range<u32>>,
+
+    /// Character encoding of a text payload, if recorded in the header.
+    ///
+    /// Present when the payload was embedded with
+    /// [`LSBConfig::with_text_encoding`].
+    pub text_encoding: Option<crate::TextEncoding>,
+}
+
+/// How far a payload returned by
+/// [`LSBEmbedder::extract_with_confidence`] can be trusted.
+///
+/// Each field records one integrity check. A payload extracted with every
+/// check passing is identical to what [`LSBEmbedder::extract`] returns.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 1000];
+/// let config = LSBConfig::linear().with_redundancy(3);
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let (_, confidence) = LSBEmbedder::extract_with_confidence(&mut image, &config).unwrap();
+/// assert!(confidence.header_valid && confidence.size_fits);
+/// assert_eq!(confidence.payload_verified, Some(true));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confidence {
+    /// Whether the header checksum matched.
+    ///
+    /// When `false`, any header field may be wrong, including the payload
+    /// size and the embedding settings it records.
+    pub header_valid: bool,
+
+    /// Whether the payload matched its hash and all its redundant copies
+    /// agreed.
+    ///
+    /// Damage to the payload is only detected when it was embedded with
+    /// [`LSBConfig::with_payload_hash`] or [`WriteMode::Or`], which record a
+    /// hash of the payload, or with [`LSBConfig::with_redundancy`]. `None`
+    /// for payloads stored once without a hash.
+    pub payload_verified: Option<bool>,
+
+    /// Whether the declared payload size fit in the image.
+    ///
+    /// When `false`, the payload was truncated to the bytes the image holds.
+    pub size_fits: bool,
+}
+
+impl Confidence {
+    /// Whether every available check passed.
+    #[must_use]
+    pub fn is_high(&self) -> bool {
+        self.header_valid && self.size_fits && self.payload_verified != Some(false)
+    }
+}
+
+/// Fixed fields of a payload header, see [`LSBEmbedder::read_header`].
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{HeaderFlags, LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 2000];
+/// LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::random()).unwrap();
+///
+/// let info = LSBEmbedder::read_header(&mut image, &LSBConfig::linear()).unwrap();
+/// assert!(info.flags.contains(HeaderFlags::SEED_EMBEDDED));
+/// assert_eq!(info.payload_size, 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderInfo {
+    /// Version of the header format.
+    pub version: u8,
+
+    /// Flags recording how the payload was embedded.
+    pub flags: HeaderFlags,
+
+    /// Size of the payload declared by the header, in bytes.
+    pub payload_size: PayloadSize,
+
+    /// Size of the whole header, in bytes.
+    pub header_size: usize,
+}
+
+/// Embedding settings read back from a payload header, see
+/// [`LSBEmbedder::describe`].
+///
+/// Only what the header records is reported: the bit index is the one the
+/// header was found at, and the seed source of password and manual-seed
+/// embeddings cannot be told apart. The [`Display`](std::fmt::Display)
+/// implementation prints one setting per line.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::lsb::{BitIndex, LSBConfig, LSBEmbedder};
+///
+/// let mut image = vec![0u8; 2000];
+/// let config = LSBConfig::random().with_bit_index(BitIndex::Bit2);
+/// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+///
+/// let summary = LSBEmbedder::describe(&mut image, &config).unwrap();
+/// assert!(summary.random_pattern && summary.seed_embedded);
+/// assert_eq!(summary.payload_size, 7);
+/// println!("{summary}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingSummary {
+    /// Bit index the header was found at.
+    pub bit_index: BitIndex,
+
+    /// Whether the body follows a random pattern rather than a linear one.
+    pub random_pattern: bool,
+
+    /// Whether the seed of the random pattern is stored in the header.
+    pub seed_embedded: bool,
+
+    /// Whether the header fields after the bootstrap are scattered.
+    pub scattered_header: bool,
+
+    /// Version of the header format.
+    pub header_version: u8,
+
+    /// Size of the header, in bytes.
+    pub header_size: usize,
+
+    /// Size of the payload declared by the header, in bytes.
+    pub payload_size: PayloadSize,
+
+    /// Number of copies of the payload.
+    pub redundancy: u8,
+
+    /// Whether metadata precedes the payload.
+    pub has_metadata: bool,
+
+    /// Types of the header extensions present, in header order.
+    pub extensions: Vec<u8>,
+}
+
+impl std::fmt::Display for EmbeddingSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |flag: bool| if flag { "yes" } else { "no" };
+        writeln!(f, "bit index: {}", self.bit_index)?;
+        let pattern = if self.random_pattern {
+            "random"
+        } else {
+            "linear"
+        };
+        writeln!(f, "pattern: {pattern}")?;
+        writeln!(f, "seed embedded: {}", yes_no(self.seed_embedded))?;
+        writeln!(
+            f,
+            "header: version {}, {} bytes, {}",
+            self.header_version,
+            self.header_size,
+            if self.scattered_header {
+                "scattered"
+            } else {
+                "linear"
+            }
+        )?;
+        writeln!(f, "payload size: {} bytes", self.payload_size)?;
+        writeln!(f, "redundancy: {}", self.redundancy)?;
+        writeln!(f, "metadata: {}", yes_no(self.has_metadata))?;
+        write!(f, "extensions: {:?}", self.extensions)
+    }
+}
+
+impl LSBEmbedder {
+    /// Embed payload into image data using specified LSB configuration.
+    ///
+    /// This is the primary embedding method that handles all aspects of
+    /// LSB steganography including header creation, pattern selection,
+    /// and payload embedding.
+    ///
+    /// # Parameters
+    /// - `image_data`: Mutable slice of image pixel data to modify
+    /// - `payload`: Payload bytes to embed
+    /// - `config`: LSB configuration specifying embedding strategy
+    ///
+    /// # Returns
+    /// `EmbedResult` containing embedding statistics and metadata.
+    ///
+    /// # Process Overview
+    /// 1. **Config validation**: Convert user config to internal runtime config
+    /// 2. **Header embedding**: Store metadata needed for extraction
+    /// 3. **Payload embedding**: Distribute payload bits according to pattern
+    /// 4. **Result generation**: Return statistics about the operation
+    ///
+    /// # Examples
+    ///
+    /// ## Linear Embedding
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    /// let payload = b"Hello, World!";
+    ///
+    /// let result = LSBEmbedder::embed(
+    ///     &mut image,
+    ///     payload,
+    ///     &LSBConfig::linear()
+    /// ).unwrap();
+    ///
+    /// println!("Used {} bytes for embedding", result.bytes_used);
+    /// ```
+    ///
+    /// ## Secure Random Embedding
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig, BitIndex};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    /// let payload = b"Secret data";
+    ///
+    /// let config = LSBConfig::random()
+    ///     .with_password("my_password".to_string())
+    ///     .with_bit_index(BitIndex::Bit1);
+    ///     
+    /// let result = LSBEmbedder::embed(&mut image, payload, &config).unwrap();
+    /// assert!(!result.seed_embedded); // Password-derived, no seed storage
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::InsufficientCapacity`: Image too small for the header
+    /// - `PngerError::PayloadTooLarge`: Payload does not fit after the header,
+    ///   which is always the case above `u32::MAX` bytes
+    /// - `PngerError::UnsupportedMode`: Image larger than the 32-bit carrier
+    ///   index space
+    /// - `PngerError::CryptoError`: Seed generation or derivation failed
+    /// - `PngerError::InvalidFormat`: Invalid configuration parameters
+    pub fn embed(
+        image_data: &mut [u8],
+        payload: &[u8],
+        config: &LSBConfig,
+    ) -> Result<EmbedResult, PngerError> {
+        with_parity(image_data, config.parity, |carrier| {
+            Self::embed_carrier(carrier, payload, config)
+        })
+    }
+
+    // Embeds into the carrier bytes of the configured parity, gathered by
+    // `embed`
+    fn embed_carrier(
+        image_data: &mut [u8],
+        payload: &[u8],
+        config: &LSBConfig,
+    ) -> Result<EmbedResult, PngerError> {
+        #[cfg(feature = "instrument")]
+        let started = std::time::Instant::now();
+
+        let resolved;
+        let config = if config.auto_pattern {
+            resolved = config.resolve_auto_pattern(image_data);
+            &resolved
+        } else {
+            config
+        };
+        let runtime_config = RuntimeConfig::from_config(config)?;
+        if config.fail_if_occupied
+            && with_chaff_removed(image_data, config, |image_data| {
+                Ok(header::header_present(image_data, config))
+            })?
+        {
+            return Err(PngerError::InvalidFormat(
+                "bit index already contains a payload".to_string(),
+            ));
+        }
+
+        #[cfg(feature = "instrument")]
+        let config_done = std::time::Instant::now();
+
+        let header_size = header::HeaderEmbedder::required_size(&runtime_config);
+        let seed_embedded = matches!(
+            runtime_config.pattern,
+            RuntimePattern::Random {
+                embed_seed: true,
+                ..
+            }
+        );
+
+        // Check the header and body fit in the carrier before modifying
+        // anything
+        let copies = usize::from(runtime_config.redundancy);
+        let metadata_len = runtime_config.metadata.as_ref().map_or(0, Vec::len);
+        check_capacity(
+            image_data.len(),
+            header_size,
+            payload.len(),
+            metadata_len,
+            copies,
+        )?;
+        if runtime_config.skip_saturated {
+            let (_, layout) = header::HeaderEmbedder::plan(
+                &runtime_config,
+                image_data.len(),
+                payload.len() as PayloadSize,
+            )?;
+            let mut candidates = layout.body_candidates(image_data.len());
+            retain_unsaturated(
+                &mut candidates,
+                image_data,
+                runtime_config.bit_index,
+                runtime_config.channel_bits.as_deref(),
+            );
+            let body_bits = (payload.len() + metadata_len) * copies * 8;
+            if body_bits > candidates.len() {
+                return Err(PngerError::PayloadTooLarge {
+                    size: payload.len(),
+                    capacity: (candidates.len() / 8 / copies).saturating_sub(metadata_len),
+                });
+            }
+        }
+
+        // Each copy carries the metadata (if any) followed by the payload
+        let body = match &runtime_config.metadata {
+            Some(metadata) => [metadata.as_slice(), payload].concat(),
+            None => payload.to_vec(),
+        };
+
+        // OR-ing only reads back as the payload when the body bits are clear
+        // wherever the payload has a zero bit
+        if runtime_config.write_mode == WriteMode::Or {
+            let (_, layout) = header::HeaderEmbedder::plan(
+                &runtime_config,
+                image_data.len(),
+                payload.len() as PayloadSize,
+            )?;
+            let found = body_embedder(
+                image_data,
+                &layout,
+                &runtime_config,
+                payload.len(),
+                body.len() * copies,
+            )
+            .extract_payload(body.len() * copies)?;
+            let conflicts: u32 = found
+                .iter()
+                .zip(body.iter().cycle())
+                .map(|(found, expected)| (found & !expected).count_ones())
+                .sum();
+            if conflicts > 0 {
+                return Err(PngerError::InvalidFormat(format!(
+                    "{conflicts} body bits are already set where the payload has zero bits, \
+                     the OR-ed payload would not read back"
+                )));
+            }
+        }
+
+        let mut header_config = runtime_config.clone();
+        header::fill_payload_hash(&mut header_config.extensions, payload);
+        let layout = header::HeaderEmbedder::new(image_data, header_config)
+            .embed(payload.len() as PayloadSize)?;
+
+        #[cfg(feature = "instrument")]
+        let header_done = std::time::Instant::now();
+
+        // The header is written, but only its own bytes changed: the body
+        // candidates still hold their cover values
+        let mut body_embedder = body_embedder(
+            image_data,
+            &layout,
+            &runtime_config,
+            payload.len(),
+            body.len() * copies,
+        );
+        debug_event!(
+            "embedding {}-byte body ({copies} copies), {:?}",
+            body.len(),
+            runtime_config.bit_index
+        );
+        body_embedder.embed_redundant(&body, copies)?;
+
+        if runtime_config.lsb_noise {
+            match &runtime_config.pattern {
+                RuntimePattern::Random { seed, .. } => {
+                    let mut rng = rand_chacha::ChaCha20Rng::from_seed(*seed);
+                    rng.set_stream(NOISE_STREAM);
+                    body_embedder.randomize_unused(layout.indices(), &mut rng);
+                }
+                RuntimePattern::Linear => {
+                    body_embedder.randomize_unused(layout.indices(), &mut rand::rng());
+                }
+            }
+        }
+
+        if let Some(seed) = &runtime_config.chaff {
+            apply_chaff(image_data, seed, runtime_config.bit_index);
+        }
+
+        Ok(EmbedResult {
+            bytes_used: (header_size + body.len() * copies) * 8,
+            header_size,
+            seed_embedded,
+            #[cfg(feature = "instrument")]
+            timing: EmbedTiming {
+                config: config_done - started,
+                header: header_done - config_done,
+                body: header_done.elapsed(),
+            },
+        })
+    }
+
+    /// Extract payload from image data using specified LSB configuration.
+    ///
+    /// This is the primary extraction method that reverses the embedding
+    /// process to recover the original payload data from modified image pixels.
+    ///
+    /// # Parameters
+    /// - `image_data`: Mutable slice of image pixel data containing embedded payload
+    /// - `config`: LSB configuration matching the one used for embedding
+    ///
+    /// # Returns
+    /// `ExtractResult` containing the extracted payload and metadata.
+    ///
+    /// # Process Overview
+    /// 1. **Header parsing**: Read embedded metadata to understand the payload
+    /// 2. **Pattern reconstruction**: Rebuild the embedding pattern using config and header
+    /// 3. **Payload extraction**: Collect payload bits according to the pattern
+    /// 4. **Result generation**: Return payload and extraction metadata
+    ///
+    /// # Configuration Matching
+    ///
+    /// The extraction configuration must match the embedding configuration:
+    /// - **Linear patterns**: No additional info needed
+    /// - **Random with auto seed**: Uses embedded seed from header
+    /// - **Random with password**: Must provide the same password
+    /// - **Random with manual seed**: Must provide the same seed
+    ///
+    /// # Examples
+    ///
+    /// ## Basic Extraction
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    ///
+    /// // First embed a payload
+    /// LSBEmbedder::embed(&mut image, b"test message", &LSBConfig::linear()).unwrap();
+    ///
+    /// // Then extract it
+    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
+    /// println!("Extracted: {:?}", String::from_utf8_lossy(&result.payload));
+    /// ```
+    ///
+    /// ## Password-Protected Extraction
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    ///
+    /// // First embed with password
+    /// let config = LSBConfig::random().with_password("same_password".to_string());
+    /// LSBEmbedder::embed(&mut image, b"secret", &config).unwrap();
+    ///
+    /// // Then extract with same password
+    /// let config = LSBConfig::random().with_password("same_password".to_string());
+    /// let result = LSBEmbedder::extract(&mut image, &config).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: Corrupted or missing header
+    /// - `PngerError::CryptoError`: Password/seed mismatch or derivation failure
+    /// - `PngerError::InsufficientData`: Image smaller than expected payload
+    pub fn extract(image_data: &mut [u8], config: &LSBConfig) -> Result<ExtractResult, PngerError> {
+        without_chaff(image_data, config, |image_data| {
+            Self::extract_unmasked(image_data, config, false)
+        })
+        .map(|(result, _)| result)
+    }
+
+    /// Extract as much of the payload as possible, reporting how far it can
+    /// be trusted.
+    ///
+    /// Unlike [`extract`](Self::extract), a header whose checksum does not
+    /// match is still used, and a declared size larger than the image is
+    /// truncated to what the image holds. The returned [`Confidence`] records
+    /// which of these checks failed, so recovery tools can decide whether to
+    /// use the payload. When every check passes, the result is the one
+    /// [`extract`](Self::extract) returns.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::linear()).unwrap();
+    ///
+    /// let (result, confidence) =
+    ///     LSBEmbedder::extract_with_confidence(&mut image, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"payload");
+    /// assert!(confidence.is_high());
+    /// ```
+    ///
+    /// # Errors
+    /// Same as [`extract`](Self::extract), except for checksum and size
+    /// mismatches. No payload is returned when the header magic or version
+    /// is not recognized.
+    pub fn extract_with_confidence(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<(ExtractResult, Confidence), PngerError> {
+        without_chaff(image_data, config, |image_data| {
+            Self::extract_unmasked(image_data, config, true)
+        })
+    }
+
+    fn extract_unmasked(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+        lenient: bool,
+    ) -> Result<(ExtractResult, Confidence), PngerError> {
+        let opened = match Self::open_body(image_data, config, lenient) {
+            Ok(opened) => opened,
+            Err(error) => {
+                // Images embedded before headers moved into the bit plane
+                // use the original layout, with a checked header and a
+                // single copy of the body
+                let confidence = Confidence {
+                    header_valid: true,
+                    payload_verified: None,
+                    size_fits: true,
+                };
+                return match legacy::extract(image_data, config) {
+                    Some(result) => result.map(|result| (result, confidence)),
+                    None => Err(error),
+                };
+            }
+        };
+        let OpenedBody {
+            header,
+            header_size,
+            body: mut body_embedder,
+            size_fits,
+        } = opened;
+        let seed_was_embedded = header
+            .fixed
+            .flags
+            .contains(header::HeaderFlags::SEED_EMBEDDED);
+
+        // Phase 3: Extract the body, voting across copies when the payload
+        // was stored redundantly
+        let metadata_len = header.metadata_len();
+        let copy_size = header.fixed.payload_size as usize + metadata_len;
+        let copies = usize::from(header.redundancy());
+        let mut payload = body_embedder.extract_redundant(copy_size, copies)?;
+
+        // Payloads are only checked against their hash, when recorded, and
+        // their redundant copies
+        let copies_agree = (lenient && copies > 1).then(|| {
+            (0..copies).all(|copy| {
+                body_embedder
+                    .extract_redundant_range(copy * copy_size, copy_size, copy_size, 1)
+                    .is_ok_and(|bytes| bytes == payload)
+            })
+        });
+
+        let metadata = if header.metadata_len.is_some() {
+            let metadata_bytes: Vec<u8> = payload.drain(..metadata_len).collect();
+            Some(PayloadMetadata::from_bytes(&metadata_bytes)?)
+        } else {
+            None
+        };
+        let hash_matches = header.payload_hash_matches(&payload).filter(|_| lenient);
+        let payload_verified = match (copies_agree, hash_matches) {
+            (Some(copies_agree), Some(hash_matches)) => Some(copies_agree && hash_matches),
+            (verified, None) | (None, verified) => verified,
+        };
+
+        let result = ExtractResult {
+            payload,
+            header_size,
+            seed_was_embedded,
+            metadata,
+            tag: header.payload_tag()?,
+            frame_index: header.frame_index()?,
+            row_range: header.row_range()?,
+            text_encoding: header.text_encoding()?,
+        };
+        let confidence = Confidence {
+            header_valid: header.crc_valid,
+            payload_verified,
+            size_fits,
+        };
+        Ok((result, confidence))
+    }
+
+    /// Extract the payload chunk by chunk, without holding it in memory.
+    ///
+    /// Works like [`extract`](Self::extract), but hands the payload to
+    /// `on_chunk` in consecutive chunks of at most [`EXTRACT_CHUNK_SIZE`]
+    /// bytes instead of collecting it. The declared payload size is validated
+    /// against the image before the first chunk is read. Metadata, if any, is
+    /// parsed and returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut image, b"streamed payload", &LSBConfig::linear()).unwrap();
+    ///
+    /// let mut payload = Vec::new();
+    /// LSBEmbedder::extract_streaming(&mut image, &LSBConfig::linear(), &mut |chunk| {
+    ///     payload.extend_from_slice(chunk)
+    /// })
+    /// .unwrap();
+    /// assert_eq!(payload, b"streamed payload");
+    /// ```
+    ///
+    /// # Errors
+    /// Same as [`extract`](Self::extract). No chunk is emitted when the header
+    /// is invalid.
+    pub fn extract_streaming(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+        on_chunk: &mut impl FnMut(&[u8]),
+    ) -> Result<Option<PayloadMetadata>, PngerError> {
+        without_chaff(image_data, config, |image_data| {
+            Self::extract_streaming_unmasked(image_data, config, on_chunk)
+        })
+    }
 
-    /// Size of the steganography header that was read.
-    ///
-    /// Indicates how many bytes at the beginning of the image
-    /// were used for metadata rather than payload storage.
-    pub header_size: usize,
+    fn extract_streaming_unmasked(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+        on_chunk: &mut impl FnMut(&[u8]),
+    ) -> Result<Option<PayloadMetadata>, PngerError> {
+        let OpenedBody {
+            header,
+            body: mut body_embedder,
+            ..
+        } = Self::open_body(image_data, config, false)?;
 
-    /// Whether the image contained an embedded random seed.
-    ///
-    /// - `true`: Seed was read from the image header (auto-generated)
-    /// - `false`: Seed was derived from password or provided manually
-    pub seed_was_embedded: bool,
-}
+        let metadata_len = header.metadata_len();
+        let payload_size = header.fixed.payload_size as usize;
+        let copy_size = payload_size + metadata_len;
+        let copies = usize::from(header.redundancy());
 
-impl LSBEmbedder {
-    /// Embed payload into image data using specified LSB configuration.
+        let metadata = if header.metadata_len.is_some() {
+            let metadata_bytes =
+                body_embedder.extract_redundant_range(0, metadata_len, copy_size, copies)?;
+            Some(PayloadMetadata::from_bytes(&metadata_bytes)?)
+        } else {
+            None
+        };
+
+        let mut offset = metadata_len;
+        while offset < copy_size {
+            let len = EXTRACT_CHUNK_SIZE.min(copy_size - offset);
+            on_chunk(&body_embedder.extract_redundant_range(offset, len, copy_size, copies)?);
+            offset += len;
+        }
+
+        Ok(metadata)
+    }
+
+    /// Check whether the image carries a payload for `config`.
     ///
-    /// This is the primary embedding method that handles all aspects of
-    /// LSB steganography including header creation, pattern selection,
-    /// and payload embedding.
+    /// Returns `true` when a valid header is found in the configured bit
+    /// plane and namespace, and the payload it declares fits in the image.
+    /// The payload itself is not read, so this is cheap even for large
+    /// payloads. Empty payloads count as present, which allows using images
+    /// as markers.
     ///
-    /// # Parameters
-    /// - `image_data`: Mutable slice of image pixel data to modify
-    /// - `payload`: Payload bytes to embed
-    /// - `config`: LSB configuration specifying embedding strategy
+    /// # Examples
     ///
-    /// # Returns
-    /// `EmbedResult` containing embedding statistics and metadata.
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// # Process Overview
-    /// 1. **Config validation**: Convert user config to internal runtime config
-    /// 2. **Header embedding**: Store metadata needed for extraction
-    /// 3. **Payload embedding**: Distribute payload bits according to pattern
-    /// 4. **Result generation**: Return statistics about the operation
+    /// let mut image = vec![0u8; 1000];
+    /// assert!(!LSBEmbedder::contains_payload(&mut image, &LSBConfig::linear()));
+    ///
+    /// LSBEmbedder::embed(&mut image, b"", &LSBConfig::linear()).unwrap();
+    /// assert!(LSBEmbedder::contains_payload(&mut image, &LSBConfig::linear()));
+    /// ```
+    pub fn contains_payload(image_data: &mut [u8], config: &LSBConfig) -> bool {
+        without_chaff(image_data, config, |image_data| {
+            Self::open_body(image_data, config, false).map(|_| ())
+        })
+        .is_ok()
+    }
+
+    /// Check whether the image already carries `payload` for `config`.
+    ///
+    /// Compares `payload` with the hash stored in the header by
+    /// [`LSBConfig::with_payload_hash`], reading the header only. Returns
+    /// `false` when no valid header is found for `config`, when the header
+    /// stores no hash, when the pattern or password of `config` differ from
+    /// the embedding, or when the hashes differ. Other settings recorded in
+    /// the header are not compared.
     ///
     /// # Examples
     ///
-    /// ## Linear Embedding
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
     ///
-    /// let mut image = vec![0u8; 1000];
-    /// let payload = b"Hello, World!";
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::random().with_password("secret".to_string()).with_payload_hash(true);
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+    /// assert!(LSBEmbedder::holds_payload(&mut image, &config, b"payload"));
     ///
-    /// let result = LSBEmbedder::embed(
-    ///     &mut image,
-    ///     payload,
-    ///     &LSBConfig::linear()
-    /// ).unwrap();
+    /// let wrong = LSBConfig::random().with_password("wrong".to_string());
+    /// assert!(!LSBEmbedder::holds_payload(&mut image, &wrong, b"payload"));
+    /// ```
+    pub fn holds_payload(image_data: &mut [u8], config: &LSBConfig, payload: &[u8]) -> bool {
+        check_carrier_len(image_data.len()).is_ok()
+            && without_chaff(image_data, config, |image_data| {
+                Ok(header::payload_hash_matches(image_data, config, payload))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Check the password of `config` before extracting anything.
     ///
-    /// println!("Used {} bytes for embedding", result.bytes_used);
+    /// Payloads embedded with a password store a 4-byte verifier derived from
+    /// the password seed in their header. This compares it with the seed
+    /// derived from `config`, reading the header only, so a wrong password is
+    /// reported without extracting a large payload. A matching verifier can
+    /// still occur by chance (1 in 2^32), and the extraction checks stay in
+    /// place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// let config = LSBConfig::random().with_password("right".to_string());
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
+    ///
+    /// assert!(LSBEmbedder::verify_password(&mut image, &config).unwrap());
+    /// let wrong = LSBConfig::random().with_password("wrong".to_string());
+    /// assert!(!LSBEmbedder::verify_password(&mut image, &wrong).unwrap());
     /// ```
     ///
-    /// ## Secure Random Embedding
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: `config` does not use a password
+    /// - `PngerError::InvalidFormat`: No valid header is found, or the payload
+    ///   was not embedded with a password
+    /// - `PngerError::CryptoError`: Password derivation failed
+    pub fn verify_password(image_data: &mut [u8], config: &LSBConfig) -> Result<bool, PngerError> {
+        if !matches!(
+            &config.pattern,
+            EmbeddingPattern::Random(RandomConfig {
+                seed_source: SeedSource::Password(_),
+                ..
+            })
+        ) {
+            return Err(PngerError::UnsupportedMode(
+                "Password verification requires a password config".to_string(),
+            ));
+        }
+        check_carrier_len(image_data.len())?;
+        without_chaff(image_data, config, |image_data| {
+            header::verify_password(image_data, config)
+        })
+    }
+
+    /// List the bit indices holding a valid header.
+    ///
+    /// Tries every bit plane with `config`, ignoring its own bit index, and
+    /// returns those where the magic and checksum of a header validate. Only
+    /// the header is checked, so linear headers are found whatever the seed
+    /// source; scattered headers are only found when `config` provides their
+    /// seed. Useful to pick a free plane before layering another payload.
+    ///
+    /// # Examples
+    ///
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig, BitIndex};
+    /// use pnger::strategy::lsb::{BitIndex, LSBConfig, LSBEmbedder};
     ///
     /// let mut image = vec![0u8; 1000];
-    /// let payload = b"Secret data";
+    /// let config = LSBConfig::linear().with_bit_index(BitIndex::Bit1);
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
     ///
-    /// let config = LSBConfig::random()
-    ///     .with_password("my_password".to_string())
-    ///     .with_bit_index(BitIndex::Bit1);
-    ///     
-    /// let result = LSBEmbedder::embed(&mut image, payload, &config).unwrap();
-    /// assert!(!result.seed_embedded); // Password-derived, no seed storage
+    /// let occupied = LSBEmbedder::occupied_bit_indices(&mut image, &LSBConfig::random());
+    /// assert_eq!(occupied, [BitIndex::Bit1]);
+    /// ```
+    pub fn occupied_bit_indices(image_data: &mut [u8], config: &LSBConfig) -> Vec<BitIndex> {
+        BitIndex::all()
+            .iter()
+            .copied()
+            .filter(|&bit_index| {
+                let config = config.clone().with_bit_index(bit_index);
+                without_chaff(image_data, &config, |image_data| {
+                    Ok(header::header_present(image_data, &config))
+                })
+                .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// List the carrier byte indices an embedding would modify, in order.
+    ///
+    /// Plans an embedding of a `payload_len`-byte payload into a carrier of
+    /// `image_len` bytes without touching any image: the header indices come
+    /// first, followed by the body indices for every copy. Metadata and
+    /// redundancy configured on `config` are accounted for. Useful to check
+    /// that pattern, seed and header settings behave as expected.
+    ///
+    /// With an auto-generated seed, each call plans with a fresh seed, like
+    /// each embedding does; use a password or manual seed for reproducible
+    /// plans. Bits written by [`LSBConfig::with_lsb_noise`] are not listed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let indices = LSBEmbedder::planned_indices(1000, &LSBConfig::linear(), 4).unwrap();
+    /// assert_eq!(indices.len(), LSBConfig::linear().header_overhead() + 4 * 8);
+    /// assert!(indices.iter().enumerate().all(|(i, &index)| index as usize == i));
     /// ```
     ///
     /// # Errors
-    /// - `PngerError::InsufficientCapacity`: Image too small for payload
+    /// - `PngerError::InsufficientCapacity`: The carrier cannot hold the header
+    /// - `PngerError::PayloadTooLarge`: The payload does not fit after the header
     /// - `PngerError::CryptoError`: Seed generation or derivation failed
-    /// - `PngerError::InvalidFormat`: Invalid configuration parameters
-    pub fn embed(
-        image_data: &mut [u8],
-        payload: &[u8],
+    /// - `PngerError::UnsupportedMode`: `config` skips saturated bytes, see
+    ///   [`LSBConfig::with_skip_saturated`]
+    pub fn planned_indices(
+        image_len: usize,
         config: &LSBConfig,
-    ) -> Result<EmbedResult, PngerError> {
+        payload_len: usize,
+    ) -> Result<Vec<u32>, PngerError> {
+        if config.skip_saturated {
+            return Err(PngerError::UnsupportedMode(
+                "Skipped saturated bytes depend on the image, which planning does not see"
+                    .to_string(),
+            ));
+        }
+        let image_len = match config.parity {
+            Some(parity) => {
+                check_carrier_len(image_len)?;
+                parity.carrier_len(image_len)
+            }
+            None => image_len,
+        };
         let runtime_config = RuntimeConfig::from_config(config)?;
-
         let header_size = header::HeaderEmbedder::required_size(&runtime_config);
-        let seed_embedded = matches!(
-            runtime_config.pattern,
-            RuntimePattern::Random {
-                embed_seed: true,
-                ..
+        let metadata_len = runtime_config.metadata.as_ref().map_or(0, Vec::len);
+        let body_len = payload_len.saturating_add(metadata_len);
+        let copies = usize::from(runtime_config.redundancy);
+        check_capacity(image_len, header_size, payload_len, metadata_len, copies)?;
+
+        let (_, layout) =
+            header::HeaderEmbedder::plan(&runtime_config, image_len, payload_len as PayloadSize)?;
+        let candidates = diffuse(
+            layout.body_candidates(image_len),
+            runtime_config.diffusion_stride,
+        );
+        let body_bits = body_len * copies * 8;
+
+        let body_pattern = runtime_config
+            .pattern
+            .for_body(payload_len as PayloadSize, runtime_config.length_bound_seed);
+        let mut body = BodyEmbedder::order_candidates(candidates, &body_pattern, body_bits / 8);
+        body.truncate(body_bits);
+
+        let mut indices = layout.indices().to_vec();
+        indices.extend(body);
+        Ok(parity_indices(indices, config.parity))
+    }
+
+    /// List the carrier byte indices an embedding in `image_data` modified.
+    ///
+    /// Like [`planned_indices`](Self::planned_indices), but planned from the
+    /// header found in the carrier, so auto-generated seeds and patterns
+    /// resolved from the cover are accounted for.
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Noise or chaff modify bits outside
+    ///   the plan
+    /// - Any error of [`extract`](Self::extract) reading the header
+    pub(crate) fn embedded_indices(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<Vec<u32>, PngerError> {
+        if config.lsb_noise || config.chaff {
+            return Err(PngerError::UnsupportedMode(
+                "Noise and chaff modify bits outside the planned indices".to_string(),
+            ));
+        }
+        let indices = with_parity(image_data, config.parity, |image_data| {
+            Self::embedded_carrier_indices(image_data, config)
+        })?;
+        Ok(parity_indices(indices, config.parity))
+    }
+
+    // Indices within the carrier bytes of the configured parity, see
+    // `embedded_indices`
+    fn embedded_carrier_indices(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<Vec<u32>, PngerError> {
+        let (complete_header, runtime_pattern, layout) = header::read_header(image_data, config)?;
+        let payload_size = usize::try_from(complete_header.fixed.payload_size).map_err(|_| {
+            PngerError::PayloadTooLarge {
+                size: usize::MAX,
+                capacity: image_data.len() / 8,
             }
+        })?;
+        let body_len = payload_size
+            .checked_add(complete_header.metadata_len())
+            .and_then(|len| len.checked_mul(usize::from(complete_header.redundancy())))
+            .ok_or(PngerError::PayloadTooLarge {
+                size: payload_size,
+                capacity: image_data.len() / 8,
+            })?;
+
+        let mut candidates = layout.body_candidates(image_data.len());
+        if complete_header.skip_saturated() {
+            retain_unsaturated(
+                &mut candidates,
+                image_data,
+                config.bit_index,
+                complete_header.channel_bits().as_deref(),
+            );
+        }
+        let candidates = diffuse(candidates, complete_header.diffusion_stride()?);
+        let body_pattern = runtime_pattern.for_body(
+            complete_header.fixed.payload_size,
+            complete_header.length_bound_seed(),
         );
+        let mut body = BodyEmbedder::order_candidates(candidates, &body_pattern, body_len);
+        body.truncate(body_len * 8);
 
-        let (header_data, body_data) = image_data.split_at_mut(header_size);
+        let mut indices = layout.indices().to_vec();
+        indices.extend(body);
+        Ok(indices)
+    }
 
-        let header_bytes_used = header::HeaderEmbedder::new(header_data, runtime_config.clone())
-            .embed(payload.len() as u32)?;
-        BodyEmbedder::new(
-            body_data,
-            &runtime_config.pattern,
-            runtime_config.bit_index,
-            payload.len(),
-        )
-        .embed_payload(payload);
+    /// Recover the seed a random-pattern payload was embedded with.
+    ///
+    /// Returns the seed stored in the header for auto-generated seeds, or the
+    /// one derived from `config` otherwise. Storing it separately (key escrow)
+    /// allows recovery with [`LSBConfig::with_external_seed`] if the seed
+    /// bytes in the image are later damaged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::random().with_seed([7; 32])).unwrap();
+    ///
+    /// let seed = LSBEmbedder::export_seed(&mut image, &LSBConfig::random().with_seed([7; 32]));
+    /// assert_eq!(seed.unwrap(), [7; 32]);
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: Missing or corrupted header, or the
+    ///   payload uses a linear pattern, which has no seed
+    /// - `PngerError::CryptoError`: Password derivation failed
+    pub fn export_seed(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<[u8; SEED_SIZE], PngerError> {
+        let pattern = without_chaff(image_data, config, |image_data| {
+            header::read_header(image_data, config).map(|(_, pattern, _)| pattern)
+        })?;
+        match pattern {
+            RuntimePattern::Random { seed, .. } => Ok(seed),
+            RuntimePattern::Linear => Err(PngerError::InvalidFormat(
+                "Payload uses a linear pattern, which has no seed".to_string(),
+            )),
+        }
+    }
 
-        Ok(EmbedResult {
-            bytes_used: header_bytes_used + (payload.len() * 8),
-            header_size,
-            seed_embedded,
+    /// Read the seed stored in the header of an auto-seed embedding.
+    ///
+    /// Only the header is read, at the bit index and namespace of `config`;
+    /// its seed source is ignored. Returns `None` for payloads embedded with
+    /// a linear pattern, a password or a manual seed, whose headers store no
+    /// seed. Unlike [`export_seed`](Self::export_seed), no password is
+    /// needed or derived.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// let mut image = vec![0u8; 2000];
+    /// LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::random()).unwrap();
+    /// let seed = LSBEmbedder::read_embedded_seed(&mut image, &LSBConfig::random()).unwrap();
+    /// assert!(seed.is_some());
+    ///
+    /// LSBEmbedder::embed(&mut image, b"payload", &LSBConfig::linear()).unwrap();
+    /// let seed = LSBEmbedder::read_embedded_seed(&mut image, &LSBConfig::random()).unwrap();
+    /// assert_eq!(seed, None);
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: Missing or corrupted header
+    pub fn read_embedded_seed(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<Option<[u8; SEED_SIZE]>, PngerError> {
+        with_parity(image_data, config.parity, |carrier| {
+            header::read_embedded_seed(carrier, config)
         })
     }
 
-    /// Extract payload from image data using specified LSB configuration.
+    /// Read and validate the header at the bit index of `config`, returning
+    /// its fixed fields.
     ///
-    /// This is the primary extraction method that reverses the embedding
-    /// process to recover the original payload data from modified image pixels.
-    ///
-    /// # Parameters
-    /// - `image_data`: Mutable slice of image pixel data containing embedded payload
-    /// - `config`: LSB configuration matching the one used for embedding
+    /// Only the header is read; its magic and checksum must validate. Like
+    /// [`describe`](Self::describe), linear headers are read without
+    /// credentials and scattered headers only with the seed of `config`.
     ///
-    /// # Returns
-    /// `ExtractResult` containing the extracted payload and metadata.
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: No header at the bit index and
+    ///   namespace of `config`, or a corrupted one
+    /// - `PngerError::CryptoError`: Password derivation failed while locating
+    ///   a scattered header
+    pub fn read_header(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<HeaderInfo, PngerError> {
+        check_carrier_len(image_data.len())?;
+        let (header, header_size) = without_chaff(image_data, config, |image_data| {
+            header::read_header_fields(image_data, config)
+        })?;
+        Ok(HeaderInfo {
+            version: header.fixed.version,
+            flags: header.fixed.flags,
+            payload_size: header.fixed.payload_size,
+            header_size,
+        })
+    }
+
+    /// Describe the embedding whose header sits at the bit index of `config`.
     ///
-    /// # Process Overview
-    /// 1. **Header parsing**: Read embedded metadata to understand the payload
-    /// 2. **Pattern reconstruction**: Rebuild the embedding pattern using config and header
-    /// 3. **Payload extraction**: Collect payload bits according to the pattern
-    /// 4. **Result generation**: Return payload and extraction metadata
+    /// Only the header is read and validated; the payload is not extracted.
+    /// Linear headers are described without credentials, whatever the seed
+    /// source of `config`. Scattered headers are only found when `config`
+    /// provides their seed.
     ///
-    /// # Configuration Matching
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: No valid header is found at the bit
+    ///   index and namespace of `config`
+    /// - `PngerError::CryptoError`: Password derivation failed while locating
+    ///   a scattered header
+    pub fn describe(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<EmbeddingSummary, PngerError> {
+        check_carrier_len(image_data.len())?;
+        let (header, header_size) = without_chaff(image_data, config, |image_data| {
+            header::read_header_fields(image_data, config)
+        })?;
+        let flags = header.fixed.flags;
+        Ok(EmbeddingSummary {
+            bit_index: config.bit_index,
+            random_pattern: flags.contains(header::HeaderFlags::RANDOM_PATTERN),
+            seed_embedded: flags.contains(header::HeaderFlags::SEED_EMBEDDED),
+            scattered_header: flags.contains(header::HeaderFlags::SCATTERED_HEADER),
+            header_version: header.fixed.version,
+            header_size,
+            payload_size: header.fixed.payload_size,
+            redundancy: header.redundancy(),
+            has_metadata: header.metadata_len.is_some(),
+            extensions: header.extension_kinds(),
+        })
+    }
+
+    /// Row range recorded in the header at the bit index of `config`, see
+    /// [`LSBConfig::with_row_range`].
     ///
-    /// The extraction configuration must match the embedding configuration:
-    /// - **Linear patterns**: No additional info needed
-    /// - **Random with auto seed**: Uses embedded seed from header
-    /// - **Random with password**: Must provide the same password
-    /// - **Random with manual seed**: Must provide the same seed
+    /// Only the header is read, like [`describe`](Self::describe), so the
+    /// range is found without knowing how many rows the carrier spans.
+    pub(crate) fn row_range(
+        image_data: &mut [u8],
+        config: &LSBConfig,
+    ) -> Result<Option<std::ops::Range<u32>>, PngerError> {
+        check_carrier_len(image_data.len())?;
+        let (header, _) = without_chaff(image_data, config, |image_data| {
+            header::read_header_fields(image_data, config)
+        })?;
+        header.row_range()
+    }
+
+    /// Read a payload body without parsing any header.
+    ///
+    /// Low-level escape hatch for recovery when the header was damaged or
+    /// lost, for example after copying the payload region to another image.
+    /// Reads `payload_size` bytes from bit `bit_index` of the carrier bytes
+    /// following the first `body_offset` ones, visited in order when `seed`
+    /// is `None` (linear pattern) or shuffled with `seed` otherwise (random
+    /// pattern).
+    ///
+    /// `body_offset` is the [`header_overhead`](LSBConfig::header_overhead)
+    /// of the configuration used for embedding, including the embedded seed
+    /// of auto-seed configurations. Payloads embedded with a scattered
+    /// header, metadata or redundancy cannot be recovered this way, and
+    /// obfuscation is not reversed. Nothing is validated: wrong parameters
+    /// return garbage rather than an error.
     ///
     /// # Examples
     ///
-    /// ## Basic Extraction
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    /// use pnger::strategy::lsb::{BitIndex, LSBConfig, LSBEmbedder};
     ///
+    /// let config = LSBConfig::random().with_seed([3; 32]);
     /// let mut image = vec![0u8; 1000];
+    /// LSBEmbedder::embed(&mut image, b"payload", &config).unwrap();
     ///
-    /// // First embed a payload
-    /// LSBEmbedder::embed(&mut image, b"test message", &LSBConfig::linear()).unwrap();
+    /// // Lose the header
+    /// image[..config.header_overhead()].fill(0xFF);
+    /// let payload =
+    ///     LSBEmbedder::extract_raw(&mut image, Some([3; 32]), BitIndex::Bit0, 7, config.header_overhead());
+    /// assert_eq!(payload.unwrap(), b"payload");
+    /// ```
     ///
-    /// // Then extract it
-    /// let result = LSBEmbedder::extract(&mut image, &LSBConfig::linear()).unwrap();
-    /// println!("Extracted: {:?}", String::from_utf8_lossy(&result.payload));
+    /// # Errors
+    /// - `PngerError::PayloadTooLarge`: `payload_size` bytes do not fit after
+    ///   `body_offset` carrier bytes
+    /// - `PngerError::UnsupportedMode`: Image larger than the 32-bit carrier
+    ///   index space
+    pub fn extract_raw(
+        image_data: &mut [u8],
+        seed: Option<[u8; SEED_SIZE]>,
+        bit_index: BitIndex,
+        payload_size: usize,
+        body_offset: usize,
+    ) -> Result<Vec<u8>, PngerError> {
+        check_carrier_len(image_data.len())?;
+        let body_bits = payload_size.checked_mul(8);
+        let available = image_data.len().saturating_sub(body_offset);
+        if body_bits.is_none_or(|bits| bits > available) {
+            return Err(PngerError::PayloadTooLarge {
+                size: payload_size,
+                capacity: available / 8,
+            });
+        }
+
+        let pattern = match seed {
+            Some(seed) => RuntimePattern::Random {
+                seed,
+                embed_seed: false,
+                scatter_header: false,
+            },
+            None => RuntimePattern::Linear,
+        };
+        let candidates = (body_offset as u32..image_data.len() as u32).collect();
+        let mut body_embedder =
+            BodyEmbedder::new(image_data, candidates, &pattern, bit_index, payload_size);
+        body_embedder.extract_payload(payload_size)
+    }
+
+    /// Extract headerless data terminated by `sentinel`.
+    ///
+    /// For interoperability with tools that write raw bits without storing a
+    /// length: reads bytes from the start of the carrier, in the order given
+    /// by the pattern, bit index, channel bit indices, bit order and parity
+    /// of `config`, and stops as soon as the bytes read end with `sentinel`.
+    /// Returns the bytes before the sentinel. Reading stops at the carrier
+    /// capacity (`image_data.len() / 8` bytes) when the sentinel never shows.
+    ///
+    /// There is no header to hold a seed, so random patterns need a password
+    /// or manual seed. Other header-based options (magic, metadata,
+    /// redundancy, chaff) do not apply.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder};
+    ///
+    /// // Raw bits of "hi" then a NUL sentinel, least significant bit first
+    /// let mut image: Vec<u8> = b"hi\0"
+    ///     .iter()
+    ///     .flat_map(|byte| (0..8).map(move |bit| 0x80 | ((byte >> bit) & 1)))
+    ///     .chain([0x81; 64])
+    ///     .collect();
+    /// let data = LSBEmbedder::extract_until_sentinel(&mut image, b"\0", &LSBConfig::linear());
+    /// assert_eq!(data.unwrap(), b"hi");
     /// ```
     ///
-    /// ## Password-Protected Extraction
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: Empty `sentinel`, or no sentinel within
+    ///   the carrier capacity
+    /// - `PngerError::UnsupportedMode`: Random pattern with an auto-generated
+    ///   seed, or image larger than the 32-bit carrier index space
+    /// - `PngerError::CryptoError`: Seed derivation failed
+    pub fn extract_until_sentinel(
+        image_data: &mut [u8],
+        sentinel: &[u8],
+        config: &LSBConfig,
+    ) -> Result<Vec<u8>, PngerError> {
+        check_carrier_len(image_data.len())?;
+        if sentinel.is_empty() {
+            return Err(PngerError::InvalidFormat(
+                "The sentinel must not be empty".to_string(),
+            ));
+        }
+        if let EmbeddingPattern::Random(RandomConfig {
+            seed_source: SeedSource::Auto,
+            ..
+        }) = &config.pattern
+        {
+            return Err(PngerError::UnsupportedMode(
+                "Sentinel extraction has no header to read an embedded seed from, use a password or manual seed"
+                    .to_string(),
+            ));
+        }
+        let runtime_config = RuntimeConfig::from_config(config)?;
+
+        with_parity(image_data, config.parity, |image_data| {
+            let capacity = image_data.len() / 8;
+            let candidates = (0..image_data.len() as u32).collect();
+            let mut body_embedder = BodyEmbedder::new(
+                image_data,
+                candidates,
+                &runtime_config.pattern,
+                config.bit_index,
+                capacity,
+            )
+            .with_channel_bits(runtime_config.channel_bits)
+            .with_bit_order(config.bit_order);
+
+            let mut data = Vec::new();
+            while data.len() < capacity {
+                data.push(body_embedder.read_u8()?);
+                if data.ends_with(sentinel) {
+                    data.truncate(data.len() - sentinel.len());
+                    return Ok(data);
+                }
+            }
+            Err(PngerError::InvalidFormat(format!(
+                "Sentinel not found within the {capacity}-byte carrier capacity"
+            )))
+        })
+    }
+
+    /// Embed into a pixel buffer whose rows may be padded.
+    ///
+    /// Works like [`embed`](Self::embed) on the sample bytes described by
+    /// `layout`, in row order. Row padding is neither modified nor counted
+    /// towards capacity, so buffers from decoders with aligned strides can be
+    /// used without repacking them first.
+    ///
+    /// # Examples
+    ///
     /// ```rust
-    /// use pnger::strategy::lsb::{LSBEmbedder, LSBConfig};
+    /// use pnger::strategy::lsb::{LSBConfig, LSBEmbedder, PixelLayout};
     ///
-    /// let mut image = vec![0u8; 1000];
+    /// // 32x32 RGB rows padded to 100 bytes
+    /// let layout = PixelLayout { width: 32, height: 32, channels: 3, row_stride: 100 };
+    /// let mut image = vec![0u8; 100 * 32];
     ///
-    /// // First embed with password
-    /// let config = LSBConfig::random().with_password("same_password".to_string());
-    /// LSBEmbedder::embed(&mut image, b"secret", &config).unwrap();
+    /// LSBEmbedder::embed_with_layout(&mut image, layout, b"strided", &LSBConfig::linear()).unwrap();
+    /// let result = LSBEmbedder::extract_with_layout(&mut image, layout, &LSBConfig::linear()).unwrap();
+    /// assert_eq!(result.payload, b"strided");
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::InvalidFormat`: `layout` does not match the buffer
+    /// - Any error returned by [`embed`](Self::embed)
+    pub fn embed_with_layout(
+        image_data: &mut [u8],
+        layout: PixelLayout,
+        payload: &[u8],
+        config: &LSBConfig,
+    ) -> Result<EmbedResult, PngerError> {
+        layout.validate(image_data.len())?;
+        let mut samples = layout.gather(image_data);
+        let result = Self::embed(&mut samples, payload, config)?;
+        layout.scatter(image_data, &samples);
+        Ok(result)
+    }
+
+    /// Extract from a pixel buffer whose rows may be padded.
     ///
-    /// // Then extract with same password
-    /// let config = LSBConfig::random().with_password("same_password".to_string());
-    /// let result = LSBEmbedder::extract(&mut image, &config).unwrap();
-    /// ```
+    /// Counterpart of [`embed_with_layout`](Self::embed_with_layout): reads
+    /// only the sample bytes described by `layout`.
     ///
     /// # Errors
-    /// - `PngerError::InvalidFormat`: Corrupted or missing header
-    /// - `PngerError::CryptoError`: Password/seed mismatch or derivation failure
-    /// - `PngerError::InsufficientData`: Image smaller than expected payload
-    pub fn extract(image_data: &mut [u8], config: &LSBConfig) -> Result<ExtractResult, PngerError> {
-        // Phase 1: Read fixed header to get flags
-        let fixed_header = header::FixedHeader::read_from_bytes(image_data)?;
-        let header_size = fixed_header.calculate_total_header_size();
+    /// - `PngerError::InvalidFormat`: `layout` does not match the buffer
+    /// - Any error returned by [`extract`](Self::extract)
+    pub fn extract_with_layout(
+        image_data: &[u8],
+        layout: PixelLayout,
+        config: &LSBConfig,
+    ) -> Result<ExtractResult, PngerError> {
+        layout.validate(image_data.len())?;
+        Self::extract(&mut layout.gather(image_data), config)
+    }
 
-        // Phase 2: Read complete header with variable data
-        let complete_header = header::CompleteHeader::read_from_bytes(&image_data[..header_size])?;
-        let seed_was_embedded = complete_header
-            .fixed
-            .flags
-            .contains(header::HeaderFlags::SEED_EMBEDDED);
+    /// Reads and validates the header, then positions a body reader over the
+    /// carrier bytes holding the payload.
+    ///
+    /// Returns the header, its size and the body reader. The declared body is
+    /// guaranteed to fit in the carrier: when `lenient`, a header with a bad
+    /// checksum is accepted and an oversized declared body is truncated to
+    /// what the carrier holds instead of failing.
+    fn open_body<'a>(
+        image_data: &'a mut [u8],
+        config: &LSBConfig,
+        lenient: bool,
+    ) -> Result<OpenedBody<'a>, PngerError> {
+        check_carrier_len(image_data.len())?;
+
+        // Phase 1: Read header from the target bit plane, reconstructing the
+        // runtime pattern from its flags and the user config
+        let (mut complete_header, runtime_pattern, layout) =
+            header::read_header_with(image_data, config, lenient)?;
+        let header_size = layout.header_size();
+        let body_pattern = runtime_pattern.for_body(
+            complete_header.fixed.payload_size,
+            complete_header.length_bound_seed(),
+        );
+
+        // Phase 2: Validate the declared size against the configured limit
+        // and the carrier bytes left to the body
+        let mut size_fits = true;
+        let mut payload_size = match usize::try_from(complete_header.fixed.payload_size) {
+            Ok(size) => size,
+            Err(_) if lenient => usize::MAX,
+            Err(_) => {
+                return Err(PngerError::PayloadTooLarge {
+                    size: usize::MAX,
+                    capacity: image_data.len() / 8,
+                });
+            }
+        };
+        if let Some(max_payload) = config.max_payload.filter(|&max| payload_size > max) {
+            if !lenient {
+                return Err(PngerError::InvalidFormat(format!(
+                    "Header declares a {payload_size}-byte payload, above the {max_payload}-byte limit"
+                )));
+            }
+            payload_size = max_payload;
+            size_fits = false;
+        }
+        let metadata_len = complete_header.metadata_len();
+        let channel_bits = complete_header.channel_bits();
+        let mut candidates = layout.body_candidates(image_data.len());
+        if complete_header.skip_saturated() {
+            retain_unsaturated(
+                &mut candidates,
+                image_data,
+                config.bit_index,
+                channel_bits.as_deref(),
+            );
+        }
+        let candidates = diffuse(candidates, complete_header.diffusion_stride()?);
+
+        // The header is untrusted: make sure the declared payload fits in the
+        // carrier before reading it
+        let available = candidates.len() / 8 / usize::from(complete_header.redundancy());
+        let copy_size = payload_size.saturating_add(metadata_len);
+        if copy_size > available {
+            if !lenient {
+                return Err(PngerError::InvalidFormat(format!(
+                    "Header declares a {copy_size}-byte body but the image only holds {available} bytes"
+                )));
+            }
+            size_fits = false;
+        }
+        if !size_fits {
+            // Later copies start past the truncated body: read the first
+            // copy only, keeping its metadata when it fits
+            complete_header.redundancy = None;
+            let available = candidates.len() / 8;
+            if metadata_len > available {
+                complete_header.metadata_len = None;
+                payload_size = 0;
+            } else {
+                payload_size = payload_size.min(available - metadata_len);
+            }
+        }
+        complete_header.fixed.payload_size = payload_size as PayloadSize;
+        let copies = usize::from(complete_header.redundancy());
+        let copy_size = payload_size + complete_header.metadata_len();
 
-        // Phase 3: Reconstruct runtime pattern from metadata and config
-        let runtime_pattern = RuntimePattern::from_header_and_config(&complete_header, config)?;
+        // The carrier of hybrid embeddings includes pixel indices, which the
+        // body positions depend on
+        if complete_header.indexed_hybrid() != config.indexed_hybrid {
+            return Err(PngerError::InvalidFormat(format!(
+                "Payload was embedded {} indexed hybrid mode, extraction {} it",
+                if complete_header.indexed_hybrid() {
+                    "in"
+                } else {
+                    "outside"
+                },
+                if config.indexed_hybrid {
+                    "uses"
+                } else {
+                    "does not use"
+                }
+            )));
+        }
+
+        // Carrier bytes are gathered before reading the header, so another
+        // parity means the header was not written for this carrier
+        let parity = complete_header.parity()?;
+        if parity != config.parity {
+            return Err(PngerError::InvalidFormat(format!(
+                "Payload was embedded with parity {parity:?}, extraction uses {:?}",
+                config.parity
+            )));
+        }
 
-        // Phase 4: Extract payload using runtime config
-        let body_data = &mut image_data[header_size..];
-        let mut body_embedder = BodyEmbedder::new(
-            body_data,
-            &runtime_pattern,
+        // OR-ed bodies read back mixed with the cover bits, which callers
+        // must expect
+        let write_mode = complete_header.write_mode()?;
+        if write_mode != config.write_mode {
+            return Err(PngerError::InvalidFormat(format!(
+                "Payload was embedded with write mode {write_mode:?}, extraction uses {:?}",
+                config.write_mode
+            )));
+        }
+
+        let bit_order = complete_header.bit_order()?;
+        let body_embedder = BodyEmbedder::new(
+            image_data,
+            candidates,
+            &body_pattern,
             config.bit_index,
-            fixed_header.payload_size as _,
-        );
-        let payload = body_embedder.extract_payload(complete_header.fixed.payload_size as usize);
+            copy_size * copies,
+        )
+        .with_channel_bits(channel_bits)
+        .with_bit_order(bit_order);
 
-        Ok(ExtractResult {
-            payload,
+        Ok(OpenedBody {
+            header: complete_header,
             header_size,
-            seed_was_embedded,
+            body: body_embedder,
+            size_fits,
         })
     }
 
@@ -1160,75 +4206,935 @@ impl LSBEmbedder {
         Self::embed(image_data, payload, &config)
     }
 
-    /// Convenience method for linear pattern extraction.
-    ///
-    /// Equivalent to calling `extract()` with `LSBConfig::linear()`.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use pnger::strategy::lsb::LSBEmbedder;
-    ///
-    /// let mut image = vec![0u8; 1000];
-    ///
-    /// // First embed with linear pattern
-    /// LSBEmbedder::embed_linear(&mut image, b"test").unwrap();
-    ///
-    /// // Then extract
-    /// let result = LSBEmbedder::extract_linear(&mut image).unwrap();
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// This function will return an error if:
-    /// - `PngerError::InvalidFormat`: Corrupted or missing header
-    /// - `PngerError::CryptoError`: Password/seed mismatch or derivation failure
-    /// - `PngerError::InsufficientData`: Image smaller than expected payload
-    pub fn extract_linear(image_data: &mut [u8]) -> Result<ExtractResult, PngerError> {
-        Self::extract(image_data, &LSBConfig::linear())
+    /// Convenience method for linear pattern extraction.
+    ///
+    /// Equivalent to calling `extract()` with `LSBConfig::linear()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBEmbedder;
+    ///
+    /// let mut image = vec![0u8; 1000];
+    ///
+    /// // First embed with linear pattern
+    /// LSBEmbedder::embed_linear(&mut image, b"test").unwrap();
+    ///
+    /// // Then extract
+    /// let result = LSBEmbedder::extract_linear(&mut image).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `PngerError::InvalidFormat`: Corrupted or missing header
+    /// - `PngerError::CryptoError`: Password/seed mismatch or derivation failure
+    /// - `PngerError::InsufficientData`: Image smaller than expected payload
+    pub fn extract_linear(image_data: &mut [u8]) -> Result<ExtractResult, PngerError> {
+        Self::extract(image_data, &LSBConfig::linear())
+    }
+
+    /// Convenience method for password-protected extraction.
+    ///
+    /// Uses random pattern with password-derived seed matching the
+    /// password used during embedding.
+    ///
+    /// # Parameters
+    /// - `image_data`: Image data containing embedded payload
+    /// - `password`: Password used during embedding
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::LSBEmbedder;
+    ///
+    /// let mut image = vec![0u8; 1000];
+    ///
+    /// // First embed with password
+    /// LSBEmbedder::embed_with_password(&mut image, b"secret", "my_password").unwrap();
+    ///
+    /// // Then extract with same password
+    /// let result = LSBEmbedder::extract_with_password(&mut image, "my_password").unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// - `PngerError::InvalidFormat`: Corrupted or missing header
+    /// - `PngerError::CryptoError`: Wrong password or derivation failure  
+    /// - `PngerError::InsufficientData`: Image smaller than expected payload
+    pub fn extract_with_password(
+        image_data: &mut [u8],
+        password: &str,
+    ) -> Result<ExtractResult, PngerError> {
+        let config = LSBConfig::random().with_password(password.to_string());
+        Self::extract(image_data, &config)
+    }
+}
+
+/// Checks that every byte of a `carrier_len`-byte carrier is addressable.
+///
+/// Carrier indices are stored as [`PayloadSize`](crate::strategy::PayloadSize).
+/// Larger carriers would wrap them around and silently corrupt the embedding.
+/// Entropy of the `bit_index` plane of `carrier`, in bits per bit.
+///
+/// Measured over groups of 4 consecutive bits rather than single bits, so
+/// regular patterns such as the alternating low bits of gradients count as
+/// structure, while staying accurate on small carriers.
+fn bit_plane_entropy(carrier: &[u8], bit_index: BitIndex) -> f64 {
+    let mut histogram = [0usize; 16];
+    let groups = carrier.chunks_exact(4);
+    let count = groups.len();
+    for group in groups {
+        let nibble = group.iter().fold(0usize, |nibble, byte| {
+            (nibble << 1) | usize::from((byte >> bit_index.position()) & 1)
+        });
+        histogram[nibble] += 1;
+    }
+    if count == 0 {
+        return 0.0;
+    }
+    let entropy: f64 = histogram
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / count as f64;
+            -p * p.log2()
+        })
+        .sum();
+    entropy / 4.0
+}
+
+/// Header and body reader of an embedded payload, see
+/// [`LSBEmbedder::open_body`].
+struct OpenedBody<'a> {
+    header: header::CompleteHeader,
+    header_size: usize,
+    body: BodyEmbedder<'a>,
+    // Whether the declared body fit in the carrier; only false when opened
+    // leniently, the header then declaring the truncated size
+    size_fits: bool,
+}
+
+fn check_channel_bits(channel_bits: Option<&[BitIndex]>) -> Result<(), PngerError> {
+    match channel_bits {
+        Some(channel_bits) if channel_bits.is_empty() || channel_bits.len() > MAX_CHANNELS => {
+            Err(PngerError::UnsupportedMode(format!(
+                "{} channel bit indices given, expected 1 to {MAX_CHANNELS}",
+                channel_bits.len()
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn chaff_unsupported() -> PngerError {
+    PngerError::UnsupportedMode(
+        "Chaff requires a password or manual seed and a single bit index".to_string(),
+    )
+}
+
+/// XORs the target bit of every carrier byte with the chaff keystream of
+/// `seed`. Applying it twice restores the carrier.
+fn apply_chaff(image_data: &mut [u8], seed: &[u8; SEED_SIZE], bit_index: BitIndex) {
+    use rand::RngCore;
+
+    let mut rng = rand_chacha::ChaCha20Rng::from_seed(*seed);
+    rng.set_stream(CHAFF_STREAM);
+    let mask = 1u8 << bit_index.position();
+    let mut keystream = [0u8; 4096];
+    for chunk in image_data.chunks_mut(keystream.len()) {
+        let keystream = &mut keystream[..chunk.len()];
+        rng.fill_bytes(keystream);
+        for (byte, key) in chunk.iter_mut().zip(keystream.iter()) {
+            *byte ^= key & mask;
+        }
+    }
+}
+
+/// Runs `read` on the carrier bytes of the parity of `config`, with the
+/// chaff of `config` removed, then restores them, so readers leave the
+/// caller's buffer unchanged.
+fn without_chaff<T>(
+    image_data: &mut [u8],
+    config: &LSBConfig,
+    read: impl FnOnce(&mut [u8]) -> Result<T, PngerError>,
+) -> Result<T, PngerError> {
+    with_parity(image_data, config.parity, |carrier| {
+        with_chaff_removed(carrier, config, read)
+    })
+}
+
+/// Runs `f` on the carrier bytes of `parity` only, see
+/// [`LSBConfig::with_parity`], then writes them back. Without a parity, `f`
+/// gets the whole carrier.
+fn with_parity<T>(
+    image_data: &mut [u8],
+    parity: Option<Parity>,
+    f: impl FnOnce(&mut [u8]) -> Result<T, PngerError>,
+) -> Result<T, PngerError> {
+    let Some(parity) = parity else {
+        return f(image_data);
+    };
+    // Indices are mapped back to the whole carrier, which must fit them
+    check_carrier_len(image_data.len())?;
+    let mut carrier: Vec<u8> = image_data
+        .iter()
+        .skip(parity.offset())
+        .step_by(2)
+        .copied()
+        .collect();
+    let result = f(&mut carrier);
+    let bytes = image_data.iter_mut().skip(parity.offset()).step_by(2);
+    for (byte, value) in bytes.zip(carrier) {
+        *byte = value;
+    }
+    result
+}
+
+/// Maps indices within the carrier bytes of `parity` back to the whole
+/// carrier.
+fn parity_indices(indices: Vec<u32>, parity: Option<Parity>) -> Vec<u32> {
+    match parity {
+        Some(parity) => indices
+            .into_iter()
+            .map(|index| index * 2 + parity.offset() as u32)
+            .collect(),
+        None => indices,
+    }
+}
+
+/// Runs `read` on the carrier with the chaff of `config` removed, then
+/// restores it.
+fn with_chaff_removed<T>(
+    image_data: &mut [u8],
+    config: &LSBConfig,
+    read: impl FnOnce(&mut [u8]) -> Result<T, PngerError>,
+) -> Result<T, PngerError> {
+    if !config.chaff {
+        return read(image_data);
+    }
+    let seed = match &config.pattern {
+        _ if config.channel_bits.is_some() => return Err(chaff_unsupported()),
+        EmbeddingPattern::Random(RandomConfig {
+            seed_source: SeedSource::Password(password),
+            ..
+        }) => crypto::CryptoContext::derive_seed_from_password(password, config.max_kdf_memory)
+            .map_err(|e| PngerError::CryptoError(e.to_string()))?,
+        EmbeddingPattern::Random(RandomConfig {
+            seed_source: SeedSource::Manual(seed),
+            ..
+        }) => *seed,
+        #[cfg(any(test, feature = "testing"))]
+        EmbeddingPattern::Random(RandomConfig {
+            seed_source: SeedSource::TestOnly,
+            ..
+        }) => SeedSource::test_seed(),
+        _ => return Err(chaff_unsupported()),
+    };
+
+    apply_chaff(image_data, &seed, config.bit_index);
+    let result = read(image_data);
+    apply_chaff(image_data, &seed, config.bit_index);
+    result
+}
+
+/// Drops the candidates whose carrier byte is near saturation, see
+/// [`LSBConfig::with_skip_saturated`].
+///
+/// A byte is near saturation when its bits other than the target one are
+/// all 0 or all 1. Flipping the target bit keeps it so, which makes the
+/// exclusion identical before and after embedding.
+fn retain_unsaturated(
+    candidates: &mut Vec<u32>,
+    image_data: &[u8],
+    bit_index: BitIndex,
+    channel_bits: Option<&[BitIndex]>,
+) {
+    candidates.retain(|&index| {
+        let index = index as usize;
+        let bit = channel_bits.map_or(bit_index, |bits| bits[index % bits.len()]);
+        let others = image_data[index] & !(1 << u8::from(bit));
+        others != 0 && others | (1 << u8::from(bit)) != u8::MAX
+    });
+}
+
+/// Creates the embedder writing a `body_len`-byte body after the header laid
+/// out in `layout`, the body carrying a `payload_len`-byte payload.
+fn body_embedder<'a>(
+    image_data: &'a mut [u8],
+    layout: &header::HeaderLayout,
+    runtime_config: &RuntimeConfig,
+    payload_len: usize,
+    body_len: usize,
+) -> BodyEmbedder<'a> {
+    let mut candidates = layout.body_candidates(image_data.len());
+    if runtime_config.skip_saturated {
+        retain_unsaturated(
+            &mut candidates,
+            image_data,
+            runtime_config.bit_index,
+            runtime_config.channel_bits.as_deref(),
+        );
+    }
+    let candidates = diffuse(candidates, runtime_config.diffusion_stride);
+    let body_pattern = runtime_config
+        .pattern
+        .for_body(payload_len as PayloadSize, runtime_config.length_bound_seed);
+    BodyEmbedder::new(
+        image_data,
+        candidates,
+        &body_pattern,
+        runtime_config.bit_index,
+        body_len,
+    )
+    .with_channel_bits(runtime_config.channel_bits.clone())
+    .with_bit_order(runtime_config.bit_order)
+    .with_write_mode(runtime_config.write_mode)
+}
+
+/// Reorders linear body candidates so that consecutive bits land `stride`
+/// candidates apart, see [`LSBConfig::with_diffusion_stride`].
+fn diffuse(candidates: Vec<u32>, stride: Option<u32>) -> Vec<u32> {
+    match stride {
+        Some(stride) if stride > 1 => {
+            let stride = stride as usize;
+            (0..stride.min(candidates.len()))
+                .flat_map(|offset| candidates[offset..].iter().step_by(stride).copied())
+                .collect()
+        }
+        _ => candidates,
+    }
+}
+
+fn check_carrier_len(carrier_len: usize) -> Result<(), PngerError> {
+    if u32::try_from(carrier_len.saturating_sub(1)).is_err() {
+        return Err(PngerError::UnsupportedMode(format!(
+            "Carriers above {} bytes are not supported, got {carrier_len} bytes",
+            u64::from(u32::MAX) + 1
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that a `header_size`-byte header followed by `copies` copies of a
+/// body, `metadata_len` bytes of metadata then a `payload_len`-byte payload,
+/// fit in a `carrier_len`-byte carrier.
+///
+/// Since the carrier is addressed with 32-bit indices, payloads above
+/// `u32::MAX` bytes are always rejected here rather than truncated.
+fn check_capacity(
+    carrier_len: usize,
+    header_size: usize,
+    payload_len: usize,
+    metadata_len: usize,
+    copies: usize,
+) -> Result<(), PngerError> {
+    check_carrier_len(carrier_len)?;
+
+    // Tiny covers may not even hold the header
+    let body_capacity = header_size
+        .checked_mul(8)
+        .and_then(|header_bits| carrier_len.checked_sub(header_bits))
+        .ok_or(PngerError::InsufficientCapacity)?;
+
+    let body_bits = payload_len
+        .checked_add(metadata_len)
+        .and_then(|bytes| bytes.checked_mul(copies))
+        .and_then(|bytes| bytes.checked_mul(8));
+    debug_event!(
+        "capacity: {carrier_len} carrier bytes, {header_size}-byte header, \
+         {body_capacity} body bits available, {body_bits:?} needed"
+    );
+    if body_bits.is_none_or(|bits| bits > body_capacity) {
+        return Err(PngerError::PayloadTooLarge {
+            size: payload_len,
+            capacity: (body_capacity / 8 / copies.max(1)).saturating_sub(metadata_len),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lsb_noise_randomizes_unused_carrier() {
+        let payload = b"tiny";
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::random().with_password("noise".to_string()),
+        ] {
+            let config = config.with_lsb_noise(true);
+            let mut image_data = vec![0u8; 8000];
+            let result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            // The tail of the carrier holds no payload bits in a linear
+            // layout; with noise, about half of its LSBs are now set
+            let ones = image_data[result.bytes_used..]
+                .iter()
+                .filter(|byte| *byte & 1 == 1)
+                .count();
+            let unused = image_data.len() - result.bytes_used;
+            assert!(
+                ones > unused / 3 && ones < unused * 2 / 3,
+                "{ones}/{unused}"
+            );
+            // Only the target bit is touched
+            assert!(image_data.iter().all(|byte| *byte <= 1));
+
+            let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extracted.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_chaff_maximizes_bit_plane_entropy() {
+        let payload = b"tiny";
+        let config = LSBConfig::random().with_seed([5u8; SEED_SIZE]);
+        let entropy = |image_data: &[u8]| {
+            let ones = image_data.iter().filter(|byte| *byte & 1 == 1).count();
+            let p = ones as f64 / image_data.len() as f64;
+            -[p, 1.0 - p]
+                .iter()
+                .filter(|p| **p > 0.0)
+                .map(|p| p * p.log2())
+                .sum::<f64>()
+        };
+
+        let mut plain = vec![0u8; 8000];
+        LSBEmbedder::embed(&mut plain, payload, &config).unwrap();
+        assert!(entropy(&plain) < 0.1, "{}", entropy(&plain));
+
+        let config = config.with_chaff(true);
+        let mut image_data = vec![0u8; 8000];
+        LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+        assert!(entropy(&image_data) > 0.99, "{}", entropy(&image_data));
+        assert!(image_data.iter().all(|byte| *byte <= 1));
+
+        // Readers restore the chaff, leaving the buffer unchanged
+        let embedded = image_data.clone();
+        assert!(LSBEmbedder::contains_payload(&mut image_data, &config));
+        let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+        assert_eq!(extracted.payload, payload);
+        assert_eq!(image_data, embedded);
+
+        // The header cannot be found without the chaff seed
+        let other = LSBConfig::random().with_seed([5u8; SEED_SIZE]);
+        assert!(!LSBEmbedder::contains_payload(&mut image_data, &other));
+    }
+
+    #[test]
+    fn test_chaff_requires_known_seed() {
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::random(),
+            LSBConfig::random()
+                .with_seed([5u8; SEED_SIZE])
+                .with_channel_bit_indices([BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit0]),
+        ] {
+            let config = config.with_chaff(true);
+            let mut image_data = vec![0u8; 4000];
+            assert!(matches!(
+                LSBEmbedder::embed(&mut image_data, b"payload", &config),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+            assert!(matches!(
+                LSBEmbedder::extract(&mut image_data, &config),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_diffusion_stride_spreads_linear_body() {
+        let config = LSBConfig::linear().with_diffusion_stride(7);
+        let payload = [0xFF; 12];
+        let mut image_data = vec![0u8; 1000];
+        let result = LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+        assert_eq!(
+            LSBEmbedder::extract(&mut image_data, &LSBConfig::linear())
+                .unwrap()
+                .payload,
+            payload
+        );
+
+        // Past the linear header, no two modified bytes are adjacent
+        let body_start = result.header_size * 8;
+        let modified: Vec<usize> = (body_start..image_data.len())
+            .filter(|&index| image_data[index] != 0)
+            .collect();
+        assert_eq!(modified.len(), payload.len() * 8);
+        assert!(modified.windows(2).all(|pair| pair[1] - pair[0] > 1));
+
+        let planned = LSBEmbedder::planned_indices(1000, &config, payload.len()).unwrap();
+        let body = &planned[body_start..];
+        assert!(body.windows(2).all(|pair| pair[1] == pair[0] + 7));
+        assert_eq!(
+            body.iter().map(|&index| index as usize).collect::<Vec<_>>(),
+            modified
+        );
+
+        // Random patterns ignore the stride
+        let random = LSBConfig::random()
+            .with_seed([3; SEED_SIZE])
+            .with_diffusion_stride(7);
+        assert_eq!(
+            LSBEmbedder::planned_indices(1000, &random, 12).unwrap(),
+            LSBEmbedder::planned_indices(1000, &LSBConfig::random().with_seed([3; SEED_SIZE]), 12)
+                .unwrap()
+        );
+        assert!(matches!(
+            LSBEmbedder::embed(
+                &mut image_data,
+                &payload,
+                &LSBConfig::linear().with_diffusion_stride(0)
+            ),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_payload_ending_at_carrier_boundary() {
+        let payload = [0x5Au8; 37];
+        let configs = [
+            LSBConfig::linear(),
+            LSBConfig::random().with_seed([3u8; SEED_SIZE]),
+            LSBConfig::random()
+                .with_password("boundary".to_string())
+                .with_scattered_header()
+                .with_redundancy(3),
+            LSBConfig::linear().with_bit_order(BitOrder::MsbFirst),
+        ];
+
+        for config in configs {
+            let copies = usize::from(config.redundancy());
+            let exact = config.header_overhead() + payload.len() * 8 * copies;
+            assert_eq!(config.max_capacity(exact), payload.len());
+            assert_eq!(config.max_capacity(exact + 8 * copies - 1), payload.len());
+
+            // The last payload bit lands on the last carrier byte
+            let mut image_data = vec![0u8; exact];
+            LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+            let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extracted.payload, payload);
+
+            // One carrier byte short fails cleanly instead of panicking
+            let mut image_data = vec![0u8; exact - 1];
+            let result = LSBEmbedder::embed(&mut image_data, &payload, &config);
+            assert!(matches!(result, Err(PngerError::PayloadTooLarge { .. })));
+        }
+    }
+
+    #[test]
+    fn test_external_seed_recovers_clobbered_seed() {
+        let mut image_data = vec![0u8; 2000];
+        let payload = b"escrow me";
+        let config = LSBConfig::random().with_scattered_header();
+        LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+        let seed = LSBEmbedder::export_seed(&mut image_data, &config).unwrap();
+
+        // Zero the carrier bytes holding the embedded seed, right after the
+        // bootstrap at the start of the header
+        let seed_start = header::BOOTSTRAP_SIZE * 8;
+        image_data[seed_start..seed_start + SEED_SIZE * 8].fill(0);
+        assert!(LSBEmbedder::extract(&mut image_data, &config).is_err());
+
+        let recovery = LSBConfig::random().with_external_seed(seed);
+        let extracted = LSBEmbedder::extract(&mut image_data, &recovery).unwrap();
+        assert_eq!(extracted.payload, payload);
+
+        let mut linear_image = vec![0u8; 2000];
+        LSBEmbedder::embed(&mut linear_image, payload, &LSBConfig::linear()).unwrap();
+        assert!(matches!(
+            LSBEmbedder::export_seed(&mut linear_image, &LSBConfig::linear()),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_bit_order_roundtrip() {
+        let payload = b"bit order";
+        let mut carriers = Vec::new();
+        for bit_order in [BitOrder::LsbFirst, BitOrder::MsbFirst] {
+            for config in [LSBConfig::linear(), LSBConfig::random().with_test_seed()] {
+                let config = config.with_bit_order(bit_order);
+                let mut image_data = vec![0u8; 2000];
+                LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+                // The order comes from the header, not the extraction config
+                let extracted = LSBEmbedder::extract(
+                    &mut image_data,
+                    &config.with_bit_order(BitOrder::LsbFirst),
+                )
+                .unwrap();
+                assert_eq!(extracted.payload, payload);
+            }
+
+            let mut image_data = vec![0u8; 2000];
+            let config = LSBConfig::linear().with_bit_order(bit_order);
+            let result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+            assert_eq!(result.header_size, config.header_overhead() / 8);
+            let body_start = result.header_size * 8;
+            carriers.push(image_data[body_start..body_start + payload.len() * 8].to_vec());
+        }
+        assert_ne!(carriers[0], carriers[1]);
+
+        // The first byte of an MSB-first body carries the high bit first
+        let first_bits: Vec<u8> = carriers[1][..8].iter().map(|byte| byte & 1).collect();
+        assert_eq!(first_bits, [0, 1, 1, 0, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_capacity_rejects_u32_overflowing_sizes() {
+        let max = u32::MAX as usize;
+
+        // The largest addressable carrier still needs room for the body
+        assert!(check_capacity(max + 1, FIXED_HEADER_SIZE, 1024, 0, 1).is_ok());
+        for payload_len in [max, max + 1, usize::MAX] {
+            assert!(matches!(
+                check_capacity(max + 1, FIXED_HEADER_SIZE, payload_len, 0, 1),
+                Err(PngerError::PayloadTooLarge { .. })
+            ));
+        }
+        assert!(matches!(
+            check_capacity(max + 1, FIXED_HEADER_SIZE, max / 8, 0, 2),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+
+        // One more byte could not be addressed
+        assert!(matches!(
+            check_capacity(max + 2, FIXED_HEADER_SIZE, 1024, 0, 1),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            LSBEmbedder::planned_indices(max + 2, &LSBConfig::linear(), 4),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            LSBEmbedder::planned_indices(2000, &LSBConfig::linear(), max + 1),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_raw_without_header() {
+        let payload = b"headerless recovery";
+        for (config, seed) in [
+            (LSBConfig::linear().with_bit_index(BitIndex::Bit2), None),
+            (
+                LSBConfig::random()
+                    .with_seed([4u8; SEED_SIZE])
+                    .with_bit_index(BitIndex::Bit2),
+                Some([4u8; SEED_SIZE]),
+            ),
+        ] {
+            let mut image_data: Vec<u8> = (0..2000).map(|i| (i * 13 % 256) as u8).collect();
+            LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            // Overwrite every header byte
+            let body_offset = config.header_overhead();
+            image_data[..body_offset].fill(0xFF);
+            assert!(LSBEmbedder::extract(&mut image_data, &config).is_err());
+
+            let recovered = LSBEmbedder::extract_raw(
+                &mut image_data,
+                seed,
+                BitIndex::Bit2,
+                payload.len(),
+                body_offset,
+            )
+            .unwrap();
+            assert_eq!(recovered, payload);
+        }
+
+        let mut image_data = vec![0u8; 100];
+        assert!(matches!(
+            LSBEmbedder::extract_raw(&mut image_data, None, BitIndex::Bit0, 12, 8),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+        assert!(LSBEmbedder::extract_raw(&mut image_data, None, BitIndex::Bit0, 11, 8).is_ok());
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let correct = LSBConfig::random().with_password("correct horse".to_string());
+        let wrong = LSBConfig::random().with_password("battery staple".to_string());
+        for scattered in [false, true] {
+            let config = correct.clone().with_header_linear(!scattered);
+            let mut image_data = vec![0u8; 4000];
+            LSBEmbedder::embed(&mut image_data, b"large payload", &config).unwrap();
+
+            assert!(LSBEmbedder::verify_password(&mut image_data, &correct).unwrap());
+            assert!(!LSBEmbedder::verify_password(&mut image_data, &wrong).unwrap());
+        }
+
+        // Payloads embedded without a password have no verifier
+        let mut image_data = vec![0u8; 4000];
+        LSBEmbedder::embed(&mut image_data, b"payload", &LSBConfig::linear()).unwrap();
+        assert!(matches!(
+            LSBEmbedder::verify_password(&mut image_data, &wrong),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            LSBEmbedder::verify_password(&mut image_data, &LSBConfig::linear()),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+
+    #[test]
+    fn test_payload_tag_roundtrip() {
+        let payload = b"tagged";
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::linear().with_wire_format(WireFormat::LittleEndian),
+            LSBConfig::random()
+                .with_test_seed()
+                .with_scattered_header()
+                .with_bit_order(BitOrder::MsbFirst),
+        ] {
+            let mut image_data = vec![0u8; 2000];
+            let tagged = config.clone().with_payload_tag(0x1234);
+            let result = LSBEmbedder::embed(&mut image_data, payload, &tagged).unwrap();
+            assert_eq!(result.header_size * 8, tagged.header_overhead());
+
+            let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extracted.payload, payload);
+            assert_eq!(extracted.tag, Some(0x1234));
+
+            LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+            assert_eq!(
+                LSBEmbedder::extract(&mut image_data, &config).unwrap().tag,
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn test_redundant_length_recovers_either_copy() {
+        let payload = b"length survives";
+        // Flips the target bit of each carrier byte of header bytes `range`
+        let corrupt = |image_data: &mut [u8], range: std::ops::Range<usize>| {
+            range.for_each(|byte| image_data[byte * 8] ^= 1);
+        };
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::linear().with_wire_format(WireFormat::LittleEndian),
+            LSBConfig::linear().with_payload_tag(0x1234),
+        ] {
+            let redundant = config.clone().with_redundant_length(true);
+            let mut image_data = vec![0u8; 2000];
+            let header_size = LSBEmbedder::embed(&mut image_data, payload, &redundant)
+                .unwrap()
+                .header_size;
+            assert_eq!(header_size * 8, redundant.header_overhead());
+            let primary = 8..12;
+            let copy = header_size - 4..header_size;
+
+            for corrupted in [primary.clone(), copy] {
+                let mut damaged = image_data.clone();
+                corrupt(&mut damaged, corrupted);
+                let extracted = LSBEmbedder::extract(&mut damaged, &config).unwrap();
+                assert_eq!(extracted.payload, payload);
+            }
+
+            // Both copies corrupted, or a single copy: the checksum fails
+            let mut damaged = image_data.clone();
+            corrupt(&mut damaged, primary.clone());
+            corrupt(&mut damaged, header_size - 1..header_size);
+            assert!(LSBEmbedder::extract(&mut damaged, &config).is_err());
+            LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+            corrupt(&mut image_data, primary);
+            assert!(LSBEmbedder::extract(&mut image_data, &config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tiny_buffers_report_no_capacity() {
+        for config in [LSBConfig::linear(), LSBConfig::random().with_test_seed()] {
+            for len in [0, 2, 4] {
+                assert_eq!(config.max_capacity(len), 0);
+
+                let mut image_data = vec![0u8; len];
+                assert!(matches!(
+                    LSBEmbedder::embed(&mut image_data, b"x", &config),
+                    Err(PngerError::InsufficientCapacity)
+                ));
+                assert!(matches!(
+                    LSBEmbedder::embed(&mut image_data, b"", &config),
+                    Err(PngerError::InsufficientCapacity)
+                ));
+                assert!(LSBEmbedder::extract(&mut image_data, &config).is_err());
+                assert!(!LSBEmbedder::contains_payload(&mut image_data, &config));
+                assert!(LSBEmbedder::occupied_bit_indices(&mut image_data, &config).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_linear_header_with_random_body() {
+        let config = LSBConfig::random().with_test_seed();
+        assert!(config.is_header_linear());
+        let header_bits = config.header_overhead();
+
+        let indices = LSBEmbedder::planned_indices(2000, &config, 32).unwrap();
+        let (header, body) = indices.split_at(header_bits);
+        assert!(
+            header
+                .iter()
+                .enumerate()
+                .all(|(i, &index)| index as usize == i)
+        );
+        assert!(body.iter().all(|&index| index as usize >= header_bits));
+        assert!(!body.windows(2).all(|pair| pair[1] == pair[0] + 1));
+
+        let scattered = config.with_header_linear(false);
+        let indices = LSBEmbedder::planned_indices(2000, &scattered, 32).unwrap();
+        assert!(
+            !indices[..header_bits]
+                .iter()
+                .enumerate()
+                .all(|(i, &index)| index as usize == i)
+        );
     }
 
-    /// Convenience method for password-protected extraction.
-    ///
-    /// Uses random pattern with password-derived seed matching the
-    /// password used during embedding.
-    ///
-    /// # Parameters
-    /// - `image_data`: Image data containing embedded payload
-    /// - `password`: Password used during embedding
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use pnger::strategy::lsb::LSBEmbedder;
-    ///
-    /// let mut image = vec![0u8; 1000];
-    ///
-    /// // First embed with password
-    /// LSBEmbedder::embed_with_password(&mut image, b"secret", "my_password").unwrap();
-    ///
-    /// // Then extract with same password
-    /// let result = LSBEmbedder::extract_with_password(&mut image, "my_password").unwrap();
-    /// ```
-    ///
-    /// # Errors
-    ///
-    /// This function will return an error if:
-    /// - `PngerError::InvalidFormat`: Corrupted or missing header
-    /// - `PngerError::CryptoError`: Wrong password or derivation failure  
-    /// - `PngerError::InsufficientData`: Image smaller than expected payload
-    pub fn extract_with_password(
-        image_data: &mut [u8],
-        password: &str,
-    ) -> Result<ExtractResult, PngerError> {
-        let config = LSBConfig::random().with_password(password.to_string());
-        Self::extract(image_data, &config)
+    #[test]
+    fn test_planned_indices() {
+        let payload = b"planned";
+        let linear =
+            LSBEmbedder::planned_indices(2000, &LSBConfig::linear(), payload.len()).unwrap();
+        assert_eq!(
+            linear.len(),
+            LSBConfig::linear().header_overhead() + payload.len() * 8
+        );
+        assert!(
+            linear
+                .iter()
+                .enumerate()
+                .all(|(i, &index)| index as usize == i)
+        );
+
+        let config = LSBConfig::random().with_test_seed();
+        let random = LSBEmbedder::planned_indices(2000, &config, payload.len()).unwrap();
+        assert_eq!(random.len(), linear.len());
+        assert_ne!(random, linear);
+        let mut unique = random.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), random.len());
+
+        // The plan covers exactly the bytes an embedding modifies
+        let original: Vec<u8> = (0..2000).map(|i| (i % 251) as u8).collect();
+        let mut image_data = original.clone();
+        let inverted: Vec<u8> = payload.iter().map(|byte| !byte).collect();
+        LSBEmbedder::embed(&mut image_data, &inverted, &config).unwrap();
+        let changed = original
+            .iter()
+            .zip(&image_data)
+            .enumerate()
+            .filter(|(_, (before, after))| before != after);
+        assert!(
+            changed
+                .into_iter()
+                .all(|(i, _)| random.contains(&(i as u32)))
+        );
+
+        assert!(matches!(
+            LSBEmbedder::planned_indices(2000, &config, 2000),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_forced_auto_seed_is_deterministic() {
+        let forced_seed = [0x5Cu8; SEED_SIZE];
+        let payload = b"auto";
+        let config = LSBConfig::random();
+        let embed = || {
+            crypto::with_forced_seed(forced_seed, || {
+                let mut image_data = vec![0u8; 1000];
+                let result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+                assert!(result.seed_embedded);
+                image_data
+            })
+        };
+
+        let image_data = embed();
+        assert_eq!(image_data, embed());
+
+        // Header bytes are stored linearly, LSB-first, in bit 0: the
+        // bootstrap, the payload size and checksum, then the seed
+        let bits = |bytes: &[u8]| -> Vec<u8> {
+            bytes
+                .iter()
+                .flat_map(|byte| (0..8).map(move |bit| (byte >> bit) & 1))
+                .collect()
+        };
+        let flags = header::HeaderFlags::RANDOM_PATTERN | header::HeaderFlags::SEED_EMBEDDED;
+        let bootstrap = bits(&[DEFAULT_MAGIC.as_slice(), &[2, flags.bits()]].concat());
+        assert_eq!(image_data[..bootstrap.len()], bootstrap);
+        assert_eq!(
+            image_data[header::BOOTSTRAP_SIZE * 8..][..32],
+            bits(&(payload.len() as u32).to_be_bytes())
+        );
+        let seed_start = FIXED_HEADER_SIZE * 8;
+        assert_eq!(
+            image_data[seed_start..seed_start + SEED_SIZE * 8],
+            bits(&forced_seed)
+        );
+
+        let mut image_data = image_data;
+        let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+        assert_eq!(extracted.payload, payload);
+        assert_ne!(
+            crypto::CryptoContext::generate_random_seed().unwrap(),
+            forced_seed
+        );
+    }
+
+    #[test]
+    fn test_test_only_seed_matches_manual_test_seed() {
+        let mut image_data = vec![0u8; 2000];
+        let payload = b"deterministic";
+        let config = LSBConfig::random().with_test_seed();
+        LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+        let manual = LSBConfig::random().with_seed(TEST_SEED);
+        let extracted = LSBEmbedder::extract(&mut image_data, &manual).unwrap();
+        assert_eq!(extracted.payload, payload);
+    }
+
+    #[test]
+    fn test_embed_with_layout_skips_row_padding() {
+        const PADDING: u8 = 0xA5;
+        let layout = PixelLayout {
+            width: 16,
+            height: 16,
+            channels: 4,
+            row_stride: 16 * 4 + 3,
+        };
+        let mut image_data = vec![PADDING; layout.row_stride * layout.height];
+        let payload = b"rows with three bytes of padding";
+        let config = LSBConfig::random().with_seed([9u8; SEED_SIZE]);
+
+        LSBEmbedder::embed_with_layout(&mut image_data, layout, payload, &config).unwrap();
+
+        let padding_intact = image_data
+            .chunks(layout.row_stride)
+            .all(|row| row[layout.row_len()..] == [PADDING; 3]);
+        assert!(padding_intact);
+
+        let extracted = LSBEmbedder::extract_with_layout(&image_data, layout, &config).unwrap();
+        assert_eq!(extracted.payload, payload);
+
+        // Capacity is computed from the samples only
+        let too_large = vec![0u8; layout.sample_count() / 8];
+        let result = LSBEmbedder::embed_with_layout(&mut image_data, layout, &too_large, &config);
+        assert!(matches!(result, Err(PngerError::PayloadTooLarge { .. })));
+    }
 
     #[test]
     fn test_lsb_config_builder_pattern() {
@@ -1360,4 +5266,634 @@ mod tests {
             _ => panic!("Expected Random pattern"),
         }
     }
+
+    #[test]
+    fn test_try_with_password_rejects_linear() {
+        let config = LSBConfig::random()
+            .try_with_password("test".to_string())
+            .unwrap();
+        match config.pattern() {
+            EmbeddingPattern::Random(random_config) => {
+                assert!(matches!(random_config.seed_source, SeedSource::Password(_)));
+            }
+            _ => panic!("Expected Random pattern"),
+        }
+
+        assert!(matches!(
+            LSBConfig::linear().try_with_password("test".to_string()),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+
+        // The permissive setter still leaves linear configs unchanged
+        let config = LSBConfig::linear().with_password("test".to_string());
+        assert!(matches!(config.pattern(), EmbeddingPattern::Linear));
+    }
+
+    #[test]
+    fn test_scattered_header_roundtrip() {
+        let payload = b"Hello, World!";
+
+        for config in [
+            LSBConfig::random().with_scattered_header(),
+            LSBConfig::random()
+                .with_password("test_password".to_string())
+                .with_scattered_header(),
+        ] {
+            let mut image_data = vec![0u8; 1000];
+            let embed_result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            let extract_result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extract_result.payload, payload);
+            assert_eq!(extract_result.header_size, embed_result.header_size);
+        }
+    }
+
+    #[test]
+    fn test_redundancy_survives_damaged_copy() {
+        let payload = b"Hello, World!";
+        let config = LSBConfig::linear().with_redundancy(3);
+
+        let mut image_data = vec![0xAAu8; 2000];
+        let embed_result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+        assert_eq!(embed_result.header_size, 15);
+
+        // Wipe out the second copy entirely
+        let copy_bits = payload.len() * 8;
+        let second_copy = embed_result.header_size * 8 + copy_bits;
+        image_data[second_copy..second_copy + copy_bits].fill(0);
+
+        let extract_result = LSBEmbedder::extract(&mut image_data, &LSBConfig::linear()).unwrap();
+        assert_eq!(extract_result.payload, payload);
+    }
+
+    #[test]
+    fn test_redundancy_random_roundtrip() {
+        let payload = b"Hello, World!";
+
+        for config in [
+            LSBConfig::random().with_redundancy(3),
+            LSBConfig::random()
+                .with_password("test_password".to_string())
+                .with_scattered_header()
+                .with_redundancy(5),
+        ] {
+            let mut image_data = vec![0u8; 2000];
+            LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            let extract_result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extract_result.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_channel_bit_indices_roundtrip() {
+        let channel_bits = [BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit1];
+        let payload = [0xFFu8; 16];
+
+        // RGB pixels: red carries the LSB, green and blue the second bit
+        let mut image_data = vec![0u8; 3 * 200];
+        let config = LSBConfig::linear().with_channel_bit_indices(channel_bits);
+        let result = LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+
+        let body_start = result.header_size * 8;
+        for (i, byte) in image_data[body_start..body_start + payload.len() * 8]
+            .iter()
+            .enumerate()
+        {
+            let expected = 1 << u8::from(channel_bits[(body_start + i) % 3]);
+            assert_eq!(*byte, expected, "carrier byte {}", body_start + i);
+        }
+
+        let extracted = LSBEmbedder::extract(&mut image_data, &LSBConfig::linear()).unwrap();
+        assert_eq!(extracted.payload, payload);
+
+        // Random patterns and scattered headers honor the mapping too
+        let config = LSBConfig::random()
+            .with_password("test_password".to_string())
+            .with_scattered_header()
+            .with_channel_bit_indices(channel_bits);
+        let mut image_data = vec![0u8; 3 * 200];
+        LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+        let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+        assert_eq!(extracted.payload, payload);
+
+        let invalid = LSBConfig::linear().with_channel_bit_indices([BitIndex::Bit0; 5]);
+        assert!(matches!(
+            LSBEmbedder::embed(&mut image_data, &payload, &invalid),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+
+    #[test]
+    fn test_streaming_extraction_matches_extract() {
+        let payload: Vec<u8> = (0..EXTRACT_CHUNK_SIZE * 2 + 123)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let config = LSBConfig::random()
+            .with_seed([5u8; SEED_SIZE])
+            .with_redundancy(3)
+            .with_payload_metadata(PayloadMetadata::new("big.bin", "application/octet-stream"));
+
+        let mut image_data = vec![0u8; (payload.len() + 64) * 3 * 8 + 1000];
+        LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+        let full = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+
+        let mut chunks = Vec::new();
+        let metadata = LSBEmbedder::extract_streaming(&mut image_data, &config, &mut |chunk| {
+            chunks.push(chunk.to_vec())
+        })
+        .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= EXTRACT_CHUNK_SIZE));
+        assert_eq!(chunks.concat(), full.payload);
+        assert_eq!(full.payload, payload);
+        assert_eq!(metadata, full.metadata);
+
+        // A header declaring more than the image holds emits nothing
+        let mut truncated = image_data[..image_data.len() / 2].to_vec();
+        let mut emitted = false;
+        let result =
+            LSBEmbedder::extract_streaming(&mut truncated, &config, &mut |_| emitted = true);
+        assert!(matches!(result, Err(PngerError::InvalidFormat(_))));
+        assert!(!emitted);
+    }
+
+    #[test]
+    fn test_empty_payload_marker() {
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::random(),
+            LSBConfig::random()
+                .with_seed([9u8; SEED_SIZE])
+                .with_scattered_header()
+                .with_redundancy(3),
+        ] {
+            // The header alone must fit, nothing more
+            let mut image_data = vec![0u8; config.header_overhead()];
+            let result = LSBEmbedder::embed(&mut image_data, &[], &config).unwrap();
+            assert_eq!(result.bytes_used, result.header_size * 8);
+            assert!(LSBEmbedder::contains_payload(&mut image_data, &config));
+
+            let extracted = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert!(extracted.payload.is_empty());
+
+            let mut chunks = 0;
+            LSBEmbedder::extract_streaming(&mut image_data, &config, &mut |_| chunks += 1).unwrap();
+            assert_eq!(chunks, 0);
+        }
+
+        let mut blank = vec![0u8; 1000];
+        assert!(!LSBEmbedder::contains_payload(
+            &mut blank,
+            &LSBConfig::linear()
+        ));
+    }
+
+    #[test]
+    fn test_occupied_bit_indices() {
+        let mut image_data = vec![0u8; 2000];
+        let password = "layer password".to_string();
+
+        let bit0 = LSBConfig::linear().with_bit_index(BitIndex::Bit0);
+        LSBEmbedder::embed(&mut image_data, b"first layer", &bit0).unwrap();
+        let bit2 = LSBConfig::random()
+            .with_password(password.clone())
+            .with_bit_index(BitIndex::Bit2);
+        LSBEmbedder::embed(&mut image_data, b"second layer", &bit2).unwrap();
+
+        let occupied = LSBEmbedder::occupied_bit_indices(&mut image_data, &LSBConfig::random());
+        assert_eq!(occupied, [BitIndex::Bit0, BitIndex::Bit2]);
+
+        // Scattered headers are only found with the right seed
+        let scattered = LSBConfig::random()
+            .with_password(password.clone())
+            .with_scattered_header()
+            .with_bit_index(BitIndex::Bit5);
+        LSBEmbedder::embed(&mut image_data, b"third layer", &scattered).unwrap();
+        let occupied = LSBEmbedder::occupied_bit_indices(&mut image_data, &LSBConfig::random());
+        assert_eq!(occupied, [BitIndex::Bit0, BitIndex::Bit2]);
+        let occupied = LSBEmbedder::occupied_bit_indices(
+            &mut image_data,
+            &LSBConfig::random().with_password(password),
+        );
+        assert_eq!(occupied, [BitIndex::Bit0, BitIndex::Bit2, BitIndex::Bit5]);
+    }
+
+    #[test]
+    fn test_namespace_separation() {
+        let payload = b"Hello, World!";
+        let custom = *b"ACME";
+
+        for (embed_config, extract_config) in [
+            (
+                LSBConfig::linear().with_namespace(custom),
+                LSBConfig::linear(),
+            ),
+            (
+                LSBConfig::linear(),
+                LSBConfig::linear().with_namespace(custom),
+            ),
+            (
+                LSBConfig::random().with_namespace(custom),
+                LSBConfig::random(),
+            ),
+            (
+                LSBConfig::random()
+                    .with_password("test_password".to_string())
+                    .with_scattered_header(),
+                LSBConfig::random()
+                    .with_password("test_password".to_string())
+                    .with_namespace(custom),
+            ),
+        ] {
+            let mut image_data = vec![0u8; 1000];
+            LSBEmbedder::embed(&mut image_data, payload, &embed_config).unwrap();
+
+            let result = LSBEmbedder::extract(&mut image_data, &extract_config);
+            assert!(matches!(result, Err(PngerError::InvalidFormat(_))));
+
+            let result = LSBEmbedder::extract(&mut image_data, &embed_config).unwrap();
+            assert_eq!(result.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_truncated_image_returns_error() {
+        let payload = b"Hello, World!";
+
+        for config in [
+            LSBConfig::random(),
+            LSBConfig::random().with_scattered_header(),
+            LSBConfig::linear().with_redundancy(3),
+        ] {
+            let mut image_data = vec![0u8; 2000];
+            let embed_result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            // Every truncation short of the full embedding must fail cleanly
+            for len in 0..embed_result.bytes_used {
+                let mut truncated = image_data[..len].to_vec();
+                let result = LSBEmbedder::extract(&mut truncated, &config);
+                assert!(
+                    matches!(result, Err(PngerError::InvalidFormat(_))),
+                    "len {len}: {result:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_payload_metadata_roundtrip() {
+        let payload = b"%PDF-1.7 not really a pdf";
+        let metadata = PayloadMetadata::new("report.pdf", "application/pdf");
+
+        for config in [
+            LSBConfig::linear(),
+            LSBConfig::random().with_scattered_header(),
+            LSBConfig::random()
+                .with_password("test_password".to_string())
+                .with_redundancy(3),
+        ] {
+            let config = config.with_payload_metadata(metadata.clone());
+            let mut image_data = vec![0u8; 4000];
+            let embed_result = LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            let extract_result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(extract_result.payload, payload);
+            assert_eq!(extract_result.metadata.as_ref(), Some(&metadata));
+            assert_eq!(extract_result.header_size, embed_result.header_size);
+        }
+
+        // Payloads without metadata report none
+        let mut image_data = vec![0u8; 1000];
+        LSBEmbedder::embed(&mut image_data, payload, &LSBConfig::linear()).unwrap();
+        let extract_result = LSBEmbedder::extract(&mut image_data, &LSBConfig::linear()).unwrap();
+        assert!(extract_result.metadata.is_none());
+    }
+
+    #[test]
+    fn test_max_payload_rejects_huge_declared_size() {
+        let mut image_data = vec![0u8; 1000];
+        let config = LSBConfig::linear();
+
+        // Forge a header claiming a payload far larger than the image
+        let runtime_config = RuntimeConfig::from_config(&config).unwrap();
+        header::HeaderEmbedder::new(&mut image_data, runtime_config)
+            .embed(PayloadSize::MAX)
+            .unwrap();
+
+        let capped = config.with_max_payload(1024);
+        match LSBEmbedder::extract(&mut image_data, &capped) {
+            Err(PngerError::InvalidFormat(message)) => assert!(message.contains("limit")),
+            other => panic!("Expected InvalidFormat, got {other:?}"),
+        }
+
+        // Payloads within the cap are extracted normally
+        let payload = b"Hello, World!";
+        LSBEmbedder::embed(&mut image_data, payload, &LSBConfig::linear()).unwrap();
+        let result = LSBEmbedder::extract(&mut image_data, &capped).unwrap();
+        assert_eq!(result.payload, payload);
+    }
+
+    #[test]
+    fn test_skip_saturated_roundtrip() {
+        // Every other byte is pure black or white, the rest mid-gray
+        let cover: Vec<u8> = (0..6000)
+            .map(|i| match i % 4 {
+                0 => 0,
+                2 => 255,
+                _ => 100 + (i % 7) as u8,
+            })
+            .collect();
+        let payload = b"kept away from the extremes";
+
+        for config in [
+            LSBConfig::linear().with_skip_saturated(true),
+            LSBConfig::random()
+                .with_password("saturated".to_string())
+                .with_bit_index(BitIndex::Bit1)
+                .with_skip_saturated(true),
+        ] {
+            let mut image_data = cover.clone();
+            LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+
+            // Only header bytes may touch the saturated samples
+            let header_len = header::HeaderEmbedder::required_size(
+                &RuntimeConfig::from_config(&config).unwrap(),
+            ) * 8;
+            for (index, (&before, &after)) in cover.iter().zip(&image_data).enumerate() {
+                if before % 255 == 0 && index >= header_len {
+                    assert_eq!(before, after, "saturated byte {index} changed");
+                }
+            }
+
+            // The header records the choice, so defaults extract it
+            let defaults = config.clone().with_skip_saturated(false);
+            let result = LSBEmbedder::extract(&mut image_data, &defaults).unwrap();
+            assert_eq!(result.payload, payload);
+        }
+
+        // Half the body is excluded, which halves the capacity
+        let mut image_data = cover.clone();
+        let config = LSBConfig::linear().with_skip_saturated(true);
+        assert!(matches!(
+            LSBEmbedder::embed(&mut image_data, &[7; 500], &config),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+        assert_eq!(image_data, cover);
+        assert!(LSBEmbedder::embed(&mut image_data, &[7; 500], &LSBConfig::linear()).is_ok());
+        assert!(matches!(
+            LSBEmbedder::planned_indices(image_data.len(), &config, 10),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+
+    #[test]
+    fn test_extract_until_sentinel() {
+        let sentinel = b"<END>";
+        let raw = b"no length prefix here";
+        let config = LSBConfig::random()
+            .with_seed([9; SEED_SIZE])
+            .with_bit_index(BitIndex::Bit2);
+
+        // Write the raw data and sentinel the way a headerless tool would
+        let mut image_data = vec![0x55u8; 2000];
+        let runtime_config = RuntimeConfig::from_config(&config).unwrap();
+        let candidates = (0..image_data.len() as u32).collect();
+        let capacity = image_data.len() / 8;
+        BodyEmbedder::new(
+            &mut image_data,
+            candidates,
+            &runtime_config.pattern,
+            BitIndex::Bit2,
+            capacity,
+        )
+        .embed_payload(&[raw.as_slice(), sentinel, b"trailing garbage"].concat())
+        .unwrap();
+
+        let data = LSBEmbedder::extract_until_sentinel(&mut image_data, sentinel, &config).unwrap();
+        assert_eq!(data, raw);
+
+        // Reading is capped at the carrier capacity
+        match LSBEmbedder::extract_until_sentinel(&mut image_data, b"missing", &config) {
+            Err(PngerError::InvalidFormat(message)) => assert!(message.contains("250-byte")),
+            other => panic!("Expected InvalidFormat, got {other:?}"),
+        }
+        assert!(matches!(
+            LSBEmbedder::extract_until_sentinel(&mut image_data, b"", &config),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            LSBEmbedder::extract_until_sentinel(&mut image_data, sentinel, &LSBConfig::random()),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+
+    #[test]
+    fn test_length_bound_seed() {
+        let body = |config: &LSBConfig, len: usize| {
+            let planned = LSBEmbedder::planned_indices(4000, config, len).unwrap();
+            planned[planned.len() - len * 8..][..80].to_vec()
+        };
+        let config = LSBConfig::random().with_seed([3; SEED_SIZE]);
+        let bound = config.clone().with_length_bound_seed(true);
+        assert_ne!(body(&bound, 10), body(&bound, 11));
+        assert_ne!(body(&bound, 10), body(&config, 10));
+        assert_eq!(body(&bound, 10), body(&bound, 10));
+
+        // Extraction follows the header, whatever the extraction config says
+        let password = LSBConfig::random().with_password("length".to_string());
+        for payload in [&b"short"[..], b"a longer payload"] {
+            let mut image_data = vec![0x33u8; 4000];
+            let bound = password.clone().with_length_bound_seed(true);
+            LSBEmbedder::embed(&mut image_data, payload, &bound).unwrap();
+            let result = LSBEmbedder::extract(&mut image_data, &password).unwrap();
+            assert_eq!(result.payload, payload);
+        }
+    }
+
+    #[test]
+    fn test_parity_leaves_other_bytes_untouched() {
+        let cover: Vec<u8> = (0..3001u32).map(|i| (i * 7 % 251) as u8).collect();
+        for (parity, other) in [(Parity::Even, 1), (Parity::Odd, 0)] {
+            let config = LSBConfig::random()
+                .with_seed([9; SEED_SIZE])
+                .with_parity(parity)
+                .with_lsb_noise(true);
+            let mut image_data = cover.clone();
+            LSBEmbedder::embed(&mut image_data, b"one parity only", &config).unwrap();
+            let untouched = |data: &[u8]| {
+                data.iter()
+                    .skip(other)
+                    .step_by(2)
+                    .copied()
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(untouched(&image_data), untouched(&cover));
+            assert_ne!(image_data, cover);
+
+            let result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+            assert_eq!(result.payload, b"one parity only");
+            assert!(
+                LSBEmbedder::extract(
+                    &mut image_data,
+                    &LSBConfig::random().with_seed([9; SEED_SIZE])
+                )
+                .is_err()
+            );
+        }
+
+        let odd = LSBConfig::linear().with_parity(Parity::Odd);
+        let planned = LSBEmbedder::planned_indices(3001, &odd, 4).unwrap();
+        assert!(planned.iter().all(|&index| index % 2 == 1 && index < 3001));
+        let capacity = odd.max_capacity(3001);
+        let mut image_data = cover.clone();
+        assert!(LSBEmbedder::embed(&mut image_data, &vec![1; capacity], &odd).is_ok());
+        assert!(LSBEmbedder::embed(&mut image_data, &vec![1; capacity + 1], &odd).is_err());
+    }
+
+    #[test]
+    fn test_or_write_mode_roundtrip() {
+        let config = LSBConfig::random()
+            .with_seed([4; SEED_SIZE])
+            .with_write_mode(WriteMode::Or);
+        let payload = b"or-ed \x00\x01 bits";
+
+        // Over a cleared bit plane the payload reads back as embedded
+        let mut image_data = vec![0x80u8; 2000];
+        LSBEmbedder::embed(&mut image_data, payload, &config).unwrap();
+        let result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+        assert_eq!(result.payload, payload);
+        assert!(matches!(
+            LSBEmbedder::extract(
+                &mut image_data,
+                &LSBConfig::random().with_seed([4; SEED_SIZE])
+            ),
+            Err(PngerError::InvalidFormat(_))
+        ));
+
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(&mut image_data.clone(), &config).unwrap();
+        assert_eq!(result.payload, payload);
+        assert_eq!(confidence.payload_verified, Some(true));
+
+        // Set bits are never cleared, so a body bit set after embedding
+        // corrupts the payload, which the recorded hash reveals
+        let planned = LSBEmbedder::planned_indices(2000, &config, payload.len()).unwrap();
+        let cleared = planned[planned.len() - payload.len() * 8..]
+            .iter()
+            .find(|&&index| image_data[index as usize] & 1 == 0)
+            .unwrap();
+        image_data[*cleared as usize] |= 1;
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(&mut image_data, &config).unwrap();
+        assert_ne!(result.payload, payload);
+        assert_eq!(confidence.payload_verified, Some(false));
+        assert!(!confidence.is_high());
+
+        // A natural cover holding set bits where the payload has zero bits is
+        // refused before anything is written
+        let cover: Vec<u8> = (0..2000).map(|i| (i * 37 % 251) as u8).collect();
+        let mut image_data = cover.clone();
+        assert!(matches!(
+            LSBEmbedder::embed(&mut image_data, payload, &config),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert_eq!(image_data, cover);
+
+        // Set bits are kept where the payload has one bits: only the header
+        // changes a full plane, and the payload still reads back
+        let all_set = vec![0xFFu8; 8];
+        let cover = vec![0x81u8; 2000];
+        let mut image_data = cover.clone();
+        let result = LSBEmbedder::embed(&mut image_data, &all_set, &config).unwrap();
+        let changed = cover
+            .iter()
+            .zip(&image_data)
+            .filter(|(a, b)| a != b)
+            .count();
+        assert!(changed <= result.header_size * 8);
+        let result = LSBEmbedder::extract(&mut image_data, &config).unwrap();
+        assert_eq!(result.payload, all_set);
+    }
+
+    #[test]
+    fn test_extract_with_confidence() {
+        let config = LSBConfig::linear().with_redundancy(3);
+        let payload = [0xA5u8; 100];
+        let mut image_data = vec![0u8; 4000];
+        LSBEmbedder::embed(&mut image_data, &payload, &config).unwrap();
+
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(&mut image_data.clone(), &config).unwrap();
+        assert_eq!(result.payload, payload);
+        assert_eq!(
+            confidence,
+            Confidence {
+                header_valid: true,
+                payload_verified: Some(true),
+                size_fits: true,
+            }
+        );
+        assert!(confidence.is_high());
+
+        // A damaged copy is outvoted, but detected
+        let header_end = result.header_size * 8;
+        let mut damaged = image_data.clone();
+        damaged[header_end] ^= 1;
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(&mut damaged, &config).unwrap();
+        assert_eq!(result.payload, payload);
+        assert_eq!(confidence.payload_verified, Some(false));
+        assert!(!confidence.is_high());
+
+        // A bad header checksum fails strict extraction only. The checksum
+        // follows the magic, version, flags, extension length and payload size
+        let crc_start = (MAGIC_SIZE + 8) * 8;
+        let mut damaged = image_data.clone();
+        for byte in &mut damaged[crc_start..crc_start + 8] {
+            *byte ^= 1;
+        }
+        assert!(LSBEmbedder::extract(&mut damaged, &config).is_err());
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(&mut damaged, &config).unwrap();
+        assert_eq!(result.payload, payload);
+        assert!(!confidence.header_valid && !confidence.is_high());
+
+        // A declared size past the carrier truncates the first copy
+        let truncated = &mut image_data[..header_end + 50 * 8];
+        assert!(LSBEmbedder::extract(truncated, &config).is_err());
+        let (result, confidence) =
+            LSBEmbedder::extract_with_confidence(truncated, &config).unwrap();
+        assert_eq!(result.payload, payload[..50]);
+        assert!(confidence.header_valid && !confidence.size_fits);
+        assert_eq!(confidence.payload_verified, None);
+    }
+
+    #[test]
+    fn test_max_kdf_memory() {
+        // Oversized requests fail before any allocation
+        match crypto::CryptoContext::derive_seed_with_memory("secret", 4 * 1024 * 1024, Some(256)) {
+            Err(crypto::CryptoError::KdfMemoryLimit {
+                requested_kib,
+                max_mib,
+            }) => assert_eq!((requested_kib, max_mib), (4 * 1024 * 1024, 256)),
+            other => panic!("Expected KdfMemoryLimit, got {other:?}"),
+        }
+        assert_eq!(
+            crypto::CryptoContext::derive_seed_with_memory("secret", crypto::KDF_MEMORY_KIB, None)
+                .unwrap(),
+            crypto::CryptoContext::derive_seed_from_password("secret", Some(19)).unwrap()
+        );
+
+        let mut image_data = vec![0u8; 2000];
+        let config = LSBConfig::random().with_password("secret".to_string());
+        LSBEmbedder::embed(&mut image_data, b"payload", &config).unwrap();
+        match LSBEmbedder::extract(&mut image_data, &config.clone().with_max_kdf_memory(18)) {
+            Err(PngerError::CryptoError(message)) => assert!(message.contains("18 MiB")),
+            other => panic!("Expected CryptoError, got {other:?}"),
+        }
+        let result = LSBEmbedder::extract(&mut image_data, &config.with_max_kdf_memory(19));
+        assert_eq!(result.unwrap().payload, b"payload");
+    }
 }
@@ -0,0 +1,157 @@
+use rand::RngCore;
+
+/// Shuffles `amount` randomly chosen elements of `slice` into its tail and
+/// returns them, clamping `amount` to the slice length.
+///
+/// Random patterns derive the body positions from this shuffle, so
+/// extraction must reproduce the exact permutation used when embedding. The
+/// algorithm is the partial Fisher-Yates shuffle of `rand` 0.9
+/// (`SliceRandom::partial_shuffle`), kept here so the permutation no longer
+/// depends on the `rand` version: it only consumes the `next_u32` and
+/// `next_u64` outputs of the generator, which are fixed by ChaCha20 itself.
+/// Images embedded before it was vendored keep extracting.
+pub(super) fn partial_shuffle<'a, T>(
+    slice: &'a mut [T],
+    rng: &mut impl RngCore,
+    amount: usize,
+) -> &'a mut [T] {
+    let len = slice.len();
+    let start = len.saturating_sub(amount);
+
+    if len < u32::MAX as usize {
+        let mut chooser = IncreasingUniform::new(start as u32);
+        for i in start..len {
+            slice.swap(i, chooser.next_index(rng));
+        }
+    } else {
+        for i in start..len {
+            let index = match u32::try_from(i + 1) {
+                Ok(bound) => sample_below_u32(rng, bound) as usize,
+                Err(_) => sample_below_u64(rng, i as u64 + 1) as usize,
+            };
+            slice.swap(i, index);
+        }
+    }
+    &mut slice[start..]
+}
+
+/// Draws numbers in `[0, n]`, then increments `n`, packing several draws
+/// into a single 32-bit sample while their product fits.
+struct IncreasingUniform {
+    n: u32,
+    // Random number below (n + 1) * (n + 2) * .. * (n + chunk_remaining)
+    chunk: u32,
+    chunk_remaining: u8,
+}
+
+impl IncreasingUniform {
+    const fn new(n: u32) -> Self {
+        // The first number drawn for n = 0 is always 0
+        Self {
+            n,
+            chunk: 0,
+            chunk_remaining: if n == 0 { 1 } else { 0 },
+        }
+    }
+
+    fn next_index(&mut self, rng: &mut impl RngCore) -> usize {
+        let next_n = self.n + 1;
+        let next_chunk_remaining = self.chunk_remaining.checked_sub(1).unwrap_or_else(|| {
+            let (bound, remaining) = chunk_bound(next_n);
+            self.chunk = sample_below_u32(rng, bound);
+            remaining - 1
+        });
+
+        let index = if next_chunk_remaining == 0 {
+            self.chunk
+        } else {
+            let index = self.chunk % next_n;
+            self.chunk /= next_n;
+            index
+        };
+        self.chunk_remaining = next_chunk_remaining;
+        self.n = next_n;
+        index as usize
+    }
+}
+
+/// Largest product `m * (m + 1) * .. * (m + count - 1)` fitting in a `u32`,
+/// with its number of factors.
+const fn chunk_bound(m: u32) -> (u32, u8) {
+    let mut product = m;
+    let mut current = m + 1;
+    loop {
+        match product.checked_mul(current) {
+            Some(next) => {
+                product = next;
+                current += 1;
+            }
+            None => return (product, (current - m) as u8),
+        }
+    }
+}
+
+/// Uniform number below `bound` from one widening multiplication, with a
+/// second sample to reduce the bias when the first falls in the biased zone.
+fn sample_below_u32(rng: &mut impl RngCore, bound: u32) -> u32 {
+    let wide = u64::from(rng.next_u32()) * u64::from(bound);
+    let (mut result, low) = ((wide >> 32) as u32, wide as u32);
+    if low > bound.wrapping_neg() {
+        let extra = ((u64::from(rng.next_u32()) * u64::from(bound)) >> 32) as u32;
+        result += u32::from(low.checked_add(extra).is_none());
+    }
+    result
+}
+
+/// 64-bit counterpart of [`sample_below_u32`], for bounds above `u32::MAX`.
+fn sample_below_u64(rng: &mut impl RngCore, bound: u64) -> u64 {
+    let wide = u128::from(rng.next_u64()) * u128::from(bound);
+    let (mut result, low) = ((wide >> 64) as u64, wide as u64);
+    if low > bound.wrapping_neg() {
+        let extra = ((u128::from(rng.next_u64()) * u128::from(bound)) >> 64) as u64;
+        result += u64::from(low.checked_add(extra).is_none());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    // Permutations produced since random patterns were introduced: any change
+    // here breaks the extraction of existing images
+    #[test]
+    fn test_known_seed_gives_fixed_permutation() {
+        let mut candidates: Vec<u32> = (0..64).collect();
+        let mut rng = ChaCha20Rng::from_seed([42; 32]);
+        assert_eq!(
+            partial_shuffle(&mut candidates, &mut rng, 16),
+            [
+                4, 49, 45, 26, 14, 36, 12, 13, 56, 58, 48, 55, 10, 59, 37, 25
+            ]
+        );
+
+        // Amounts beyond the slice shuffle all of it
+        let mut candidates: Vec<u32> = (0..12).collect();
+        let mut rng = ChaCha20Rng::from_seed([42; 32]);
+        assert_eq!(
+            partial_shuffle(&mut candidates, &mut rng, 40),
+            [7, 9, 2, 11, 6, 8, 1, 3, 4, 5, 0, 10]
+        );
+
+        // Large carriers draw one index per 32-bit sample
+        let mut candidates: Vec<u32> = (0..100_000).collect();
+        let mut rng = ChaCha20Rng::from_seed([7; 32]);
+        let shuffled = partial_shuffle(&mut candidates, &mut rng, 8000);
+        assert_eq!(
+            shuffled[..8],
+            [43330, 23706, 66983, 40422, 79293, 31508, 949, 82569]
+        );
+        assert_eq!(
+            shuffled.iter().map(|&index| u64::from(index)).sum::<u64>(),
+            398_213_515
+        );
+    }
+}
@@ -1,12 +1,18 @@
 //! Steganography strategy configuration for payload embedding.
 //!
 //! This module defines the strategy types used to configure how payloads are embedded
-//! into PNG images. Currently supports LSB (Least Significant Bit) steganography
-//! with plans for additional algorithms in the future.
+//! into PNG images. Currently supports LSB (Least Significant Bit) steganography,
+//! an experimental DCT-domain strategy surviving JPEG re-encoding, and
+//! pixel-value differencing (PVD) for higher capacity in textured images.
 
-use crate::strategy::lsb::LSBConfig;
+use crate::PngerError;
+use crate::strategy::dct::DCTConfig;
+use crate::strategy::lsb::{LSBConfig, PixelLayout};
+use crate::strategy::pvd::PVDConfig;
 
+pub mod dct;
 pub mod lsb;
+pub mod pvd;
 
 /// Wire format payload size type for cross-platform compatibility.
 ///
@@ -28,6 +34,16 @@ pub type PayloadSize = u32;
 /// to encode payload information. This provides a good balance between
 /// capacity and visual imperceptibility.
 ///
+/// ## DCT (experimental)
+/// The DCT strategy embeds into the frequency coefficients of 8x8 blocks, so
+/// the payload survives JPEG re-encoding at moderate quality, at a much lower
+/// capacity. See the [`dct`] module for its limits.
+///
+/// ## PVD (Pixel-Value Differencing)
+/// The PVD strategy hides a variable number of bits in each pair of adjacent
+/// pixels, depending on their difference: edges and textures carry more bits
+/// than smooth areas. See the [`pvd`] module for details.
+///
 /// # Examples
 ///
 /// ## Basic LSB Strategy
@@ -59,7 +75,7 @@ pub type PayloadSize = u32;
 /// - **Random patterns** provide better security at the cost of some performance
 /// - **Password-derived seeds** don't require embedding seed data in the image
 /// - **Auto-generated seeds** provide maximum entropy but must be stored in the image
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Strategy {
     /// LSB (Least Significant Bit) steganography with configurable options.
     ///
@@ -67,6 +83,22 @@ pub enum Strategy {
     /// image pixels. The `LSBConfig` determines the specific embedding pattern,
     /// bit positions, and security options.
     LSB(LSBConfig),
+
+    /// Experimental DCT-domain steganography, resilient to JPEG re-encoding.
+    ///
+    /// This strategy embeds data into mid-frequency DCT coefficients of the
+    /// image luminance. It only works on whole images: the LSB-specific
+    /// builders of [`EmbeddingOptions`](crate::EmbeddingOptions) have no
+    /// effect, and APIs working on raw carrier bytes return
+    /// `PngerError::UnsupportedMode`.
+    DCT(DCTConfig),
+
+    /// Pixel-value differencing steganography, for higher capacity in
+    /// textured regions.
+    ///
+    /// This strategy embeds data into the differences between adjacent
+    /// pixels. Like [`Strategy::DCT`], it only works on whole images.
+    PVD(PVDConfig),
 }
 
 impl Default for Strategy {
@@ -88,3 +120,19 @@ impl Default for Strategy {
         Strategy::LSB(LSBConfig::default())
     }
 }
+
+/// Pixel layout of a decoded PNG image, for the strategies working on the
+/// image geometry.
+pub(crate) fn png_layout(info: &png::Info) -> Result<PixelLayout, PngerError> {
+    if info.bit_depth != png::BitDepth::Eight {
+        return Err(PngerError::UnsupportedMode(format!(
+            "Strategy supports 8-bit images only, not {}-bit",
+            info.bit_depth as u8
+        )));
+    }
+    Ok(PixelLayout::packed(
+        info.width as usize,
+        info.height as usize,
+        info.color_type.samples(),
+    ))
+}
@@ -0,0 +1,543 @@
+//! Pixel-value differencing (PVD) steganography.
+//!
+//! PVD hides more bits where the image can absorb them: horizontally adjacent
+//! pixels are grouped in pairs, and the difference between the two samples
+//! of each pair selects a range of a range table. Smooth areas (small
+//! differences) carry few bits, edges and textures (large differences) carry
+//! many, since changes there are harder to notice. The payload bits replace
+//! the offset of the difference within its range, and both samples are
+//! adjusted evenly to reach the new difference, which stays in the same range
+//! so the extractor finds the same number of bits.
+//!
+//! ## Layout
+//!
+//! A 21-byte header (magic, range table, payload length and CRC32) is stored
+//! in the least significant bits of the color samples of the first rows.
+//! Pairs start on the next row: pixels `(0, 1)`, `(2, 3)`, ... of each row,
+//! every color channel separately. The alpha channel is left untouched.
+//! Pairs whose samples would leave `0..=255` for some difference of their
+//! range are skipped. The range table is read from the header, so extraction
+//! needs no configuration.
+//!
+//! ## Capacity
+//!
+//! With the default Wu-Tsai table, each usable pair carries 3 to 7 bits, that
+//! is at least 1.5 bits per sample against 1 for LSB embedding. The exact
+//! capacity depends on the image content, see [`PVDEmbedder::capacity`].
+//! Like the DCT strategy, PVD needs the image geometry, so it is only
+//! available through the whole-image APIs.
+
+use crate::PngerError;
+use crate::strategy::lsb::PixelLayout;
+
+/// Size in bytes of the header: magic, range table, payload length and CRC32.
+pub const HEADER_SIZE: usize = MAGIC.len() + 1 + MAX_RANGES + 4 + 4;
+
+/// Largest number of ranges in a range table.
+pub const MAX_RANGES: usize = 8;
+
+/// Lower bounds of the ranges of Wu and Tsai's original table: 3 bits for
+/// differences up to 15, then 4, 5, 6 and 7 bits.
+pub const WU_TSAI_RANGES: [u8; 6] = [0, 8, 16, 32, 64, 128];
+
+const MAGIC: [u8; 4] = *b"PVD1";
+
+/// Configuration of the PVD strategy.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::strategy::{Strategy, pvd::PVDConfig};
+///
+/// // Quieter table: 2 bits only for differences up to 7
+/// let strategy = Strategy::PVD(PVDConfig::new().with_range_bounds([0, 4, 8, 16, 32, 64, 128]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PVDConfig {
+    range_bounds: Vec<u8>,
+}
+
+impl Default for PVDConfig {
+    fn default() -> Self {
+        Self {
+            range_bounds: WU_TSAI_RANGES.to_vec(),
+        }
+    }
+}
+
+impl PVDConfig {
+    /// Creates a configuration using the Wu-Tsai range table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the range table by the lower bound of each range.
+    ///
+    /// Bounds must start at 0 and increase, with at most [`MAX_RANGES`]
+    /// ranges whose widths (up to the next bound, or 256 for the last one)
+    /// are powers of two. A range of width `2^n` carries `n` bits per pair.
+    /// The table is validated when embedding.
+    pub fn with_range_bounds(mut self, bounds: impl Into<Vec<u8>>) -> Self {
+        self.range_bounds = bounds.into();
+        self
+    }
+
+    /// Lower bounds of the ranges of the table.
+    pub fn range_bounds(&self) -> &[u8] {
+        &self.range_bounds
+    }
+
+    /// Payload capacity, in bytes, of a smooth `width` x `height` image with
+    /// `channels` samples per pixel.
+    ///
+    /// In a smooth image, every pair falls in the first range and carries its
+    /// bits. Textured images usually hold more, see
+    /// [`PVDEmbedder::capacity`], while images with many saturated pixels may
+    /// hold less. Returns 0 for invalid range tables or channel counts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::pvd::PVDConfig;
+    ///
+    /// // 3 bits for each of the 64 * 32 * 3 pairs below the header row
+    /// assert_eq!(PVDConfig::new().smooth_capacity(64, 65, 3), 2304);
+    /// ```
+    pub fn smooth_capacity(&self, width: usize, height: usize, channels: usize) -> usize {
+        let Ok(table) = RangeTable::new(&self.range_bounds) else {
+            return 0;
+        };
+        if !(1..=4).contains(&channels) {
+            return 0;
+        }
+        let layout = PixelLayout::packed(width, height, channels);
+        let (_, _, bits) = table.range(0);
+        let pairs = height
+            .saturating_sub(header_rows(layout))
+            .saturating_mul(width / 2)
+            .saturating_mul(color_channels(layout));
+        pairs.saturating_mul(bits as usize) / 8
+    }
+}
+
+// Validated range table
+struct RangeTable {
+    bounds: Vec<u8>,
+}
+
+impl RangeTable {
+    fn new(bounds: &[u8]) -> Result<Self, PngerError> {
+        let widths: Vec<usize> = bounds
+            .windows(2)
+            .map(|pair| usize::from(pair[1]).saturating_sub(usize::from(pair[0])))
+            .chain(bounds.last().map(|&last| 256 - usize::from(last)))
+            .collect();
+        let valid = (1..=MAX_RANGES).contains(&bounds.len())
+            && bounds[0] == 0
+            && widths.iter().all(|width| width.is_power_of_two());
+        if !valid {
+            return Err(PngerError::UnsupportedMode(format!(
+                "Invalid PVD range bounds {bounds:?}: expected 1 to {MAX_RANGES} increasing \
+                 bounds from 0 with power-of-two widths"
+            )));
+        }
+        Ok(Self {
+            bounds: bounds.to_vec(),
+        })
+    }
+
+    // Lower and upper bounds of the range holding `difference`, and the
+    // number of bits it carries
+    fn range(&self, difference: i32) -> (i32, i32, u32) {
+        let magnitude = difference.unsigned_abs();
+        let index = self
+            .bounds
+            .iter()
+            .rposition(|&lower| u32::from(lower) <= magnitude)
+            .unwrap_or(0);
+        let lower = i32::from(self.bounds[index]);
+        let upper = self
+            .bounds
+            .get(index + 1)
+            .map_or(255, |&next| i32::from(next) - 1);
+        (lower, upper, (upper - lower + 1).ilog2())
+    }
+}
+
+/// PVD embedding and extraction on raw 8-bit pixel buffers.
+pub struct PVDEmbedder;
+
+impl PVDEmbedder {
+    /// Payload capacity, in bytes, of `pixels` with the range table of
+    /// `config`.
+    ///
+    /// Depends on the image content: textured images hold more.
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Unsupported channel count or range
+    ///   table
+    /// - `PngerError::InvalidFormat`: `layout` does not fit in `pixels`
+    pub fn capacity(
+        pixels: &[u8],
+        layout: PixelLayout,
+        config: &PVDConfig,
+    ) -> Result<usize, PngerError> {
+        let table = RangeTable::new(&config.range_bounds)?;
+        check_layout(pixels, layout)?;
+        Ok(capacity_bits(pixels, layout, &table) / 8)
+    }
+
+    /// Embed `payload` into the pixel pairs of `pixels`.
+    ///
+    /// `layout` describes the buffer: 1 or 2 channels for grayscale (with
+    /// alpha), 3 or 4 for RGB (with alpha).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pnger::strategy::lsb::PixelLayout;
+    /// use pnger::strategy::pvd::{PVDConfig, PVDEmbedder};
+    ///
+    /// let layout = PixelLayout::packed(32, 32, 1);
+    /// let mut pixels: Vec<u8> = (0..32 * 32).map(|i| (i * 37 % 200) as u8 + 20).collect();
+    ///
+    /// PVDEmbedder::embed(&mut pixels, layout, b"textured", &PVDConfig::new()).unwrap();
+    /// assert_eq!(PVDEmbedder::extract(&pixels, layout).unwrap(), b"textured");
+    /// ```
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Unsupported channel count or range
+    ///   table
+    /// - `PngerError::InvalidFormat`: `layout` does not fit in `pixels`
+    /// - `PngerError::InsufficientCapacity`: The image cannot hold the header
+    /// - `PngerError::PayloadTooLarge`: The payload exceeds the
+    ///   [`capacity`](Self::capacity)
+    pub fn embed(
+        pixels: &mut [u8],
+        layout: PixelLayout,
+        payload: &[u8],
+        config: &PVDConfig,
+    ) -> Result<(), PngerError> {
+        let table = RangeTable::new(&config.range_bounds)?;
+        check_layout(pixels, layout)?;
+        if !header_fits(layout) {
+            return Err(PngerError::InsufficientCapacity);
+        }
+        let capacity_bits = capacity_bits(pixels, layout, &table);
+        let too_large = || PngerError::PayloadTooLarge {
+            size: payload.len(),
+            capacity: capacity_bits / 8,
+        };
+        let payload_bits = payload.len().checked_mul(8);
+        if payload_bits.is_none_or(|bits| bits > capacity_bits) {
+            return Err(too_large());
+        }
+        let payload_len = u32::try_from(payload.len()).map_err(|_| too_large())?;
+
+        let mut header = Vec::with_capacity(HEADER_SIZE);
+        header.extend_from_slice(&MAGIC);
+        header.push(table.bounds.len() as u8);
+        header.extend(
+            table
+                .bounds
+                .iter()
+                .copied()
+                .chain([0; MAX_RANGES])
+                .take(MAX_RANGES),
+        );
+        header.extend_from_slice(&payload_len.to_be_bytes());
+        header.extend_from_slice(&crc32fast::hash(payload).to_be_bytes());
+        for (index, bit) in header_samples(layout).zip(bits_of(&header)) {
+            pixels[index] = (pixels[index] & !1) | bit;
+        }
+
+        let mut bits = bits_of(payload).peekable();
+        for (first, second) in pairs(layout) {
+            if bits.peek().is_none() {
+                break;
+            }
+            let (p1, p2) = (i32::from(pixels[first]), i32::from(pixels[second]));
+            let difference = p2 - p1;
+            let (lower, upper, count) = table.range(difference);
+            if !usable(p1, p2, upper) {
+                continue;
+            }
+
+            // The last pair may be padded with zero bits
+            let value = (0..count).fold(0, |value, _| {
+                (value << 1) | i32::from(bits.next().unwrap_or(0))
+            });
+            let target = if difference < 0 {
+                -(lower + value)
+            } else {
+                lower + value
+            };
+            let (q1, q2) = adjust(p1, p2, target);
+            pixels[first] = q1 as u8;
+            pixels[second] = q2 as u8;
+        }
+        Ok(())
+    }
+
+    /// Extract a payload embedded by [`embed`](Self::embed).
+    ///
+    /// The range table is read from the header.
+    ///
+    /// # Errors
+    /// - `PngerError::UnsupportedMode`: Unsupported channel count
+    /// - `PngerError::InvalidFormat`: `layout` does not fit in `pixels`, or
+    ///   no valid header or intact payload is found
+    pub fn extract(pixels: &[u8], layout: PixelLayout) -> Result<Vec<u8>, PngerError> {
+        check_layout(pixels, layout)?;
+        let header_bits: Vec<u8> = header_samples(layout)
+            .take(HEADER_SIZE * 8)
+            .map(|index| pixels[index] & 1)
+            .collect();
+        let header = bytes_of(&header_bits);
+        if header.len() < HEADER_SIZE || header[..MAGIC.len()] != MAGIC {
+            return Err(PngerError::InvalidFormat("No PVD header found".to_string()));
+        }
+
+        let range_count = usize::from(header[MAGIC.len()]).min(MAX_RANGES);
+        let bounds_start = MAGIC.len() + 1;
+        let table = RangeTable::new(&header[bounds_start..bounds_start + range_count])
+            .map_err(|e| PngerError::InvalidFormat(e.to_string()))?;
+        let fields = &header[bounds_start + MAX_RANGES..];
+        let payload_len = u32::from_be_bytes([fields[0], fields[1], fields[2], fields[3]]) as usize;
+        let checksum = u32::from_be_bytes([fields[4], fields[5], fields[6], fields[7]]);
+
+        let capacity = capacity_bits(pixels, layout, &table) / 8;
+        if payload_len > capacity {
+            return Err(PngerError::InvalidFormat(format!(
+                "PVD header declares a {payload_len}-byte payload but the image only holds \
+                 {capacity} bytes"
+            )));
+        }
+
+        let mut bits = Vec::with_capacity(payload_len * 8);
+        for (first, second) in pairs(layout) {
+            if bits.len() >= payload_len * 8 {
+                break;
+            }
+            let (p1, p2) = (i32::from(pixels[first]), i32::from(pixels[second]));
+            let (lower, upper, count) = table.range(p2 - p1);
+            if !usable(p1, p2, upper) {
+                continue;
+            }
+            let value = (p2 - p1).abs() - lower;
+            bits.extend((0..count).rev().map(|shift| ((value >> shift) & 1) as u8));
+        }
+        bits.truncate(payload_len * 8);
+
+        let payload = bytes_of(&bits);
+        if crc32fast::hash(&payload) != checksum {
+            return Err(PngerError::InvalidFormat(
+                "PVD payload checksum mismatch".to_string(),
+            ));
+        }
+        Ok(payload)
+    }
+}
+
+fn check_layout(pixels: &[u8], layout: PixelLayout) -> Result<(), PngerError> {
+    if !(1..=4).contains(&layout.channels) {
+        return Err(PngerError::UnsupportedMode(format!(
+            "PVD strategy supports 1 to 4 channels, not {}",
+            layout.channels
+        )));
+    }
+    layout.validate(pixels.len())
+}
+
+// Color samples per pixel, leaving the alpha channel out
+fn color_channels(layout: PixelLayout) -> usize {
+    if layout.channels >= 3 { 3 } else { 1 }
+}
+
+/// Whether an image with `layout` holds the header, and so an empty payload.
+pub(crate) fn header_fits(layout: PixelLayout) -> bool {
+    header_rows(layout) <= layout.height
+}
+
+// Rows holding the header, excluded from the pairs
+fn header_rows(layout: PixelLayout) -> usize {
+    let row_samples = layout.width * color_channels(layout);
+    if row_samples == 0 {
+        return usize::MAX;
+    }
+    (HEADER_SIZE * 8).div_ceil(row_samples)
+}
+
+// Indices of the color samples in raster order, from the first row
+fn header_samples(layout: PixelLayout) -> impl Iterator<Item = usize> {
+    let channels = color_channels(layout);
+    (0..layout.height)
+        .flat_map(move |y| (0..layout.width).map(move |x| (y, x)))
+        .flat_map(move |(y, x)| {
+            (0..channels).map(move |c| y * layout.row_stride + x * layout.channels + c)
+        })
+}
+
+// Sample index pairs of horizontally adjacent pixels, after the header rows
+fn pairs(layout: PixelLayout) -> impl Iterator<Item = (usize, usize)> {
+    let channels = color_channels(layout);
+    (header_rows(layout).min(layout.height)..layout.height)
+        .flat_map(move |y| (0..layout.width / 2).map(move |pair| (y, pair * 2)))
+        .flat_map(move |(y, x)| {
+            (0..channels).map(move |c| {
+                let first = y * layout.row_stride + x * layout.channels + c;
+                (first, first + layout.channels)
+            })
+        })
+}
+
+fn capacity_bits(pixels: &[u8], layout: PixelLayout, table: &RangeTable) -> usize {
+    pairs(layout)
+        .map(|(first, second)| {
+            let (p1, p2) = (i32::from(pixels[first]), i32::from(pixels[second]));
+            let (_, upper, count) = table.range(p2 - p1);
+            if usable(p1, p2, upper) {
+                count as usize
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+// Whether both extreme differences of the range keep the pair in `0..=255`.
+// Checking both signs makes the test give the same answer on the cover and
+// stego pairs, including when a difference is embedded as 0.
+fn usable(p1: i32, p2: i32, upper: i32) -> bool {
+    let in_bounds = |(q1, q2): (i32, i32)| (0..=255).contains(&q1) && (0..=255).contains(&q2);
+    in_bounds(adjust(p1, p2, upper)) && in_bounds(adjust(p1, p2, -upper))
+}
+
+// Moves both samples evenly so that `p2 - p1 == target`
+fn adjust(p1: i32, p2: i32, target: i32) -> (i32, i32) {
+    let change = target - (p2 - p1);
+    let floor_half = change.div_euclid(2);
+    let ceil_half = change - floor_half;
+    if (p2 - p1) % 2 != 0 {
+        (p1 - ceil_half, p2 + floor_half)
+    } else {
+        (p1 - floor_half, p2 + ceil_half)
+    }
+}
+
+// Bits of `bytes`, most significant first
+fn bits_of(bytes: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |shift| (byte >> shift) & 1))
+}
+
+fn bytes_of(bits: &[u8]) -> Vec<u8> {
+    bits.chunks_exact(8)
+        .map(|bits| bits.iter().fold(0, |byte, bit| (byte << 1) | bit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::lsb::LSBConfig;
+
+    fn textured_rgb(width: usize, height: usize) -> Vec<u8> {
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let checker = if (x / 3 + y / 2) % 2 == 0 { 60 } else { 0 };
+                let noise = (x * 31 + y * 17) % 23;
+                [
+                    (90 + checker + noise) as u8,
+                    (200 - checker - noise) as u8,
+                    (40 + (x * y) % 180) as u8,
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pairs_stay_in_range_and_usable() {
+        let table = RangeTable::new(&WU_TSAI_RANGES).unwrap();
+        for p1 in 0..=255 {
+            for p2 in 0..=255 {
+                let difference = p2 - p1;
+                let (lower, upper, _) = table.range(difference);
+                if !usable(p1, p2, upper) {
+                    continue;
+                }
+                for magnitude in [lower, (lower + upper) / 2, upper] {
+                    let target = if difference < 0 {
+                        -magnitude
+                    } else {
+                        magnitude
+                    };
+                    let (q1, q2) = adjust(p1, p2, target);
+                    assert_eq!(q2 - q1, target);
+                    assert!(usable(q1, q2, upper), "({p1}, {p2}) -> ({q1}, {q2})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_embed_extract_textured_image() {
+        let layout = PixelLayout::packed(48, 40, 3);
+        let cover = textured_rgb(48, 40);
+        for config in [
+            PVDConfig::new(),
+            PVDConfig::new().with_range_bounds([0, 4, 8, 16, 32, 64, 128]),
+        ] {
+            let mut pixels = cover.clone();
+            let capacity = PVDEmbedder::capacity(&pixels, layout, &config).unwrap();
+            let payload: Vec<u8> = (0..capacity).map(|i| (i * 7) as u8).collect();
+
+            PVDEmbedder::embed(&mut pixels, layout, &payload, &config).unwrap();
+            assert_eq!(PVDEmbedder::extract(&pixels, layout).unwrap(), payload);
+            assert_eq!(
+                PVDEmbedder::capacity(&pixels, layout, &config).unwrap(),
+                capacity
+            );
+        }
+    }
+
+    #[test]
+    fn test_capacity_exceeds_lsb() {
+        let layout = PixelLayout::packed(64, 64, 3);
+        let pixels = textured_rgb(64, 64);
+        let pvd = PVDEmbedder::capacity(&pixels, layout, &PVDConfig::new()).unwrap();
+        let lsb = LSBConfig::linear().max_capacity(pixels.len());
+        assert!(pvd > lsb * 3 / 2, "PVD {pvd} bytes, LSB {lsb} bytes");
+    }
+
+    #[test]
+    fn test_errors() {
+        let layout = PixelLayout::packed(16, 16, 1);
+        let mut pixels = vec![128u8; 16 * 16];
+        assert!(matches!(
+            PVDEmbedder::extract(&pixels, layout),
+            Err(PngerError::InvalidFormat(_))
+        ));
+
+        let capacity = PVDEmbedder::capacity(&pixels, layout, &PVDConfig::new()).unwrap();
+        assert!(matches!(
+            PVDEmbedder::embed(
+                &mut pixels,
+                layout,
+                &vec![0; capacity + 1],
+                &PVDConfig::new()
+            ),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+
+        for bounds in [&[][..], &[1, 8], &[0, 8, 4], &[0, 6], &[0; 9]] {
+            let config = PVDConfig::new().with_range_bounds(bounds);
+            assert!(matches!(
+                PVDEmbedder::embed(&mut pixels, layout, b"", &config),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! PNG fixtures shared by the unit tests.
+
+/// Creates an 8-bit encoder of `color_type` writing into `png_data`.
+pub(crate) fn encoder(
+    png_data: &mut Vec<u8>,
+    width: u32,
+    height: u32,
+    color_type: png::ColorType,
+) -> png::Encoder<'_, &mut Vec<u8>> {
+    let mut encoder = png::Encoder::new(png_data, width, height);
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+}
+
+/// Encodes 8-bit samples of `color_type` as a PNG image.
+pub(crate) fn encode_png(
+    width: u32,
+    height: u32,
+    color_type: png::ColorType,
+    image_data: &[u8],
+) -> Vec<u8> {
+    let mut png_data = Vec::new();
+    let mut writer = encoder(&mut png_data, width, height, color_type)
+        .write_header()
+        .unwrap();
+    writer.write_image_data(image_data).unwrap();
+    writer.finish().unwrap();
+    png_data
+}
+
+/// Encodes an image of `color_type` whose samples cycle through 0 to 250,
+/// so that no two neighbouring samples are equal.
+pub(crate) fn gradient_png(width: u32, height: u32, color_type: png::ColorType) -> Vec<u8> {
+    let len = (width * height) as usize * color_type.samples();
+    let image_data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+    encode_png(width, height, color_type, &image_data)
+}
+
+/// Encodes an indexed image using all 256 entries of a palette of distinct
+/// colors, pixel `i` holding index `i % 256`.
+pub(crate) fn indexed_png(width: u32, height: u32) -> Vec<u8> {
+    let mut png_data = Vec::new();
+    let mut encoder = encoder(&mut png_data, width, height, png::ColorType::Indexed);
+    encoder.set_palette(
+        (0..=255u8)
+            .flat_map(|i| [i, 255 - i, i / 2])
+            .collect::<Vec<_>>(),
+    );
+    let indices: Vec<u8> = (0..width * height).map(|i| i as u8).collect();
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&indices).unwrap();
+    writer.finish().unwrap();
+    png_data
+}
@@ -0,0 +1,114 @@
+//! Text payloads with an explicit character encoding.
+//!
+//! [`embed_text_from_bytes`](crate::embed_text_from_bytes) encodes a string
+//! with a [`TextEncoding`] and records the encoding in the header, so
+//! [`extract_payload_as_string`](crate::extract_payload_as_string) decodes it
+//! the same way on any platform.
+
+use crate::PngerError;
+
+/// Character encoding of a text payload.
+///
+/// # Examples
+///
+/// ```rust
+/// use pnger::TextEncoding;
+///
+/// let bytes = TextEncoding::Utf16.encode("hé").unwrap();
+/// assert_eq!(bytes, [b'h', 0, 0xE9, 0]);
+/// assert_eq!(TextEncoding::Utf16.decode(&bytes).unwrap(), "hé");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextEncoding {
+    /// UTF-8, the encoding assumed when none is recorded.
+    #[default]
+    Utf8,
+    /// UTF-16 in little-endian byte order, without byte order mark.
+    Utf16,
+    /// ISO-8859-1, one byte per character up to U+00FF.
+    Latin1,
+}
+
+impl TextEncoding {
+    /// Encodes `text`.
+    ///
+    /// # Errors
+    /// - `PngerError::PayloadError`: `text` holds a character Latin-1 cannot
+    ///   represent
+    pub fn encode(self, text: &str) -> Result<Vec<u8>, PngerError> {
+        match self {
+            Self::Utf8 => Ok(text.as_bytes().to_vec()),
+            Self::Utf16 => Ok(text.encode_utf16().flat_map(u16::to_le_bytes).collect()),
+            Self::Latin1 => text
+                .chars()
+                .map(|c| {
+                    u8::try_from(c).map_err(|_| PngerError::PayloadError {
+                        message: format!("character {c:?} cannot be encoded in Latin-1"),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Decodes `bytes` into a string.
+    ///
+    /// Invalid text usually means the extraction options do not match the
+    /// embedding ones, so the error says so.
+    ///
+    /// # Errors
+    /// - `PngerError::PayloadError`: `bytes` is not valid text in this
+    ///   encoding
+    pub fn decode(self, bytes: &[u8]) -> Result<String, PngerError> {
+        let invalid = |detail: String| PngerError::PayloadError {
+            message: format!(
+                "payload is not valid {} ({detail}); it may be binary, or the password, seed or \
+                 obfuscation key may not match the ones used for embedding",
+                self.name()
+            ),
+        };
+        match self {
+            Self::Utf8 => String::from_utf8(bytes.to_vec())
+                .map_err(|err| invalid(err.utf8_error().to_string())),
+            Self::Utf16 => {
+                if bytes.len() % 2 != 0 {
+                    return Err(invalid(format!("odd length of {} bytes", bytes.len())));
+                }
+                let units = bytes
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+                char::decode_utf16(units)
+                    .collect::<Result<String, _>>()
+                    .map_err(|err| invalid(err.to_string()))
+            }
+            Self::Latin1 => Ok(bytes.iter().copied().map(char::from).collect()),
+        }
+    }
+
+    /// Human-readable name of the encoding.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16 => "UTF-16",
+            Self::Latin1 => "Latin-1",
+        }
+    }
+
+    /// Identifier stored in the header.
+    pub(crate) const fn id(self) -> u8 {
+        match self {
+            Self::Utf8 => 0,
+            Self::Utf16 => 1,
+            Self::Latin1 => 2,
+        }
+    }
+
+    /// Encoding stored in the header as `id`, if known.
+    pub(crate) const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Utf8),
+            1 => Some(Self::Utf16),
+            2 => Some(Self::Latin1),
+            _ => None,
+        }
+    }
+}
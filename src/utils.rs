@@ -1,12 +1,26 @@
 use crate::error::PngerError;
+use std::borrow::Cow;
 use std::io::BufWriter;
 
 /// Setup PNG encoder from decoder info
+///
+/// Interlacing is not carried over: the encoder cannot write Adam7 images, so
+/// interlaced inputs, which are decoded into full frames, are written back
+/// non-interlaced with identical pixels.
+///
+/// The embedded ICC profile (`iCCP`) is carried over through the encoder
+/// info, as `png` 0.17 has no encoder setter for it. Like other encoders, it
+/// is only written when the image has no `sRGB` chunk, which takes precedence.
 pub fn setup_png_encoder<'a>(
     info: &png::Info,
     writer: &'a mut BufWriter<Vec<u8>>,
 ) -> Result<png::Encoder<'a, &'a mut BufWriter<Vec<u8>>>, PngerError> {
-    let mut encoder = png::Encoder::new(writer, info.width, info.height);
+    let mut encoder_info = png::Info::with_size(info.width, info.height);
+    encoder_info.icc_profile = info
+        .icc_profile
+        .as_deref()
+        .map(|profile| Cow::Owned(profile.to_vec()));
+    let mut encoder = png::Encoder::with_info(writer, encoder_info)?;
     encoder.set_color(info.color_type);
     encoder.set_depth(info.bit_depth);
     encoder.set_compression(info.compression);
@@ -41,3 +55,361 @@ pub fn copy_png_metadata<'a>(
         encoder.set_source_srgb(*srgb);
     }
 }
+
+/// Validate that the PNG color type and bit depth can carry a payload.
+///
+/// LSB embedding works on whole sample bytes, so 8-bit and 16-bit grayscale,
+/// grayscale with alpha, RGB and RGBA images are supported. Indexed images
+/// (whose bytes are palette indices) and sub-byte grayscale depths (where a
+/// byte packs several pixels) are rejected.
+pub fn validate_color_format(info: &png::Info) -> Result<(), PngerError> {
+    use png::{BitDepth, ColorType};
+
+    let supported = match info.color_type {
+        ColorType::Indexed => false,
+        ColorType::Grayscale => matches!(info.bit_depth, BitDepth::Eight | BitDepth::Sixteen),
+        ColorType::GrayscaleAlpha | ColorType::Rgb | ColorType::Rgba => true,
+    };
+
+    if supported {
+        Ok(())
+    } else {
+        Err(PngerError::UnsupportedMode(format!(
+            "{} {}-bit images are not supported",
+            color_type_name(info.color_type),
+            info.bit_depth as u8
+        )))
+    }
+}
+
+fn color_type_name(color_type: png::ColorType) -> &'static str {
+    match color_type {
+        png::ColorType::Grayscale => "grayscale",
+        png::ColorType::Rgb => "RGB",
+        png::ColorType::Indexed => "indexed",
+        png::ColorType::GrayscaleAlpha => "grayscale-alpha",
+        png::ColorType::Rgba => "RGBA",
+    }
+}
+
+/// Position of the alpha samples in interleaved image data.
+///
+/// Used to keep the alpha channel intact by embedding only into the color
+/// samples. Each sample spans `bytes_per_sample` bytes and the alpha sample
+/// comes last in each pixel, as in PNG scanlines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AlphaLayout {
+    channels: usize,
+    bytes_per_sample: usize,
+}
+
+impl AlphaLayout {
+    /// Alpha layout of pixels with `channels` samples, the last being alpha.
+    pub const fn new(channels: usize, bytes_per_sample: usize) -> Self {
+        Self {
+            channels,
+            bytes_per_sample,
+        }
+    }
+
+    /// Alpha layout of a PNG image, or `None` if it has no alpha channel.
+    pub fn from_png_info(info: &png::Info) -> Option<Self> {
+        matches!(
+            info.color_type,
+            png::ColorType::GrayscaleAlpha | png::ColorType::Rgba
+        )
+        .then(|| {
+            Self::new(
+                info.color_type.samples(),
+                usize::from(info.bit_depth as u8).div_ceil(8),
+            )
+        })
+    }
+
+    fn is_alpha_byte(self, index: usize) -> bool {
+        (index / self.bytes_per_sample) % self.channels == self.channels - 1
+    }
+
+    /// Copies the color sample bytes, skipping alpha.
+    pub fn color_samples(self, image_data: &[u8]) -> Vec<u8> {
+        image_data
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.is_alpha_byte(*index))
+            .map(|(_, byte)| *byte)
+            .collect()
+    }
+
+    /// Writes color sample bytes produced by [`color_samples`](Self::color_samples)
+    /// back in place, leaving alpha untouched.
+    pub fn restore_color_samples(self, image_data: &mut [u8], color_samples: &[u8]) {
+        image_data
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, _)| !self.is_alpha_byte(*index))
+            .zip(color_samples)
+            .for_each(|((_, byte), color)| *byte = *color);
+    }
+}
+
+/// Runs `f` on the carrier bytes of `image_data`: all of them, or only the
+/// color samples when `alpha` is given. Changes made by `f` are written back.
+pub fn with_color_samples<R>(
+    image_data: &mut [u8],
+    alpha: Option<AlphaLayout>,
+    f: impl FnOnce(&mut [u8]) -> Result<R, PngerError>,
+) -> Result<R, PngerError> {
+    let Some(alpha) = alpha else {
+        return f(image_data);
+    };
+    let mut color_samples = alpha.color_samples(image_data);
+    let result = f(&mut color_samples)?;
+    alpha.restore_color_samples(image_data, &color_samples);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        strategy::lsb::BitIndex,
+        test_utils::{encode_png, indexed_png},
+    };
+    use png::{BitDepth, ColorType};
+
+    #[test]
+    fn test_validate_color_format() {
+        let cases = [
+            (ColorType::Rgb, BitDepth::Eight, true),
+            (ColorType::Rgba, BitDepth::Sixteen, true),
+            (ColorType::Grayscale, BitDepth::Eight, true),
+            (ColorType::GrayscaleAlpha, BitDepth::Eight, true),
+            (ColorType::Grayscale, BitDepth::One, false),
+            (ColorType::Grayscale, BitDepth::Four, false),
+            (ColorType::Indexed, BitDepth::Four, false),
+            (ColorType::Indexed, BitDepth::Eight, false),
+        ];
+
+        for (color_type, bit_depth, supported) in cases {
+            let mut info = png::Info::with_size(8, 8);
+            info.color_type = color_type;
+            info.bit_depth = bit_depth;
+            assert_eq!(
+                validate_color_format(&info).is_ok(),
+                supported,
+                "{color_type:?} {bit_depth:?}"
+            );
+        }
+
+        let mut info = png::Info::with_size(8, 8);
+        info.color_type = ColorType::Indexed;
+        info.bit_depth = BitDepth::Four;
+        match validate_color_format(&info) {
+            Err(PngerError::UnsupportedMode(message)) => {
+                assert_eq!(message, "indexed 4-bit images are not supported");
+            }
+            other => panic!("Expected UnsupportedMode, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_icc_profile_survives_embedding() {
+        let profile: Vec<u8> = (0..=255u8).cycle().take(600).collect();
+        let mut info = png::Info::with_size(32, 32);
+        info.color_type = ColorType::Rgb;
+        info.bit_depth = BitDepth::Eight;
+        info.icc_profile = Some(Cow::Borrowed(&profile));
+        let mut png_data = Vec::new();
+        let encoder = png::Encoder::with_info(&mut png_data, info).unwrap();
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[128; 32 * 32 * 3]).unwrap();
+        writer.finish().unwrap();
+
+        let options = crate::EmbeddingOptions::linear();
+        let embedded =
+            crate::embed_payload_from_bytes_with_options(&png_data, b"print", options.clone())
+                .unwrap();
+
+        let (_, embedded_info) = crate::decode_png_info(&embedded).unwrap();
+        assert_eq!(
+            embedded_info.icc_profile.as_deref(),
+            Some(profile.as_slice())
+        );
+        assert_eq!(
+            crate::extract_payload_from_bytes_with_options(&embedded, options).unwrap(),
+            b"print"
+        );
+    }
+
+    #[test]
+    fn test_grayscale_alpha_preserves_alpha() {
+        let pixels: Vec<u8> = (0..32 * 32)
+            .flat_map(|i| [(i % 251) as u8, (i % 7) as u8 * 30])
+            .collect();
+        let png_data = encode_png(32, 32, ColorType::GrayscaleAlpha, &pixels);
+        let split = |data: &[u8]| -> (Vec<u8>, Vec<u8>) {
+            (
+                data.iter().step_by(2).copied().collect(),
+                data.iter().skip(1).step_by(2).copied().collect(),
+            )
+        };
+        let (gray, alpha) = split(&pixels);
+
+        // Only the gray samples carry the payload, without any option, and a
+        // single channel bit index targets them (the header stays in bit 0)
+        let payload = b"gray samples only";
+        for (options, bits) in [
+            (
+                crate::EmbeddingOptions::random_with_password("password"),
+                0b1,
+            ),
+            (
+                crate::EmbeddingOptions::linear().with_channel_bit_indices([BitIndex::Bit1]),
+                0b11,
+            ),
+        ] {
+            let embedded =
+                crate::embed_payload_from_bytes_with_options(&png_data, payload, options.clone())
+                    .unwrap();
+            let (mut reader, _) = crate::decode_png_info(&embedded).unwrap();
+            let (embedded_gray, embedded_alpha) =
+                split(&crate::read_image_data(&mut reader).unwrap());
+            assert_eq!(embedded_alpha, alpha);
+            assert_ne!(embedded_gray, gray);
+            assert!(
+                embedded_gray
+                    .iter()
+                    .zip(&gray)
+                    .all(|(embedded, original)| (embedded ^ original) & !bits == 0)
+            );
+
+            let extracted =
+                crate::extract_payload_from_bytes_with_options(&embedded, options).unwrap();
+            assert_eq!(extracted, payload);
+        }
+    }
+
+    #[test]
+    fn test_alpha_layout_sixteen_bit() {
+        // RGBA 16-bit: 8 bytes per pixel, the last two are alpha
+        let alpha = AlphaLayout::new(4, 2);
+        let image_data: Vec<u8> = (0..16).collect();
+        let color = alpha.color_samples(&image_data);
+        assert_eq!(color, [0, 1, 2, 3, 4, 5, 8, 9, 10, 11, 12, 13]);
+
+        let mut restored = vec![0u8; 16];
+        alpha.restore_color_samples(&mut restored, &color);
+        assert_eq!(
+            restored,
+            [0, 1, 2, 3, 4, 5, 0, 0, 8, 9, 10, 11, 12, 13, 0, 0]
+        );
+    }
+
+    /// Builds an 8-bit RGB Adam7-interlaced PNG, storing the image data
+    /// uncompressed since the encoder cannot write interlaced images.
+    fn interlaced_png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+        const PASSES: [(u32, u32, u32, u32); 7] = [
+            (0, 0, 8, 8),
+            (4, 0, 8, 8),
+            (0, 4, 4, 8),
+            (2, 0, 4, 4),
+            (0, 2, 2, 4),
+            (1, 0, 2, 2),
+            (0, 1, 1, 2),
+        ];
+        let mut raw = Vec::new();
+        for (x0, y0, dx, dy) in PASSES {
+            if x0 >= width || y0 >= height {
+                continue;
+            }
+            for y in (y0..height).step_by(dy as usize) {
+                raw.push(0); // no filter
+                for x in (x0..width).step_by(dx as usize) {
+                    let offset = ((y * width + x) * 3) as usize;
+                    raw.extend_from_slice(&pixels[offset..offset + 3]);
+                }
+            }
+        }
+
+        // zlib stream made of stored deflate blocks
+        let mut zlib = vec![0x78, 0x01];
+        let blocks: Vec<&[u8]> = raw.chunks(u16::MAX as usize).collect();
+        for (i, block) in blocks.iter().enumerate() {
+            zlib.push(u8::from(i == blocks.len() - 1));
+            let len = block.len() as u16;
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        let (a, b) = raw.iter().fold((1u32, 0u32), |(a, b), byte| {
+            let a = (a + u32::from(*byte)) % 65521;
+            (a, (b + a) % 65521)
+        });
+        zlib.extend_from_slice(&((b << 16) | a).to_be_bytes());
+
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 1]); // 8-bit RGB, Adam7
+
+        let mut png_data = b"\x89PNG\r\n\x1a\n".to_vec();
+        for (kind, data) in [(b"IHDR", ihdr), (b"IDAT", zlib), (b"IEND", Vec::new())] {
+            png_data.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            let mut chunk = kind.to_vec();
+            chunk.extend_from_slice(&data);
+            png_data.extend_from_slice(&chunk);
+            png_data.extend_from_slice(&crc32fast::hash(&chunk).to_be_bytes());
+        }
+        png_data
+    }
+
+    #[test]
+    fn test_interlaced_png_written_back_non_interlaced() {
+        let pixels: Vec<u8> = (0..37 * 29 * 3).map(|i| (i * 7 % 256) as u8).collect();
+        let png_data = interlaced_png(37, 29, &pixels);
+        let (mut reader, info) = crate::decode_png_info(&png_data).unwrap();
+        assert!(info.interlaced);
+        assert_eq!(crate::read_image_data(&mut reader).unwrap(), pixels);
+
+        let options = crate::EmbeddingOptions::random_with_password("adam7");
+        let embedded =
+            crate::embed_payload_from_bytes_with_options(&png_data, b"interlaced", options.clone())
+                .unwrap();
+
+        let (mut reader, info) = crate::decode_png_info(&embedded).unwrap();
+        assert!(!info.interlaced);
+        let embedded_pixels = crate::read_image_data(&mut reader).unwrap();
+        assert!(
+            embedded_pixels
+                .iter()
+                .zip(&pixels)
+                .all(|(embedded, original)| embedded >> 1 == original >> 1)
+        );
+
+        let extracted = crate::extract_payload_from_bytes_with_options(&embedded, options).unwrap();
+        assert_eq!(extracted, b"interlaced");
+    }
+
+    #[test]
+    fn test_encode_rejects_resized_buffer() {
+        let pixels: Vec<u8> = (0..37 * 29 * 3).map(|i| (i % 256) as u8).collect();
+        let png_data = interlaced_png(37, 29, &pixels);
+        let (_, info) = crate::decode_png_info(&png_data).unwrap();
+
+        assert!(crate::encode_png_with_data(&info, &pixels, None).is_ok());
+        for len in [pixels.len() - 1, pixels.len() + 3] {
+            let mut resized = pixels.clone();
+            resized.resize(len, 0);
+            let result = crate::encode_png_with_data(&info, &resized, None);
+            assert!(
+                matches!(result, Err(PngerError::PayloadError { message }) if message.contains("37x29"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_unsupported_png_fails_fast() {
+        let result = crate::embed_payload_from_bytes(indexed_png(16, 16), b"payload");
+        assert!(matches!(result, Err(PngerError::UnsupportedMode(_))));
+    }
+}
@@ -0,0 +1,106 @@
+//! Integration tests for the `pnger` command line tool.
+
+#![cfg(feature = "bin")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Creates a scratch directory holding a cover PNG and a payload file.
+fn setup(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pnger-cli-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let mut png_data = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_data, 64, 64);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        let pixels: Vec<u8> = (0..64 * 64 * 3).map(|i| (i % 256) as u8).collect();
+        writer.write_image_data(&pixels).unwrap();
+    }
+    std::fs::write(dir.join("cover.png"), png_data).unwrap();
+    std::fs::write(dir.join("payload.txt"), b"verified payload").unwrap();
+    dir
+}
+
+fn pnger(dir: &Path, args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_pnger"))
+        .current_dir(dir)
+        .args(["-i", "cover.png", "-p", "payload.txt", "-o", "out.png"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_verify_reports_success() {
+    let dir = setup("verify");
+
+    for args in [
+        &["--verify"][..],
+        &[
+            "--verify",
+            "--lsb-password",
+            "secret",
+            "--xor",
+            "--xor-key",
+            "key",
+        ][..],
+    ] {
+        let output = pnger(&dir, args);
+        assert!(output.status.success(), "{output:?}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("verification OK"), "{stdout}");
+        assert!(dir.join("out.png").exists());
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_embed_batch_continues_on_errors() {
+    let dir = setup("batch");
+    let covers = dir.join("covers");
+    let secrets = dir.join("secrets");
+    std::fs::create_dir_all(&covers).unwrap();
+    std::fs::create_dir_all(&secrets).unwrap();
+    let cover = std::fs::read(dir.join("cover.png")).unwrap();
+    for name in ["alpha.png", "beta.png", "orphan.png"] {
+        std::fs::write(covers.join(name), &cover).unwrap();
+    }
+    std::fs::write(covers.join("broken.png"), b"not a png").unwrap();
+    std::fs::write(secrets.join("alpha.txt"), b"first secret").unwrap();
+    std::fs::write(secrets.join("beta.bin"), b"second secret").unwrap();
+    std::fs::write(secrets.join("broken.txt"), b"never embedded").unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_pnger"))
+        .current_dir(&dir)
+        .args(["embed-batch", "--input-dir", "covers", "--payload-dir"])
+        .args(["secrets", "--output-dir", "out", "--lsb-password", "secret"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("ok      alpha.png"), "{stdout}");
+    assert!(stdout.contains("FAILED  broken.png"), "{stdout}");
+    assert!(
+        stdout.contains("FAILED  orphan.png: no matching payload"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("2 embedded, 2 failed"), "{stdout}");
+
+    let options = pnger::EmbeddingOptions::random_with_password("secret");
+    for (name, payload) in [
+        ("alpha.png", &b"first secret"[..]),
+        ("beta.png", b"second secret"),
+    ] {
+        let embedded = std::fs::read(dir.join("out").join(name)).unwrap();
+        let extracted =
+            pnger::extract_payload_from_bytes_with_options(embedded, options.clone()).unwrap();
+        assert_eq!(extracted, payload);
+    }
+    assert!(!dir.join("out/orphan.png").exists());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
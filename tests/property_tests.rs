@@ -5,9 +5,12 @@
 //! 2. Different configurations produce different results
 //! 3. Deterministic: same inputs produce same outputs
 
+use pnger::strategy::lsb::LSBConfig;
 use pnger::{
-    EmbeddingOptions, embed_payload_from_bytes_with_options,
-    extract_payload_from_bytes_with_options,
+    EmbeddingOptions, PngerError, capacity_efficiency, contains_payload, embed_payload_auto_split,
+    embed_payload_delta, embed_payload_from_bytes_with_options, extract_all_layers,
+    extract_payload_as_string, extract_payload_from_bytes_with_options,
+    extract_payload_with_manifest, rekey_payload,
 };
 use proptest::prelude::*;
 
@@ -140,6 +143,997 @@ mod unit_tests {
         assert!(png_data.len() < 1000);
     }
 
+    #[test]
+    fn test_tiny_cover_insufficient_capacity() {
+        // A 1x1 RGB image has 3 carrier bytes, far less than the header needs
+        let png_data = create_simple_png(1, 1, [128, 128, 128]);
+
+        for options in [EmbeddingOptions::linear(), EmbeddingOptions::random()] {
+            let result = embed_payload_from_bytes_with_options(&png_data, b"test", options);
+            assert!(matches!(result, Err(PngerError::InsufficientCapacity)));
+        }
+
+        // A cover holding the header but not the payload is rejected too
+        let png_data = create_simple_png(8, 8, [128, 128, 128]);
+        let result =
+            embed_payload_from_bytes_with_options(&png_data, [0u8; 64], EmbeddingOptions::linear());
+        assert!(matches!(result, Err(PngerError::PayloadTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_empty_payload_roundtrip() {
+        let png_data = create_simple_png(16, 16, [128, 128, 128]);
+
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random_with_password("marker").with_xor_string("key"),
+        ] {
+            assert!(!contains_payload(&png_data, &options).unwrap());
+            let embedded =
+                embed_payload_from_bytes_with_options(&png_data, [], options.clone()).unwrap();
+            assert!(contains_payload(&embedded, &options).unwrap());
+
+            let extracted = extract_payload_from_bytes_with_options(&embedded, options).unwrap();
+            assert!(extracted.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_extract_all_layers() {
+        use pnger::strategy::lsb::BitIndex;
+
+        let mut png_data = create_simple_png(48, 48, [10, 200, 30]);
+        let layers = [
+            (
+                BitIndex::Bit0,
+                EmbeddingOptions::linear(),
+                b"layer zero".as_slice(),
+            ),
+            (BitIndex::Bit1, EmbeddingOptions::random(), b"layer one"),
+            (
+                BitIndex::Bit2,
+                EmbeddingOptions::random_with_password("layers"),
+                b"layer two",
+            ),
+        ];
+        for (bit_index, options, payload) in &layers {
+            let options = options.clone().with_bit_index(*bit_index);
+            png_data = embed_payload_from_bytes_with_options(&png_data, payload, options).unwrap();
+        }
+
+        let extracted = extract_all_layers(&png_data, Some("layers")).unwrap();
+        let expected: Vec<_> = layers
+            .iter()
+            .map(|(bit_index, _, payload)| (*bit_index, payload.to_vec()))
+            .collect();
+        assert_eq!(extracted, expected);
+
+        // Password-derived layers stay hidden without the password
+        let without_password = extract_all_layers(&png_data, None).unwrap();
+        assert_eq!(without_password, expected[..2]);
+    }
+
+    #[test]
+    fn test_rekey_payload() {
+        let png_data = create_simple_png(32, 32, [90, 120, 150]);
+        let payload = b"rotate my keys";
+        let key_a = EmbeddingOptions::random_with_password("password A").with_xor_string("key A");
+        let key_b = EmbeddingOptions::random_with_password("password B").with_xor_string("key B");
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, payload, key_a.clone()).unwrap();
+        let rekeyed = rekey_payload(&embedded, key_a.clone(), key_b.clone()).unwrap();
+
+        let extracted = extract_payload_from_bytes_with_options(&rekeyed, key_b).unwrap();
+        assert_eq!(extracted, payload);
+        // The old key now yields an error or garbage
+        let with_old_key = extract_payload_from_bytes_with_options(&rekeyed, key_a);
+        assert!(with_old_key.map_or(true, |old| old != payload));
+    }
+
+    #[test]
+    fn test_extract_payload_as_string() {
+        let png_data = create_simple_png(32, 32, [128, 128, 128]);
+        let options = EmbeddingOptions::random_with_password("text").with_xor_string("key");
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, "héllo wörld", options.clone())
+                .unwrap();
+        assert_eq!(
+            extract_payload_as_string(&embedded, options).unwrap(),
+            "héllo wörld"
+        );
+
+        let binary = embed_payload_from_bytes_with_options(
+            &png_data,
+            [0xFF, 0xFE, 0x00, 0x80],
+            EmbeddingOptions::linear(),
+        )
+        .unwrap();
+        let result = extract_payload_as_string(&binary, EmbeddingOptions::linear());
+        assert!(
+            matches!(result, Err(PngerError::PayloadError { message }) if message.contains("password"))
+        );
+    }
+
+    #[test]
+    fn test_verify_password() {
+        let png_data = create_simple_png(32, 32, [90, 60, 30]);
+        let embedded = embed_payload_from_bytes_with_options(
+            &png_data,
+            b"secret payload",
+            EmbeddingOptions::random_with_password("open sesame"),
+        )
+        .unwrap();
+
+        assert!(pnger::verify_password(&embedded, "open sesame").unwrap());
+        assert!(!pnger::verify_password(&embedded, "open barley").unwrap());
+        assert!(pnger::verify_password(&png_data, "open sesame").is_err());
+    }
+
+    #[test]
+    fn test_dct_strategy_roundtrip() {
+        use pnger::strategy::dct::DCTConfig;
+
+        let png_data = create_simple_png(64, 64, [120, 100, 140]);
+        let options =
+            EmbeddingOptions::new(pnger::Strategy::DCT(DCTConfig::new())).with_xor_string("key");
+        let payload = b"jpeg resilient";
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, payload, options.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+            payload
+        );
+        let new_options =
+            EmbeddingOptions::new(pnger::Strategy::DCT(DCTConfig::new())).with_xor_string("new");
+        let rekeyed = rekey_payload(&embedded, options.clone(), new_options.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&rekeyed, new_options).unwrap(),
+            payload
+        );
+        assert!(matches!(
+            contains_payload(&embedded, &options),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(&png_data, [0u8; 200], options),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_min_dimensions_fit_payload() {
+        use pnger::min_dimensions_for;
+        use pnger::strategy::{dct::DCTConfig, pvd::PVDConfig};
+
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random().with_xor_string("key"),
+            EmbeddingOptions::linear().with_redundancy(3),
+            EmbeddingOptions::new(pnger::Strategy::DCT(DCTConfig::new())),
+            EmbeddingOptions::new(pnger::Strategy::PVD(PVDConfig::new())),
+        ] {
+            for payload_len in [0, 1, 100, 5000] {
+                let payload = vec![0x5A; payload_len];
+                let (width, height) = min_dimensions_for(payload_len, &options, 3).unwrap();
+                assert!(width.abs_diff(height) <= width / 2 + 8, "{width}x{height}");
+
+                let png_data = create_simple_png(width, height, [120, 100, 140]);
+                let embedded =
+                    embed_payload_from_bytes_with_options(&png_data, &payload, options.clone())
+                        .unwrap();
+                assert_eq!(
+                    extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+                    payload
+                );
+
+                if height > 1 {
+                    let smaller = create_simple_png(width, height - 1, [120, 100, 140]);
+                    assert!(
+                        embed_payload_from_bytes_with_options(&smaller, &payload, options.clone())
+                            .is_err()
+                    );
+                }
+            }
+        }
+
+        // Alpha samples never carry payload bits in grayscale-alpha images,
+        // and only carry them in RGBA images unless alpha is preserved
+        let options = EmbeddingOptions::linear();
+        assert_eq!(
+            min_dimensions_for(1000, &options, 2).unwrap(),
+            min_dimensions_for(1000, &options, 1).unwrap()
+        );
+        assert_ne!(
+            min_dimensions_for(1000, &options, 4).unwrap(),
+            min_dimensions_for(1000, &options, 3).unwrap()
+        );
+        assert_eq!(
+            min_dimensions_for(1000, &options.clone().with_alpha_preserved(), 4).unwrap(),
+            min_dimensions_for(1000, &options, 3).unwrap()
+        );
+
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::new(pnger::Strategy::DCT(DCTConfig::new())),
+            EmbeddingOptions::new(pnger::Strategy::PVD(PVDConfig::new())),
+        ] {
+            assert!(matches!(
+                min_dimensions_for(usize::MAX, &options, 4),
+                Err(PngerError::PayloadTooLarge { size: usize::MAX, capacity }) if capacity > 0
+            ));
+        }
+        assert!(matches!(
+            min_dimensions_for(100, &EmbeddingOptions::linear(), 0),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_auto_pattern_follows_cover_noise() {
+        use pnger::export_seed;
+
+        let mut state = 0x2545_F491u32;
+        let noise: Vec<u8> = (0..64 * 64 * 3)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (state >> 24) as u8
+            })
+            .collect();
+        let mut noisy_png = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut noisy_png, 64, 64);
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(&noise).unwrap();
+        }
+        let smooth_png = create_simple_png(64, 64, [90, 120, 150]);
+
+        let options = EmbeddingOptions::auto_pattern();
+        for (png_data, random) in [(smooth_png, true), (noisy_png, false)] {
+            let embedded =
+                embed_payload_from_bytes_with_options(&png_data, b"auto", options.clone()).unwrap();
+            // Only random patterns have a seed
+            assert_eq!(export_seed(&embedded, &options).is_ok(), random);
+            assert_eq!(
+                extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+                b"auto"
+            );
+        }
+    }
+
+    #[test]
+    fn test_text_encoding_roundtrip() {
+        use pnger::{TextEncoding, embed_text_from_bytes};
+
+        let png_data = create_simple_png(64, 64, [200, 180, 160]);
+        let text = "Grüße, 世界 🦀";
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random_with_password("text").with_xor_string("key"),
+        ] {
+            for encoding in [TextEncoding::Utf8, TextEncoding::Utf16] {
+                let embedded =
+                    embed_text_from_bytes(&png_data, text, encoding, options.clone()).unwrap();
+                let payload =
+                    extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap();
+                assert_eq!(payload, encoding.encode(text).unwrap());
+                assert_eq!(
+                    extract_payload_as_string(&embedded, options.clone()).unwrap(),
+                    text
+                );
+            }
+        }
+
+        assert!(matches!(
+            embed_text_from_bytes(
+                &png_data,
+                text,
+                TextEncoding::Latin1,
+                EmbeddingOptions::linear()
+            ),
+            Err(PngerError::PayloadError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_capacity_efficiency() {
+        let options = EmbeddingOptions::random_with_password("secret");
+        let small = capacity_efficiency(create_simple_png(16, 16, [1, 2, 3]), &options).unwrap();
+        let large = capacity_efficiency(create_simple_png(256, 256, [1, 2, 3]), &options).unwrap();
+        assert!(
+            0.0 < small && small < large && large < 1.0,
+            "{small} {large}"
+        );
+    }
+
+    #[test]
+    fn test_auto_split_with_manifest() {
+        let covers = [
+            create_simple_png(32, 32, [10, 20, 30]),
+            create_simple_png(4, 4, [10, 20, 30]),
+            create_simple_png(32, 32, [40, 50, 60]),
+            create_simple_png(48, 48, [70, 80, 90]),
+        ];
+        let options = EmbeddingOptions::linear().with_xor_string("key");
+        let payload: Vec<u8> = (0..1000u32).map(|i| (i * 13) as u8).collect();
+
+        let (images, manifest) =
+            embed_payload_auto_split(&covers, &payload, options.clone()).unwrap();
+        assert_eq!(manifest.total_size, 1000);
+        assert_eq!(images.len(), manifest.parts.len());
+        // The 4x4 cover is too small for a header and is skipped
+        let indices: Vec<usize> = manifest.parts.iter().map(|part| part.image_index).collect();
+        assert_eq!(indices, [0, 2, 3]);
+        assert_eq!(
+            manifest.parts.iter().map(|part| part.size).sum::<u64>(),
+            1000
+        );
+
+        assert_eq!(
+            extract_payload_with_manifest(&images, &manifest, options.clone()).unwrap(),
+            payload
+        );
+        assert!(matches!(
+            extract_payload_with_manifest(&images[..2], &manifest, options.clone()),
+            Err(PngerError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            embed_payload_auto_split(&covers[..2], &payload, options),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_strict_minimal_diff() {
+        use pnger::strategy::lsb::LSBEmbedder;
+
+        let png_data = create_simple_png(32, 32, [90, 91, 92]);
+        let decode = |data: &[u8]| {
+            let mut reader = png::Decoder::new(data).read_info().unwrap();
+            let mut samples = vec![0; reader.output_buffer_size()];
+            reader.next_frame(&mut samples).unwrap();
+            samples
+        };
+        let payload = b"forensic payload";
+        let config = LSBConfig::random().with_seed([9; 32]);
+        let options =
+            EmbeddingOptions::new(pnger::Strategy::LSB(config.clone())).strict_minimal_diff(true);
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, payload, options.clone()).unwrap();
+
+        // Every modified sample is a planned index, changed in its LSB only
+        let (cover, stego) = (decode(&png_data), decode(&embedded));
+        let planned: std::collections::HashSet<u32> =
+            LSBEmbedder::planned_indices(cover.len(), &config, payload.len())
+                .unwrap()
+                .into_iter()
+                .collect();
+        let changed: Vec<usize> = (0..cover.len()).filter(|&i| cover[i] != stego[i]).collect();
+        assert!(!changed.is_empty());
+        assert!(changed.iter().all(|&i| planned.contains(&(i as u32))));
+        assert!(changed.iter().all(|&i| cover[i] ^ stego[i] == 1));
+
+        // Auto-generated seeds are planned from the header
+        let auto = EmbeddingOptions::random().strict_minimal_diff(true);
+        assert!(embed_payload_from_bytes_with_options(&png_data, payload, auto).is_ok());
+
+        // Noise and non-LSB strategies have no plan to check against
+        for options in [
+            EmbeddingOptions::linear()
+                .with_lsb_noise(true)
+                .strict_minimal_diff(true),
+            EmbeddingOptions::new(pnger::Strategy::DCT(Default::default()))
+                .strict_minimal_diff(true),
+        ] {
+            assert!(matches!(
+                embed_payload_from_bytes_with_options(&png_data, payload, options),
+                Err(PngerError::UnsupportedMode(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_read_embedded_seed() {
+        let png_data = create_simple_png(32, 32, [15, 25, 35]);
+        let embedded = embed_payload_from_bytes_with_options(
+            &png_data,
+            b"payload",
+            EmbeddingOptions::random(),
+        )
+        .unwrap();
+        let seed = pnger::export_seed(&embedded, &EmbeddingOptions::random()).unwrap();
+        assert_eq!(pnger::read_embedded_seed(&embedded).unwrap(), Some(seed));
+
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::new(pnger::Strategy::LSB(LSBConfig::random().with_seed([4; 32]))),
+        ] {
+            let embedded =
+                embed_payload_from_bytes_with_options(&png_data, b"payload", options).unwrap();
+            assert_eq!(pnger::read_embedded_seed(&embedded).unwrap(), None);
+        }
+        assert!(matches!(
+            pnger::read_embedded_seed(&png_data),
+            Err(PngerError::InvalidFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_fail_if_occupied() {
+        use pnger::strategy::lsb::BitIndex;
+
+        let png_data = create_simple_png(64, 64, [60, 70, 80]);
+        let options = EmbeddingOptions::linear().fail_if_occupied(true);
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, b"first", options.clone()).unwrap();
+
+        let second = embed_payload_from_bytes_with_options(&embedded, b"second", options.clone());
+        assert!(
+            matches!(&second, Err(PngerError::InvalidFormat(message)) if message == "bit index already contains a payload")
+        );
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+            b"first"
+        );
+
+        // Other bit indices stay free, and the check is opt-in
+        let layered = options.clone().with_bit_index(BitIndex::Bit1);
+        assert!(embed_payload_from_bytes_with_options(&embedded, b"second", layered).is_ok());
+        assert!(
+            embed_payload_from_bytes_with_options(&embedded, b"second", EmbeddingOptions::linear())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_options_equality_and_hash() {
+        use pnger::Obfuscation;
+        use pnger::obfuscation::PayloadTransform;
+        use std::hash::{BuildHasher, RandomState};
+        use std::sync::Arc;
+
+        let build = || {
+            EmbeddingOptions::random_with_password("cache key")
+                .with_xor_key(vec![1, 2, 3])
+                .with_bit_index(pnger::strategy::lsb::BitIndex::Bit2)
+                .with_output_compression(png::Compression::Best)
+        };
+        let hasher = RandomState::new();
+        assert_eq!(build(), build());
+        assert_eq!(hasher.hash_one(build()), hasher.hash_one(build()));
+
+        for different in [
+            EmbeddingOptions::random_with_password("other key")
+                .with_xor_key(vec![1, 2, 3])
+                .with_bit_index(pnger::strategy::lsb::BitIndex::Bit2)
+                .with_output_compression(png::Compression::Best),
+            build().with_xor_key(vec![1, 2, 4]),
+            build().with_output_compression(png::Compression::Fast),
+            build().with_alpha_preserved(),
+            EmbeddingOptions::new(pnger::Strategy::LSB(LSBConfig::random().with_seed([7; 32]))),
+        ] {
+            assert_ne!(build(), different);
+        }
+        assert_eq!(
+            EmbeddingOptions::new(pnger::Strategy::LSB(LSBConfig::random().with_seed([7; 32]))),
+            EmbeddingOptions::new(pnger::Strategy::LSB(LSBConfig::random().with_seed([7; 32])))
+        );
+
+        // Custom transforms only match themselves
+        struct Identity;
+        impl PayloadTransform for Identity {
+            fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+                Ok(data.to_vec())
+            }
+            fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+                Ok(data.to_vec())
+            }
+        }
+        let with_custom = |transform: Arc<dyn PayloadTransform>| {
+            let mut options = EmbeddingOptions::linear();
+            options.set_obfuscation(Some(Obfuscation::Custom(transform)));
+            options
+        };
+        let transform: Arc<dyn PayloadTransform> = Arc::new(Identity);
+        let custom = with_custom(transform.clone());
+        let same = with_custom(transform);
+        assert_eq!(custom, same);
+        assert_eq!(hasher.hash_one(&custom), hasher.hash_one(&same));
+        assert_ne!(custom, with_custom(Arc::new(Identity)));
+    }
+
+    #[test]
+    fn test_capacity_checked_after_obfuscation_growth() {
+        use pnger::Obfuscation;
+        use pnger::obfuscation::PayloadTransform;
+        use std::sync::Arc;
+
+        // Stand-in for an AEAD cipher prepending a 12-byte nonce and
+        // appending a 16-byte tag
+        struct Sealed;
+        impl PayloadTransform for Sealed {
+            fn forward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+                Ok([&[0; 12][..], data, &[0xAA; 16]].concat())
+            }
+            fn backward(&self, data: &[u8]) -> Result<Vec<u8>, PngerError> {
+                Ok(data[12..data.len() - 16].to_vec())
+            }
+        }
+
+        let png_data = create_simple_png(32, 32, [90, 120, 150]);
+        let capacity = LSBConfig::linear().max_capacity(32 * 32 * 3);
+        let payload = vec![0x5A; capacity];
+        assert!(
+            embed_payload_from_bytes_with_options(&png_data, &payload, EmbeddingOptions::linear())
+                .is_ok()
+        );
+
+        let mut sealed = EmbeddingOptions::linear();
+        sealed.set_obfuscation(Some(Obfuscation::Custom(Arc::new(Sealed))));
+        // The error reports the sealed size against the capacity
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(&png_data, &payload, sealed.clone()),
+            Err(PngerError::PayloadTooLarge { size, capacity: available })
+                if size == capacity + 28 && available == capacity
+        ));
+        let fitting = &payload[..capacity - 28];
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, fitting, sealed.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, sealed).unwrap(),
+            fitting
+        );
+    }
+
+    #[test]
+    fn test_read_header_validates_checksum() {
+        use pnger::read_header;
+        use pnger::strategy::lsb::HeaderFlags;
+
+        let cover = create_simple_png(32, 32, [40, 80, 160]);
+        assert!(matches!(
+            read_header(&cover),
+            Err(PngerError::InvalidFormat(_))
+        ));
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&cover, b"header", EmbeddingOptions::random())
+                .unwrap();
+        let header = read_header(&embedded).unwrap();
+        assert_eq!(header.version, 2);
+        assert_eq!(
+            header.flags,
+            HeaderFlags::RANDOM_PATTERN | HeaderFlags::SEED_EMBEDDED
+        );
+        assert_eq!(header.payload_size, 6);
+
+        // Flip the lowest bit of the payload size, stored after the magic,
+        // version and flags
+        let mut reader = png::Decoder::new(std::io::Cursor::new(embedded.as_slice()))
+            .read_info()
+            .unwrap();
+        let mut image_data = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut image_data).unwrap();
+        image_data[9 * 8] ^= 1;
+        let mut corrupted = Vec::new();
+        let mut encoder = png::Encoder::new(&mut corrupted, 32, 32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&image_data).unwrap();
+        writer.finish().unwrap();
+        match read_header(&corrupted) {
+            Err(PngerError::InvalidFormat(message)) => assert!(message.contains("CRC")),
+            other => panic!("Expected InvalidFormat, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_embed_if_changed_skips_identical_payload() {
+        use pnger::embed_if_changed;
+
+        let cover = create_simple_png(32, 32, [70, 110, 190]);
+        for options in [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random_with_password("retry").with_xor_string("key"),
+            EmbeddingOptions::random(),
+        ] {
+            let first = embed_if_changed(&cover, b"job 42", options.clone()).unwrap();
+            assert_ne!(first, cover);
+            assert_eq!(
+                embed_if_changed(&first, b"job 42", options.clone()).unwrap(),
+                first
+            );
+
+            // Another payload is embedded over the first one
+            let second = embed_if_changed(&first, b"job 43", options.clone()).unwrap();
+            assert_ne!(second, first);
+            assert_eq!(
+                extract_payload_from_bytes_with_options(&second, options).unwrap(),
+                b"job 43"
+            );
+        }
+
+        // Other credentials do not recognize the payload
+        let first = embed_if_changed(
+            &cover,
+            b"job 42",
+            EmbeddingOptions::random_with_password("first"),
+        )
+        .unwrap();
+        let other = EmbeddingOptions::random_with_password("second");
+        let second = embed_if_changed(&first, b"job 42", other.clone()).unwrap();
+        assert_ne!(second, first);
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&second, other).unwrap(),
+            b"job 42"
+        );
+    }
+
+    #[test]
+    fn test_validate_for_extraction() {
+        use pnger::strategy::dct::DCTConfig;
+        use pnger::strategy::lsb::BitIndex;
+
+        let valid = [
+            EmbeddingOptions::linear(),
+            EmbeddingOptions::random(),
+            EmbeddingOptions::random_with_password("secret").with_chaff(true),
+            EmbeddingOptions::random_with_password("secret").with_max_kdf_memory(64),
+            EmbeddingOptions::new(pnger::Strategy::DCT(DCTConfig::new())),
+        ];
+        for options in valid {
+            assert!(options.validate_for_extraction().is_ok(), "{options:?}");
+        }
+
+        // Chaff must be removed before the header is read, so its seed
+        // cannot be embedded
+        let unremovable_chaff = [
+            EmbeddingOptions::random().with_chaff(true),
+            EmbeddingOptions::linear().with_chaff(true),
+            EmbeddingOptions::random_with_password("secret")
+                .with_chaff(true)
+                .with_channel_bit_indices([BitIndex::Bit0, BitIndex::Bit1, BitIndex::Bit0]),
+        ];
+        for options in unremovable_chaff {
+            assert!(
+                matches!(
+                    options.validate_for_extraction(),
+                    Err(PngerError::UnsupportedMode(_))
+                ),
+                "{options:?}"
+            );
+        }
+
+        assert!(matches!(
+            EmbeddingOptions::random_with_password("secret")
+                .with_max_kdf_memory(1)
+                .validate_for_extraction(),
+            Err(PngerError::CryptoError(_))
+        ));
+        assert!(matches!(
+            EmbeddingOptions::linear()
+                .with_channel_bit_indices([BitIndex::Bit0; 5])
+                .validate_for_extraction(),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            EmbeddingOptions::new(pnger::Strategy::DCT(
+                DCTConfig::new().with_quantization_step(2)
+            ))
+            .validate_for_extraction(),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+    }
+
+    #[test]
+    fn test_describe_embedding_matches_settings() {
+        use pnger::describe_embedding;
+        use pnger::strategy::lsb::BitIndex;
+
+        let png_data = create_simple_png(48, 48, [60, 140, 210]);
+        assert!(matches!(
+            describe_embedding(&png_data),
+            Err(PngerError::InvalidFormat(_))
+        ));
+
+        // Described without the password
+        let options = EmbeddingOptions::random_with_password("described")
+            .with_bit_index(BitIndex::Bit1)
+            .with_redundancy(2)
+            .with_payload_tag(7);
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, b"summary payload", options).unwrap();
+        let summary = describe_embedding(&embedded).unwrap();
+        assert_eq!(summary.bit_index, BitIndex::Bit1);
+        assert!(summary.random_pattern);
+        assert!(!summary.seed_embedded);
+        assert!(!summary.scattered_header);
+        assert_eq!(summary.header_version, 3);
+        assert_eq!(summary.payload_size, 15);
+        assert_eq!(summary.redundancy, 2);
+        assert!(summary.extensions.contains(&2));
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, b"linear", EmbeddingOptions::linear())
+                .unwrap();
+        let summary = describe_embedding(&embedded).unwrap();
+        assert_eq!(summary.bit_index, BitIndex::Bit0);
+        assert!(!summary.random_pattern && !summary.seed_embedded);
+        assert_eq!((summary.payload_size, summary.redundancy), (6, 1));
+        assert!(summary.to_string().contains("pattern: linear"));
+    }
+
+    #[test]
+    fn test_bad_png_signature() {
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00,
+        ];
+        match extract_payload_from_bytes_with_options(jpeg, EmbeddingOptions::linear()) {
+            Err(PngerError::InvalidFormat(message)) => assert_eq!(
+                message,
+                "not a PNG: bad signature (first bytes: [ff d8 ff e0 00 10 4a 46])"
+            ),
+            other => panic!("Expected InvalidFormat, got {other:?}"),
+        }
+
+        // Truncated signatures are reported the same way
+        let truncated = &create_simple_png(4, 4, [1, 2, 3])[..3];
+        let error =
+            embed_payload_from_bytes_with_options(truncated, b"x", EmbeddingOptions::linear())
+                .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("not a PNG: bad signature (first bytes: [89 50 4e])")
+        );
+    }
+
+    #[test]
+    fn test_embed_payload_delta() {
+        let cover = create_simple_png(24, 24, [90, 120, 150]);
+        let options = EmbeddingOptions::random_with_password("patch").with_xor_string("key");
+        let delta = embed_payload_delta(&cover, b"shipped as a patch", options.clone()).unwrap();
+        assert!(!delta.is_empty());
+        assert!(delta.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        // Apply the delta to a fresh decode of the cover, then re-encode it
+        let decoder = png::Decoder::new(std::io::Cursor::new(cover.as_slice()));
+        let mut reader = decoder.read_info().unwrap();
+        let mut image_data = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut image_data).unwrap();
+        for &(offset, value) in &delta {
+            image_data[offset] = value;
+        }
+        let mut patched = Vec::new();
+        let mut encoder = png::Encoder::new(&mut patched, 24, 24);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&image_data).unwrap();
+        writer.finish().unwrap();
+
+        let extracted = extract_payload_from_bytes_with_options(&patched, options).unwrap();
+        assert_eq!(extracted, b"shipped as a patch");
+    }
+
+    #[test]
+    fn test_explain_mismatch() {
+        use pnger::explain_mismatch;
+        use pnger::strategy::lsb::BitIndex;
+
+        let embed = EmbeddingOptions::random_with_password("secret").with_xor_string("key");
+        let extract = EmbeddingOptions::linear()
+            .with_bit_index(BitIndex::Bit2)
+            .with_xor_string("key");
+        assert_eq!(
+            explain_mismatch(&embed, &extract),
+            [
+                "bit index: embedded in bit 0, extracting from bit 2",
+                "pattern: embedded with a random pattern (a password), extracting with a linear pattern",
+            ]
+        );
+        assert!(explain_mismatch(&embed, &embed.clone()).is_empty());
+
+        // The explained options do fail to extract
+        let png_data = create_simple_png(64, 64, [90, 120, 150]);
+        let embedded = embed_payload_from_bytes_with_options(&png_data, b"payload", embed).unwrap();
+        assert!(extract_payload_from_bytes_with_options(&embedded, extract).is_err());
+    }
+
+    #[test]
+    fn test_pvd_strategy_roundtrip() {
+        use pnger::strategy::pvd::PVDConfig;
+
+        let png_data = create_simple_png(64, 64, [120, 100, 140]);
+        let options =
+            EmbeddingOptions::new(pnger::Strategy::PVD(PVDConfig::new())).with_xor_string("key");
+        let payload = b"pixel-value differencing";
+
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, payload, options.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+            payload
+        );
+        assert!(matches!(
+            contains_payload(&embedded, &options),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(&png_data, [0u8; 4000], options),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_palette_mode_roundtrip() {
+        let mut png_data = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_data, 16, 16);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(
+                (0..=255u8)
+                    .flat_map(|i| [i, 255 - i, i / 2])
+                    .collect::<Vec<_>>(),
+            );
+            let mut writer = encoder.write_header().unwrap();
+            writer
+                .write_image_data(&(0..=255u8).collect::<Vec<_>>())
+                .unwrap();
+        }
+        let decode = |data: &[u8]| {
+            let mut reader = png::Decoder::new(data).read_info().unwrap();
+            let mut indices = vec![0; reader.output_buffer_size()];
+            reader.next_frame(&mut indices).unwrap();
+            (indices, reader.info().palette.clone().unwrap().into_owned())
+        };
+
+        let payload = b"palette payload";
+        let options = EmbeddingOptions::new(pnger::Strategy::LSB(
+            LSBConfig::random()
+                .with_password("palette".to_string())
+                .palette_mode(),
+        ));
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, payload, options.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, options.clone()).unwrap(),
+            payload
+        );
+
+        // Rekeying keeps the payload in the palette
+        let new_options = EmbeddingOptions::new(pnger::Strategy::LSB(
+            LSBConfig::random()
+                .with_password("rekeyed".to_string())
+                .palette_mode(),
+        ));
+        let rekeyed = rekey_payload(&embedded, options, new_options.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&rekeyed, new_options).unwrap(),
+            payload
+        );
+
+        // Indices are untouched, palette entries differ in their LSB at most
+        let (indices, palette) = decode(&png_data);
+        let (embedded_indices, embedded_palette) = decode(&embedded);
+        assert_eq!(embedded_indices, indices);
+        assert_ne!(embedded_palette, palette);
+        assert!(
+            palette
+                .iter()
+                .zip(&embedded_palette)
+                .all(|(before, after)| before & !1 == after & !1)
+        );
+
+        // Indexed images need palette mode, and palette mode needs them
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(&png_data, payload, EmbeddingOptions::linear()),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        let palette_linear =
+            EmbeddingOptions::new(pnger::Strategy::LSB(LSBConfig::linear().palette_mode()));
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(
+                create_simple_png(16, 16, [1, 2, 3]),
+                payload,
+                palette_linear.clone()
+            ),
+            Err(PngerError::UnsupportedMode(_))
+        ));
+        assert!(matches!(
+            embed_payload_from_bytes_with_options(&png_data, [0u8; 83], palette_linear),
+            Err(PngerError::PayloadTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_indexed_hybrid_roundtrip() {
+        // Gray palette: entries 2k and 2k+1 differ by one level
+        let palette: Vec<u8> = (0..=255u8).flat_map(|i| [i, i, i]).collect();
+        let mut png_data = Vec::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png_data, 64, 64);
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_palette(palette);
+            let mut writer = encoder.write_header().unwrap();
+            let indices: Vec<u8> = (0..64 * 64u32).map(|i| (i * 7 % 256) as u8).collect();
+            writer.write_image_data(&indices).unwrap();
+        }
+        let decode_rgb = |data: &[u8]| {
+            let mut decoder = png::Decoder::new(data);
+            decoder.set_transformations(png::Transformations::EXPAND);
+            let mut reader = decoder.read_info().unwrap();
+            let mut rgb = vec![0; reader.output_buffer_size()];
+            reader.next_frame(&mut rgb).unwrap();
+            rgb
+        };
+
+        // Well beyond the 82 bytes of palette mode
+        let payload: Vec<u8> = (0..400u32).map(|i| (i * 31) as u8).collect();
+        let hybrid = EmbeddingOptions::new(pnger::Strategy::LSB(
+            LSBConfig::random()
+                .with_password("hybrid".to_string())
+                .indexed_hybrid(),
+        ));
+        let embedded =
+            embed_payload_from_bytes_with_options(&png_data, &payload, hybrid.clone()).unwrap();
+        assert_eq!(
+            extract_payload_from_bytes_with_options(&embedded, hybrid).unwrap(),
+            payload
+        );
+
+        // The image stays decodable, each color moves by at most two levels
+        let (before, after) = (decode_rgb(&png_data), decode_rgb(&embedded));
+        assert_eq!(before.len(), after.len());
+        assert_ne!(before, after);
+        assert!(
+            before
+                .iter()
+                .zip(&after)
+                .all(|(before, after)| before.abs_diff(*after) <= 2)
+        );
+
+        // Plain palette mode neither fits the payload nor reads it back
+        let palette_only = EmbeddingOptions::new(pnger::Strategy::LSB(
+            LSBConfig::random()
+                .with_password("hybrid".to_string())
+                .palette_mode(),
+        ));
+        assert!(
+            embed_payload_from_bytes_with_options(&png_data, &payload, palette_only.clone())
+                .is_err()
+        );
+        assert!(extract_payload_from_bytes_with_options(&embedded, palette_only).is_err());
+    }
+
+    #[test]
+    fn test_output_compression() {
+        let png_data = create_simple_png(128, 128, [90, 160, 30]);
+        let payload = vec![0x5A; 1024];
+        let embed = |compression| {
+            let options = EmbeddingOptions::random_with_password("level")
+                .with_output_compression(compression);
+            embed_payload_from_bytes_with_options(&png_data, &payload, options).unwrap()
+        };
+
+        let best = embed(png::Compression::Best);
+        let fast = embed(png::Compression::Fast);
+        assert!(best.len() <= fast.len(), "{} > {}", best.len(), fast.len());
+
+        // Compression is lossless, both outputs extract the payload
+        for embedded in [best, fast] {
+            let options = EmbeddingOptions::random_with_password("level");
+            let extracted = extract_payload_from_bytes_with_options(&embedded, options).unwrap();
+            assert_eq!(extracted, payload);
+        }
+    }
+
     #[test]
     fn test_basic_roundtrip() {
         // Simple unit test version of the property test